@@ -0,0 +1,209 @@
+### 영어 메시지 (아이디의 `.`은 `-`로 씀)
+
+## 폴더 신뢰
+trust-restricted = 🔒 '{ $path }' is a system or home directory. AI CLI runs in read-only mode here.
+trust-trusted = ✅ Folder '{ $path }' is now trusted
+trust-title = 🔒 Security Notice
+trust-untrusted = This folder is not trusted: { $path }
+trust-read_only = AI CLI can only read files in untrusted folders.
+trust-required = To enable AI features, you must trust this folder.
+trust-prompt = Do you want to trust this folder? [Y/n]{" "}
+trust-declined = ❌ Folder not trusted. AI CLI will run in read-only mode.
+
+## 명령어 승인
+approval-session_active = ✅ '{ $command }' is approved for this session ({ $remaining } remaining)
+approval-title = ⚠️  Security Approval Required
+approval-command = Command to execute: { $command }
+approval-type = Type: { $kind }
+approval-scope_repo = in this repository
+approval-scope_global = everywhere
+approval-options = Options:
+approval-yes = {"  "}[Y]es     - Execute this command once
+approval-session = {"  "}[S]ession - Approve this exact command { $scope } for { $ttl }
+approval-pattern = {"  "}[P]attern - Approve commands matching a glob (e.g. git push origin feature/*) for { $ttl }
+approval-dir = {"  "}[D]ir     - Approve { $kind } commands on paths under a directory for { $ttl }
+approval-no = {"  "}[N]o      - Cancel execution
+approval-edit = {"  "}[E]dit    - Modify the command and retry
+approval-choice = Your choice [Y/S/P/D/N/E]:{" "}
+approval-glob = Glob pattern
+approval-directory = Directory
+approval-invalid = Invalid choice. Assuming 'No'.
+
+## MCP 도구 승인
+tool-session_active = ✅ MCP tool '{ $tool }' is approved for this session ({ $remaining } remaining)
+tool-call = MCP tool to call: { $tool }
+tool-destructive = {" "}(destructive)
+tool-arguments = Arguments:
+tool-yes = {"  "}[Y]es     - Call this tool once
+tool-session = {"  "}[S]ession - Approve all calls to this tool { $scope } for { $ttl }
+tool-no = {"  "}[N]o      - Cancel the call
+tool-choice = Your choice [Y/S/N]:{" "}
+
+## 팀 설정
+team-title = 🔒 Repository Config Notice
+team-asks = { $file } asks to change where and how AI requests are sent:
+team-risk = A cloned repository can use these settings to send your code to other servers.
+team-prompt = Apply these settings for this repository? [y/N]{" "}
+team-applied = ✅ Applied the settings in '{ $path }'
+team-ignored = ❌ Ignoring these settings (run 'ai-cli config team' to review them again)
+team-ignoring = ⚠️  Ignoring { $count } setting(s) in { $file } that change where AI requests are sent; run 'ai-cli config team' to review them
+team-config = 👥 Team config: { $path }
+team-types = {"  "}Commit types: { $types }
+team-conventions = {"  "}Commit conventions: ✓
+team-guidelines = {"  "}Prompt guidelines: ✓
+team-privacy = {"  "}Required privacy rules: ✓
+team-excluded = {"  "}Excluded paths: { $paths }
+team-none = ℹ No team config in this repository ({ $path })
+team-repo_config = 📁 Repo config: { $path }
+team-nothing_gated = ✓ No settings in { $file } that need your approval
+team-gated = Settings in { $file } that change where AI requests are sent ({ $status }):
+team-status_applied = applied
+team-status_ignored = ignored
+team-status_pending = not reviewed yet
+
+## 위험한 명령어
+danger-title = 🚨 DANGEROUS COMMAND WARNING
+danger-irreversible = This command may cause irreversible damage:
+danger-prompt = Are you absolutely sure you want to execute this? Type 'YES' to confirm:{" "}
+danger-cancelled = Dangerous command cancelled by user
+command-denied = Command '{ $command }' is denied by policy (rule: { $rule })
+command-cancelled = Command cancelled by user
+command-modified = Enter modified command:{" "}
+command-empty = Empty command. Execution cancelled.
+
+## 커밋
+commit-generating = 🤖 AI is generating your commit message...
+commit-skip_staging = 🔒 Skipping staging: this folder is not trusted (read-only mode)
+commit-staging = 📋 Staging all changes...
+commit-analyzing = 📝 Analyzing { $lines } lines of changes...
+commit-generated = 📝 Generated commit message:
+commit-read_only = 🔒 Not committing: this folder is not trusted (read-only mode)
+commit-regenerating = 🤖 Regenerating commit message...
+commit-regenerating_with = 🤖 Regenerating commit message with { $model }...
+commit-regenerating_temperature = 🤖 Regenerating commit message (temperature { $temperature })...
+commit-candidate = 🔁 Showing candidate { $index }/{ $count }
+commit-options = Message options:
+commit-option_generate = {"  "}[G]enerate - Regenerate with a different temperature
+commit-option_candidates = {"  "}[<] / [>]  - Previous / next candidate ({ $index }/{ $count })
+commit-option_model = {"  "}[M]odel    - Regenerate with another backend (e.g. 'm anthropic'; 'm' alone picks { $model })
+commit-choice = Your choice [Y/S/P/D/N/E/G/M{ $candidates }]:{" "}
+commit-box_title = --- AI Generated Commit Message ---
+commit-cancelled = ❌ Commit cancelled by user.
+commit-empty = ❌ Empty commit message. Commit cancelled.
+commit-custom = Enter custom commit message:{" "}
+commit-executing = 🔄 Executing git commit...
+commit-success = ✅ Commit successful!
+commit-hook_rejected = ❌ Commit rejected by the { $hook } hook!
+commit-failed = ❌ Commit failed!
+commit-no_changes = No changes found to analyze.
+
+## 커밋 메시지 편집
+edit-help =
+    Edit the commit message. Lines starting with '#' are ignored,
+    and an empty message cancels the commit.
+edit-files = Changes to be committed:
+edit-rules = Commit rules the message breaks:
+edit-violations = ⚠️  The edited message breaks { $count } commit rule(s):
+edit-choice = [E]dit again, [C]ommit anyway, or [N]o to cancel?{" "}
+
+## 훅 실패
+hook-title = ⚠️  Commit was rejected (see hook output above)
+hook-retry = {"  "}[R]etry      - Retry the commit with the same message
+hook-generate = {"  "}[G]enerate   - Regenerate the message from the staged changes
+hook-edit = {"  "}[E]dit       - Edit the message and retry
+hook-no = {"  "}[N]o         - Cancel the commit
+hook-choice = Your choice [R/G/E/N]:{" "}
+
+## 커밋 후 작업
+followup-title = 🚀 Next steps for { $branch }:
+followup-amend = {"  "}[A]mend   - Edit the message of the new commit
+followup-push = {"  "}[P]ush    - { $command }
+followup-request = {"  "}[R]equest - Open a { $kind } into { $base } on { $forge } (via MCP)
+followup-choice = Pick any (e.g. 'pr'), or Enter to finish:{" "}
+followup-invalid = Unknown choice '{ $choice }'. Skipping follow-ups.
+followup-message = New commit message (Enter keeps the current one):{" "}
+followup-amended = ✅ Amended the commit
+followup-pushed = ⬆️  Pushed { $branch }
+followup-failed = ❌ { $action } failed: { $error }
+
+## 완료 알림
+notify-commit = Commit message is ready for review
+notify-explain = Change analysis is ready
+notify-agent = MCP task finished
+
+## 커밋 검토 화면
+review-title = {" "}🤖 Commit review · model: { $model }
+review-diff = Staged changes ({ $lines } lines)
+review-message = Commit message
+review-candidate = {" "}· candidate { $index }/{ $count }
+review-keys = {" "}[a]ccept  [e]dit  [r]egenerate  [</>] candidate  [m]odel → { $model }  [Tab] pane  [j/k] scroll  [q]uit
+
+## 변경 설명
+explain-analyzing = 🔍 AI is analyzing the changes...
+explain-result = 📄 AI Analysis:
+
+## 클립보드
+clipboard-copied = 📋 Copied to the clipboard ({ $method })
+clipboard-failed = ⚠️  Could not copy to the clipboard: { $error }
+
+## 외부 전송
+outbound-sending = 📤 Sending { $bytes } bytes (~{ $tokens } tokens, { $files } file(s)) to { $destination }
+outbound-confirm = This exceeds the confirmation threshold of { $threshold } bytes. Send anyway? [y/N]{" "}
+outbound-cancelled = Request to { $destination } cancelled by user
+
+## 사용 팁
+tip-line = 💡 Tip: { $tip } (--no-tips to hide)
+tip-commit-regenerate = at the approval prompt, g regenerates at another temperature, 'm anthropic' tries another backend and < / > switch between candidates
+tip-index = run 'ai-cli index' once so commit and explain also send the most related code
+tip-lint = 'ai-cli lint' checks any commit message against the [commit] rules
+tip-show_payload = add --show-payload to see exactly what would be sent to a remote model
+tip-copy = add --copy to put the result on the clipboard
+tip-explain-detailed = add --detailed for a longer explanation with impact and migration notes
+tip-explain-show_diff = add --show-diff to print the highlighted diff before the analysis
+tip-config-where = 'ai-cli config where' shows which file or environment variable each setting comes from
+
+## MCP 샘플링
+sampling-request = 🤖 MCP server '{ $server }' requests an AI completion ({ $backend } { $model }, up to { $tokens } tokens)
+sampling-declined = {"   "}Declined: sampling needs interactive approval
+sampling-prompt = Allow this request? [y/N]{" "}
+
+## 초기화
+init-start = 🔧 Initializing AI CLI configuration...
+init-model = ✓ Default model set to: { $model }
+init-openai = ✓ OpenAI API key configured
+init-anthropic = ✓ Anthropic API key configured
+init-ollama = ✓ Ollama URL set to: { $url }
+init-saved = ✓ Saved settings to: { $path }
+init-saved_profile = ✓ Saved settings for profile '{ $profile }' to: { $path }
+init-project_config = ✓ Created PROJECT.md at: { $path }
+init-global_config = ✓ Created global config at: { $path }
+init-no_mcp = ℹ No MCP servers configured. Add them to ~/.ai-cli/{ $file } to use external tools.
+init-mcp_ready = ✓ MCP client initialized successfully
+init-mcp_tools = ✓ Available MCP tools: { $tools }
+init-mcp_failed = ⚠ MCP client initialization failed: { $error }
+init-done = 🎉 AI CLI initialization complete!
+init-next = Run 'ai-cli commit' to generate your first AI-powered commit message.
+
+## 설정
+config-title = ⚙️  AI CLI Configuration
+config-verbose_hint = Use --verbose for detailed configuration
+config-init_hint = Run 'ai-cli init' to configure
+config-set = ✓ { $key } = { $value } ({ $path })
+config-set_profile = ✓ { $key } = { $value } (profile '{ $profile }', { $path })
+config-overridden = ⚠ { $var } is set in the environment and overrides this value
+config-removed = ✓ Removed { $key } ({ $path })
+config-removed_profile = ✓ Removed { $key } (profile '{ $profile }', { $path })
+config-not_set = ℹ { $key } is not set in the config file
+config-no_env = ℹ No settings found in environment variables
+config-exported = 📦 Exported settings, { $servers } MCP server(s) and { $files } file(s) to { $path }
+config-redacted = 🔒 Redacted { $count } secret(s)
+config-contains_keys = ⚠️  The bundle contains API keys; use --redact-secrets to share it
+config-pulled = ⬇️  Pulled settings from { $remote }
+config-server = {"  "}✓ MCP server '{ $server }'
+config-not_synced = 🔒 API keys and MCP tokens are not synced; this machine keeps its own
+config-pushed = ⬆️  Pushed settings to { $remote }
+config-up_to_date = ✓ Settings are up to date with { $remote }
+config-merged = ✓ Settings merged into { $path }
+config-imported_server = ✓ MCP server '{ $server }'
+config-kept = ℹ Kept existing (use --force to replace):
+config-set_secrets = 🔒 These secrets were redacted in the bundle; set them on this machine:
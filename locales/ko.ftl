@@ -0,0 +1,209 @@
+### 한국어 메시지 (아이디의 `.`은 `-`로 씀)
+
+## 폴더 신뢰
+trust-restricted = 🔒 '{ $path }'은(는) 시스템 또는 홈 디렉토리입니다. 여기서는 읽기 전용 모드로 실행합니다.
+trust-trusted = ✅ '{ $path }' 폴더를 신뢰합니다
+trust-title = 🔒 보안 알림
+trust-untrusted = 신뢰하지 않는 폴더입니다: { $path }
+trust-read_only = 신뢰하지 않는 폴더에서는 파일을 읽기만 할 수 있습니다.
+trust-required = AI 기능을 사용하려면 이 폴더를 신뢰해야 합니다.
+trust-prompt = 이 폴더를 신뢰하시겠습니까? [Y/n]{" "}
+trust-declined = ❌ 폴더를 신뢰하지 않았습니다. 읽기 전용 모드로 실행합니다.
+
+## 명령어 승인
+approval-session_active = ✅ '{ $command }'은(는) 이 세션에서 승인되었습니다 ({ $remaining } 남음)
+approval-title = ⚠️  보안 승인이 필요합니다
+approval-command = 실행할 명령어: { $command }
+approval-type = 유형: { $kind }
+approval-scope_repo = 이 리포지토리에서
+approval-scope_global = 모든 곳에서
+approval-options = 선택:
+approval-yes = {"  "}[Y]es     - 이 명령어를 한 번 실행
+approval-session = {"  "}[S]ession - 이 명령어를 { $scope } { $ttl } 동안 승인
+approval-pattern = {"  "}[P]attern - glob에 맞는 명령어를 { $ttl } 동안 승인 (예: git push origin feature/*)
+approval-dir = {"  "}[D]ir     - 디렉토리 아래 경로의 { $kind } 명령어를 { $ttl } 동안 승인
+approval-no = {"  "}[N]o      - 실행 취소
+approval-edit = {"  "}[E]dit    - 명령어를 수정해 다시 시도
+approval-choice = 선택 [Y/S/P/D/N/E]:{" "}
+approval-glob = glob 패턴
+approval-directory = 디렉토리
+approval-invalid = 잘못된 선택입니다. 'No'로 처리합니다.
+
+## MCP 도구 승인
+tool-session_active = ✅ MCP 도구 '{ $tool }'은(는) 이 세션에서 승인되었습니다 ({ $remaining } 남음)
+tool-call = 호출할 MCP 도구: { $tool }
+tool-destructive = {" "}(파괴적)
+tool-arguments = 인자:
+tool-yes = {"  "}[Y]es     - 이 도구를 한 번 호출
+tool-session = {"  "}[S]ession - 이 도구의 모든 호출을 { $scope } { $ttl } 동안 승인
+tool-no = {"  "}[N]o      - 호출 취소
+tool-choice = 선택 [Y/S/N]:{" "}
+
+## 팀 설정
+team-title = 🔒 리포지토리 설정 알림
+team-asks = { $file }이(가) AI 요청을 보낼 곳과 방법을 바꾸려고 합니다:
+team-risk = 클론한 리포지토리는 이 설정으로 코드를 다른 서버에 보낼 수 있습니다.
+team-prompt = 이 리포지토리에 이 설정을 적용하시겠습니까? [y/N]{" "}
+team-applied = ✅ '{ $path }'의 설정을 적용했습니다
+team-ignored = ❌ 이 설정을 무시합니다 (다시 검토하려면 'ai-cli config team' 실행)
+team-ignoring = ⚠️  { $file }에서 AI 요청을 보낼 곳을 바꾸는 설정 { $count }개를 무시합니다. 검토하려면 'ai-cli config team'을 실행하세요
+team-config = 👥 팀 설정: { $path }
+team-types = {"  "}커밋 타입: { $types }
+team-conventions = {"  "}커밋 규칙: ✓
+team-guidelines = {"  "}프롬프트 지침: ✓
+team-privacy = {"  "}필수 개인정보 규칙: ✓
+team-excluded = {"  "}제외 경로: { $paths }
+team-none = ℹ 이 리포지토리에는 팀 설정이 없습니다 ({ $path })
+team-repo_config = 📁 리포지토리 설정: { $path }
+team-nothing_gated = ✓ { $file }에는 승인이 필요한 설정이 없습니다
+team-gated = { $file }에서 AI 요청을 보낼 곳을 바꾸는 설정 ({ $status }):
+team-status_applied = 적용함
+team-status_ignored = 무시함
+team-status_pending = 아직 검토하지 않음
+
+## 위험한 명령어
+danger-title = 🚨 위험한 명령어 경고
+danger-irreversible = 이 명령어는 되돌릴 수 없는 피해를 줄 수 있습니다:
+danger-prompt = 정말 실행하시겠습니까? 확인하려면 'YES'를 입력하세요:{" "}
+danger-cancelled = 사용자가 위험한 명령어를 취소했습니다
+command-denied = 정책에 의해 '{ $command }' 명령어가 거부되었습니다 (규칙: { $rule })
+command-cancelled = 사용자가 명령어를 취소했습니다
+command-modified = 수정한 명령어 입력:{" "}
+command-empty = 명령어가 비어 있어 실행을 취소했습니다.
+
+## 커밋
+commit-generating = 🤖 AI가 커밋 메시지를 생성하는 중...
+commit-skip_staging = 🔒 스테이징을 건너뜁니다: 신뢰하지 않는 폴더입니다 (읽기 전용 모드)
+commit-staging = 📋 모든 변경 사항을 스테이징하는 중...
+commit-analyzing = 📝 변경 사항 { $lines }줄을 분석하는 중...
+commit-generated = 📝 생성한 커밋 메시지:
+commit-read_only = 🔒 커밋하지 않습니다: 신뢰하지 않는 폴더입니다 (읽기 전용 모드)
+commit-regenerating = 🤖 커밋 메시지를 다시 생성하는 중...
+commit-regenerating_with = 🤖 { $model }(으)로 커밋 메시지를 다시 생성하는 중...
+commit-regenerating_temperature = 🤖 커밋 메시지를 다시 생성하는 중 (온도 { $temperature })...
+commit-candidate = 🔁 후보 { $index }/{ $count } 표시
+commit-options = 메시지 선택:
+commit-option_generate = {"  "}[G]enerate - 다른 온도로 다시 생성
+commit-option_candidates = {"  "}[<] / [>]  - 이전 / 다음 후보 ({ $index }/{ $count })
+commit-option_model = {"  "}[M]odel    - 다른 백엔드로 다시 생성 (예: 'm anthropic', 'm'만 입력하면 { $model })
+commit-choice = 선택 [Y/S/P/D/N/E/G/M{ $candidates }]:{" "}
+commit-box_title = --- AI가 생성한 커밋 메시지 ---
+commit-cancelled = ❌ 사용자가 커밋을 취소했습니다.
+commit-empty = ❌ 커밋 메시지가 비어 있어 커밋을 취소했습니다.
+commit-custom = 커밋 메시지 입력:{" "}
+commit-executing = 🔄 git commit 실행 중...
+commit-success = ✅ 커밋했습니다!
+commit-hook_rejected = ❌ { $hook } 훅이 커밋을 거부했습니다!
+commit-failed = ❌ 커밋하지 못했습니다!
+commit-no_changes = 분석할 변경 사항이 없습니다.
+
+## 커밋 메시지 편집
+edit-help =
+    커밋 메시지를 수정하세요. '#'으로 시작하는 줄은 무시하고,
+    메시지를 비우면 커밋을 취소합니다.
+edit-files = 커밋할 변경 사항:
+edit-rules = 메시지가 어긴 커밋 규칙:
+edit-violations = ⚠️  수정한 메시지가 커밋 규칙 { $count }개를 어깁니다:
+edit-choice = [E] 다시 수정, [C] 그대로 커밋, [N] 취소?{" "}
+
+## 훅 실패
+hook-title = ⚠️  커밋이 거부되었습니다 (위의 훅 출력 참고)
+hook-retry = {"  "}[R]etry      - 같은 메시지로 다시 커밋
+hook-generate = {"  "}[G]enerate   - 스테이징한 변경 사항으로 메시지 다시 생성
+hook-edit = {"  "}[E]dit       - 메시지를 수정해 다시 시도
+hook-no = {"  "}[N]o         - 커밋 취소
+hook-choice = 선택 [R/G/E/N]:{" "}
+
+## 커밋 후 작업
+followup-title = 🚀 { $branch } 다음 작업:
+followup-amend = {"  "}[A]mend   - 새 커밋의 메시지 수정
+followup-push = {"  "}[P]ush    - { $command }
+followup-request = {"  "}[R]equest - { $forge }에 { $base }(으)로 가는 { $kind } 열기 (MCP)
+followup-choice = 여러 개 선택 가능 (예: 'pr'), 끝내려면 Enter:{" "}
+followup-invalid = 알 수 없는 선택 '{ $choice }'입니다. 다음 작업을 건너뜁니다.
+followup-message = 새 커밋 메시지 (Enter를 누르면 그대로):{" "}
+followup-amended = ✅ 커밋을 수정했습니다
+followup-pushed = ⬆️  { $branch }을(를) 푸시했습니다
+followup-failed = ❌ { $action } 실패: { $error }
+
+## 완료 알림
+notify-commit = 커밋 메시지를 검토할 준비가 되었습니다
+notify-explain = 변경 사항 분석이 끝났습니다
+notify-agent = MCP 작업이 끝났습니다
+
+## 커밋 검토 화면
+review-title = {" "}🤖 커밋 검토 · 모델: { $model }
+review-diff = 스테이징한 변경 사항 ({ $lines }줄)
+review-message = 커밋 메시지
+review-candidate = {" "}· 후보 { $index }/{ $count }
+review-keys = {" "}[a] 수락  [e] 편집  [r] 다시 생성  [</>] 후보  [m] 모델 → { $model }  [Tab] 창  [j/k] 스크롤  [q] 종료
+
+## 변경 설명
+explain-analyzing = 🔍 AI가 변경 사항을 분석하는 중...
+explain-result = 📄 AI 분석:
+
+## 클립보드
+clipboard-copied = 📋 클립보드에 복사했습니다 ({ $method })
+clipboard-failed = ⚠️  클립보드에 복사하지 못했습니다: { $error }
+
+## 외부 전송
+outbound-sending = 📤 { $destination }(으)로 { $bytes }바이트 전송 (약 { $tokens }토큰, 파일 { $files }개)
+outbound-confirm = 확인 기준({ $threshold }바이트)을 넘습니다. 그래도 보내시겠습니까? [y/N]{" "}
+outbound-cancelled = 사용자가 { $destination } 요청을 취소했습니다
+
+## 사용 팁
+tip-line = 💡 팁: { $tip } (--no-tips로 숨김)
+tip-commit-regenerate = 승인 프롬프트에서 g는 다른 온도로 다시 생성, 'm anthropic'은 다른 백엔드로 생성, < / >는 후보 사이를 이동합니다
+tip-index = 'ai-cli index'를 한 번 실행하면 commit과 explain이 가장 관련 있는 코드도 함께 보냅니다
+tip-lint = 'ai-cli lint'로 커밋 메시지가 [commit] 규칙에 맞는지 확인할 수 있습니다
+tip-show_payload = --show-payload를 붙이면 원격 모델로 보낼 내용을 그대로 볼 수 있습니다
+tip-copy = --copy를 붙이면 결과를 클립보드에 복사합니다
+tip-explain-detailed = --detailed를 붙이면 영향과 마이그레이션까지 다룬 자세한 설명을 받습니다
+tip-explain-show_diff = --show-diff를 붙이면 분석 앞에 강조한 diff를 보여 줍니다
+tip-config-where = 'ai-cli config where'는 각 설정이 어느 파일이나 환경 변수에서 왔는지 보여 줍니다
+
+## MCP 샘플링
+sampling-request = 🤖 MCP 서버 '{ $server }'이(가) AI 응답 생성을 요청합니다 ({ $backend } { $model }, 최대 { $tokens } 토큰)
+sampling-declined = {"   "}거절: 샘플링은 대화형 승인이 필요합니다
+sampling-prompt = 이 요청을 허용하시겠습니까? [y/N]{" "}
+
+## 초기화
+init-start = 🔧 AI CLI 설정을 초기화하는 중...
+init-model = ✓ 기본 모델: { $model }
+init-openai = ✓ OpenAI API 키를 설정했습니다
+init-anthropic = ✓ Anthropic API 키를 설정했습니다
+init-ollama = ✓ Ollama URL: { $url }
+init-saved = ✓ 설정을 저장했습니다: { $path }
+init-saved_profile = ✓ '{ $profile }' 프로필 설정을 저장했습니다: { $path }
+init-project_config = ✓ PROJECT.md를 만들었습니다: { $path }
+init-global_config = ✓ 전역 설정을 만들었습니다: { $path }
+init-no_mcp = ℹ 설정한 MCP 서버가 없습니다. 외부 도구를 쓰려면 ~/.ai-cli/{ $file }에 추가하세요.
+init-mcp_ready = ✓ MCP 클라이언트를 초기화했습니다
+init-mcp_tools = ✓ 사용할 수 있는 MCP 도구: { $tools }
+init-mcp_failed = ⚠ MCP 클라이언트 초기화 실패: { $error }
+init-done = 🎉 AI CLI 초기화를 마쳤습니다!
+init-next = 'ai-cli commit'을 실행해 첫 AI 커밋 메시지를 만들어 보세요.
+
+## 설정
+config-title = ⚙️  AI CLI 설정
+config-verbose_hint = 자세한 설정은 --verbose로 확인하세요
+config-init_hint = 설정하려면 'ai-cli init'을 실행하세요
+config-set = ✓ { $key } = { $value } ({ $path })
+config-set_profile = ✓ { $key } = { $value } ('{ $profile }' 프로필, { $path })
+config-overridden = ⚠ 환경 변수 { $var }이(가) 설정되어 있어 이 값보다 우선합니다
+config-removed = ✓ { $key }을(를) 지웠습니다 ({ $path })
+config-removed_profile = ✓ { $key }을(를) 지웠습니다 ('{ $profile }' 프로필, { $path })
+config-not_set = ℹ 설정 파일에 { $key }이(가) 없습니다
+config-no_env = ℹ 환경 변수에서 찾은 설정이 없습니다
+config-exported = 📦 설정, MCP 서버 { $servers }개, 파일 { $files }개를 { $path }에 내보냈습니다
+config-redacted = 🔒 비밀 값 { $count }개를 가렸습니다
+config-contains_keys = ⚠️  번들에 API 키가 들어 있습니다. 공유하려면 --redact-secrets를 쓰세요
+config-pulled = ⬇️  { $remote }에서 설정을 가져왔습니다
+config-server = {"  "}✓ MCP 서버 '{ $server }'
+config-not_synced = 🔒 API 키와 MCP 토큰은 동기화하지 않으며, 이 컴퓨터의 값을 그대로 씁니다
+config-pushed = ⬆️  { $remote }에 설정을 올렸습니다
+config-up_to_date = ✓ 설정이 { $remote }와(과) 같습니다
+config-merged = ✓ 설정을 { $path }에 합쳤습니다
+config-imported_server = ✓ MCP 서버 '{ $server }'
+config-kept = ℹ 기존 항목을 유지했습니다 (바꾸려면 --force):
+config-set_secrets = 🔒 번들에서 가린 비밀 값입니다. 이 컴퓨터에서 설정하세요:
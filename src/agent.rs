@@ -0,0 +1,256 @@
+use anyhow::{Result, anyhow};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::ai_utils::{self, AIBackend, ToolSpec, ToolTurn};
+use crate::injection;
+use crate::mcp::{cancel, BuiltInTool, MCPClient};
+
+/// 에이전트 모듈
+/// 연결된 MCP 서버의 도구 스키마를 function calling으로 모델에 제공하고, 모델이 고른 도구를 실행해
+/// 결과를 다시 전달하는 과정을 모델이 최종 답변을 낼 때까지 반복 (PR 생성, 이슈 등록 등 외부 작업용)
+/// MCP 도구와 함께 프로젝트 시맨틱 인덱스를 찾는 내장 검색 도구도 제공
+///
+/// 기본 최대 단계 수 (모델 응답 한 번이 한 단계)
+pub const DEFAULT_MAX_STEPS: usize = 8;
+
+/// 모델 응답 한 번의 최대 토큰 수
+const MAX_RESPONSE_TOKENS: u32 = 2048;
+
+/// 모델에 돌려주는 도구 결과의 최대 길이 (문자)
+const MAX_RESULT_CHARS: usize = 8000;
+
+/// 내장 코드 검색 도구의 함수 이름
+const SEARCH_TOOL: &str = "search_code";
+
+/// 내장 코드 검색 도구가 돌려주는 기본/최대 조각 수
+const DEFAULT_SEARCH_RESULTS: usize = 5;
+const MAX_SEARCH_RESULTS: usize = 20;
+
+/// 에이전트 시스템 프롬프트
+const AGENT_SYSTEM_PROMPT: &str = "You are ai-cli, a Git assistant that completes tasks by calling the provided tools.
+- Call tools only when the task needs information or an external action; prefer the fewest calls that finish the task.
+- Use exact argument names and types from each tool's schema. If a call fails, read the error and adjust or stop.
+- When the task is done (or cannot be done), reply with a short summary for the user without calling more tools.";
+
+/// 실행한 도구 호출 기록
+#[derive(Debug, Clone)]
+pub struct ToolCallRecord {
+    pub tool: String,
+    pub succeeded: bool,
+}
+
+/// 에이전트 실행 결과
+#[derive(Debug, Clone)]
+pub struct AgentRun {
+    /// 모델의 최종 답변
+    pub answer: String,
+    pub calls: Vec<ToolCallRecord>,
+}
+
+/// 모델에 보낼 도구 이름 (함수 이름에 쓸 수 없는 문자는 `_`로 바꾸고 64자로 자름)
+fn function_name(tool: &str) -> String {
+    tool.chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '_' || c == '-' { c } else { '_' })
+        .take(64)
+        .collect()
+}
+
+/// 내장 코드 검색 도구 정의
+fn search_spec() -> ToolSpec {
+    ToolSpec {
+        name: SEARCH_TOOL.to_string(),
+        description: "Search this repository's semantic code index for the code most related to a natural-language query. \
+            Returns file paths, line ranges and snippets."
+            .to_string(),
+        parameters: serde_json::json!({
+            "type": "object",
+            "properties": {
+                "query": {"type": "string", "description": "What to look for (e.g. \"where failed requests are retried\")"},
+                "top_k": {"type": "integer", "minimum": 1, "maximum": MAX_SEARCH_RESULTS, "description": "Number of matches to return"}
+            },
+            "required": ["query"]
+        }),
+    }
+}
+
+/// 내장 코드 검색 실행 (인덱스가 없으면 `ai-cli index`를 안내하는 오류)
+async fn run_search(arguments: &serde_json::Value) -> Result<String> {
+    let query = arguments
+        .get("query")
+        .and_then(serde_json::Value::as_str)
+        .filter(|query| !query.trim().is_empty())
+        .ok_or_else(|| anyhow!("'query' must be a non-empty string"))?;
+    let top_k = arguments
+        .get("top_k")
+        .and_then(serde_json::Value::as_u64)
+        .map_or(DEFAULT_SEARCH_RESULTS, |top_k| (top_k as usize).clamp(1, MAX_SEARCH_RESULTS));
+
+    let result = BuiltInTool::SemanticSearch { query: query.to_string(), top_k }.execute().await?;
+    Ok(serde_json::to_string_pretty(&result)?)
+}
+
+/// 클라이언트의 도구를 모델용 정의로 변환 (함수 이름 → MCP 도구 이름 매핑 포함)
+/// 내장 검색 도구가 맨 앞에 오고, 같은 함수 이름의 MCP 도구는 건너뜀
+fn tool_specs(client: &MCPClient) -> (Vec<ToolSpec>, HashMap<String, String>) {
+    let mut specs = vec![search_spec()];
+    let mut names = HashMap::new();
+    for name in client.list_tools() {
+        let Some(tool) = client.get_tool(&name) else { continue };
+        let function = function_name(&name);
+        if function == SEARCH_TOOL || names.contains_key(&function) {
+            tracing::warn!("Skipping MCP tool '{}': its function name '{}' is already used", name, function);
+            continue;
+        }
+        specs.push(ToolSpec {
+            name: function.clone(),
+            description: tool.description.clone(),
+            parameters: serde_json::to_value(&tool.input_schema).unwrap_or_else(|_| serde_json::json!({"type": "object"})),
+        });
+        names.insert(function, name);
+    }
+    (specs, names)
+}
+
+/// 도구 결과를 모델에 돌려줄 텍스트로 변환 (길면 자르고, 외부 데이터이므로 신뢰할 수 없는 블록으로 감쌈)
+fn tool_result_text(tool: &str, result: &Result<String>) -> String {
+    let text = match result {
+        Ok(text) => text.clone(),
+        Err(e) => format!("Error: {}", e),
+    };
+    let text = text.trim();
+    // 문자 경계에서 자르고, 실제로 잘랐을 때만 표시
+    let text = match text.char_indices().nth(MAX_RESULT_CHARS) {
+        Some((end, _)) => format!("{}\n[truncated]", &text[..end]),
+        None => text.to_string(),
+    };
+    injection::wrap_untrusted(&format!("TOOL RESULT {}", tool), &text)
+}
+
+/// 작업을 모델과 MCP 도구로 수행 (`context`는 리포지토리 정보 등 모델에 함께 줄 내용)
+/// 변경 도구 호출은 `MCPClient::call_tools`에서 보안 승인을 거치고, 모델이 한 번에 고른 도구들은 동시에 실행
+pub async fn run(
+    backend: &AIBackend,
+    client: &Arc<MCPClient>,
+    task: &str,
+    context: Option<&str>,
+    max_steps: usize,
+) -> Result<AgentRun> {
+    let (tools, names) = tool_specs(client);
+
+    let system = format!("{}\n\n{}", AGENT_SYSTEM_PROMPT, injection::UNTRUSTED_CONTENT_RULES);
+    let task = match context {
+        Some(context) => format!("{}\n\nCONTEXT:\n{}", task, context),
+        None => task.to_string(),
+    };
+    let mut turns = vec![ToolTurn::User(task)];
+    let mut calls = Vec::new();
+
+    for _ in 0..max_steps {
+        let response = ai_utils::generate_with_tools(backend, &system, &turns, &tools, MAX_RESPONSE_TOKENS).await?;
+        turns.push(ToolTurn::Assistant { content: response.content.clone(), tool_calls: response.tool_calls.clone() });
+        if response.tool_calls.is_empty() {
+            return Ok(AgentRun { answer: response.content, calls });
+        }
+
+        let mut requests = Vec::new();
+        for call in &response.tool_calls {
+            if let Some(tool) = names.get(&call.name) {
+                println!("🔧 Calling {}...", tool);
+                requests.push((tool.clone(), Some(call.arguments.clone())));
+            }
+        }
+        let mut results = client.call_tools(requests).await.into_iter();
+
+        for call in response.tool_calls {
+            let result = match names.get(&call.name) {
+                None if call.name == SEARCH_TOOL => {
+                    println!("🔍 Searching code...");
+                    let result = run_search(&call.arguments).await;
+                    if let Err(e) = &result {
+                        println!("⚠️  {} failed: {}", SEARCH_TOOL, e);
+                    }
+                    calls.push(ToolCallRecord { tool: SEARCH_TOOL.to_string(), succeeded: result.is_ok() });
+                    result
+                }
+                Some(tool) => {
+                    let result = results
+                        .next()
+                        .unwrap_or_else(|| Err(anyhow!("No result for {}", tool)))
+                        .and_then(|result| {
+                            let text = result.text();
+                            if result.is_error() { Err(anyhow!("{}", text.trim())) } else { Ok(text) }
+                        });
+                    if let Err(e) = &result {
+                        // 사용자가 Ctrl-C로 취소하면 모델에 결과를 돌려주지 않고 작업 중단
+                        if cancel::is_cancelled(e) {
+                            return Err(anyhow!("Cancelled while running {} ({} tool call(s) made)", tool, calls.len()));
+                        }
+                        println!("⚠️  {} failed: {}", tool, e);
+                    }
+                    calls.push(ToolCallRecord { tool: tool.clone(), succeeded: result.is_ok() });
+                    result
+                }
+                None => Err(anyhow!("Unknown tool '{}'", call.name)),
+            };
+            turns.push(ToolTurn::ToolResult {
+                call_id: call.id,
+                name: call.name.clone(),
+                content: tool_result_text(&call.name, &result),
+            });
+        }
+    }
+
+    Err(anyhow!("Stopped after {} steps without a final answer ({} tool call(s) made)", max_steps, calls.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_function_name() {
+        assert_eq!(function_name("create_issue"), "create_issue");
+        assert_eq!(function_name("github.create_issue"), "github_create_issue");
+        assert_eq!(function_name(&"x".repeat(80)).len(), 64);
+    }
+
+    #[test]
+    fn test_tool_specs_include_search() {
+        let client = crate::mcp::MCPClientBuilder::new("ai-cli").build();
+        let (specs, names) = tool_specs(&client);
+
+        let search = specs.iter().find(|spec| spec.name == SEARCH_TOOL).unwrap();
+        assert_eq!(search.parameters["required"], serde_json::json!(["query"]));
+        assert!(!names.contains_key(SEARCH_TOOL));
+    }
+
+    #[tokio::test]
+    async fn test_run_search_requires_query() {
+        assert!(run_search(&serde_json::json!({})).await.is_err());
+        assert!(run_search(&serde_json::json!({"query": "  "})).await.is_err());
+    }
+
+    #[test]
+    fn test_tool_result_text() {
+        let ok = tool_result_text("get_issue", &Ok("Login fails on Safari".to_string()));
+        assert!(ok.starts_with("<<<UNTRUSTED TOOL RESULT get_issue"));
+        assert!(ok.contains("\nLogin fails on Safari\n"));
+
+        let failed = tool_result_text("get_issue", &Err(anyhow!("Not Found")));
+        assert!(failed.contains("Error: Not Found"));
+
+        let long = tool_result_text("search", &Ok("a".repeat(MAX_RESULT_CHARS + 10)));
+        assert!(long.contains("[truncated]"));
+
+        // 끝의 공백만 있던 결과는 자른 것이 아님
+        let padded = tool_result_text("search", &Ok(format!("{}\n\n  ", "a".repeat(100))));
+        assert!(!padded.contains("[truncated]"));
+
+        // 여러 바이트 문자도 문자 단위로 셈
+        let wide = tool_result_text("search", &Ok("가".repeat(MAX_RESULT_CHARS)));
+        assert!(!wide.contains("[truncated]"));
+        let wide = tool_result_text("search", &Ok("가".repeat(MAX_RESULT_CHARS + 1)));
+        assert!(wide.contains(&format!("{}\n[truncated]", "가".repeat(MAX_RESULT_CHARS))));
+    }
+}
@@ -0,0 +1,113 @@
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use std::path::{Path, PathBuf};
+
+use crate::config::{team, RepoConfig, REPO_CONFIG_FILE};
+
+/// `.aiignore` 모듈
+/// 프로젝트 루트의 `.aiignore`(gitignore 문법)에 해당하는 파일은 diff 내용, 컨텍스트, 시맨틱 인덱스,
+/// 내장 도구의 파일 읽기/목록 어디에서도 AI에 전달하지 않음
+/// `.ai-cli.toml`과 `.ai-cli/team.toml`의 `exclude` 패턴도 같은 규칙으로 적용
+///
+/// 무시 규칙 파일 이름
+pub const AIIGNORE_FILE: &str = ".aiignore";
+
+/// `.aiignore` 규칙
+#[derive(Debug, Clone)]
+pub struct AiIgnore {
+    root: PathBuf,
+    matcher: Option<Gitignore>,
+}
+
+impl AiIgnore {
+    /// 프로젝트 루트의 `.aiignore`와 `.ai-cli.toml`, 팀 설정의 `exclude` 로드 (없으면 아무것도 무시하지 않음)
+    pub fn load(root: &Path) -> Self {
+        let path = root.join(AIIGNORE_FILE);
+        let mut excludes = RepoConfig::load(root).map(|config| config.exclude).unwrap_or_default();
+        excludes.extend(team::active_in(root).map(|team| team.exclude).unwrap_or_default());
+        let matcher = if path.is_file() || !excludes.is_empty() {
+            let mut builder = GitignoreBuilder::new(root);
+            if path.is_file() {
+                if let Some(e) = builder.add(&path) {
+                    tracing::warn!("Some rules in {} could not be parsed: {}", path.display(), e);
+                }
+            }
+            for pattern in &excludes {
+                if let Err(e) = builder.add_line(None, pattern) {
+                    tracing::warn!("Ignoring exclude pattern '{}' in {} or {}: {}", pattern, REPO_CONFIG_FILE, team::TEAM_CONFIG_FILE, e);
+                }
+            }
+            match builder.build() {
+                Ok(matcher) => Some(matcher),
+                Err(e) => {
+                    tracing::warn!("Ignoring {}: {}", path.display(), e);
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        Self { root: root.to_path_buf(), matcher }
+    }
+
+    /// 경로(루트 기준 상대 경로 또는 루트 아래 절대 경로)가 무시 대상인지 확인
+    /// 상위 디렉토리가 무시되면 그 아래 파일도 무시
+    pub fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        let Some(matcher) = &self.matcher else {
+            return false;
+        };
+
+        let relative = if path.is_absolute() {
+            match path.strip_prefix(&self.root) {
+                Ok(relative) => relative,
+                Err(_) => return false,
+            }
+        } else {
+            path
+        };
+        if relative.as_os_str().is_empty() {
+            return false;
+        }
+
+        matcher.matched_path_or_any_parents(relative, is_dir).is_ignore()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_aiignore_rules() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join(AIIGNORE_FILE), "vendor/\n*.min.js\nsecrets/**\n!secrets/README.md\n").unwrap();
+        let aiignore = AiIgnore::load(temp_dir.path());
+
+        assert!(aiignore.is_ignored(Path::new("vendor/lib/a.rs"), false));
+        assert!(aiignore.is_ignored(Path::new("vendor"), true));
+        assert!(aiignore.is_ignored(Path::new("static/app.min.js"), false));
+        assert!(aiignore.is_ignored(&temp_dir.path().join("secrets/key.txt"), false));
+        assert!(!aiignore.is_ignored(Path::new("secrets/README.md"), false));
+        assert!(!aiignore.is_ignored(Path::new("src/main.rs"), false));
+        assert!(!aiignore.is_ignored(Path::new("/elsewhere/vendor/a.rs"), false));
+    }
+
+    #[test]
+    fn test_missing_aiignore_ignores_nothing() {
+        let temp_dir = TempDir::new().unwrap();
+        assert!(!AiIgnore::load(temp_dir.path()).is_ignored(Path::new("vendor/a.rs"), false));
+    }
+
+    #[test]
+    fn test_repo_config_excludes() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join(REPO_CONFIG_FILE), "exclude = [\"fixtures/\", \"*.snap\"]\n").unwrap();
+        let aiignore = AiIgnore::load(temp_dir.path());
+
+        assert!(aiignore.is_ignored(Path::new("fixtures/customers.json"), false));
+        assert!(aiignore.is_ignored(Path::new("tests/output.snap"), false));
+        assert!(!aiignore.is_ignored(Path::new("src/main.rs"), false));
+    }
+}
@@ -0,0 +1,213 @@
+use anyhow::{Result, anyhow};
+use std::io::{self, Read, Write};
+use std::process::{Command, Stdio};
+
+/// ASCII 출력 모듈 (`--ascii`, `AI_CLI_ASCII=1`)
+/// 그림 문자와 상자 그리기 문자를 `[ok]`, `[warning]`, `-`, `->` 같은 글자로 바꾸고, 목록에 없는 그림 문자는 지움 (한글 등 글자는 그대로)
+/// `--json`처럼 같은 명령을 자식 프로세스로 다시 실행해 표준 출력과 표준 에러를 받아 바꿔 씀
+/// 자식의 출력은 터미널이 아니므로 스피너, 진행 줄, 전체 화면 검토, 페이저, 색 없이 한 줄씩 출력되어 화면 낭독기로도 읽기 쉬움
+///
+/// ASCII 출력을 켜는 환경 변수 (`--ascii`)
+const ASCII_ENV: &str = "AI_CLI_ASCII";
+
+/// 출력을 바꿔 쓰는 자식 프로세스에만 설정
+const CHILD_ENV: &str = "AI_CLI_ASCII_CHILD";
+
+/// 바꿀 문자와 대신 쓸 글자
+const GLYPHS: &[(char, &str)] = &[
+    ('✅', "[ok]"),
+    ('✓', "[ok]"),
+    ('✔', "[ok]"),
+    ('❌', "[error]"),
+    ('✗', "[x]"),
+    ('⚠', "[warning]"),
+    ('🚨', "[danger]"),
+    ('ℹ', "[info]"),
+    ('🤖', "[ai]"),
+    ('📝', "[message]"),
+    ('🔍', "[search]"),
+    ('🔎', "[search]"),
+    ('📋', "[clipboard]"),
+    ('📄', "[result]"),
+    ('🔄', "[running]"),
+    ('🔁', "[candidate]"),
+    ('⏳', "[wait]"),
+    ('🔒', "[security]"),
+    ('🔑', "[sign-in]"),
+    ('🔌', "[mcp]"),
+    ('🔧', "[tool]"),
+    ('📤', "[send]"),
+    ('📦', "[package]"),
+    ('🎫', "[ticket]"),
+    ('⬆', "[push]"),
+    ('⬇', "[pull]"),
+    ('🎉', "[done]"),
+    ('💡', "[tip]"),
+    ('🚀', "[next]"),
+    ('🔗', "[link]"),
+    ('🗂', "[list]"),
+    ('📊', "[stats]"),
+    ('•', "*"),
+    ('·', "-"),
+    ('─', "-"),
+    ('═', "="),
+    ('│', "|"),
+    ('→', "->"),
+    ('←', "<-"),
+    ('↻', "[retry]"),
+    ('▶', ">"),
+    ('…', "..."),
+    ('—', "--"),
+    ('×', "x"),
+    ('█', "#"),
+    ('░', "."),
+];
+
+/// ASCII 출력을 켜야 하는지 (이미 자식 프로세스면 `false`)
+pub fn is_requested(flag: bool) -> bool {
+    let enabled = flag || std::env::var(ASCII_ENV).is_ok_and(|value| matches!(value.trim(), "1" | "true" | "yes" | "on"));
+    enabled && std::env::var_os(CHILD_ENV).is_none()
+}
+
+/// 출력 조각을 이어 받아 바꿔 주는 변환기 (조각 경계에서 잘린 UTF-8 문자와 지운 그림 문자 뒤의 공백 처리)
+#[derive(Debug, Default)]
+struct Transliterator {
+    pending: Vec<u8>,
+    dropped: bool,
+}
+
+impl Transliterator {
+    /// 받은 바이트를 바꿔 돌려줌 (끝에 잘린 문자는 다음 조각과 합침)
+    fn push(&mut self, bytes: &[u8]) -> String {
+        self.pending.extend_from_slice(bytes);
+        let mut out = String::new();
+        loop {
+            match std::str::from_utf8(&self.pending) {
+                Ok(text) => {
+                    let text = text.to_string();
+                    self.convert(&text, &mut out);
+                    self.pending.clear();
+                    return out;
+                }
+                Err(e) => {
+                    let valid = e.valid_up_to();
+                    let text = String::from_utf8_lossy(&self.pending[..valid]).into_owned();
+                    self.convert(&text, &mut out);
+                    match e.error_len() {
+                        // 잘못된 바이트는 `?`로
+                        Some(len) => {
+                            out.push('?');
+                            self.pending.drain(..valid + len);
+                        }
+                        None => {
+                            self.pending.drain(..valid);
+                            return out;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn convert(&mut self, text: &str, out: &mut String) {
+        for c in text.chars() {
+            // 이모지 변형 선택자와 결합 문자는 앞 문자와 함께 처리
+            if matches!(c, '\u{FE0E}' | '\u{FE0F}' | '\u{200D}') {
+                continue;
+            }
+            // 지운 그림 문자 뒤의 공백도 지워 줄이 공백으로 시작하지 않게 함
+            if self.dropped && c == ' ' {
+                continue;
+            }
+            self.dropped = false;
+            match GLYPHS.iter().find(|(glyph, _)| *glyph == c) {
+                Some((_, text)) => out.push_str(text),
+                None if is_symbol(c) => self.dropped = true,
+                None => out.push(c),
+            }
+        }
+    }
+}
+
+/// 글자가 아닌 그림 문자, 화살표, 상자 그리기/점자(스피너) 문자인지
+fn is_symbol(c: char) -> bool {
+    matches!(c,
+        '\u{2190}'..='\u{21FF}'
+        | '\u{2300}'..='\u{23FF}'
+        | '\u{2500}'..='\u{27BF}'
+        | '\u{2800}'..='\u{28FF}'
+        | '\u{2B00}'..='\u{2BFF}'
+        | '\u{1F000}'..='\u{1FAFF}')
+}
+
+/// 같은 명령을 자식 프로세스로 실행하고 출력을 바꿔 씀 (자식의 종료 코드를 돌려줌)
+/// `--json`이면 표준 출력은 결과 JSON이므로 문자열 값이 바뀌지 않도록 그대로 두고 표준 에러만 바꿈
+pub fn run_as_child(json: bool) -> Result<i32> {
+    let mut child = Command::new(std::env::current_exe()?)
+        .args(std::env::args_os().skip(1))
+        .env(CHILD_ENV, "1")
+        .env("NO_COLOR", "1")
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| anyhow!("Failed to run ai-cli for --ascii: {}", e))?;
+
+    let stdout = child.stdout.take().map(|from| std::thread::spawn(move || forward(from, io::stdout(), !json)));
+    let stderr = child.stderr.take().map(|from| std::thread::spawn(move || forward(from, io::stderr(), true)));
+    let status = child.wait()?;
+    for thread in stdout.into_iter().chain(stderr) {
+        let _ = thread.join();
+    }
+    Ok(status.code().unwrap_or(1))
+}
+
+/// 자식 출력을 바꿔 씀 (`transliterate`가 false면 그대로 전달, 줄바꿈 없는 확인 프롬프트도 바로 보이도록 조각마다 flush)
+fn forward(mut from: impl Read, mut to: impl Write, transliterate: bool) {
+    let mut transliterator = Transliterator::default();
+    let mut buffer = [0u8; 4096];
+    while let Ok(read) = from.read(&mut buffer) {
+        if read == 0 {
+            break;
+        }
+        let written = if transliterate {
+            to.write_all(transliterator.push(&buffer[..read]).as_bytes())
+        } else {
+            to.write_all(&buffer[..read])
+        };
+        if written.and_then(|()| to.flush()).is_err() {
+            break;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transliterate() {
+        let mut t = Transliterator::default();
+        assert_eq!(t.push("✅ Commit created\n⚠️  Large diff → trimmed…\n".as_bytes()), "[ok] Commit created\n[warning]  Large diff -> trimmed...\n");
+        assert_eq!(t.push("🧮 Embedding · 3 chunk(s)\n🗑️  Removed\n".as_bytes()), "Embedding - 3 chunk(s)\nRemoved\n");
+        assert_eq!(t.push("• 한글 그대로 ─│\n".as_bytes()), "* 한글 그대로 -|\n");
+
+        // 조각 경계에서 잘린 문자
+        let bytes = "🤖 ok".as_bytes();
+        assert_eq!(t.push(&bytes[..2]), "");
+        assert_eq!(t.push(&bytes[2..]), "[ai] ok");
+        assert_eq!(t.push(b"bad \xff byte"), "bad ? byte");
+    }
+
+    #[test]
+    fn test_forward_keeps_json_untouched() {
+        let json = "{\"result\":{\"message\":\"✨ add → parser\"}}\n";
+
+        let mut out = Vec::new();
+        forward(json.as_bytes(), &mut out, false);
+        assert_eq!(String::from_utf8(out).unwrap(), json);
+
+        let mut out = Vec::new();
+        forward("✅ done\n".as_bytes(), &mut out, true);
+        assert_eq!(String::from_utf8(out).unwrap(), "[ok] done\n");
+    }
+}
@@ -0,0 +1,241 @@
+use anyhow::{Result, anyhow};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// 감사 로그 모듈
+/// AI 요청과 실행된 명령어를 해시 체인으로 연결된 추가 전용(append-only) 로그에 기록
+/// 각 항목은 이전 항목의 해시를 포함하므로 중간 항목을 수정하거나 삭제하면 검증에 실패
+///
+/// 첫 항목의 이전 해시
+const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// 감사 이벤트 종류
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AuditEvent {
+    /// AI 백엔드로 보낸 요청
+    AiRequest {
+        backend: String,
+        model: String,
+        prompt_sha256: String,
+        bytes_sent: usize,
+        files: Vec<String>,
+    },
+    /// 실행된 명령어
+    Command {
+        command: String,
+        exit_code: Option<i32>,
+    },
+}
+
+/// 감사 로그 항목
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub seq: u64,
+    pub timestamp: String,
+    pub event: AuditEvent,
+    pub prev_hash: String,
+    pub hash: String,
+}
+
+/// 해시 계산 대상 (hash 필드를 제외한 항목)
+#[derive(Serialize)]
+struct HashedFields<'a> {
+    seq: u64,
+    timestamp: &'a str,
+    event: &'a AuditEvent,
+    prev_hash: &'a str,
+}
+
+impl AuditEntry {
+    /// 항목 내용으로 해시 계산
+    fn compute_hash(&self) -> Result<String> {
+        let fields = HashedFields {
+            seq: self.seq,
+            timestamp: &self.timestamp,
+            event: &self.event,
+            prev_hash: &self.prev_hash,
+        };
+        Ok(sha256_hex(serde_json::to_string(&fields)?.as_bytes()))
+    }
+}
+
+/// 감사 로그
+pub struct AuditLog {
+    path: PathBuf,
+}
+
+impl AuditLog {
+    /// 지정한 경로의 감사 로그
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// 기본 감사 로그 (~/.ai-cli/audit.log)
+    pub fn open_default() -> Result<Self> {
+        let home_dir = dirs::home_dir().ok_or_else(|| anyhow!("Could not find home directory"))?;
+        Ok(Self::new(home_dir.join(".ai-cli").join("audit.log")))
+    }
+
+    /// 로그 파일 경로
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// 새 이벤트 추가
+    pub fn append(&self, event: AuditEvent) -> Result<AuditEntry> {
+        let last = self.entries()?.pop();
+        let (seq, prev_hash) = match last {
+            Some(entry) => (entry.seq + 1, entry.hash),
+            None => (1, GENESIS_HASH.to_string()),
+        };
+
+        let mut entry = AuditEntry {
+            seq,
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            event,
+            prev_hash,
+            hash: String::new(),
+        };
+        entry.hash = entry.compute_hash()?;
+
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(file, "{}", serde_json::to_string(&entry)?)?;
+
+        Ok(entry)
+    }
+
+    /// 모든 항목 읽기
+    pub fn entries(&self) -> Result<Vec<AuditEntry>> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+
+        fs::read_to_string(&self.path)?
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(index, line)| {
+                serde_json::from_str(line)
+                    .map_err(|e| anyhow!("Malformed audit log entry at line {}: {}", index + 1, e))
+            })
+            .collect()
+    }
+
+    /// 해시 체인 검증 (검증된 항목 수 반환)
+    pub fn verify(&self) -> Result<usize> {
+        let entries = self.entries()?;
+        let mut expected_prev = GENESIS_HASH.to_string();
+
+        for (index, entry) in entries.iter().enumerate() {
+            let expected_seq = index as u64 + 1;
+            if entry.seq != expected_seq {
+                return Err(anyhow!("Audit log broken at entry {}: expected sequence {}, found {}", index + 1, expected_seq, entry.seq));
+            }
+            if entry.prev_hash != expected_prev {
+                return Err(anyhow!("Audit log broken at entry {}: previous hash does not match", entry.seq));
+            }
+            if entry.compute_hash()? != entry.hash {
+                return Err(anyhow!("Audit log broken at entry {}: entry was modified", entry.seq));
+            }
+            expected_prev = entry.hash.clone();
+        }
+
+        Ok(entries.len())
+    }
+}
+
+/// AI 요청 기록 (기록 실패는 경고만 남기고 계속 진행)
+pub fn record_ai_request(backend: &str, model: &str, prompt: &str) {
+    record(AuditEvent::AiRequest {
+        backend: backend.to_string(),
+        model: model.to_string(),
+        prompt_sha256: sha256_hex(prompt.as_bytes()),
+        bytes_sent: prompt.len(),
+        files: referenced_files(prompt),
+    });
+}
+
+/// 실행된 명령어 기록 (기록 실패는 경고만 남기고 계속 진행)
+pub fn record_command(command: &str, exit_code: Option<i32>) {
+    record(AuditEvent::Command {
+        command: command.to_string(),
+        exit_code,
+    });
+}
+
+fn record(event: AuditEvent) {
+    let result = AuditLog::open_default().and_then(|log| log.append(event));
+    if let Err(e) = result {
+        tracing::warn!("Failed to write audit log: {}", e);
+    }
+}
+
+/// 프롬프트에 포함된 diff에서 파일 경로 추출 (`diff --git a/<path> b/<path>`)
+pub fn referenced_files(prompt: &str) -> Vec<String> {
+    let mut files: Vec<String> = prompt
+        .lines()
+        .filter_map(|line| line.strip_prefix("diff --git a/"))
+        .filter_map(|rest| rest.split(" b/").last())
+        .map(|path| path.trim().to_string())
+        .collect();
+    files.dedup();
+    files
+}
+
+/// SHA-256 해시를 16진수 문자열로 변환
+fn sha256_hex(data: &[u8]) -> String {
+    Sha256::digest(data)
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_append_and_verify() {
+        let temp_dir = TempDir::new().unwrap();
+        let log = AuditLog::new(temp_dir.path().join("audit.log"));
+
+        let first = log.append(AuditEvent::Command { command: "git status".to_string(), exit_code: Some(0) }).unwrap();
+        let second = log.append(AuditEvent::Command { command: "git log".to_string(), exit_code: Some(0) }).unwrap();
+
+        assert_eq!(first.prev_hash, GENESIS_HASH);
+        assert_eq!(second.prev_hash, first.hash);
+        assert_eq!(log.verify().unwrap(), 2);
+    }
+
+    #[test]
+    fn test_verify_detects_tampering() {
+        let temp_dir = TempDir::new().unwrap();
+        let log = AuditLog::new(temp_dir.path().join("audit.log"));
+
+        log.append(AuditEvent::Command { command: "git status".to_string(), exit_code: Some(0) }).unwrap();
+        log.append(AuditEvent::Command { command: "git log".to_string(), exit_code: Some(0) }).unwrap();
+
+        let content = fs::read_to_string(log.path()).unwrap();
+        fs::write(log.path(), content.replace("git status", "rm -rf /")).unwrap();
+
+        assert!(log.verify().is_err());
+    }
+
+    #[test]
+    fn test_referenced_files() {
+        let prompt = "diff --git a/src/main.rs b/src/main.rs\n+fn main() {}\ndiff --git a/README.md b/README.md\n";
+        assert_eq!(referenced_files(prompt), vec!["src/main.rs", "README.md"]);
+    }
+}
@@ -0,0 +1,266 @@
+use std::io::{self, IsTerminal, Write};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::review;
+use crate::spinner;
+
+/// 체크리스트 진행 표시 모듈
+/// 파일 여러 개를 차례로 AI에 보내는 작업에서 파일마다 상태(대기, 진행, 재시도, 완료, 건너뜀), 토큰 수, 소요 시간을 표시
+/// 표준 에러가 터미널이면 목록을 제자리에서 다시 그리고(파일이 많으면 끝나지 않은 파일만), 끝나면 전체 목록을 남김
+/// 터미널이 아니면(파이프, `--ascii`) 파일이 끝날 때마다 한 줄씩 출력하고, `--quiet`이면 실패한 파일만 알림
+const TICK: Duration = Duration::from_millis(200);
+
+/// 다시 그리는 동안 보여 줄 파일 줄 수 (넘으면 끝난 파일을 접음)
+const LIVE_ROWS: usize = 10;
+
+/// 오류 메시지를 줄일 길이
+const ERROR_CHARS: usize = 80;
+
+/// 파일 하나의 상태
+#[derive(Debug, Clone, PartialEq)]
+enum Status {
+    Pending,
+    Running(Instant),
+    Retrying { started: Instant, error: String },
+    Done { tokens: Option<u32>, elapsed: Duration },
+    Failed { error: String },
+}
+
+#[derive(Debug, Clone)]
+struct Item {
+    name: String,
+    status: Status,
+}
+
+/// 표시 방식
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    Live,
+    Lines,
+    Quiet,
+}
+
+struct State {
+    title: String,
+    items: Vec<Item>,
+    drawn: usize,
+    width: usize,
+    finished: bool,
+}
+
+/// 파일별 진행 목록 (버리면 마지막 목록을 남기고 끝남)
+pub struct Checklist {
+    state: Arc<Mutex<State>>,
+    mode: Mode,
+    _spinner: Option<spinner::Suspended>,
+}
+
+impl Checklist {
+    /// 목록 표시 시작 (모든 파일은 대기 상태)
+    pub fn start(title: impl Into<String>, names: Vec<String>) -> Self {
+        let mode = if spinner::is_quiet() {
+            Mode::Quiet
+        } else if io::stderr().is_terminal() {
+            Mode::Live
+        } else {
+            Mode::Lines
+        };
+        let state = State {
+            title: title.into(),
+            items: names.into_iter().map(|name| Item { name, status: Status::Pending }).collect(),
+            drawn: 0,
+            width: review::terminal_size().map_or(80, |(_, columns)| columns),
+            finished: false,
+        };
+        if mode == Mode::Lines {
+            eprintln!("{}", state.title);
+        }
+        let state = Arc::new(Mutex::new(state));
+        // 목록을 그리는 동안 AI 요청 스피너가 같은 줄을 덮어쓰지 않게 멈춤
+        let suspended = (mode == Mode::Live).then(spinner::suspend);
+        if mode == Mode::Live {
+            let state = Arc::clone(&state);
+            thread::spawn(move || loop {
+                thread::sleep(TICK);
+                let mut state = state.lock().unwrap_or_else(|e| e.into_inner());
+                if state.finished {
+                    return;
+                }
+                redraw(&mut state);
+            });
+        }
+        Self { state, mode, _spinner: suspended }
+    }
+
+    /// 파일 처리 시작
+    pub fn running(&self, index: usize) {
+        self.update(index, Status::Running(Instant::now()));
+    }
+
+    /// 실패해서 다시 시도하는 중
+    pub fn retrying(&self, index: usize, error: &str) {
+        self.update(index, Status::Retrying { started: Instant::now(), error: short_error(error) });
+    }
+
+    /// 파일 처리 완료 (쓴 토큰 수)
+    pub fn done(&self, index: usize, tokens: Option<u32>) {
+        let elapsed = self.started(index).map(|started| started.elapsed()).unwrap_or_default();
+        self.update(index, Status::Done { tokens, elapsed });
+    }
+
+    /// 다시 시도해도 실패해 건너뜀
+    pub fn failed(&self, index: usize, error: &str) {
+        self.update(index, Status::Failed { error: short_error(error) });
+    }
+
+    fn started(&self, index: usize) -> Option<Instant> {
+        let state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        match state.items.get(index)?.status {
+            Status::Running(started) | Status::Retrying { started, .. } => Some(started),
+            _ => None,
+        }
+    }
+
+    fn update(&self, index: usize, status: Status) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let Some(item) = state.items.get_mut(index) else {
+            return;
+        };
+        item.status = status;
+        let line = item_line(item);
+        let running = matches!(item.status, Status::Running(_));
+        let failed = matches!(item.status, Status::Failed { .. });
+        match self.mode {
+            Mode::Live => redraw(&mut state),
+            Mode::Lines if !running => eprintln!("{}", line),
+            Mode::Quiet if failed => eprintln!("{}", line),
+            _ => {}
+        }
+    }
+}
+
+impl Drop for Checklist {
+    fn drop(&mut self) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.finished = true;
+        if self.mode == Mode::Live {
+            let mut stderr = io::stderr();
+            let _ = write!(stderr, "{}", clear(state.drawn));
+            let _ = writeln!(stderr, "{}", title_line(&state));
+            for item in &state.items {
+                let _ = writeln!(stderr, "{}", item_line(item));
+            }
+            let _ = stderr.flush();
+        }
+    }
+}
+
+/// 그린 줄로 돌아가 지우는 제어 문자
+fn clear(drawn: usize) -> String {
+    if drawn == 0 {
+        "\r\x1b[J".to_string()
+    } else {
+        format!("\x1b[{}F\x1b[J", drawn)
+    }
+}
+
+/// 목록을 제자리에서 다시 그림 (터미널 너비를 넘는 줄은 잘라 줄 수가 바뀌지 않게 함)
+fn redraw(state: &mut State) {
+    let width = state.width.saturating_sub(1);
+    let lines = live_lines(state);
+    let mut screen = clear(state.drawn);
+    for line in &lines {
+        screen.push_str(&line.chars().take(width).collect::<String>());
+        screen.push('\n');
+    }
+    let mut stderr = io::stderr();
+    let _ = write!(stderr, "{}", screen);
+    let _ = stderr.flush();
+    state.drawn = lines.len();
+}
+
+/// 다시 그릴 줄 (파일이 많으면 끝난 파일을 제목의 개수로만 보여 줌)
+fn live_lines(state: &State) -> Vec<String> {
+    let mut lines = vec![title_line(state)];
+    if state.items.len() <= LIVE_ROWS {
+        lines.extend(state.items.iter().map(item_line));
+        return lines;
+    }
+    let open: Vec<&Item> = state.items.iter().filter(|item| !matches!(item.status, Status::Done { .. })).collect();
+    lines.extend(open.iter().take(LIVE_ROWS).map(|item| item_line(item)));
+    if open.len() > LIVE_ROWS {
+        lines.push(format!("  … {} more", open.len() - LIVE_ROWS));
+    }
+    lines
+}
+
+/// 제목 줄 (`Summarizing files 3/12`)
+fn title_line(state: &State) -> String {
+    let finished = state
+        .items
+        .iter()
+        .filter(|item| matches!(item.status, Status::Done { .. } | Status::Failed { .. }))
+        .count();
+    format!("{} {}/{}", state.title, finished, state.items.len())
+}
+
+/// 파일 한 줄 (`  ✓ src/main.rs  1.2k tokens  3.4s`)
+fn item_line(item: &Item) -> String {
+    match &item.status {
+        Status::Pending => format!("  · {}", item.name),
+        Status::Running(started) => format!("  … {}  {:.1}s", item.name, started.elapsed().as_secs_f64()),
+        Status::Retrying { started, error } => {
+            format!("  ↻ {}  retrying after: {}  {:.1}s", item.name, error, started.elapsed().as_secs_f64())
+        }
+        Status::Done { tokens, elapsed } => match tokens.filter(|tokens| *tokens > 0) {
+            Some(tokens) => format!(
+                "  ✓ {}  {} tokens  {:.1}s",
+                item.name,
+                spinner::format_count(tokens as usize),
+                elapsed.as_secs_f64()
+            ),
+            None => format!("  ✓ {}  {:.1}s", item.name, elapsed.as_secs_f64()),
+        },
+        Status::Failed { error } => format!("  ✗ {}  skipped: {}", item.name, error),
+    }
+}
+
+/// 오류 메시지 첫 줄을 짧게
+fn short_error(error: &str) -> String {
+    let line = error.lines().next().unwrap_or_default().trim();
+    if line.chars().count() > ERROR_CHARS {
+        format!("{}…", line.chars().take(ERROR_CHARS).collect::<String>())
+    } else {
+        line.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(name: &str, status: Status) -> Item {
+        Item { name: name.to_string(), status }
+    }
+
+    #[test]
+    fn test_checklist_lines() {
+        assert_eq!(item_line(&item("README.md", Status::Pending)), "  · README.md");
+        let done = Status::Done { tokens: Some(1234), elapsed: Duration::from_millis(3420) };
+        assert_eq!(item_line(&item("src/main.rs", done)), "  ✓ src/main.rs  1.2k tokens  3.4s");
+        let failed = Status::Failed { error: short_error("Ollama API error: model not found\nmore") };
+        assert_eq!(item_line(&item("src/lib.rs", failed)), "  ✗ src/lib.rs  skipped: Ollama API error: model not found");
+
+        let mut state = State { title: "Summarizing files".to_string(), items: Vec::new(), drawn: 0, width: 80, finished: false };
+        for index in 0..12 {
+            let status = if index < 10 { Status::Done { tokens: None, elapsed: Duration::ZERO } } else { Status::Pending };
+            state.items.push(item(&format!("f{}.rs", index), status));
+        }
+        assert_eq!(live_lines(&state), vec!["Summarizing files 10/12", "  · f10.rs", "  · f11.rs"]);
+        state.items.truncate(3);
+        assert_eq!(live_lines(&state).len(), 4);
+        assert_eq!(short_error(&"x".repeat(100)).chars().count(), ERROR_CHARS + 1);
+    }
+}
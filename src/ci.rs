@@ -0,0 +1,302 @@
+use anyhow::{Result, anyhow};
+use serde_json::Value;
+use std::env;
+use std::fs;
+
+use crate::ai_utils::{self, AIBackend, ChatMessage};
+use crate::config;
+use crate::git_utils::{self, DiffSettings};
+use crate::injection;
+use crate::map_reduce;
+use crate::output;
+
+/// CI 모듈 (`ai-cli ci`)
+/// GitHub Actions의 `pull_request` 이벤트에서 실행해 PR 변경 사항으로 설명과 검토 댓글을 만들고 GitHub API로 게시
+/// 이벤트 정보는 Actions가 설정하는 `GITHUB_EVENT_PATH`, `GITHUB_REPOSITORY`, `GITHUB_API_URL`에서, 토큰은 `GITHUB_TOKEN`에서 읽음
+/// 설명은 PR 본문의 표시 블록에만 쓰므로 사람이 쓴 내용은 그대로 두고, 검토 댓글은 하나만 남겨 다시 실행하면 고침
+/// diff를 읽으려면 두 커밋이 모두 있어야 함 (`actions/checkout`의 `fetch-depth: 0`)
+///
+/// PR 본문에서 생성한 설명을 감싸는 표시
+const DESCRIPTION_START: &str = "<!-- ai-cli:description -->";
+const DESCRIPTION_END: &str = "<!-- /ai-cli:description -->";
+
+/// ai-cli가 남긴 검토 댓글 표시
+const REVIEW_MARKER: &str = "<!-- ai-cli:review -->";
+
+/// Actions의 `GITHUB_TOKEN`으로 단 댓글의 작성자
+const ACTIONS_BOT: &str = "github-actions[bot]";
+
+/// 프롬프트에 넣을 최대 커밋 수
+const MAX_COMMITS: usize = 50;
+
+/// 검토할 PR
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PullRequest {
+    number: u64,
+    title: String,
+    body: String,
+    base_sha: String,
+    head_sha: String,
+}
+
+impl PullRequest {
+    /// 이벤트 페이로드나 API 응답의 `pull_request` 객체
+    fn from_json(value: &Value) -> Option<Self> {
+        Some(Self {
+            number: value["number"].as_u64()?,
+            title: value["title"].as_str().unwrap_or_default().to_string(),
+            body: value["body"].as_str().unwrap_or_default().to_string(),
+            base_sha: value["base"]["sha"].as_str()?.to_string(),
+            head_sha: value["head"]["sha"].as_str()?.to_string(),
+        })
+    }
+}
+
+/// GitHub API 연결 정보
+struct GitHub {
+    api_url: String,
+    repository: String,
+    token: String,
+}
+
+impl GitHub {
+    fn from_env() -> Result<Self> {
+        let repository = env::var("GITHUB_REPOSITORY").map_err(|_| anyhow!("GITHUB_REPOSITORY is not set; run this inside GitHub Actions"))?;
+        let token = env::var("GITHUB_TOKEN")
+            .or_else(|_| env::var("GH_TOKEN"))
+            .map_err(|_| anyhow!("GITHUB_TOKEN is not set; pass it with 'env: GITHUB_TOKEN: ${{{{ secrets.GITHUB_TOKEN }}}}'"))?;
+        let api_url = env::var("GITHUB_API_URL").unwrap_or_else(|_| "https://api.github.com".to_string());
+        Ok(Self { api_url: api_url.trim_end_matches('/').to_string(), repository, token })
+    }
+
+    /// API 요청 (`path`는 `/repos/...`)
+    async fn request(&self, method: reqwest::Method, path: &str, body: Option<Value>) -> Result<Value> {
+        let url = format!("{}{}", self.api_url, path);
+        let client = ai_utils::http_client(&url)?;
+        let mut request = client
+            .request(method.clone(), &url)
+            .bearer_auth(&self.token)
+            .header("Accept", "application/vnd.github+json")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .header("User-Agent", concat!("ai-cli/", env!("CARGO_PKG_VERSION")));
+        if let Some(body) = body {
+            request = request.json(&body);
+        }
+        let response = request.send().await.map_err(|e| anyhow!("GitHub API request failed: {}", e))?;
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        if !status.is_success() {
+            let message = serde_json::from_str::<Value>(&text)
+                .ok()
+                .and_then(|error| error["message"].as_str().map(str::to_string))
+                .unwrap_or(text);
+            return Err(anyhow!("GitHub API {} {} returned {}: {}", method, path, status, message));
+        }
+        Ok(serde_json::from_str(&text).unwrap_or(Value::Null))
+    }
+}
+
+/// `ci pr-describe`: PR 설명과 검토 댓글을 만들어 게시 (`dry_run`이면 출력만)
+pub async fn pr_describe(number: Option<u64>, model: Option<&str>, no_review: bool, dry_run: bool) -> Result<()> {
+    let github = GitHub::from_env()?;
+    let pull_request = load_pull_request(&github, number).await?;
+    println!("🔍 Describing pull request #{} ({})", pull_request.number, pull_request.title);
+
+    let diff = git_utils::get_range_diff_with(&pull_request.base_sha, &pull_request.head_sha, &DiffSettings::default())?;
+    if diff.trim().is_empty() {
+        return Err(anyhow!("Pull request #{} has no changes to describe", pull_request.number));
+    }
+    let commits = git_utils::get_push_commits(&pull_request.head_sha, Some(&pull_request.base_sha), "origin", MAX_COMMITS)?;
+    let subjects: Vec<String> = commits
+        .iter()
+        .map(|(_, message)| format!("- {}", message.lines().next().unwrap_or_default()))
+        .collect();
+
+    let backend = ai_utils::get_ai_backend(&config::resolve_model(model))?;
+    // 큰 PR은 파일별 요약을 모아 전달
+    let analyzed = if map_reduce::should_split(&diff, false) {
+        map_reduce::summarize_files(&diff, &backend).await?
+    } else {
+        diff
+    };
+    let context = format!(
+        "PULL REQUEST TITLE:\n{}\n\nCOMMITS:\n{}\n\nCHANGES:\n{}",
+        injection::wrap_untrusted("TITLE", &pull_request.title),
+        injection::wrap_untrusted("COMMITS", &subjects.join("\n")),
+        ai_utils::wrap_diff(&analyzed)
+    );
+
+    let description = ask(
+        &backend,
+        "Write the description of this GitHub pull request in Markdown with exactly these sections: \
+         '## Summary' (two or three sentences on what changes and why), '## Changes' (one bullet per notable change), \
+         and '## Testing' (how a reviewer can verify it). Do not add a title or any preamble.",
+        &context,
+    )
+    .await?;
+    let review = if no_review {
+        None
+    } else {
+        Some(
+            ask(
+                &backend,
+                "Review this GitHub pull request like a careful senior engineer. List concrete bugs, risky changes, \
+                 missing tests and unclear code as short Markdown bullets, most important first, naming the file for each. \
+                 If nothing needs attention, reply with one sentence saying so.",
+                &context,
+            )
+            .await?,
+        )
+    };
+
+    let body = merge_description(&pull_request.body, &description);
+    let comment = review.as_deref().map(|review| review_comment(review, backend.display_name()));
+    if dry_run {
+        println!("\n{}\n", body);
+        if let Some(comment) = &comment {
+            println!("{}", comment);
+        }
+    } else {
+        let path = format!("/repos/{}/pulls/{}", github.repository, pull_request.number);
+        github.request(reqwest::Method::PATCH, &path, Some(serde_json::json!({ "body": body }))).await?;
+        println!("✅ Updated the description of #{}", pull_request.number);
+        if let Some(comment) = &comment {
+            post_review_comment(&github, pull_request.number, comment).await?;
+        }
+    }
+    output::record(serde_json::json!({
+        "pull_request": pull_request.number,
+        "description": description,
+        "review": review,
+        "posted": !dry_run,
+    }));
+    Ok(())
+}
+
+/// 이벤트 페이로드의 PR (없거나 `number`를 주면 API로 조회)
+async fn load_pull_request(github: &GitHub, number: Option<u64>) -> Result<PullRequest> {
+    if let Some(number) = number {
+        let value = github.request(reqwest::Method::GET, &format!("/repos/{}/pulls/{}", github.repository, number), None).await?;
+        return PullRequest::from_json(&value).ok_or_else(|| anyhow!("Unexpected response for pull request #{}", number));
+    }
+    let path = env::var("GITHUB_EVENT_PATH").map_err(|_| anyhow!("GITHUB_EVENT_PATH is not set; pass --pr <number> outside pull_request events"))?;
+    let event: Value = serde_json::from_str(&fs::read_to_string(&path).map_err(|e| anyhow!("Failed to read {}: {}", path, e))?)?;
+    PullRequest::from_json(&event["pull_request"]).ok_or_else(|| {
+        anyhow!(
+            "The {} event has no pull request; run on pull_request events or pass --pr <number>",
+            env::var("GITHUB_EVENT_NAME").unwrap_or_default()
+        )
+    })
+}
+
+/// 시스템 지시와 PR 내용으로 응답 받기
+async fn ask(backend: &AIBackend, instructions: &str, context: &str) -> Result<String> {
+    let system = format!("{}\n\n{}", instructions, injection::UNTRUSTED_CONTENT_RULES);
+    let messages = [ChatMessage { role: "user".to_string(), content: context.to_string() }];
+    let response = ai_utils::generate_chat(backend, Some(&system), &messages, 800, Some(0.3)).await?;
+    if let Some(usage) = &response.usage {
+        output::add_usage(usage);
+    }
+    Ok(response.content.trim().to_string())
+}
+
+/// PR 본문에 생성한 설명 넣기 (표시 블록이 있으면 그 안만 바꾸고, 없으면 사람이 쓴 내용 뒤에 추가)
+fn merge_description(body: &str, description: &str) -> String {
+    let block = format!("{}\n{}\n{}", DESCRIPTION_START, description.trim(), DESCRIPTION_END);
+    if let (Some(start), Some(end)) = (body.find(DESCRIPTION_START), body.find(DESCRIPTION_END)) {
+        if start < end {
+            return format!("{}{}{}", &body[..start], block, &body[end + DESCRIPTION_END.len()..]);
+        }
+    }
+    if body.trim().is_empty() {
+        block
+    } else {
+        format!("{}\n\n{}", body.trim_end(), block)
+    }
+}
+
+/// 검토 댓글 본문
+fn review_comment(review: &str, model: &str) -> String {
+    format!("{}\n### 🤖 ai-cli review\n\n{}\n\n<sub>Generated by ai-cli with {}. Re-runs update this comment.</sub>", REVIEW_MARKER, review.trim(), model)
+}
+
+/// 검토 댓글 게시 (이전에 남긴 댓글이 있으면 고침)
+async fn post_review_comment(github: &GitHub, number: u64, comment: &str) -> Result<()> {
+    let comments = github
+        .request(reqwest::Method::GET, &format!("/repos/{}/issues/{}/comments?per_page=100", github.repository, number), None)
+        .await?;
+    let login = token_login(github).await;
+    let existing = own_review_comment(&comments, &login);
+    let body = serde_json::json!({ "body": comment });
+    match existing {
+        Some(id) => {
+            github.request(reqwest::Method::PATCH, &format!("/repos/{}/issues/comments/{}", github.repository, id), Some(body)).await?;
+            println!("✅ Updated the review comment on #{}", number);
+        }
+        None => {
+            github.request(reqwest::Method::POST, &format!("/repos/{}/issues/{}/comments", github.repository, number), Some(body)).await?;
+            println!("✅ Posted a review comment on #{}", number);
+        }
+    }
+    Ok(())
+}
+
+/// 토큰으로 댓글을 다는 계정
+/// 개인 토큰은 `/user`의 로그인이고, Actions의 `GITHUB_TOKEN` 같은 앱 설치 토큰은 `/user`를 읽을 수 없으므로 `github-actions[bot]`
+async fn token_login(github: &GitHub) -> String {
+    github
+        .request(reqwest::Method::GET, "/user", None)
+        .await
+        .ok()
+        .and_then(|user| user["login"].as_str().map(str::to_string))
+        .unwrap_or_else(|| ACTIONS_BOT.to_string())
+}
+
+/// 이 토큰의 계정이 단 검토 댓글 ID (다른 사람이 표시를 붙여 단 댓글은 고치지 않음)
+fn own_review_comment(comments: &Value, login: &str) -> Option<u64> {
+    comments
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|existing| existing["user"]["login"].as_str() == Some(login))
+        .find(|existing| existing["body"].as_str().is_some_and(|body| body.starts_with(REVIEW_MARKER)))
+        .and_then(|existing| existing["id"].as_u64())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_description() {
+        let block = format!("{}\n## Summary\nNew.\n{}", DESCRIPTION_START, DESCRIPTION_END);
+        assert_eq!(merge_description("", "## Summary\nNew."), block);
+        assert_eq!(merge_description("Fixes #12\n", "## Summary\nNew."), format!("Fixes #12\n\n{}", block));
+
+        // 다시 실행하면 표시 블록만 바뀜
+        let existing = format!("Fixes #12\n\n{}\n## Summary\nOld.\n{}\n\nThanks!", DESCRIPTION_START, DESCRIPTION_END);
+        assert_eq!(merge_description(&existing, "## Summary\nNew."), format!("Fixes #12\n\n{}\n\nThanks!", block));
+
+        let event = serde_json::json!({
+            "number": 7,
+            "title": "Add parser",
+            "body": null,
+            "base": { "sha": "a".repeat(40) },
+            "head": { "sha": "b".repeat(40) },
+        });
+        let pull_request = PullRequest::from_json(&event).unwrap();
+        assert_eq!((pull_request.number, pull_request.body.as_str()), (7, ""));
+        assert!(review_comment("- Looks fine", "Ollama").starts_with(REVIEW_MARKER));
+    }
+
+    #[test]
+    fn test_own_review_comment() {
+        let comments = serde_json::json!([
+            { "id": 1, "user": { "login": "mallory" }, "body": format!("{}\nfake review", REVIEW_MARKER) },
+            { "id": 2, "user": { "login": ACTIONS_BOT }, "body": "Thanks!" },
+            { "id": 3, "user": { "login": ACTIONS_BOT }, "body": format!("{}\n### 🤖 ai-cli review", REVIEW_MARKER) },
+        ]);
+        assert_eq!(own_review_comment(&comments, ACTIONS_BOT), Some(3));
+        assert_eq!(own_review_comment(&comments, "alice"), None);
+    }
+}
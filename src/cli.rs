@@ -1,4 +1,7 @@
-use clap::{Parser, Subcommand};
+use clap::{Args, Parser, Subcommand};
+
+use crate::context::{ContextLayer, ContextLayers};
+use crate::git_utils::DiffSettings;
 
 #[derive(Parser)]
 #[command(
@@ -11,6 +14,51 @@ use clap::{Parser, Subcommand};
 pub struct Cli {
     #[command(subcommand)]
     pub command: Commands,
+
+    /// Never send repository content to non-loopback hosts (same as AI_CLI_PRIVACY=local-only)
+    #[arg(long, global = true)]
+    pub local_only: bool,
+
+    /// Use a named profile from the config file (same as AI_CLI_PROFILE)
+    #[arg(long, global = true, value_name = "NAME")]
+    pub profile: Option<String>,
+
+    /// Hide spinners and progress lines (same as AI_CLI_QUIET=1)
+    #[arg(short, long, global = true)]
+    pub quiet: bool,
+
+    /// Print one JSON object (result, usage, timing, error) on stdout and send human-readable output to stderr
+    #[arg(long, global = true)]
+    pub json: bool,
+
+    /// Print long output directly instead of through $PAGER / core.pager (same as AI_CLI_NO_PAGER=1)
+    #[arg(long, global = true)]
+    pub no_pager: bool,
+
+    /// Replace emoji and box-drawing glyphs with plain text and print line by line without colors, spinners or the full-screen review (same as AI_CLI_ASCII=1)
+    #[arg(long, global = true)]
+    pub ascii: bool,
+
+    /// Don't show one-line tips about features you haven't used yet (same as 'ai-cli config set tips off')
+    #[arg(long, global = true)]
+    pub no_tips: bool,
+
+    /// Log level for console output and the log file (off, error, warn, info, debug, trace; same as AI_CLI_LOG_LEVEL)
+    #[arg(long, global = true, value_name = "LEVEL", value_parser = ["off", "error", "warn", "info", "debug", "trace"])]
+    pub log_level: Option<String>,
+
+    /// Also write logs (git operations, AI requests, MCP traffic; no prompts or secrets) to ~/.ai-cli/logs (same as AI_CLI_LOG_FILE=1)
+    #[arg(long, global = true)]
+    pub log_file: bool,
+}
+
+/// 명령줄에서 실행한 하위 명령 이름 (`ai-cli --quiet commit` → `commit`)
+pub fn subcommand_name(args: &[String]) -> Option<String> {
+    let subcommands: Vec<String> = <Cli as clap::CommandFactory>::command()
+        .get_subcommands()
+        .map(|subcommand| subcommand.get_name().to_string())
+        .collect();
+    args.iter().skip(1).find(|arg| subcommands.contains(arg)).cloned()
 }
 
 #[derive(Subcommand)]
@@ -19,63 +67,553 @@ pub enum Commands {
     Commit {
         /// Provide extra context or instructions to the AI
         #[arg(short, long)]
-        pub message: Option<String>,
+        message: Option<String>,
 
         /// Automatically stage all changes (git add -A) before committing
         #[arg(short, long)]
-        pub all: bool,
+        all: bool,
 
-        /// Use specific AI model (local: ollama, remote: openai, anthropic)
-        #[arg(short, long, default_value = "local")]
-        pub model: String,
+        /// Use specific AI model (local: ollama, remote: openai, anthropic; defaults to default_model in the config file, otherwise local with an OpenAI fallback)
+        #[arg(long)]
+        model: Option<String>,
 
         /// Force commit without confirmation (use with caution)
         #[arg(short, long)]
-        pub yes: bool,
+        yes: bool,
+
+        /// Bypass pre-commit and commit-msg hooks (git commit --no-verify)
+        #[arg(long)]
+        no_verify: bool,
+
+        /// Number of recent commit subjects touching the staged files to include as style examples (0 to disable)
+        #[arg(long, default_value_t = 10)]
+        history: usize,
+
+        /// Print exactly what would be sent to the remote backend and exit
+        #[arg(long)]
+        show_payload: bool,
+
+        /// Use the plain approval prompts instead of the full-screen review (diff, message, accept/edit/regenerate/model keys)
+        #[arg(long)]
+        no_review: bool,
+
+        /// Copy the generated message to the system clipboard (you can still cancel the commit and paste it elsewhere)
+        #[arg(long)]
+        copy: bool,
+
+        #[command(flatten)]
+        diff: DiffArgs,
+
+        #[command(flatten)]
+        context: ContextArgs,
     },
 
     /// Explain the staged (or specific commit) changes in natural language
     Explain {
         /// Target a specific commit hash instead of staged changes
         #[arg(long)]
-        pub hash: Option<String>,
+        hash: Option<String>,
 
-        /// Use specific AI model
-        #[arg(short, long, default_value = "local")]
-        pub model: String,
+        /// Use specific AI model (defaults to default_model in the config file, then local)
+        #[arg(short, long)]
+        model: Option<String>,
 
         /// Output format (text, markdown, json)
         #[arg(short, long, default_value = "text")]
-        pub format: String,
+        format: String,
 
         /// Include detailed line-by-line analysis
         #[arg(long)]
-        pub detailed: bool,
+        detailed: bool,
+
+        /// Print the analyzed diff (colored and syntax-highlighted on a terminal) before the analysis
+        #[arg(long)]
+        show_diff: bool,
+
+        /// Summarize each changed file separately, then explain the summaries (automatic for diffs over 40 KB)
+        #[arg(long)]
+        per_file: bool,
+
+        /// Skip confirmation prompts (large remote requests are still capped by egress.max_bytes)
+        #[arg(short, long)]
+        yes: bool,
+
+        /// Print exactly what would be sent to the remote backend and exit
+        #[arg(long)]
+        show_payload: bool,
+
+        /// Share the analysis on a Slack channel after approval (e.g. #team-channel)
+        #[arg(long, value_name = "CHANNEL")]
+        notify: Option<String>,
+
+        /// Copy the analysis to the system clipboard
+        #[arg(long)]
+        copy: bool,
+
+        #[command(flatten)]
+        diff: DiffArgs,
+
+        #[command(flatten)]
+        context: ContextArgs,
     },
 
     /// Initialize AI CLI configuration
     Init {
         /// Set default AI model
         #[arg(short, long)]
-        pub model: Option<String>,
+        model: Option<String>,
 
         /// Set OpenAI API key
         #[arg(long)]
-        pub openai_key: Option<String>,
+        openai_key: Option<String>,
 
         /// Set Anthropic API key
         #[arg(long)]
-        pub anthropic_key: Option<String>,
+        anthropic_key: Option<String>,
 
         /// Ollama server URL
         #[arg(long, default_value = "http://localhost:11434")]
-        pub ollama_url: String,
+        ollama_url: String,
+    },
+
+    /// Build or update the semantic index used to retrieve relevant code for prompts
+    Index {
+        /// Embedding backend (local: ollama, remote: openai)
+        #[arg(short, long, default_value = "local")]
+        model: String,
+
+        /// Re-embed every file instead of only changed ones
+        #[arg(long)]
+        rebuild: bool,
+
+        /// Keep running and update the index whenever files or git refs change
+        #[arg(long)]
+        watch: bool,
+
+        /// Seconds between change checks in --watch mode
+        #[arg(long, value_name = "SECONDS", default_value_t = 2, requires = "watch")]
+        interval: u64,
+
+        /// Skip confirmation prompts (large remote requests are still capped by egress.max_bytes)
+        #[arg(short, long)]
+        yes: bool,
+    },
+
+    /// Search the semantic index for code related to a natural-language query
+    Search {
+        /// What to look for (e.g. "where do we retry failed requests")
+        #[arg(required = true)]
+        query: Vec<String>,
+
+        /// Number of matches to show
+        #[arg(short = 'k', long, default_value_t = 5)]
+        top_k: usize,
+
+        /// Lines of each match to preview
+        #[arg(long, default_value_t = crate::index::DEFAULT_SNIPPET_LINES)]
+        lines: usize,
+
+        /// Output format (text, json)
+        #[arg(short, long, default_value = "text")]
+        format: String,
+
+        /// Skip confirmation prompts (large remote requests are still capped by egress.max_bytes)
+        #[arg(short, long)]
+        yes: bool,
+    },
+
+    /// Check a commit message against the configured [commit] rules
+    Lint {
+        /// Message to check (default: the last commit's message)
+        message: Option<String>,
+
+        /// Read the message from a file (e.g. .git/COMMIT_EDITMSG)
+        #[arg(short = 'F', long, conflicts_with = "message")]
+        file: Option<String>,
+    },
+
+    /// Run in CI (e.g. a GitHub Actions job on pull_request events)
+    Ci {
+        #[command(subcommand)]
+        action: CiAction,
+    },
+
+    /// Run as a git hook (e.g. 'exec ai-cli hook commit-msg "$1"' in .git/hooks/commit-msg)
+    Hook {
+        #[command(subcommand)]
+        action: HookAction,
+    },
+
+    /// Inspect the tamper-evident audit log of AI requests and executed commands
+    Audit {
+        #[command(subcommand)]
+        action: AuditAction,
+    },
+
+    /// Review what a past session did (prompts, responses, approvals, commands, MCP tool calls); lists recent sessions without an argument
+    Replay {
+        /// Transcript file, its name in ~/.ai-cli/transcripts, or 'last'
+        transcript: Option<String>,
+
+        /// Show prompts, responses and command output in full instead of the first lines
+        #[arg(long)]
+        full: bool,
+    },
+
+    /// Show local usage stats (command counts, how often generated messages are accepted, backend latency); enable with 'ai-cli config set stats on'
+    Stats {
+        /// Show a terminal dashboard with bar charts instead of the one-line summary
+        #[arg(long)]
+        dashboard: bool,
+
+        /// Delete the recorded stats
+        #[arg(long, conflicts_with = "dashboard")]
+        reset: bool,
+    },
+
+    /// Manage and inspect the MCP servers configured in ~/.ai-cli/mcp.json
+    Mcp {
+        #[command(subcommand)]
+        action: McpAction,
+    },
+
+    /// Manage project context (PROJECT.md / NOTES.md)
+    Context {
+        #[command(subcommand)]
+        action: ContextAction,
     },
 
-    /// Show current configuration
+    /// Show or change configuration (~/.config/ai-cli/config.toml)
     Config {
         /// Show all configuration details
         #[arg(short, long)]
-        pub verbose: bool,
+        verbose: bool,
+
+        #[command(subcommand)]
+        action: Option<ConfigAction>,
+    },
+}
+
+/// config 하위 명령어
+#[derive(Subcommand)]
+pub enum ConfigAction {
+    /// Set a value in the config file (e.g. openai.model gpt-4o)
+    Set {
+        /// Config key (default_model, ollama.url, ollama.model, openai.api_key, openai.model, anthropic.api_key, anthropic.model)
+        key: String,
+
+        /// New value
+        value: String,
+    },
+
+    /// Remove a value from the config file
+    Unset {
+        /// Config key
+        key: String,
+    },
+
+    /// Open the config file in $EDITOR and save it only if it is valid
+    Edit {
+        /// Edit the repository's .ai-cli.toml instead of the user config
+        #[arg(long)]
+        repo: bool,
     },
+
+    /// Save settings from legacy environment variables to the config file (API keys go to the OS keychain)
+    Migrate,
+
+    /// Review the repository's .ai-cli/team.toml and .ai-cli.toml and approve or ignore settings that change where requests go
+    Team,
+
+    /// Show each effective setting and where it comes from (flag, env, repo config, profile, global config, default)
+    Where,
+
+    /// Bundle settings, MCP servers, CONFIG.md, policy.toml and profile prompt templates into one file
+    Export {
+        /// Leave out API keys and MCP server tokens
+        #[arg(long)]
+        redact_secrets: bool,
+
+        /// Write the bundle to this file instead of stdout
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+
+    /// Keep settings (without secrets) in sync across machines through a private git repository
+    Sync {
+        /// Git URL of the sync repository (needed the first time on each machine)
+        #[arg(long)]
+        remote: Option<String>,
+
+        /// Take the remote settings when both sides changed
+        #[arg(long, conflicts_with = "push")]
+        pull: bool,
+
+        /// Overwrite the remote with this machine's settings
+        #[arg(long)]
+        push: bool,
+    },
+
+    /// Apply a bundle created by 'config export' (e.g. on a new machine)
+    Import {
+        /// Bundle file
+        file: String,
+
+        /// Replace MCP servers and files that already exist with different content
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+/// ci 하위 명령어
+#[derive(Subcommand)]
+pub enum CiAction {
+    /// Generate or update the pull request description and a review comment (reads GITHUB_EVENT_PATH, GITHUB_REPOSITORY and GITHUB_TOKEN)
+    PrDescribe {
+        /// Pull request number (default: the pull request of the triggering event)
+        #[arg(long)]
+        pr: Option<u64>,
+
+        /// Model to use (local, openai, anthropic)
+        #[arg(short, long)]
+        model: Option<String>,
+
+        /// Only update the description, without the review comment
+        #[arg(long)]
+        no_review: bool,
+
+        /// Print the description and comment instead of posting them
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+/// hook 하위 명령어
+#[derive(Subcommand)]
+pub enum HookAction {
+    /// Check the final commit message against the [commit] rules and reject it with a suggested fix
+    CommitMsg {
+        /// Message file git passes to the hook (usually .git/COMMIT_EDITMSG)
+        #[arg(value_name = "MSG_FILE")]
+        file: String,
+    },
+
+    /// Review the commits being pushed (refs on stdin) for secrets, debug code and broken conventions, and block the push on findings (skip with AI_CLI_SKIP_PRE_PUSH=1)
+    PrePush {
+        /// Remote name git passes to the hook
+        remote: Option<String>,
+
+        /// Remote URL git passes to the hook (unused)
+        url: Option<String>,
+
+        /// Lowest severity that blocks the push (info, warning, error; same as AI_CLI_PRE_PUSH_FAIL_ON, default error)
+        #[arg(long, value_name = "SEVERITY", value_parser = crate::hook::SEVERITIES)]
+        fail_on: Option<String>,
+
+        /// Also ask a model to review the pushed diff (local, openai, anthropic)
+        #[arg(short, long)]
+        model: Option<String>,
+    },
+}
+
+/// audit 하위 명령어
+#[derive(Subcommand)]
+pub enum AuditAction {
+    /// Show recorded audit entries
+    Show {
+        /// Only show the most recent N entries
+        #[arg(short = 'n', long)]
+        limit: Option<usize>,
+    },
+
+    /// Verify the hash chain of the audit log
+    Verify,
+}
+
+/// mcp 하위 명령어
+#[derive(Subcommand)]
+pub enum McpAction {
+    /// List configured servers without connecting to them
+    List,
+
+    /// Add a server (e.g. `mcp add github --command npx -- -y @modelcontextprotocol/server-github`)
+    Add {
+        /// Server name
+        name: String,
+
+        /// Command that starts a local (stdio) server
+        #[arg(long, conflicts_with = "url", required_unless_present = "url")]
+        command: Option<String>,
+
+        /// URL of a remote (Streamable HTTP or HTTP+SSE) server
+        #[arg(long)]
+        url: Option<String>,
+
+        /// Environment variable for a local server (KEY=VALUE; ${VAR} is expanded when connecting)
+        #[arg(short, long = "env", value_name = "KEY=VALUE", conflicts_with = "url")]
+        env: Vec<String>,
+
+        /// Working directory for a local server (${PROJECT_ROOT} and ${CWD} are expanded when connecting)
+        #[arg(long, conflicts_with = "url")]
+        cwd: Option<String>,
+
+        /// Request header for a remote server ("Name: value"; ${VAR} is expanded when connecting)
+        #[arg(long = "header", value_name = "NAME: VALUE", conflicts_with = "command")]
+        headers: Vec<String>,
+
+        /// Replace an existing server with the same name
+        #[arg(short, long)]
+        force: bool,
+
+        /// Arguments for the server command (after --)
+        #[arg(last = true, conflicts_with = "url")]
+        args: Vec<String>,
+    },
+
+    /// Remove a server
+    Remove {
+        /// Server name in ~/.ai-cli/mcp.json
+        name: String,
+    },
+
+    /// Connect to one server and report whether it works
+    Test {
+        /// Server name in ~/.ai-cli/mcp.json
+        name: String,
+    },
+
+    /// List the tools a server provides and whether each needs approval
+    Tools {
+        /// Server name in ~/.ai-cli/mcp.json
+        name: String,
+
+        /// Output format (text, json)
+        #[arg(short, long, default_value = "text")]
+        format: String,
+    },
+
+    /// Connect to every configured server and show its status, tool count and ping latency
+    Status {
+        /// Output format (text, json)
+        #[arg(short, long, default_value = "text")]
+        format: String,
+    },
+
+    /// Let the model complete a task with the tools of the configured servers (e.g. "open a PR for this branch")
+    Run {
+        /// What to do (e.g. "file an issue for the failing login test")
+        #[arg(required = true)]
+        task: Vec<String>,
+
+        /// Use specific AI model (local: ollama, remote: openai, anthropic; defaults to default_model in the config file); the model must support tool calling
+        #[arg(short, long)]
+        model: Option<String>,
+
+        /// Maximum number of model turns before giving up
+        #[arg(long, default_value_t = crate::agent::DEFAULT_MAX_STEPS)]
+        max_steps: usize,
+
+        /// Leave out recent shell commands even when AI_CLI_SHELL_HISTORY is set
+        #[arg(long)]
+        no_shell_history: bool,
+    },
+
+    /// Forget the stored OAuth token for a remote server (the next connection signs in again)
+    Logout {
+        /// Server name in ~/.ai-cli/mcp.json
+        server: String,
+    },
+}
+
+/// context 하위 명령어
+#[derive(Subcommand)]
+pub enum ContextAction {
+    /// Append a timestamped note to the project context
+    Add {
+        /// Note text (e.g. "mid-migration to axum; prefer fixes over type refactors")
+        #[arg(required = true)]
+        note: Vec<String>,
+
+        /// Label the note (e.g. decision, constraint, todo)
+        #[arg(short, long)]
+        tag: Option<String>,
+
+        /// Write to NOTES.md instead of PROJECT.md
+        #[arg(long)]
+        notes: bool,
+    },
+}
+
+/// 프롬프트에 넣을 컨텍스트 층 선택 (commit, explain 공통, `policy.toml`의 `[context]`에 더해 적용)
+#[derive(Args, Debug, Clone)]
+pub struct ContextArgs {
+    /// Leave out your personal global context (~/.ai-cli/CONFIG.md)
+    #[arg(long)]
+    pub no_global_context: bool,
+
+    /// Leave out repository context files (PROJECT.md, CLAUDE.md, ..., including subdirectory files)
+    #[arg(long)]
+    pub no_project_context: bool,
+
+    /// Leave out recent shell commands even when AI_CLI_SHELL_HISTORY is set
+    #[arg(long)]
+    pub no_shell_history: bool,
+}
+
+impl ContextArgs {
+    /// 정책 파일 설정과 합친 컨텍스트 층
+    pub fn layers(&self) -> ContextLayers {
+        let mut disabled = Vec::new();
+        if self.no_global_context {
+            disabled.push(ContextLayer::Global);
+        }
+        if self.no_project_context {
+            disabled.extend([ContextLayer::Project, ContextLayer::Directory]);
+        }
+        if self.no_shell_history {
+            disabled.push(ContextLayer::ShellHistory);
+        }
+        ContextLayers::load(&disabled)
+    }
+}
+
+/// Diff 생성 옵션 (commit, explain 공통)
+#[derive(Args, Debug, Clone)]
+pub struct DiffArgs {
+    /// Number of context lines around each change sent to the AI
+    #[arg(long, default_value_t = 3)]
+    pub context_lines: u32,
+
+    /// Show changes word-by-word instead of line-by-line
+    #[arg(long)]
+    pub word_diff: bool,
+
+    /// Ignore whitespace-only changes
+    #[arg(short = 'w', long)]
+    pub ignore_whitespace: bool,
+
+    /// Include contents of sensitive files (.env, private keys, certificates) for this run
+    #[arg(long)]
+    pub include_sensitive: bool,
+
+    /// Send raw lockfile diffs (Cargo.lock, package-lock.json, ...) instead of only the dependency summary
+    #[arg(long)]
+    pub include_lockfiles: bool,
+
+    /// Also send the complete function/type around each change, up to N lines each
+    #[arg(long, value_name = "N", num_args = 0..=1, default_missing_value = "200")]
+    pub expand_context: Option<usize>,
+}
+
+impl DiffArgs {
+    /// git_utils용 diff 설정으로 변환
+    pub fn settings(&self) -> DiffSettings {
+        DiffSettings {
+            context_lines: self.context_lines,
+            word_diff: self.word_diff,
+            ignore_whitespace: self.ignore_whitespace,
+            include_sensitive: self.include_sensitive,
+            include_lockfiles: self.include_lockfiles,
+        }
+    }
 }
\ No newline at end of file
@@ -0,0 +1,94 @@
+use anyhow::{Result, anyhow};
+use std::fs::OpenOptions;
+use std::io::{self, IsTerminal, Write};
+
+/// 클립보드 모듈 (`--copy`)
+/// 생성한 커밋 메시지나 설명을 arboard로 시스템 클립보드(macOS, Windows, X11, Wayland)에 복사하고,
+/// 디스플레이가 없는 환경(SSH 접속, 헤드리스 서버)에서는 터미널에 OSC 52 시퀀스를 보내 복사 (터미널이 지원해야 함)
+///
+/// 복사에 쓴 방법 (출력용)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Method {
+    System,
+    Terminal,
+}
+
+impl std::fmt::Display for Method {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Method::System => write!(f, "system clipboard"),
+            Method::Terminal => write!(f, "OSC 52"),
+        }
+    }
+}
+
+/// 텍스트를 클립보드에 복사
+pub fn copy(text: &str) -> Result<Method> {
+    match arboard::Clipboard::new().and_then(|mut clipboard| clipboard.set_text(text)) {
+        Ok(()) => Ok(Method::System),
+        Err(e) => {
+            tracing::debug!("System clipboard unavailable, falling back to OSC 52: {}", e);
+            copy_with_terminal(text).map_err(|terminal| anyhow!("{} ({})", terminal, e))?;
+            Ok(Method::Terminal)
+        }
+    }
+}
+
+/// 복사하고 결과 출력 (실패해도 명령은 계속)
+pub fn copy_or_warn(text: &str) {
+    match copy(text) {
+        Ok(method) => println!("{}", tr!("clipboard.copied", method = method)),
+        Err(e) => println!("{}", tr!("clipboard.failed", error = e)),
+    }
+}
+
+/// 터미널에 OSC 52 시퀀스 출력 (표준 출력이 파이프여도 제어 터미널에 씀)
+fn copy_with_terminal(text: &str) -> Result<()> {
+    let sequence = osc52(text);
+    if let Ok(mut tty) = OpenOptions::new().write(true).open(if cfg!(windows) { "CONOUT$" } else { "/dev/tty" }) {
+        tty.write_all(sequence.as_bytes())?;
+        return Ok(tty.flush()?);
+    }
+    if io::stderr().is_terminal() {
+        let mut stderr = io::stderr();
+        stderr.write_all(sequence.as_bytes())?;
+        return Ok(stderr.flush()?);
+    }
+    Err(anyhow!("no system clipboard and no terminal to copy through"))
+}
+
+/// OSC 52 클립보드 설정 시퀀스
+fn osc52(text: &str) -> String {
+    format!("\x1b]52;c;{}\x07", base64(text.as_bytes()))
+}
+
+/// 패딩 있는 표준 base64 인코딩
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let value = chunk.iter().enumerate().fold(0u32, |value, (i, byte)| value | ((*byte as u32) << (16 - 8 * i)));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(ALPHABET[((value >> (18 - 6 * i)) & 0x3f) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_osc52() {
+        assert_eq!(base64(b"hello"), "aGVsbG8=");
+        assert_eq!(base64(b"feat"), "ZmVhdA==");
+        assert_eq!(base64(b"fix"), "Zml4");
+        assert_eq!(osc52("hello"), "\x1b]52;c;aGVsbG8=\x07");
+        assert_eq!(Method::System.to_string(), "system clipboard");
+    }
+}
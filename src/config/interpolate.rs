@@ -0,0 +1,80 @@
+//! 설정 값의 환경 변수 치환
+//!
+//! 사용자 설정과 프로필의 값, MCP 서버 정의(`url`, `headers`, `command`, `args`, `env`, `cwd`)에 `${VAR}`를 쓰면
+//! 사용할 때 환경 변수 값으로 바꿔, 설정 파일 하나를 여러 환경에서 쓸 수 있게 합니다. `${VAR:-기본값}`은 변수가 없거나
+//! 비었을 때 기본값을 쓰고, `$${VAR}`는 치환하지 않고 `${VAR}`로 남깁니다. 기본값 없이 설정되지 않은 변수를 쓰면
+//! 빈 값으로 요청하는 대신 어떤 변수가 없는지 알려 주는 오류가 납니다.
+//!
+//! 리포지토리 설정(`.ai-cli.toml`)과 팀 설정은 클론한 리포지토리가 환경 변수를 밖으로 보낼 수 있으므로 치환하지 않습니다.
+
+use regex::{Captures, Regex};
+use std::sync::OnceLock;
+
+/// 설정되지 않은 환경 변수를 참조함
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("environment variable '{name}' is not set (export it, or write ${{{name}:-default}} to fall back to a default)")]
+pub struct UnsetVariable {
+    pub name: String,
+}
+
+fn variable_regex() -> &'static Regex {
+    static VARIABLE: OnceLock<Regex> = OnceLock::new();
+    VARIABLE.get_or_init(|| Regex::new(r"\$(\$?)\{([A-Za-z_][A-Za-z0-9_]*)(?::-([^}]*))?\}").expect("invalid variable pattern"))
+}
+
+/// 문자열에 환경 변수 참조가 있는지
+pub fn has_variables(value: &str) -> bool {
+    variable_regex().is_match(value)
+}
+
+/// 문자열의 `${VAR}`와 `${VAR:-기본값}`을 환경 변수 값으로 치환
+pub fn expand_env(value: &str) -> Result<String, UnsetVariable> {
+    expand_with(value, |name| std::env::var(name).ok())
+}
+
+/// 변수 조회 함수를 지정한 치환
+fn expand_with(value: &str, lookup: impl Fn(&str) -> Option<String>) -> Result<String, UnsetVariable> {
+    let mut unset = None;
+    let expanded = variable_regex().replace_all(value, |captures: &Captures| {
+        let name = &captures[2];
+        if !captures[1].is_empty() {
+            return captures[0][1..].to_string();
+        }
+        match (lookup(name).filter(|found| !found.is_empty() || captures.get(3).is_none()), captures.get(3)) {
+            (Some(found), _) => found,
+            (None, Some(default)) => default.as_str().to_string(),
+            (None, None) => {
+                unset.get_or_insert_with(|| UnsetVariable { name: name.to_string() });
+                String::new()
+            }
+        }
+    });
+    match unset {
+        Some(error) => Err(error),
+        None => Ok(expanded.into_owned()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand_env() {
+        let lookup = |name: &str| match name {
+            "HOST" => Some("ollama.internal".to_string()),
+            "EMPTY" => Some(String::new()),
+            _ => None,
+        };
+        assert_eq!(expand_with("http://${HOST}:11434", lookup).unwrap(), "http://ollama.internal:11434");
+        assert_eq!(expand_with("${PORT:-11434}/${EMPTY:-x}", lookup).unwrap(), "11434/x");
+        assert_eq!(expand_with("${EMPTY}", lookup).unwrap(), "");
+        assert_eq!(expand_with("$${HOST} costs $5", lookup).unwrap(), "${HOST} costs $5");
+        assert_eq!(expand_with("Bearer ${TOKEN}", lookup).unwrap_err(), UnsetVariable { name: "TOKEN".to_string() });
+        assert!(has_variables("${HOST}") && !has_variables("$HOST"));
+
+        std::env::set_var("AI_CLI_TEST_MCP_TOKEN", "secret");
+        assert_eq!(expand_env("Bearer ${AI_CLI_TEST_MCP_TOKEN}").unwrap(), "Bearer secret");
+        assert!(expand_env("${AI_CLI_TEST_MCP_MISSING}x").is_err());
+    }
+}
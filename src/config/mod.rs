@@ -0,0 +1,1386 @@
+use anyhow::{Result, anyhow};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+use crate::context::ContextEngine;
+use crate::policy::PrivacyRules;
+use crate::security::SecurityManager;
+
+pub mod bundle;
+pub mod interpolate;
+pub mod schema;
+pub mod sync;
+pub mod team;
+
+pub use interpolate::expand_env;
+pub use schema::ConfigError;
+pub use team::TeamConfig;
+
+/// 사용자 설정 파일 (`~/.config/ai-cli/config.toml`)
+///
+/// `init`과 `config set`이 기본 모델, 백엔드 URL/모델, API 키를 이 파일에 저장하고, 시작할 때 읽어 해당 환경 변수로
+/// 적용합니다. 이미 설정된 환경 변수는 덮어쓰지 않으므로 환경 변수가 파일보다 우선합니다.
+/// 경로는 `AI_CLI_CONFIG`로 바꿀 수 있습니다.
+///
+/// API 키는 설정 파일 옆의 `credentials.toml`(소유자만 읽기 가능, `credentials` 키로 경로 변경)에 따로 저장하므로
+/// 설정 파일은 공유하거나 커밋해도 됩니다. 설정 파일에 직접 적은 키도 읽고, 다음에 저장할 때 자격 증명 파일로 옮깁니다.
+/// `keyring`에 적은 키(`openai.api_key`, `profiles.work.openai.api_key` 등)는 OS 키체인에 저장합니다.
+/// 값의 `${VAR}`, `${VAR:-기본값}`은 적용할 때 환경 변수로 치환합니다 (`interpolate` 모듈).
+///
+/// 설정 파일이 없을 때 이전 방식의 환경 변수(`AI_CLI_*`, `OPENAI_API_KEY`, `ANTHROPIC_API_KEY`)가 있으면 처음 실행할 때
+/// 설정 파일로 옮길지 묻습니다 (`config migrate`로 직접 옮길 수도 있음).
+///
+/// `[profiles.<이름>]`에는 고객사별 백엔드와 키, 개인 정보 규칙(`privacy`), 커밋 규칙(`commit.conventions`)을 두고
+/// `--profile` 또는 `AI_CLI_PROFILE`(없으면 `profile` 키)로 선택합니다. 프로필의 값은 최상위 값보다 우선합니다.
+///
+/// ```toml
+/// profile = "work"
+/// default_model = "local"
+///
+/// [profiles.work]
+/// default_model = "openai"
+/// openai = { api_key = "sk-...", model = "gpt-4o" }
+/// privacy = { scrub = ["email", "name"], names = ["Acme Corp"] }
+/// commit = { conventions = "Start the subject with the Jira key (ACME-123)" }
+/// ```
+///
+/// 프로젝트 루트의 `.ai-cli.toml`(커밋 여부와 무관)은 그 프로젝트에서 전역 설정과 프로필, 팀 설정(`team` 모듈)보다
+/// 우선합니다.
+/// 기본 모델과 백엔드 모델, 커밋 규칙(`[commit]`의 `types`, `conventions`, `template`), AI에 보내지 않을 경로
+/// (`exclude`, `.aiignore`와 같은 문법)를 둘 수 있고, 리포지토리에 올라갈 수 있으므로 API 키는 무시합니다.
+///
+/// 설정 파일 이름
+pub const CONFIG_FILE: &str = "config.toml";
+
+/// API 키를 저장하는 파일 이름 (설정 파일과 같은 디렉토리)
+pub const CREDENTIALS_FILE: &str = "credentials.toml";
+
+/// API 키를 저장하는 키체인 항목의 서비스 이름
+const KEYRING_SERVICE: &str = "ai-cli";
+
+/// 기본 모델을 지정하지 않았을 때 쓰는 백엔드
+pub const DEFAULT_MODEL: &str = "local";
+
+/// 사용할 프로필을 지정하는 환경 변수 (`--profile`도 이 값을 설정)
+pub const PROFILE_ENV: &str = "AI_CLI_PROFILE";
+
+/// 설정 키와 그 값을 덮어쓰는 환경 변수
+pub const KEYS: &[(&str, &str)] = &[
+    ("default_model", "AI_CLI_DEFAULT_MODEL"),
+    ("ollama.url", "AI_CLI_OLLAMA_URL"),
+    ("ollama.model", "AI_CLI_LOCAL_MODEL"),
+    ("openai.api_key", "OPENAI_API_KEY"),
+    ("openai.model", "AI_CLI_OPENAI_MODEL"),
+    ("anthropic.api_key", "ANTHROPIC_API_KEY"),
+    ("anthropic.model", "AI_CLI_ANTHROPIC_MODEL"),
+    ("language", "AI_CLI_LANG"),
+    ("notify", "AI_CLI_NOTIFY"),
+    ("tips", "AI_CLI_TIPS"),
+    ("transcripts", "AI_CLI_TRANSCRIPTS"),
+    ("stats", "AI_CLI_STATS"),
+];
+
+/// 백엔드별 설정
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BackendConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+}
+
+impl BackendConfig {
+    fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// 모델과 백엔드 설정 (최상위와 각 프로필에 공통)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    /// `--model`을 생략했을 때 쓰는 백엔드 (local, openai, anthropic)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_model: Option<String>,
+    #[serde(skip_serializing_if = "BackendConfig::is_empty")]
+    pub ollama: BackendConfig,
+    #[serde(skip_serializing_if = "BackendConfig::is_empty")]
+    pub openai: BackendConfig,
+    #[serde(skip_serializing_if = "BackendConfig::is_empty")]
+    pub anthropic: BackendConfig,
+    /// 출력 언어 (en, ko; 없으면 `LANG`을 따름)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    /// 오래 걸린 작업이 끝났을 때 알림 (bell, desktop, off)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notify: Option<String>,
+    /// 아직 쓰지 않은 기능의 팁 표시 (on, off)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tips: Option<String>,
+    /// 세션 기록 (on, off)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transcripts: Option<String>,
+    /// 로컬 사용 통계 (on, off; 기본 off)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stats: Option<String>,
+}
+
+/// 커밋 메시지 규칙
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CommitRules {
+    /// 커밋 메시지 프롬프트에 추가할 규칙 (예: 제목을 Jira 키로 시작)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub conventions: Option<String>,
+    /// 허용하는 커밋 타입 (비어 있으면 Conventional Commits 기본 타입)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub types: Vec<String>,
+    /// 커밋 메시지 프롬프트 템플릿 파일 (프로젝트 루트 기준 경로)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+    /// 제목에 scope를 반드시 붙임 (`feat(parser): ...`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub require_scope: Option<bool>,
+    /// 제목 최대 길이 (기본 72자)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_subject_length: Option<usize>,
+    /// 제목 아래 본문을 반드시 씀
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub require_body: Option<bool>,
+    /// 메시지에 있어야 하는 티켓 번호 정규식 (예: `[A-Z]+-[0-9]+`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ticket_pattern: Option<String>,
+    /// 커밋 메시지 형식 (conventional, gitmoji, plain, custom-template; 없으면 conventional)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub style: Option<String>,
+}
+
+/// 기본 제목 최대 길이
+pub const DEFAULT_MAX_SUBJECT_LENGTH: usize = 72;
+
+/// `commit.style`에 쓸 수 있는 값
+pub const COMMIT_STYLES: [&str; 4] = ["conventional", "gitmoji", "plain", "custom-template"];
+
+/// 커밋 메시지 형식 (생성 프롬프트, 응답 정리, 규칙 검사가 따름)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommitStyle {
+    /// `type(scope): description`
+    Conventional,
+    /// `✨ description`
+    Gitmoji,
+    /// 접두사 없는 한 줄 제목
+    Plain,
+    /// `commit.template` 프롬프트를 그대로 쓰고 형식을 바꾸지 않음
+    CustomTemplate,
+}
+
+impl CommitRules {
+    /// 다른 규칙을 덮어씀 (규칙 문장은 이어 붙이고, 타입과 템플릿은 설정한 경우에만 바꿈)
+    fn overlay(&mut self, other: &CommitRules) {
+        self.conventions = match (self.conventions.take(), &other.conventions) {
+            (Some(base), Some(extra)) => Some(format!("{}\n{}", base.trim_end(), extra.trim())),
+            (base, extra) => base.or_else(|| extra.clone()),
+        };
+        if !other.types.is_empty() {
+            self.types = other.types.clone();
+        }
+        if other.template.is_some() {
+            self.template = other.template.clone();
+        }
+        if other.require_scope.is_some() {
+            self.require_scope = other.require_scope;
+        }
+        if other.max_subject_length.is_some() {
+            self.max_subject_length = other.max_subject_length;
+        }
+        if other.require_body.is_some() {
+            self.require_body = other.require_body;
+        }
+        if other.ticket_pattern.is_some() {
+            self.ticket_pattern = other.ticket_pattern.clone();
+        }
+        if other.style.is_some() {
+            self.style = other.style.clone();
+        }
+    }
+
+    /// 커밋 메시지 형식
+    pub fn style(&self) -> CommitStyle {
+        match self.style.as_deref() {
+            Some("gitmoji") => CommitStyle::Gitmoji,
+            Some("plain") => CommitStyle::Plain,
+            Some("custom-template") => CommitStyle::CustomTemplate,
+            _ => CommitStyle::Conventional,
+        }
+    }
+
+    /// 제목 최대 길이
+    pub fn max_subject_length(&self) -> usize {
+        self.max_subject_length.unwrap_or(DEFAULT_MAX_SUBJECT_LENGTH)
+    }
+
+    /// 규칙을 프롬프트에 넣을 문장으로 (자유 형식 규칙 포함)
+    pub fn describe(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        if self.require_scope == Some(true) {
+            lines.push("- Always include a scope: `<type>(<scope>): <description>`.".to_string());
+        }
+        if self.max_subject_length.is_some() {
+            lines.push(format!("- Keep the subject line at most {} characters.", self.max_subject_length()));
+        }
+        if self.require_body == Some(true) {
+            lines.push("- Always add a body after a blank line explaining what changed and why.".to_string());
+        }
+        if let Some(pattern) = &self.ticket_pattern {
+            lines.push(format!("- Reference the ticket (matching `{}`) in the message; take it from the context if given.", pattern));
+        }
+        if let Some(conventions) = self.conventions.as_deref().map(str::trim).filter(|conventions| !conventions.is_empty()) {
+            lines.push(conventions.to_string());
+        }
+        lines
+    }
+}
+
+/// 리포지토리 설정 파일 이름 (프로젝트 루트)
+pub const REPO_CONFIG_FILE: &str = ".ai-cli.toml";
+
+/// 리포지토리 설정 (`.ai-cli.toml`)
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct RepoConfig {
+    #[serde(flatten)]
+    pub settings: Settings,
+    pub commit: CommitRules,
+    /// AI에 보내지 않을 경로 (`.aiignore` 규칙에 더해짐)
+    pub exclude: Vec<String>,
+}
+
+/// 읽은 리포지토리 설정 (파일이 없으면 None, 잘못되었으면 문제 목록)
+type LoadedRepoConfig = std::result::Result<Option<RepoConfig>, Vec<schema::Problem>>;
+
+impl RepoConfig {
+    /// 프로젝트 루트의 `.ai-cli.toml` 읽기 (없거나 잘못되었으면 None)
+    pub fn load(root: &Path) -> Option<Self> {
+        Self::load_checked(root).unwrap_or_else(|e| {
+            tracing::warn!("Ignoring {}", e);
+            None
+        })
+    }
+
+    /// 프로젝트 루트의 `.ai-cli.toml` 읽기 (없으면 None, 스키마에 맞지 않으면 `ConfigError`, 한 번 읽은 파일은 다시 읽지 않음)
+    pub fn load_checked(root: &Path) -> Result<Option<Self>> {
+        static LOADED: Mutex<BTreeMap<PathBuf, LoadedRepoConfig>> = Mutex::new(BTreeMap::new());
+
+        let path = root.join(REPO_CONFIG_FILE);
+        let loaded = LOADED.lock().unwrap().get(&path).cloned();
+        let loaded = loaded.unwrap_or_else(|| {
+            let loaded = match fs::read_to_string(&path) {
+                Ok(content) => match schema::validate(&content, true) {
+                    problems if problems.is_empty() => Self::parse(&content).map(Some).map_err(|e| {
+                        vec![schema::Problem { line: None, key: None, message: e.to_string(), example: None }]
+                    }),
+                    problems => Err(problems),
+                },
+                Err(_) => Ok(None),
+            };
+            LOADED.lock().unwrap().insert(path.clone(), loaded.clone());
+            loaded
+        });
+        loaded.map_err(|problems| ConfigError { path, problems }.into())
+    }
+
+    /// 현재 디렉토리의 프로젝트 루트에서 리포지토리 설정 찾기 (확인하지 않은 설정은 뺌)
+    pub fn discover() -> Option<(PathBuf, Self)> {
+        let root = Self::discover_root()?;
+        let config = Self::load(&root)?.honored(&root);
+        Some((root, config))
+    }
+
+    /// 요청을 보낼 곳과 방법을 바꾸는 설정 (`key = value` 형식, 팀 설정처럼 확인한 뒤에만 적용)
+    pub fn gated_settings(&self) -> Vec<String> {
+        team::gated(&self.settings, &self.commit)
+    }
+
+    /// 요청을 보낼 곳과 방법을 바꾸는 설정의 해시 (바뀌면 다시 확인)
+    pub fn digest(&self) -> String {
+        team::digest(&self.gated_settings())
+    }
+
+    /// 적용 상태 (`SecurityManager`의 확인 기록 기준)
+    pub fn trust(&self, root: &Path) -> team::TeamTrust {
+        team::trust(&root.join(REPO_CONFIG_FILE), &self.gated_settings())
+    }
+
+    /// 확인하지 않은 설정을 뺀 리포지토리 설정 (커밋 규칙과 제외 경로는 그대로)
+    pub fn honored(mut self, root: &Path) -> Self {
+        if !self.trust(root).is_applied() {
+            self.settings = Settings::default();
+            self.commit.template = None;
+        }
+        self
+    }
+
+    /// 현재 디렉토리의 프로젝트 루트
+    pub fn discover_root() -> Option<PathBuf> {
+        ContextEngine::new().find_project_root(&std::env::current_dir().ok()?).ok()
+    }
+
+    /// 설정 내용 해석 (API 키는 무시)
+    fn parse(content: &str) -> Result<Self> {
+        let mut config: Self = toml::from_str(content)?;
+        for key in ["openai.api_key", "anthropic.api_key"] {
+            if config.settings.unset(key)? {
+                tracing::warn!("Ignoring {} in {}; keep API keys in the user config file", key, REPO_CONFIG_FILE);
+            }
+        }
+        Ok(config)
+    }
+}
+
+/// 이름 붙인 프로필
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Profile {
+    #[serde(flatten)]
+    pub settings: Settings,
+    /// 정책 파일의 `[privacy]` 규칙에 더해지는 개인 정보 규칙
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub privacy: Option<PrivacyRules>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commit: Option<CommitRules>,
+}
+
+/// 사용자 설정
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// `--profile`과 `AI_CLI_PROFILE`이 없을 때 쓰는 프로필
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
+    /// 자격 증명 파일 경로 (상대 경로는 설정 파일 기준, 없으면 `credentials.toml`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub credentials: Option<String>,
+    /// OS 키체인에 저장한 API 키
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub keyring: Vec<String>,
+    #[serde(flatten)]
+    pub settings: Settings,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub profiles: BTreeMap<String, Profile>,
+}
+
+/// 시작할 때 설정 파일에서 적용한 환경 변수
+static APPLIED_VARS: OnceLock<Vec<&'static str>> = OnceLock::new();
+
+/// 키체인에서 읽은 API 키 (찾지 못했으면 None)
+static KEYRING_CACHE: Mutex<BTreeMap<String, Option<String>>> = Mutex::new(BTreeMap::new());
+
+/// `--profile`로 선택한 프로필
+static PROFILE_FLAG: OnceLock<String> = OnceLock::new();
+
+/// 설정 값의 출처 (우선순위가 높은 순)
+#[derive(Debug, Clone, PartialEq)]
+pub enum Source {
+    Flag(&'static str),
+    Env(&'static str),
+    Repo,
+    Team,
+    Profile(String),
+    Global,
+    Default,
+}
+
+impl std::fmt::Display for Source {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Source::Flag(flag) => write!(f, "{} flag", flag),
+            Source::Env(var) => write!(f, "env {}", var),
+            Source::Repo => write!(f, "repo config"),
+            Source::Team => write!(f, "team config"),
+            Source::Profile(name) => write!(f, "profile '{}'", name),
+            Source::Global => write!(f, "global config"),
+            Source::Default => write!(f, "default"),
+        }
+    }
+}
+
+/// 설정 값과 그 출처
+#[derive(Debug, Clone, PartialEq)]
+pub struct Provenance {
+    pub key: &'static str,
+    pub value: Option<String>,
+    pub source: Source,
+    /// 값이 있지만 더 우선하는 출처에 가려진 곳
+    pub shadowed: Vec<Source>,
+}
+
+/// `--profile`로 프로필 선택 (`AI_CLI_PROFILE`보다 우선)
+pub fn select_profile(name: &str) {
+    std::env::set_var(PROFILE_ENV, name);
+    let _ = PROFILE_FLAG.set(name.to_string());
+}
+
+/// 설정 키의 기본값
+pub fn default_value(key: &str) -> Option<&'static str> {
+    match key {
+        "default_model" => Some(DEFAULT_MODEL),
+        "ollama.url" => Some("http://localhost:11434"),
+        "ollama.model" => Some("gemma2:9b"),
+        "openai.model" => Some("gpt-4o-mini"),
+        "anthropic.model" => Some("claude-3-5-sonnet-20241022"),
+        _ => None,
+    }
+}
+
+/// 적용된 설정 값 (환경 변수, 없으면 기본값)
+pub fn setting(key: &str) -> String {
+    env_var(key)
+        .and_then(|var| std::env::var(var).ok())
+        .or_else(|| default_value(key).map(str::to_string))
+        .unwrap_or_default()
+}
+
+/// 사용자가 직접 설정한 환경 변수 값 (설정 파일에서 적용한 값은 제외)
+fn external_env(var: &'static str) -> Option<String> {
+    if APPLIED_VARS.get().is_some_and(|applied| applied.contains(&var)) {
+        return None;
+    }
+    std::env::var(var).ok()
+}
+
+/// 우선순위대로 값을 찾아 출처 계산 (환경 변수, 설정 계층, 기본값 순)
+fn trace(key: &'static str, env: Option<(&'static str, String)>, layers: &[(Source, &Settings)]) -> Provenance {
+    let mut found: Vec<(Source, String)> = Vec::new();
+    if let Some((var, value)) = env {
+        found.push((Source::Env(var), value));
+    }
+    for (source, settings) in layers {
+        if let Some(value) = settings.get(key) {
+            found.push((source.clone(), value.to_string()));
+        }
+    }
+    let mut found = found.into_iter();
+    match found.next() {
+        Some((source, value)) => Provenance { key, value: Some(value), source, shadowed: found.map(|(source, _)| source).collect() },
+        None => Provenance { key, value: default_value(key).map(str::to_string), source: Source::Default, shadowed: Vec::new() },
+    }
+}
+
+/// 설정 키를 사용자가 환경 변수로 덮어썼는지 (설정 파일에서 적용한 값은 제외)
+pub fn is_overridden(key: &str) -> bool {
+    env_var(key).is_some_and(|var| {
+        std::env::var_os(var).is_some() && !APPLIED_VARS.get().is_some_and(|applied| applied.contains(&var))
+    })
+}
+
+/// 설정 파일 경로
+pub fn config_path() -> Option<PathBuf> {
+    if let Ok(path) = std::env::var("AI_CLI_CONFIG") {
+        return Some(PathBuf::from(path));
+    }
+    dirs::home_dir().map(|home| home.join(".config").join("ai-cli").join(CONFIG_FILE))
+}
+
+/// `--model`을 생략했을 때 쓸 백엔드 (환경 변수, 설정 파일, `local` 순)
+pub fn default_model() -> String {
+    configured_model(None).unwrap_or_else(|| DEFAULT_MODEL.to_string())
+}
+
+/// 명령줄, 환경 변수, 설정 파일(프로필, `.ai-cli.toml` 포함) 중 하나에서 지정한 모델 (어디에도 없으면 None)
+pub fn configured_model(model: Option<&str>) -> Option<String> {
+    model.map(str::to_string).or_else(|| {
+        std::env::var("AI_CLI_DEFAULT_MODEL")
+            .ok()
+            .filter(|model| !model.trim().is_empty())
+    })
+}
+
+/// 명령줄에서 지정한 모델, 없으면 기본 모델
+pub fn resolve_model(model: Option<&str>) -> String {
+    model.map(str::to_string).unwrap_or_else(default_model)
+}
+
+/// 설정 키의 환경 변수 이름
+pub fn env_var(key: &str) -> Option<&'static str> {
+    KEYS.iter().find(|(known, _)| *known == key).map(|(_, var)| *var)
+}
+
+/// 비밀 값인 키인지 (출력할 때 가림)
+pub fn is_secret(key: &str) -> bool {
+    key.ends_with("api_key")
+}
+
+/// `--profile`이나 `AI_CLI_PROFILE`로 지정한 프로필 이름
+fn requested_profile() -> Option<String> {
+    std::env::var(PROFILE_ENV).ok().map(|name| name.trim().to_string()).filter(|name| !name.is_empty())
+}
+
+/// 선택한 프로필의 개인 정보 규칙 (설정 파일을 읽을 수 없거나 프로필이 없으면 None)
+pub fn active_privacy_rules() -> Option<PrivacyRules> {
+    let config = Config::load().ok()?;
+    config.active_profile().ok()??.1.privacy.clone()
+}
+
+/// 선택한 프로필, 팀 설정, 리포지토리 설정을 합친 커밋 규칙
+pub fn commit_rules() -> CommitRules {
+    let mut rules = CommitRules::default();
+    if let Some(commit) = Config::load().ok().and_then(|config| config.active_profile().ok()??.1.commit.clone()) {
+        rules.overlay(&commit);
+    }
+    if let Some(team) = team::active() {
+        rules.overlay(&team.commit);
+    }
+    if let Some((_, repo)) = RepoConfig::discover() {
+        rules.overlay(&repo.commit);
+    }
+    rules
+}
+
+/// 커밋 메시지 프롬프트 템플릿 읽기 (`~/`는 홈 디렉토리, 상대 경로는 프로젝트 루트 기준, 읽지 못하면 None)
+pub fn load_commit_template(path: &str) -> Option<String> {
+    let path = match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(path),
+    };
+    let path = match RepoConfig::discover_root() {
+        Some(root) if path.is_relative() => root.join(path),
+        _ => path,
+    };
+    match fs::read_to_string(&path) {
+        Ok(template) => Some(template),
+        Err(e) => {
+            tracing::warn!("Ignoring commit prompt template {}: {}", path.display(), e);
+            None
+        }
+    }
+}
+
+/// 프로필과 리포지토리의 커밋 규칙을 담은 프롬프트 섹션
+pub fn commit_conventions_section() -> Option<String> {
+    let rules = commit_rules().describe();
+    if rules.is_empty() {
+        return None;
+    }
+    Some(format!("COMMIT CONVENTIONS for this project (follow them):\n{}", rules.join("\n")))
+}
+
+impl Settings {
+    fn field(&self, key: &str) -> Option<&Option<String>> {
+        match key {
+            "default_model" => Some(&self.default_model),
+            "ollama.url" => Some(&self.ollama.url),
+            "ollama.model" => Some(&self.ollama.model),
+            "openai.api_key" => Some(&self.openai.api_key),
+            "openai.model" => Some(&self.openai.model),
+            "anthropic.api_key" => Some(&self.anthropic.api_key),
+            "anthropic.model" => Some(&self.anthropic.model),
+            "language" => Some(&self.language),
+            "notify" => Some(&self.notify),
+            "tips" => Some(&self.tips),
+            "transcripts" => Some(&self.transcripts),
+            "stats" => Some(&self.stats),
+            _ => None,
+        }
+    }
+
+    fn field_mut(&mut self, key: &str) -> Result<&mut Option<String>> {
+        match key {
+            "default_model" => Ok(&mut self.default_model),
+            "ollama.url" => Ok(&mut self.ollama.url),
+            "ollama.model" => Ok(&mut self.ollama.model),
+            "openai.api_key" => Ok(&mut self.openai.api_key),
+            "openai.model" => Ok(&mut self.openai.model),
+            "anthropic.api_key" => Ok(&mut self.anthropic.api_key),
+            "anthropic.model" => Ok(&mut self.anthropic.model),
+            "language" => Ok(&mut self.language),
+            "notify" => Ok(&mut self.notify),
+            "tips" => Ok(&mut self.tips),
+            "transcripts" => Ok(&mut self.transcripts),
+            "stats" => Ok(&mut self.stats),
+            _ => {
+                let known: Vec<&str> = KEYS.iter().map(|(key, _)| *key).collect();
+                Err(anyhow!("Unknown config key '{}' (expected one of {})", key, known.join(", ")))
+            }
+        }
+    }
+
+    /// 설정 값
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.field(key).and_then(|value| value.as_deref())
+    }
+
+    /// 설정 값 변경 (`openai.model`처럼 점으로 구분한 키)
+    pub fn set(&mut self, key: &str, value: &str) -> Result<()> {
+        let value = value.trim();
+        if value.is_empty() {
+            return Err(anyhow!("Config value for '{}' is empty; use 'config unset {}' to remove it", key, key));
+        }
+        if key == "default_model" && !matches!(value, "local" | "openai" | "anthropic") {
+            return Err(anyhow!("default_model must be one of local, openai, anthropic (got '{}')", value));
+        }
+        if key == "language" && !crate::i18n::LANGUAGES.contains(&value) {
+            return Err(anyhow!("language must be one of {} (got '{}')", crate::i18n::LANGUAGES.join(", "), value));
+        }
+        if key == "notify" && !crate::notify::NOTIFY_MODES.contains(&value) {
+            return Err(anyhow!("notify must be one of {} (got '{}')", crate::notify::NOTIFY_MODES.join(", "), value));
+        }
+        if key == "tips" && !crate::tips::TIP_MODES.contains(&value) {
+            return Err(anyhow!("tips must be one of {} (got '{}')", crate::tips::TIP_MODES.join(", "), value));
+        }
+        if key == "transcripts" && !crate::transcript::TRANSCRIPT_MODES.contains(&value) {
+            return Err(anyhow!("transcripts must be one of {} (got '{}')", crate::transcript::TRANSCRIPT_MODES.join(", "), value));
+        }
+        if key == "stats" && !crate::stats::STATS_MODES.contains(&value) {
+            return Err(anyhow!("stats must be one of {} (got '{}')", crate::stats::STATS_MODES.join(", "), value));
+        }
+        *self.field_mut(key)? = Some(value.to_string());
+        Ok(())
+    }
+
+    /// 설정 값 삭제 (삭제했으면 true)
+    pub fn unset(&mut self, key: &str) -> Result<bool> {
+        Ok(self.field_mut(key)?.take().is_some())
+    }
+
+    /// 설정된 값과 그 환경 변수
+    pub fn env_values(&self) -> Vec<(&'static str, &str)> {
+        KEYS.iter().filter_map(|(key, var)| self.get(key).map(|value| (*var, value))).collect()
+    }
+
+    /// API 키를 떼어 낸 설정 (떼어 낸 키만 담김)
+    fn take_secrets(&mut self) -> Settings {
+        let mut secrets = Settings::default();
+        for (key, _) in KEYS.iter().filter(|(key, _)| is_secret(key)) {
+            if let (Ok(value), Ok(secret)) = (self.field_mut(key), secrets.field_mut(key)) {
+                *secret = value.take();
+            }
+        }
+        secrets
+    }
+
+    /// 값의 `${VAR}` 치환 (설정되지 않은 변수면 어느 키의 값인지 알려 줌)
+    fn expand_env(&mut self) -> Result<()> {
+        for (key, _) in KEYS {
+            if let Some(value) = self.field_mut(key)?.as_mut() {
+                *value = expand_env(value).map_err(|e| anyhow!("Config value '{}': {}", key, e))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// 프로필 값을 덮어쓴 설정
+    fn overlay(&self, profile: &Settings) -> Settings {
+        let mut merged = self.clone();
+        for (key, _) in KEYS {
+            if let (Some(value), Ok(field)) = (profile.get(key), merged.field_mut(key)) {
+                *field = Some(value.to_string());
+            }
+        }
+        merged
+    }
+}
+
+impl Config {
+    /// 설정 파일 읽기 (없으면 기본값, 스키마에 맞지 않으면 `ConfigError`)
+    pub fn load() -> Result<Self> {
+        let Some(path) = config_path() else {
+            return Ok(Self::default());
+        };
+        match fs::read_to_string(&path) {
+            Ok(content) => {
+                let problems = schema::validate(&content, false);
+                if !problems.is_empty() {
+                    return Err(ConfigError { path, problems }.into());
+                }
+                let mut config: Self = toml::from_str(&content)?;
+                config.merge_credentials(&config.credentials_path(&path))?;
+                config.merge_keyring();
+                Ok(config)
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                let mut config = Self::default();
+                config.merge_credentials(&config.credentials_path(&path))?;
+                Ok(config)
+            }
+            Err(e) => Err(anyhow!("Failed to read config file {}: {}", path.display(), e)),
+        }
+    }
+
+    /// 자격 증명 파일 경로 (`credentials` 키, 없으면 설정 파일 옆의 `credentials.toml`)
+    pub fn credentials_path(&self, config_file: &Path) -> PathBuf {
+        let dir = config_file.parent().unwrap_or(Path::new("."));
+        match self.credentials.as_deref() {
+            Some(path) => match (path.strip_prefix("~/"), dirs::home_dir()) {
+                (Some(rest), Some(home)) => home.join(rest),
+                _ => dir.join(path),
+            },
+            None => dir.join(CREDENTIALS_FILE),
+        }
+    }
+
+    /// 자격 증명 파일의 API 키 합치기 (설정 파일의 키보다 우선)
+    fn merge_credentials(&mut self, path: &Path) -> Result<()> {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+            Err(e) => return Err(anyhow!("Failed to read credentials file {}: {}", path.display(), e)),
+        };
+        warn_if_readable_by_others(path);
+        let problems = schema::validate_credentials(&content);
+        if !problems.is_empty() {
+            return Err(ConfigError { path: path.to_path_buf(), problems }.into());
+        }
+
+        let credentials: Config = toml::from_str(&content)?;
+        self.settings = self.settings.overlay(&credentials.settings);
+        for (name, profile) in credentials.profiles {
+            let target = self.profiles.entry(name).or_default();
+            target.settings = target.settings.overlay(&profile.settings);
+        }
+        Ok(())
+    }
+
+    /// API 키 위치(`openai.api_key`, `profiles.work.openai.api_key`)의 값
+    fn secret_mut(&mut self, path: &str) -> Option<&mut Option<String>> {
+        let (settings, key) = match path.strip_prefix("profiles.") {
+            Some(rest) => {
+                let (name, key) = rest.split_once('.')?;
+                (&mut self.profiles.get_mut(name)?.settings, key)
+            }
+            None => (&mut self.settings, path),
+        };
+        if !is_secret(key) {
+            return None;
+        }
+        settings.field_mut(key).ok()
+    }
+
+    /// 키체인에 저장한 API 키 합치기 (자격 증명 파일보다 우선)
+    fn merge_keyring(&mut self) {
+        for path in self.keyring.clone() {
+            let secret = KEYRING_CACHE
+                .lock()
+                .unwrap()
+                .entry(path.clone())
+                .or_insert_with(|| crate::keyring::get(KEYRING_SERVICE, &path))
+                .clone();
+            if let (Some(secret), Some(field)) = (secret, self.secret_mut(&path)) {
+                *field = Some(secret);
+            }
+        }
+    }
+
+    /// `keyring`에 적힌 API 키를 키체인에 저장하고 설정에서 뺌
+    /// 저장하지 못한 키는 자격 증명 파일에 남기고, 삭제한 키는 키체인에서도 지움
+    fn store_keyring_secrets(&mut self) {
+        for path in std::mem::take(&mut self.keyring) {
+            let Some(field) = self.secret_mut(&path) else { continue };
+            let mut cache = KEYRING_CACHE.lock().unwrap();
+            match field.take() {
+                None => {
+                    // 키체인에서 읽었던 키를 지웠으면 키체인에서도 지우고, 읽지 못했던 키는 그대로 둠
+                    if cache.get(&path).is_some_and(Option::is_some) {
+                        crate::keyring::delete(KEYRING_SERVICE, &path);
+                        cache.remove(&path);
+                    } else {
+                        self.keyring.push(path);
+                    }
+                }
+                Some(secret) if cache.get(&path).is_some_and(|stored| stored.as_ref() == Some(&secret)) => self.keyring.push(path),
+                Some(secret) => match crate::keyring::set(KEYRING_SERVICE, &path, &secret) {
+                    Ok(()) => {
+                        cache.insert(path.clone(), Some(secret));
+                        self.keyring.push(path);
+                    }
+                    Err(e) => {
+                        tracing::debug!("OS keychain unavailable, storing {} in {}: {}", path, CREDENTIALS_FILE, e);
+                        *field = Some(secret);
+                    }
+                },
+            }
+        }
+    }
+
+    /// API 키를 뺀 설정과 API 키만 담은 자격 증명으로 나누기
+    fn split_credentials(&self) -> (Config, Config) {
+        let mut config = self.clone();
+        let mut credentials = Config { settings: config.settings.take_secrets(), ..Config::default() };
+        for (name, profile) in &mut config.profiles {
+            let secrets = profile.settings.take_secrets();
+            if secrets != Settings::default() {
+                credentials.profiles.entry(name.clone()).or_default().settings = secrets;
+            }
+        }
+        (config, credentials)
+    }
+
+    /// 설정 파일 저장 (API 키는 소유자만 읽을 수 있는 자격 증명 파일에 따로 저장)
+    pub fn save(&self) -> Result<PathBuf> {
+        let path = config_path().ok_or_else(|| anyhow!("Cannot determine the home directory to store the config file"))?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let mut stored = self.clone();
+        stored.store_keyring_secrets();
+        let (config, credentials) = stored.split_credentials();
+        let credentials_path = self.credentials_path(&path);
+        if credentials != Config::default() || credentials_path.exists() {
+            if let Some(parent) = credentials_path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            write_config_file(&credentials_path, &toml::to_string_pretty(&credentials)?, true)?;
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::PermissionsExt;
+                fs::set_permissions(&credentials_path, fs::Permissions::from_mode(0o600))?;
+            }
+        }
+        write_config_file(&path, &toml::to_string_pretty(&config)?, false)?;
+        Ok(path)
+    }
+
+    /// 사용할 프로필 이름 (`--profile`/`AI_CLI_PROFILE`, 없으면 `profile` 키)
+    pub fn active_profile_name(&self) -> Option<String> {
+        requested_profile().or_else(|| self.profile.clone())
+    }
+
+    /// 사용할 프로필 (지정한 프로필이 설정 파일에 없으면 오류)
+    pub fn active_profile(&self) -> Result<Option<(String, &Profile)>> {
+        let Some(name) = self.active_profile_name() else {
+            return Ok(None);
+        };
+        match self.profiles.get(&name) {
+            Some(profile) => Ok(Some((name, profile))),
+            None if self.profiles.is_empty() => Err(anyhow!("Unknown profile '{}' (no profiles are configured)", name)),
+            None => {
+                let known: Vec<&str> = self.profiles.keys().map(String::as_str).collect();
+                Err(anyhow!("Unknown profile '{}' (expected one of {})", name, known.join(", ")))
+            }
+        }
+    }
+
+    /// 선택한 프로필, 팀 설정, 리포지토리 설정을 적용한 설정 (리포지토리 설정이 가장 우선)
+    pub fn effective_settings(&self, team: Option<&TeamConfig>, repo: Option<&RepoConfig>) -> Result<Settings> {
+        let mut settings = match self.active_profile()? {
+            Some((_, profile)) => self.settings.overlay(&profile.settings),
+            None => self.settings.clone(),
+        };
+        // 리포지토리와 팀 설정의 값은 치환하지 않음 (환경 변수 유출 방지)
+        settings.expand_env()?;
+        if let Some(team) = team {
+            settings = settings.overlay(&team.settings);
+        }
+        if let Some(repo) = repo {
+            settings = settings.overlay(&repo.settings);
+        }
+        Ok(settings)
+    }
+
+    /// 값을 바꿀 설정 (프로필을 지정했으면 그 프로필, 없으면 새로 만듦)
+    pub fn settings_mut(&mut self, profile: Option<&str>) -> &mut Settings {
+        match profile {
+            Some(name) => &mut self.profiles.entry(name.to_string()).or_default().settings,
+            None => &mut self.settings,
+        }
+    }
+
+    /// 선택한 프로필을 적용한 값을 환경 변수로 적용 (이미 설정된 환경 변수가 우선)
+    pub fn apply_to_env(&self) -> Result<()> {
+        let mut applied = Vec::new();
+        let (team, repo) = match RepoConfig::discover_root() {
+            Some(root) => {
+                TeamConfig::load_checked(&root)?;
+                (team::active_in(&root), RepoConfig::load_checked(&root)?.map(|repo| repo.honored(&root)))
+            }
+            None => (None, None),
+        };
+        for (var, value) in self.effective_settings(team.as_ref(), repo.as_ref())?.env_values() {
+            if std::env::var_os(var).is_none() {
+                std::env::set_var(var, value);
+                applied.push(var);
+            }
+        }
+        let _ = APPLIED_VARS.set(applied);
+        Ok(())
+    }
+
+    /// 선택한 프로필과 각 설정의 현재 값, 그 출처
+    pub fn provenance(&self, team: Option<&TeamConfig>, repo: Option<&RepoConfig>) -> Result<Vec<Provenance>> {
+        let profile_source = match (PROFILE_FLAG.get(), requested_profile(), &self.profile) {
+            (Some(name), _, _) => Some((Source::Flag("--profile"), name.clone())),
+            (None, Some(name), _) => Some((Source::Env(PROFILE_ENV), name)),
+            (None, None, Some(name)) => Some((Source::Global, name.clone())),
+            _ => None,
+        };
+        let mut rows = vec![match profile_source {
+            Some((source, name)) => {
+                let shadowed = if source != Source::Global && self.profile.is_some() { vec![Source::Global] } else { Vec::new() };
+                Provenance { key: "profile", value: Some(name), source, shadowed }
+            }
+            None => Provenance { key: "profile", value: None, source: Source::Default, shadowed: Vec::new() },
+        }];
+
+        let mut layers = Vec::new();
+        if let Some(repo) = repo {
+            layers.push((Source::Repo, &repo.settings));
+        }
+        if let Some(team) = team {
+            layers.push((Source::Team, &team.settings));
+        }
+        if let Some((name, profile)) = self.active_profile()? {
+            layers.push((Source::Profile(name), &profile.settings));
+        }
+        layers.push((Source::Global, &self.settings));
+
+        for (key, var) in KEYS {
+            let env = external_env(var).map(|value| (*var, value));
+            rows.push(trace(key, env, &layers));
+        }
+        Ok(rows)
+    }
+}
+
+/// 설정 값이 들어 있는 이전 방식의 환경 변수 (설정 키, 환경 변수, 설정 파일에서 적용한 값은 제외)
+pub fn legacy_env_vars() -> Vec<(&'static str, &'static str)> {
+    KEYS.iter()
+        .filter(|(_, var)| external_env(var).is_some_and(|value| !value.trim().is_empty()))
+        .copied()
+        .collect()
+}
+
+/// 설정 파일이 아직 없고 옮길 환경 변수가 있는지
+pub fn needs_migration() -> bool {
+    config_path().is_some_and(|path| !path.exists()) && !legacy_env_vars().is_empty()
+}
+
+/// 환경 변수 값을 설정 파일로 옮김 (API 키는 키체인, 사용할 수 없으면 자격 증명 파일)
+/// 옮긴 설정 키와 저장한 곳을 돌려줌
+pub fn migrate_env() -> Result<Vec<(&'static str, String)>> {
+    let mut config = Config::load()?;
+    let mut migrated = Vec::new();
+    for (key, var) in legacy_env_vars() {
+        let value = std::env::var(var).unwrap_or_default();
+        if let Err(e) = config.settings.set(key, &value) {
+            println!("⚠️  Skipping {}: {}", var, e);
+            continue;
+        }
+        if is_secret(key) && !config.keyring.iter().any(|path| path == key) {
+            config.keyring.push(key.to_string());
+        }
+        migrated.push(key);
+    }
+    let path = config.save()?;
+
+    let saved = Config::load()?;
+    let credentials = saved.credentials_path(&path);
+    Ok(migrated
+        .into_iter()
+        .map(|key| {
+            let location = match is_secret(key) {
+                true if saved.keyring.iter().any(|path| path == key) => "OS keychain".to_string(),
+                true => credentials.display().to_string(),
+                false => path.display().to_string(),
+            };
+            (key, location)
+        })
+        .collect())
+}
+
+/// 처음 실행할 때 환경 변수를 설정 파일로 옮길지 묻기 (거절하면 빈 설정 파일을 만들어 다시 묻지 않음)
+pub fn offer_migration() -> Result<()> {
+    use std::io::Write;
+
+    let vars: Vec<&str> = legacy_env_vars().into_iter().map(|(_, var)| var).collect();
+    let path = config_path().ok_or_else(|| anyhow!("Cannot determine the home directory for the config file"))?;
+    println!("📦 ai-cli now keeps settings in {}", path.display());
+    println!("   Found settings in environment variables: {}", vars.join(", "));
+    print!("Save them to the config file (API keys go to the OS keychain)? [Y/n] ");
+    std::io::stdout().flush()?;
+    let mut response = String::new();
+    std::io::stdin().read_line(&mut response)?;
+
+    if matches!(response.trim().to_lowercase().as_str(), "n" | "no") {
+        Config::default().save()?;
+        println!("ℹ Keeping environment variables only; run 'ai-cli config migrate' to save them later\n");
+        return Ok(());
+    }
+    for (key, location) in migrate_env()? {
+        println!("✓ {} → {}", key, location);
+    }
+    println!("ℹ Environment variables still override the config file; remove them from your shell profile when ready\n");
+    Ok(())
+}
+
+/// 다른 사용자가 읽을 수 있는 자격 증명 파일이면 한 번 경고
+#[cfg_attr(not(unix), allow(unused_variables))]
+fn warn_if_readable_by_others(path: &Path) {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        static WARNED: std::sync::Once = std::sync::Once::new();
+        if let Ok(metadata) = fs::metadata(path) {
+            let mode = metadata.permissions().mode() & 0o777;
+            if mode & 0o077 != 0 {
+                WARNED.call_once(|| {
+                    println!("⚠️  {} is accessible by other users (mode {:o}); run 'chmod 600 {}'", path.display(), mode, path.display());
+                });
+            }
+        }
+    }
+}
+
+/// 새 설정 파일을 편집할 때 보여 줄 내용
+fn edit_template(repo: bool) -> &'static str {
+    if repo {
+        "# ai-cli project settings (override the user config in this repository; API keys are ignored)\n\
+         # default_model = \"local\"\n\
+         # exclude = [\"fixtures/\"]\n\
+         #\n\
+         # [commit]\n\
+         # types = [\"feat\", \"fix\", \"docs\", \"chore\"]\n\
+         # conventions = \"Use the crate name as scope\"\n"
+    } else {
+        "# ai-cli settings (environment variables override these values)\n\
+         # API keys are kept in credentials.toml next to this file ('ai-cli config set openai.api_key ...')\n\
+         # default_model = \"local\"\n\
+         # language = \"ko\"\n\
+         # notify = \"bell\"\n\
+         # tips = \"off\"\n\
+         # transcripts = \"off\"\n\
+         # stats = \"on\"\n\
+         #\n\
+         # [openai]\n\
+         # model = \"gpt-4o-mini\"\n\
+         #\n\
+         # [profiles.work]\n\
+         # default_model = \"openai\"\n"
+    }
+}
+
+/// 사용자 편집기 (`VISUAL`, `EDITOR`, 없으면 플랫폼 기본 편집기)
+pub(crate) fn editor() -> String {
+    std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .ok()
+        .filter(|editor| !editor.trim().is_empty())
+        .unwrap_or_else(|| if cfg!(windows) { "notepad".to_string() } else { "vi".to_string() })
+}
+
+/// 편집기로 파일을 여는 명령
+/// git처럼 셸로 실행하므로 `code --wait`, 따옴표로 묶은 공백 있는 경로 등 편집기 값을 셸 문법 그대로 씀
+pub(crate) fn editor_process(editor: &str, file: &Path) -> std::process::Command {
+    #[cfg(windows)]
+    let command = {
+        use std::os::windows::process::CommandExt;
+        // /S: 바깥 따옴표 한 쌍만 벗기고 나머지는 그대로 cmd가 해석
+        let mut command = std::process::Command::new("cmd");
+        command.raw_arg(format!("/S /C \"{} \"{}\"\"", editor, file.display()));
+        command
+    };
+
+    #[cfg(not(windows))]
+    let command = {
+        let mut command = std::process::Command::new("sh");
+        command.arg("-c").arg(format!("{} \"$@\"", editor)).arg(editor).arg(file);
+        command
+    };
+
+    command
+}
+
+/// 설정 쓰기 (`private`이면 Unix에서는 새 파일을 소유자만 읽기 가능하게 만듦)
+#[cfg_attr(not(unix), allow(unused_variables))]
+fn write_config_file(path: &Path, content: &str, private: bool) -> Result<()> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    if private {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    std::io::Write::write_all(&mut options.open(path)?, content.as_bytes())?;
+    Ok(())
+}
+
+/// 설정 파일을 편집기로 열고, 저장한 내용이 올바를 때만 반영 (잘못되었으면 다시 편집하거나 버림)
+pub fn edit(path: &Path, repo: bool) -> Result<()> {
+    use std::io::Write;
+
+    let original = match fs::read_to_string(path) {
+        Ok(content) => Some(content),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+        Err(e) => return Err(anyhow!("Failed to read {}: {}", path.display(), e)),
+    };
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let draft = path.with_extension("edit.toml");
+    write_config_file(&draft, original.as_deref().unwrap_or(edit_template(repo)), !repo)?;
+
+    let result = (|| -> Result<()> {
+        let editor = editor();
+        loop {
+            let status = editor_process(&editor, &draft)
+                .status()
+                .map_err(|e| anyhow!("Failed to start editor '{}': {}", editor, e))?;
+            if !status.success() {
+                return Err(anyhow!("Editor exited with {}; {} was not modified", status, path.display()));
+            }
+
+            let edited = fs::read_to_string(&draft)?;
+            let problems = schema::validate(&edited, repo);
+            if problems.is_empty() {
+                if original.as_deref().unwrap_or(edit_template(repo)) == edited {
+                    println!("ℹ No changes to {}", path.display());
+                } else {
+                    write_config_file(path, &edited, !repo)?;
+                    println!("✓ Saved {}", path.display());
+                    // 직접 편집한 리포지토리 설정은 다시 묻지 않음
+                    if let Some(config) = repo.then(|| RepoConfig::parse(&edited).ok()).flatten() {
+                        if !config.gated_settings().is_empty() {
+                            SecurityManager::record_team_config(path, &config.digest(), true)?;
+                        }
+                    }
+                }
+                return Ok(());
+            }
+
+            println!("❌ {} has problems:", path.display());
+            for problem in &problems {
+                println!("  {}", problem.to_string().replace('\n', "\n  "));
+            }
+            print!("Edit again? [Y/n] ");
+            std::io::stdout().flush()?;
+            let mut response = String::new();
+            std::io::stdin().read_line(&mut response)?;
+            if matches!(response.trim().to_lowercase().as_str(), "n" | "no") {
+                println!("✗ Discarded changes; {} was not modified", path.display());
+                return Ok(());
+            }
+        }
+    })();
+
+    let _ = fs::remove_file(&draft);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_and_unset() {
+        let mut settings = Settings::default();
+        settings.set("openai.model", " gpt-4o ").unwrap();
+        settings.set("default_model", "anthropic").unwrap();
+        assert_eq!(settings.get("openai.model"), Some("gpt-4o"));
+        assert_eq!(settings.env_values(), vec![("AI_CLI_DEFAULT_MODEL", "anthropic"), ("AI_CLI_OPENAI_MODEL", "gpt-4o")]);
+
+        assert!(settings.set("default_model", "gemini").is_err());
+        assert!(settings.set("language", "ko").is_ok() && settings.set("language", "fr").is_err());
+        assert!(settings.set("notify", "desktop").is_ok() && settings.set("notify", "popup").is_err());
+        assert!(settings.set("tips", "off").is_ok() && settings.set("tips", "never").is_err());
+        assert!(settings.set("transcripts", "off").is_ok() && settings.set("transcripts", "full").is_err());
+        assert!(settings.set("stats", "on").is_ok() && settings.set("stats", "yes").is_err());
+        assert!(settings.set("openai.model", "").is_err());
+        assert!(settings.set("openai.org", "acme").unwrap_err().to_string().starts_with("Unknown config key 'openai.org'"));
+
+        assert!(settings.unset("openai.model").unwrap());
+        assert!(!settings.unset("openai.model").unwrap());
+        assert_eq!(settings.get("openai.model"), None);
+    }
+
+    #[test]
+    fn test_toml_roundtrip() {
+        let config: Config = toml::from_str("default_model = \"openai\"\n\n[openai]\napi_key = \"sk-test\"\n").unwrap();
+        assert_eq!(config.settings.get("openai.api_key"), Some("sk-test"));
+        assert_eq!(config.settings.anthropic, BackendConfig::default());
+
+        let saved = toml::to_string_pretty(&config).unwrap();
+        assert_eq!(toml::from_str::<Config>(&saved).unwrap(), config);
+        assert!(!saved.contains("url"));
+        assert!(!saved.contains("[anthropic]"));
+        assert!(KEYS.iter().all(|(key, _)| config.settings.field(key).is_some()));
+    }
+
+    #[test]
+    fn test_profiles() {
+        let mut config: Config = toml::from_str(
+            r#"
+            profile = "work"
+            default_model = "local"
+            [openai]
+            api_key = "sk-personal"
+            model = "gpt-4o-mini"
+
+            [profiles.work]
+            default_model = "openai"
+            openai = { api_key = "sk-work" }
+            privacy = { scrub = ["email"], names = ["Acme Corp"] }
+            commit = { conventions = "Start the subject with the Jira key" }
+
+            [profiles.oss]
+            "#,
+        )
+        .unwrap();
+
+        let (name, profile) = config.active_profile().unwrap().unwrap();
+        assert_eq!(name, "work");
+        assert_eq!(profile.privacy.as_ref().unwrap().names, vec!["Acme Corp".to_string()]);
+        let settings = config.effective_settings(None, None).unwrap();
+        assert_eq!(settings.get("default_model"), Some("openai"));
+        assert_eq!(settings.get("openai.api_key"), Some("sk-work"));
+        assert_eq!(settings.get("openai.model"), Some("gpt-4o-mini"));
+
+        let repo = RepoConfig::parse("default_model = \"anthropic\"\nopenai = { api_key = \"sk-leak\" }\nexclude = [\"vendor/\"]\n").unwrap();
+        assert_eq!(repo.settings.get("openai.api_key"), None);
+        let settings = config.effective_settings(None, Some(&repo)).unwrap();
+        assert_eq!(settings.get("default_model"), Some("anthropic"));
+        assert_eq!(settings.get("openai.api_key"), Some("sk-work"));
+
+        config.profile = Some("client".to_string());
+        assert_eq!(config.active_profile().unwrap_err().to_string(), "Unknown profile 'client' (expected one of oss, work)");
+
+        config.settings_mut(Some("client")).set("anthropic.model", "claude-3-5-haiku-latest").unwrap();
+        let saved = toml::to_string_pretty(&config).unwrap();
+        assert!(saved.contains("[profiles.client.anthropic]"));
+        assert_eq!(toml::from_str::<Config>(&saved).unwrap(), config);
+    }
+
+    #[test]
+    fn test_untrusted_repo_config_cannot_change_backend() {
+        let root = tempfile::TempDir::new().unwrap();
+        let repo = RepoConfig::parse(
+            "default_model = \"local\"\nexclude = [\"vendor/\"]\n[ollama]\nurl = \"http://attacker.example.com\"\n[commit]\ntypes = [\"feat\"]\ntemplate = \"prompt.txt\"\n",
+        )
+        .unwrap();
+        assert_eq!(
+            repo.gated_settings(),
+            vec!["default_model = \"local\"", "ollama.url = \"http://attacker.example.com\"", "commit.template = \"prompt.txt\""]
+        );
+        assert_eq!(repo.trust(root.path()), team::TeamTrust::Pending);
+
+        // 확인하지 않은 리포지토리 설정은 백엔드, 주소, 템플릿을 바꾸지 못하고 커밋 규칙과 제외 경로만 적용
+        let honored = repo.clone().honored(root.path());
+        let settings = Config::default().effective_settings(None, Some(&honored)).unwrap();
+        assert_eq!(settings.get("default_model"), None);
+        assert_eq!(settings.get("ollama.url"), None);
+        assert_eq!(honored.commit.template, None);
+        assert_eq!(honored.commit.types, vec!["feat"]);
+        assert_eq!(honored.exclude, vec!["vendor/"]);
+    }
+
+    #[test]
+    fn test_expand_env_values() {
+        std::env::set_var("AI_CLI_TEST_OLLAMA_HOST", "ollama.internal");
+        let config: Config = toml::from_str("[ollama]\nurl = \"http://${AI_CLI_TEST_OLLAMA_HOST}:11434\"\nmodel = \"${AI_CLI_TEST_OLLAMA_MODEL:-llama3}\"\n").unwrap();
+        let settings = config.effective_settings(None, None).unwrap();
+        assert_eq!(settings.get("ollama.url"), Some("http://ollama.internal:11434"));
+        assert_eq!(settings.get("ollama.model"), Some("llama3"));
+
+        // 리포지토리 설정의 값은 치환하지 않음
+        let repo = RepoConfig::parse("[openai]\nmodel = \"${AI_CLI_TEST_OLLAMA_HOST}\"\n").unwrap();
+        assert_eq!(config.effective_settings(None, Some(&repo)).unwrap().get("openai.model"), Some("${AI_CLI_TEST_OLLAMA_HOST}"));
+
+        let missing: Config = toml::from_str("[openai]\napi_key = \"${AI_CLI_TEST_MISSING_KEY}\"\n").unwrap();
+        let error = missing.effective_settings(None, None).unwrap_err().to_string();
+        assert!(error.starts_with("Config value 'openai.api_key': environment variable 'AI_CLI_TEST_MISSING_KEY' is not set"));
+    }
+
+    #[test]
+    fn test_split_credentials() {
+        let mut config: Config = toml::from_str(
+            "default_model = \"openai\"\n[openai]\napi_key = \"sk-personal\"\nmodel = \"gpt-4o\"\n\n[profiles.work.anthropic]\napi_key = \"sk-ant\"\n",
+        )
+        .unwrap();
+        let (shared, credentials) = config.split_credentials();
+        let shared_text = toml::to_string_pretty(&shared).unwrap();
+        assert!(!shared_text.contains("sk-"));
+        assert!(shared_text.contains("[profiles.work]"));
+
+        let credentials_text = toml::to_string_pretty(&credentials).unwrap();
+        assert!(schema::validate_credentials(&credentials_text).is_empty());
+        let mut loaded: Config = toml::from_str(&shared_text).unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(CREDENTIALS_FILE);
+        fs::write(&path, credentials_text).unwrap();
+        loaded.merge_credentials(&path).unwrap();
+        assert_eq!(loaded, config);
+
+        assert_eq!(config.credentials_path(&dir.path().join(CONFIG_FILE)), path);
+        config.credentials = Some("secrets/keys.toml".to_string());
+        assert_eq!(config.credentials_path(&dir.path().join(CONFIG_FILE)), dir.path().join("secrets/keys.toml"));
+    }
+
+    #[test]
+    fn test_secret_paths() {
+        let mut config: Config = toml::from_str("[openai]\napi_key = \"sk\"\n[profiles.work.anthropic]\napi_key = \"sk-ant\"\n").unwrap();
+        assert_eq!(config.secret_mut("openai.api_key").unwrap().as_deref(), Some("sk"));
+        assert_eq!(config.secret_mut("profiles.work.anthropic.api_key").unwrap().take().as_deref(), Some("sk-ant"));
+        assert_eq!(config.profiles["work"].settings.get("anthropic.api_key"), None);
+        assert!(config.secret_mut("openai.model").is_none());
+        assert!(config.secret_mut("profiles.oss.openai.api_key").is_none());
+    }
+
+    #[test]
+    fn test_trace_provenance() {
+        let global: Settings = toml::from_str("default_model = \"openai\"\n[openai]\nmodel = \"gpt-4o\"\n").unwrap();
+        let profile: Settings = toml::from_str("default_model = \"anthropic\"\n").unwrap();
+        let repo: Settings = toml::from_str("[openai]\nmodel = \"gpt-4.1\"\n").unwrap();
+        let layers = [(Source::Repo, &repo), (Source::Profile("work".to_string()), &profile), (Source::Global, &global)];
+
+        let row = trace("default_model", None, &layers);
+        assert_eq!(row.value.as_deref(), Some("anthropic"));
+        assert_eq!(row.source, Source::Profile("work".to_string()));
+        assert_eq!(row.shadowed, vec![Source::Global]);
+
+        let row = trace("openai.model", Some(("AI_CLI_OPENAI_MODEL", "gpt-4o-mini".to_string())), &layers);
+        assert_eq!(row.source, Source::Env("AI_CLI_OPENAI_MODEL"));
+        assert_eq!(row.shadowed, vec![Source::Repo, Source::Global]);
+
+        let row = trace("ollama.url", None, &layers);
+        assert_eq!((row.value.as_deref(), row.source), (Some("http://localhost:11434"), Source::Default));
+        assert_eq!(trace("openai.api_key", None, &layers).value, None);
+    }
+
+    #[test]
+    fn test_commit_rules_overlay() {
+        let mut rules = CommitRules { conventions: Some("Reference the Jira key".to_string()), ..CommitRules::default() };
+        let repo: RepoConfig = toml::from_str(
+            "[commit]\ntypes = [\"feat\", \"fix\", \"deps\"]\nconventions = \"Use the crate name as scope\"\ntemplate = \".ai-cli/commit.txt\"\n",
+        )
+        .unwrap();
+        rules.overlay(&repo.commit);
+        assert_eq!(rules.conventions.as_deref(), Some("Reference the Jira key\nUse the crate name as scope"));
+        assert_eq!(rules.types, vec!["feat", "fix", "deps"]);
+        assert_eq!(rules.template.as_deref(), Some(".ai-cli/commit.txt"));
+
+        rules.overlay(&CommitRules::default());
+        assert_eq!(rules.types.len(), 3);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_editor_process_uses_shell_syntax() {
+        // 공백이 있는 경로를 따옴표로 묶고 옵션을 붙인 값 (`sh "/opt/My Editor/edit" --wait`)
+        // 스크립트를 직접 실행하지 않고 sh로 읽어, 병렬 테스트의 fork와 겹쳐도 "Text file busy"가 나지 않음
+        let dir = tempfile::tempdir().unwrap();
+        let editor_dir = dir.path().join("My Editor");
+        fs::create_dir(&editor_dir).unwrap();
+        let script = editor_dir.join("edit");
+        fs::write(&script, "#!/bin/sh\nprintf '%s|%s' \"$1\" \"$(basename \"$2\")\" > \"$2\"\n").unwrap();
+
+        let file = dir.path().join("draft file.toml");
+        let editor = format!("sh \"{}\" --wait", script.display());
+        let status = editor_process(&editor, &file).status().unwrap();
+        assert!(status.success());
+        assert_eq!(fs::read_to_string(&file).unwrap(), "--wait|draft file.toml");
+    }
+}
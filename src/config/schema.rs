@@ -0,0 +1,457 @@
+//! 설정 파일 검증
+//!
+//! 사용자 설정, 자격 증명 파일, `.ai-cli.toml`, 팀 설정을 키 목록과 값 종류(문자열, URL, 모델 이름, 선택지 등)로 검증해, 요청 도중에
+//! 알아보기 어렵게 실패하는 대신 시작할 때 잘못된 키의 파일, 줄, 올바른 값의 예를 알려 줍니다.
+//! 모르는 키는 비슷한 키를 제안합니다.
+
+use std::fmt;
+use std::path::PathBuf;
+
+use super::team::TeamConfig;
+use super::{Config, RepoConfig};
+
+/// 값 종류
+#[derive(Clone, Copy)]
+enum Kind {
+    Text,
+    /// http(s) URL
+    Url,
+    /// 백엔드 이름 (local, openai, anthropic)
+    Model,
+    TextList,
+    Bool,
+    /// 양의 정수
+    Count,
+    /// 정규식 문자열
+    Regex,
+    Choice(&'static [&'static str]),
+    ChoiceList(&'static [&'static str]),
+}
+
+/// 설정 파일에서 허용하는 키
+enum Schema {
+    /// 값과 올바른 값의 예
+    Value(Kind, &'static str),
+    /// 정해진 키만 있는 표
+    Table(&'static [(&'static str, Schema)]),
+    /// 이름을 마음대로 붙이는 표 (`profiles`)
+    Map(&'static Schema),
+}
+
+const MODELS: &[&str] = &["local", "openai", "anthropic"];
+
+const BACKEND_SCHEMA: Schema = Schema::Table(&[
+    ("url", Schema::Value(Kind::Url, "\"http://localhost:11434\"")),
+    ("model", Schema::Value(Kind::Text, "\"gpt-4o-mini\"")),
+    ("api_key", Schema::Value(Kind::Text, "\"sk-...\"")),
+]);
+
+const PRIVACY_SCHEMA: Schema = Schema::Table(&[
+    ("mode", Schema::Value(Kind::Choice(&["default", "local-only"]), "\"local-only\"")),
+    ("scrub", Schema::Value(Kind::ChoiceList(&["email", "ip", "phone", "name"]), "[\"email\", \"name\"]")),
+    ("names", Schema::Value(Kind::TextList, "[\"Jane Doe\"]")),
+    ("anonymize_paths", Schema::Value(Kind::Bool, "true")),
+]);
+
+const COMMIT_SCHEMA: Schema = Schema::Table(&[
+    ("conventions", Schema::Value(Kind::Text, "\"Start the subject with the Jira key\"")),
+    ("types", Schema::Value(Kind::TextList, "[\"feat\", \"fix\", \"docs\"]")),
+    ("template", Schema::Value(Kind::Text, "\".ai-cli/commit-prompt.txt\"")),
+    ("require_scope", Schema::Value(Kind::Bool, "true")),
+    ("max_subject_length", Schema::Value(Kind::Count, "72")),
+    ("require_body", Schema::Value(Kind::Bool, "true")),
+    ("ticket_pattern", Schema::Value(Kind::Regex, "\"[A-Z]+-[0-9]+\"")),
+    ("style", Schema::Value(Kind::Choice(&super::COMMIT_STYLES), "\"gitmoji\"")),
+]);
+
+const DEFAULT_MODEL_SCHEMA: Schema = Schema::Value(Kind::Model, "\"local\"");
+
+const LANGUAGE_SCHEMA: Schema = Schema::Value(Kind::Choice(&crate::i18n::LANGUAGES), "\"ko\"");
+
+const NOTIFY_SCHEMA: Schema = Schema::Value(Kind::Choice(&crate::notify::NOTIFY_MODES), "\"bell\"");
+
+const TIPS_SCHEMA: Schema = Schema::Value(Kind::Choice(&crate::tips::TIP_MODES), "\"off\"");
+
+const TRANSCRIPTS_SCHEMA: Schema = Schema::Value(Kind::Choice(&crate::transcript::TRANSCRIPT_MODES), "\"off\"");
+
+const STATS_SCHEMA: Schema = Schema::Value(Kind::Choice(&crate::stats::STATS_MODES), "\"on\"");
+
+const PROFILE_SCHEMA: Schema = Schema::Table(&[
+    ("default_model", DEFAULT_MODEL_SCHEMA),
+    ("language", LANGUAGE_SCHEMA),
+    ("notify", NOTIFY_SCHEMA),
+    ("tips", TIPS_SCHEMA),
+    ("transcripts", TRANSCRIPTS_SCHEMA),
+    ("stats", STATS_SCHEMA),
+    ("ollama", BACKEND_SCHEMA),
+    ("openai", BACKEND_SCHEMA),
+    ("anthropic", BACKEND_SCHEMA),
+    ("privacy", PRIVACY_SCHEMA),
+    ("commit", COMMIT_SCHEMA),
+]);
+
+const USER_SCHEMA: Schema = Schema::Table(&[
+    ("profile", Schema::Value(Kind::Text, "\"work\"")),
+    ("credentials", Schema::Value(Kind::Text, "\"~/.config/ai-cli/credentials.toml\"")),
+    ("keyring", Schema::Value(Kind::TextList, "[\"openai.api_key\"]")),
+    ("default_model", DEFAULT_MODEL_SCHEMA),
+    ("language", LANGUAGE_SCHEMA),
+    ("notify", NOTIFY_SCHEMA),
+    ("tips", TIPS_SCHEMA),
+    ("transcripts", TRANSCRIPTS_SCHEMA),
+    ("stats", STATS_SCHEMA),
+    ("ollama", BACKEND_SCHEMA),
+    ("openai", BACKEND_SCHEMA),
+    ("anthropic", BACKEND_SCHEMA),
+    ("profiles", Schema::Map(&PROFILE_SCHEMA)),
+]);
+
+const REPO_SCHEMA: Schema = Schema::Table(&[
+    ("default_model", DEFAULT_MODEL_SCHEMA),
+    ("ollama", BACKEND_SCHEMA),
+    ("openai", BACKEND_SCHEMA),
+    ("anthropic", BACKEND_SCHEMA),
+    ("commit", COMMIT_SCHEMA),
+    ("exclude", Schema::Value(Kind::TextList, "[\"fixtures/\", \"*.snap\"]")),
+]);
+
+/// 리포지토리에 올라가는 팀 설정의 백엔드 (API 키는 둘 수 없음)
+const TEAM_BACKEND_SCHEMA: Schema = Schema::Table(&[
+    ("url", Schema::Value(Kind::Url, "\"http://ollama.internal:11434\"")),
+    ("model", Schema::Value(Kind::Text, "\"gpt-4o-mini\"")),
+]);
+
+const TEAM_SCHEMA: Schema = Schema::Table(&[
+    ("default_model", DEFAULT_MODEL_SCHEMA),
+    ("ollama", TEAM_BACKEND_SCHEMA),
+    ("openai", TEAM_BACKEND_SCHEMA),
+    ("anthropic", TEAM_BACKEND_SCHEMA),
+    ("commit", COMMIT_SCHEMA),
+    ("prompt", Schema::Value(Kind::Text, "\"Mention the affected service in every summary\"")),
+    ("privacy", PRIVACY_SCHEMA),
+    ("exclude", Schema::Value(Kind::TextList, "[\"fixtures/\", \"*.snap\"]")),
+]);
+
+const API_KEY_SCHEMA: Schema = Schema::Table(&[("api_key", Schema::Value(Kind::Text, "\"sk-...\""))]);
+
+const CREDENTIALS_PROFILE_SCHEMA: Schema = Schema::Table(&[("openai", API_KEY_SCHEMA), ("anthropic", API_KEY_SCHEMA)]);
+
+const CREDENTIALS_SCHEMA: Schema = Schema::Table(&[
+    ("openai", API_KEY_SCHEMA),
+    ("anthropic", API_KEY_SCHEMA),
+    ("profiles", Schema::Map(&CREDENTIALS_PROFILE_SCHEMA)),
+]);
+
+/// 설정 파일의 문제 하나
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Problem {
+    pub line: Option<usize>,
+    /// 점으로 구분한 키 경로
+    pub key: Option<String>,
+    pub message: String,
+    /// 올바른 설정의 예 (`key = value`)
+    pub example: Option<String>,
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(line) = self.line {
+            write!(f, "line {}: ", line)?;
+        }
+        if let Some(key) = &self.key {
+            write!(f, "'{}' ", key)?;
+        }
+        write!(f, "{}", self.message)?;
+        if let Some(example) = &self.example {
+            write!(f, " (e.g. {})", example)?;
+        }
+        Ok(())
+    }
+}
+
+/// 설정 파일이 스키마에 맞지 않음
+#[derive(Debug, thiserror::Error)]
+#[error("Invalid config file {}:\n{}", .path.display(), .problems.iter().map(|p| format!("  - {}", p.to_string().replace('\n', "\n    "))).collect::<Vec<_>>().join("\n"))]
+pub struct ConfigError {
+    pub path: PathBuf,
+    pub problems: Vec<Problem>,
+}
+
+/// 값이 종류에 맞지 않으면 문제 설명
+fn value_problem(kind: Kind, value: &toml::Value) -> Option<String> {
+    let text = value.as_str();
+    // `${VAR}`가 있는 값은 치환한 뒤에야 알 수 있음
+    if text.is_some_and(super::interpolate::has_variables) && matches!(kind, Kind::Url | Kind::Model | Kind::Choice(_)) {
+        return None;
+    }
+    let list = value.as_array().map(|items| items.iter().map(toml::Value::as_str).collect::<Option<Vec<_>>>());
+    match kind {
+        Kind::Text => text.is_none().then(|| format!("must be a string (got {})", value.type_str())),
+        Kind::Url => match text {
+            None => Some(format!("must be a URL string (got {})", value.type_str())),
+            Some(url) => match reqwest::Url::parse(url) {
+                Ok(parsed) if matches!(parsed.scheme(), "http" | "https") => None,
+                Ok(parsed) => Some(format!("must be an http or https URL (got scheme '{}')", parsed.scheme())),
+                Err(e) => Some(format!("is not a valid URL ({}): '{}'", e, url)),
+            },
+        },
+        Kind::Model | Kind::Choice(_) => {
+            let choices = if let Kind::Choice(choices) = kind { choices } else { MODELS };
+            match text {
+                Some(text) if choices.contains(&text) => None,
+                Some(text) => Some(format!("must be one of {} (got '{}')", choices.join(", "), text)),
+                None => Some(format!("must be one of {} (got {})", choices.join(", "), value.type_str())),
+            }
+        }
+        Kind::TextList => match list {
+            Some(Some(_)) => None,
+            _ => Some(format!("must be a list of strings (got {})", value.type_str())),
+        },
+        Kind::ChoiceList(choices) => match list {
+            Some(Some(items)) => {
+                let unknown: Vec<&str> = items.into_iter().filter(|item| !choices.contains(item)).collect();
+                (!unknown.is_empty()).then(|| format!("has unknown values {} (expected any of {})", unknown.join(", "), choices.join(", ")))
+            }
+            _ => Some(format!("must be a list of strings (got {})", value.type_str())),
+        },
+        Kind::Bool => (!value.is_bool()).then(|| format!("must be true or false (got {})", value.type_str())),
+        Kind::Count => match value.as_integer() {
+            Some(count) if count > 0 => None,
+            Some(count) => Some(format!("must be a positive number (got {})", count)),
+            None => Some(format!("must be a positive number (got {})", value.type_str())),
+        },
+        Kind::Regex => match text {
+            None => Some(format!("must be a regular expression string (got {})", value.type_str())),
+            Some(pattern) => regex::Regex::new(pattern).err().map(|e| format!("is not a valid regular expression: {}", e)),
+        },
+    }
+}
+
+/// 두 키의 편집 거리 (비슷한 키 제안용)
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut previous = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let current = row[j + 1];
+            row[j + 1] = if ca == *cb { previous } else { 1 + previous.min(row[j]).min(row[j + 1]) };
+            previous = current;
+        }
+    }
+    row[b.len()]
+}
+
+/// 스키마와 다른 키와 값 찾기
+fn check(value: &toml::Value, schema: &Schema, path: &str, problems: &mut Vec<Problem>) {
+    let name = path.rsplit('.').next().unwrap_or(path);
+    match schema {
+        Schema::Value(kind, example) => {
+            if let Some(message) = value_problem(*kind, value) {
+                problems.push(Problem { line: None, key: Some(path.to_string()), message, example: Some(format!("{} = {}", name, example)) });
+            }
+        }
+        Schema::Table(keys) => {
+            let Some(table) = value.as_table() else {
+                problems.push(Problem {
+                    line: None,
+                    key: Some(path.to_string()),
+                    message: format!("must be a table (got {})", value.type_str()),
+                    example: Some(format!("[{}]", path)),
+                });
+                return;
+            };
+            for (key, value) in table {
+                let child = if path.is_empty() { key.clone() } else { format!("{}.{}", path, key) };
+                match keys.iter().find(|(known, _)| known == key) {
+                    Some((_, schema)) => check(value, schema, &child, problems),
+                    None => {
+                        let known: Vec<&str> = keys.iter().map(|(known, _)| *known).collect();
+                        let message = match known.iter().filter(|known| edit_distance(key, known) <= 2).min_by_key(|known| edit_distance(key, known)) {
+                            Some(similar) => format!("is not a known key (did you mean '{}'?)", similar),
+                            None => format!("is not a known key (expected one of {})", known.join(", ")),
+                        };
+                        problems.push(Problem { line: None, key: Some(child), message, example: None });
+                    }
+                }
+            }
+        }
+        Schema::Map(schema) => match value.as_table() {
+            Some(table) => {
+                for (key, value) in table {
+                    check(value, schema, &format!("{}.{}", path, key), problems);
+                }
+            }
+            None => problems.push(Problem {
+                line: None,
+                key: Some(path.to_string()),
+                message: format!("must be a table of named entries (got {})", value.type_str()),
+                example: Some(format!("[{}.work]", path)),
+            }),
+        },
+    }
+}
+
+/// 줄이 정의하는 키 경로 (표 머리글이면 표 경로도 바꿈)
+fn line_key(line: &str, table: &mut String) -> Option<String> {
+    let line = line.trim();
+    let unquote = |key: &str| key.split('.').map(|part| part.trim().trim_matches('"').trim_matches('\'')).collect::<Vec<_>>().join(".");
+    if line.starts_with('[') {
+        *table = unquote(line.trim_start_matches('[').split(']').next().unwrap_or(""));
+        return Some(table.clone());
+    }
+    let (key, _) = line.split_once('=')?;
+    if line.starts_with('#') {
+        return None;
+    }
+    let key = unquote(key);
+    Some(if table.is_empty() { key } else { format!("{}.{}", table, key) })
+}
+
+/// 키가 정의된 줄 번호 (1부터, 키가 인라인 표 안에 있으면 그 표의 줄)
+fn key_line(text: &str, key: &str) -> Option<usize> {
+    let mut table = String::new();
+    let mut best: Option<(usize, usize)> = None;
+    for (index, line) in text.lines().enumerate() {
+        let Some(defined) = line_key(line, &mut table) else { continue };
+        if defined == key {
+            return Some(index + 1);
+        }
+        if key.starts_with(&format!("{}.", defined)) && best.is_none_or(|(_, len)| defined.len() > len) {
+            best = Some((index + 1, defined.len()));
+        }
+    }
+    best.map(|(line, _)| line)
+}
+
+/// TOML 문법과 스키마 검사 (줄 번호 순)
+fn check_text(text: &str, schema: &Schema) -> Vec<Problem> {
+    let value: toml::Value = match toml::from_str(text) {
+        Ok(value) => value,
+        Err(e) => {
+            let line = e.span().map(|span| text[..span.start.min(text.len())].lines().count().max(1));
+            return vec![Problem { line, key: None, message: e.message().trim_end().to_string(), example: None }];
+        }
+    };
+
+    let mut problems = Vec::new();
+    check(&value, schema, "", &mut problems);
+    for problem in &mut problems {
+        problem.line = problem.key.as_deref().and_then(|key| key_line(text, key));
+    }
+    problems.sort_by_key(|problem| problem.line.unwrap_or(usize::MAX));
+    problems
+}
+
+/// 자격 증명 파일 내용 검증 (API 키만 허용)
+pub fn validate_credentials(text: &str) -> Vec<Problem> {
+    check_text(text, &CREDENTIALS_SCHEMA)
+}
+
+/// 팀 설정(`.ai-cli/team.toml`) 내용 검증
+pub fn validate_team(text: &str) -> Vec<Problem> {
+    let mut problems = check_text(text, &TEAM_SCHEMA);
+    if problems.is_empty() {
+        if let Err(e) = toml::from_str::<TeamConfig>(text) {
+            problems.push(Problem { line: None, key: None, message: e.to_string().trim_end().to_string(), example: None });
+        }
+    }
+    problems
+}
+
+/// 설정 파일 내용 검증 (문제가 없으면 빈 목록)
+pub fn validate(text: &str, repo: bool) -> Vec<Problem> {
+    let mut problems = check_text(text, if repo { &REPO_SCHEMA } else { &USER_SCHEMA });
+
+    if !repo && problems.is_empty() {
+        if let Ok(config) = toml::from_str::<Config>(text) {
+            if let Some(name) = config.profile.as_deref().filter(|name| !config.profiles.contains_key(*name)) {
+                let example = config.profiles.keys().next().map(|known| format!("profile = \"{}\"", known));
+                problems.push(Problem {
+                    line: key_line(text, "profile"),
+                    key: Some("profile".to_string()),
+                    message: format!("refers to '{}', which is not defined under [profiles]", name),
+                    example,
+                });
+            }
+        }
+    }
+
+    // 스키마 검사를 통과했는데 해석에 실패하는 경우 (남은 타입 오류)
+    if problems.is_empty() {
+        let parsed = if repo { toml::from_str::<RepoConfig>(text).err() } else { toml::from_str::<Config>(text).err() };
+        if let Some(e) = parsed {
+            problems.push(Problem { line: None, key: None, message: e.to_string().trim_end().to_string(), example: None });
+        }
+    }
+    problems
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate() {
+        assert!(validate("default_model = \"openai\"\n\n[profiles.work.openai]\napi_key = \"sk\"\n", false).is_empty());
+        assert!(validate("exclude = [\"fixtures/\"]\n[commit]\ntypes = [\"feat\"]\n", true).is_empty());
+
+        let text = "default_model = \"gemini\"\n\n[openai]\nmodle = \"gpt-4o\"\n\n[profiles.work]\nprivacy = { scrubs = [\"email\"] }\n";
+        let problems: Vec<String> = validate(text, false).iter().map(Problem::to_string).collect();
+        assert_eq!(problems, vec![
+            "line 1: 'default_model' must be one of local, openai, anthropic (got 'gemini') (e.g. default_model = \"local\")".to_string(),
+            "line 4: 'openai.modle' is not a known key (did you mean 'model'?)".to_string(),
+            "line 7: 'profiles.work.privacy.scrubs' is not a known key (did you mean 'scrub'?)".to_string(),
+        ]);
+        assert_eq!(
+            validate("profile = \"work\"\n", false)[0].to_string(),
+            "line 1: 'profile' refers to 'work', which is not defined under [profiles]"
+        );
+        assert_eq!(validate("profiles = {}\n", true)[0].key.as_deref(), Some("profiles"));
+
+        let problems = validate("[openai\nmodel = 1\n", false);
+        assert_eq!(problems[0].line, Some(1));
+    }
+
+    #[test]
+    fn test_value_kinds() {
+        let text = "[ollama]\nurl = \"localhost:11434\"\n\n[profiles.work]\nollama = { url = \"http://ollama.internal:11434\" }\nprivacy = { scrub = [\"email\", \"ssn\"], anonymize_paths = \"yes\" }\n";
+        let problems = validate(text, false);
+        assert_eq!(problems.len(), 3);
+        assert_eq!(problems[0].key.as_deref(), Some("ollama.url"));
+        assert_eq!(problems[0].line, Some(2));
+        assert_eq!(problems[0].example.as_deref(), Some("url = \"http://localhost:11434\""));
+        assert_eq!(problems[1].message, "must be true or false (got string)");
+        assert_eq!(problems[1].line, Some(6));
+        assert_eq!(problems[2].message, "has unknown values ssn (expected any of email, ip, phone, name)");
+
+        let problems = validate("[commit]\ntypes = \"feat\"\n", true);
+        assert_eq!(problems[0].to_string(), "line 2: 'commit.types' must be a list of strings (got string) (e.g. types = [\"feat\", \"fix\", \"docs\"])");
+
+        let problems = validate("[commit]\nmax_subject_length = 0\nticket_pattern = \"[A-Z+-\"\nrequire_scope = true\n", true);
+        assert_eq!(problems.len(), 2);
+        assert_eq!(problems[0].message, "must be a positive number (got 0)");
+        assert!(problems[1].message.starts_with("is not a valid regular expression"));
+        assert!(validate("default_model = \"${AI_CLI_BACKEND:-local}\"\n[ollama]\nurl = \"${OLLAMA_URL}\"\n", false).is_empty());
+        assert_eq!(edit_distance("modle", "model"), 2);
+    }
+
+    #[test]
+    fn test_validate_credentials() {
+        assert!(validate_credentials("[openai]\napi_key = \"sk\"\n\n[profiles.work.anthropic]\napi_key = \"sk-ant\"\n").is_empty());
+
+        let problems = validate_credentials("[openai]\napi_key = \"sk\"\nmodel = \"gpt-4o\"\n");
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].to_string(), "line 3: 'openai.model' is not a known key (expected one of api_key)");
+    }
+
+    #[test]
+    fn test_validate_team() {
+        assert!(validate_team("prompt = \"Mention the service\"\nexclude = [\"secrets/\"]\n[privacy]\nscrub = [\"email\"]\n").is_empty());
+
+        let problems = validate_team("[openai]\napi_key = \"sk-team\"\n");
+        assert_eq!(problems[0].key.as_deref(), Some("openai.api_key"));
+        assert_eq!(problems[0].line, Some(2));
+    }
+}
@@ -0,0 +1,201 @@
+//! 원격 설정 동기화 (`config sync --remote <git-url>`)
+//!
+//! 여러 컴퓨터의 설정을 맞출 수 있도록 API 키와 토큰을 가린 설정 묶음(`bundle` 모듈)을 개인 git 리포지토리의
+//! `ai-cli.json`으로 주고받습니다. 리포지토리는 `~/.ai-cli/sync`에 클론해 두고, 마지막으로 동기화한 묶음과 비교해
+//! 바뀐 쪽을 적용합니다. 원격만 바뀌었으면 가져오고(이 컴퓨터에만 있는 값은 남겨서 다시 올림), 이 컴퓨터만 바뀌었으면
+//! 올립니다. 양쪽 모두 바뀌었으면 `--pull`이나 `--push`로 어느 쪽을 쓸지 정해야 합니다.
+//! git 인증은 사용자의 git 설정(SSH 키, credential helper)을 그대로 씁니다.
+
+use anyhow::{Result, anyhow};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use super::bundle::{Bundle, ImportReport};
+
+/// 동기화 리포지토리에 저장하는 파일
+pub const SYNC_FILE: &str = "ai-cli.json";
+
+/// 어느 쪽을 우선할지
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncMode {
+    /// 바뀐 쪽을 적용 (양쪽 모두 바뀌었으면 오류)
+    Auto,
+    /// 원격 설정을 가져옴 (겹치는 값은 원격이 우선)
+    Pull,
+    /// 이 컴퓨터의 설정으로 원격을 덮어씀
+    Push,
+}
+
+/// 할 일
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Plan {
+    UpToDate,
+    Pull,
+    Push,
+}
+
+/// 동기화 결과
+#[derive(Debug, Default)]
+pub struct SyncReport {
+    pub remote: String,
+    /// 원격 설정을 가져왔으면 그 결과
+    pub pulled: Option<ImportReport>,
+    pub pushed: bool,
+}
+
+/// 로컬 클론 경로 (`~/.ai-cli/sync`)
+pub fn sync_dir() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(".ai-cli").join("sync"))
+}
+
+/// git 명령 실행 (출력 그대로, 실패하면 stderr를 담은 오류)
+fn git(dir: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .map_err(|e| anyhow!("Failed to run git: {}", e))?;
+    if !output.status.success() {
+        return Err(anyhow!("git {} failed: {}", args[0], String::from_utf8_lossy(&output.stderr).trim()));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// 마지막으로 본 원격 브랜치의 설정 묶음 (없으면 None)
+fn upstream_file(dir: &Path) -> Option<String> {
+    let branch = git(dir, &["symbolic-ref", "--short", "HEAD"]).ok()?;
+    git(dir, &["show", &format!("origin/{}:{}", branch.trim(), SYNC_FILE)]).ok()
+}
+
+/// 동기화할 묶음 (비밀 값을 빼고, 컴퓨터마다 다른 자격 증명 위치와 가린 값 목록도 넣지 않음)
+fn local_bundle() -> Result<Bundle> {
+    let mut bundle = Bundle::collect(true)?;
+    bundle.config.credentials = None;
+    bundle.config.keyring.clear();
+    bundle.redacted.clear();
+    Ok(bundle)
+}
+
+/// 마지막 동기화(`base`), 이 컴퓨터(`local`), 원격(`remote`)의 묶음을 비교해 할 일 결정
+fn plan(base: Option<&str>, local: &str, remote: Option<&str>, mode: SyncMode) -> Result<Plan> {
+    let Some(remote) = remote else {
+        return match mode {
+            SyncMode::Pull => Err(anyhow!("Nothing to pull: the remote has no {} yet", SYNC_FILE)),
+            _ => Ok(Plan::Push),
+        };
+    };
+    Ok(match mode {
+        _ if local == remote => Plan::UpToDate,
+        SyncMode::Pull => Plan::Pull,
+        SyncMode::Push => Plan::Push,
+        // 이 컴퓨터에서 처음 동기화하거나 원격만 바뀜
+        SyncMode::Auto if base.is_none() || base == Some(local) => Plan::Pull,
+        SyncMode::Auto if base == Some(remote) => Plan::Push,
+        SyncMode::Auto => {
+            return Err(anyhow!(
+                "Settings changed both on this machine and on the remote since the last sync; rerun with --pull to take the remote settings or --push to keep this machine's"
+            ));
+        }
+    })
+}
+
+/// 로컬 클론 준비 (없으면 클론하고, 있으면 원격의 최신 상태로 맞춤)
+fn prepare(dir: &Path, remote: Option<&str>) -> Result<String> {
+    if !dir.join(".git").exists() {
+        let remote = remote.ok_or_else(|| anyhow!("No sync repository yet; pass --remote <git-url> the first time"))?;
+        let parent = dir.parent().ok_or_else(|| anyhow!("Invalid sync directory {}", dir.display()))?;
+        fs::create_dir_all(parent)?;
+        git(parent, &["clone", "-q", remote, &dir.to_string_lossy()])?;
+        return Ok(remote.to_string());
+    }
+
+    let current = git(dir, &["remote", "get-url", "origin"])?.trim().to_string();
+    if let Some(remote) = remote {
+        if remote != current {
+            return Err(anyhow!(
+                "Settings already sync with {}; remove {} to switch to {}",
+                current,
+                dir.display(),
+                remote
+            ));
+        }
+    }
+    git(dir, &["fetch", "-q", "origin"])?;
+    // 올리지 못한 커밋은 버려도 됨 (올릴 내용은 매번 현재 설정에서 다시 만듦)
+    let branch = git(dir, &["symbolic-ref", "--short", "HEAD"])?;
+    let upstream = format!("origin/{}", branch.trim());
+    if git(dir, &["rev-parse", "--verify", "-q", &upstream]).is_ok() {
+        git(dir, &["reset", "-q", "--hard", &upstream])?;
+    }
+    Ok(current)
+}
+
+/// 설정 묶음을 커밋하고 원격에 올림
+fn push(dir: &Path, json: &str) -> Result<()> {
+    fs::write(dir.join(SYNC_FILE), json)?;
+    git(dir, &["add", SYNC_FILE])?;
+    // 지난번에 올리지 못한 커밋과 같으면 새로 커밋하지 않음
+    if git(dir, &["diff", "--cached", "--quiet"]).is_err() {
+        git(dir, &["commit", "-q", "-m", "Update ai-cli settings"])?;
+    }
+    git(dir, &["push", "-q", "origin", "HEAD"])
+        .map_err(|e| anyhow!("{}\nThe remote may have changed meanwhile; run 'ai-cli config sync' again", e))?;
+    Ok(())
+}
+
+/// 설정을 원격 리포지토리와 동기화
+pub fn sync(remote: Option<&str>, mode: SyncMode) -> Result<SyncReport> {
+    let dir = sync_dir().ok_or_else(|| anyhow!("Cannot determine the home directory"))?;
+
+    // 받아 오기 전의 원격 브랜치가 마지막으로 동기화한 상태
+    let base = upstream_file(&dir);
+    let remote_url = prepare(&dir, remote)?;
+    let remote_json = upstream_file(&dir);
+    let remote_bundle = remote_json.as_deref().map(Bundle::parse).transpose()?;
+
+    let local_json = local_bundle()?.to_json()?;
+    let mut report = SyncReport { remote: remote_url, ..SyncReport::default() };
+
+    match plan(base.as_deref(), &local_json, remote_json.as_deref(), mode)? {
+        Plan::UpToDate => {}
+        Plan::Push => {
+            push(&dir, &local_json)?;
+            report.pushed = true;
+        }
+        Plan::Pull => {
+            if let Some(bundle) = &remote_bundle {
+                report.pulled = Some(bundle.apply(true)?);
+            }
+            // 이 컴퓨터에만 있던 값이 남았으면 합친 설정을 올림
+            let merged_json = local_bundle()?.to_json()?;
+            if Some(&merged_json) != remote_json.as_ref() {
+                push(&dir, &merged_json)?;
+                report.pushed = true;
+            }
+        }
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plan() {
+        // 원격이 비어 있으면 올림
+        assert_eq!(plan(None, "a", None, SyncMode::Auto).unwrap(), Plan::Push);
+        assert!(plan(None, "a", None, SyncMode::Pull).is_err());
+
+        assert_eq!(plan(Some("a"), "a", Some("a"), SyncMode::Auto).unwrap(), Plan::UpToDate);
+        assert_eq!(plan(Some("a"), "a", Some("b"), SyncMode::Auto).unwrap(), Plan::Pull);
+        assert_eq!(plan(Some("a"), "b", Some("a"), SyncMode::Auto).unwrap(), Plan::Push);
+        assert_eq!(plan(None, "a", Some("b"), SyncMode::Auto).unwrap(), Plan::Pull);
+
+        // 양쪽 모두 바뀌면 선택해야 함
+        assert!(plan(Some("a"), "b", Some("c"), SyncMode::Auto).unwrap_err().to_string().contains("--pull"));
+        assert_eq!(plan(Some("a"), "b", Some("c"), SyncMode::Pull).unwrap(), Plan::Pull);
+        assert_eq!(plan(Some("a"), "b", Some("c"), SyncMode::Push).unwrap(), Plan::Push);
+    }
+}
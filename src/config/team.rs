@@ -0,0 +1,212 @@
+use anyhow::Result;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use super::{schema, CommitRules, ConfigError, RepoConfig, Settings, KEYS, REPO_CONFIG_FILE};
+use crate::policy::PrivacyRules;
+use crate::security::SecurityManager;
+
+/// 팀 공유 설정 모듈 (`.ai-cli/team.toml`)
+/// 리포지토리에 커밋해 팀이 함께 쓰는 설정으로, 커밋 규칙, 프롬프트 지침(`prompt`), 반드시 적용할 개인 정보 규칙(`[privacy]`),
+/// AI에 보내지 않을 경로(`exclude`)는 바로 적용
+/// 요청을 보낼 백엔드, 주소, 모델과 `commit.template`은 클론한 리포지토리가 코드를 다른 곳으로 보내게 할 수 있으므로
+/// 팀 설정과 리포지토리 설정(`.ai-cli.toml`) 모두 사용자가 확인한 뒤에만 적용 (확인 결과는 설정 해시와 함께 기록해 바뀌면 다시 물음)
+/// 두 설정이 겹치면 `.ai-cli.toml`이 우선
+///
+/// 팀 설정 파일 경로 (프로젝트 루트 기준)
+pub const TEAM_CONFIG_FILE: &str = ".ai-cli/team.toml";
+
+/// 팀 설정
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct TeamConfig {
+    /// 백엔드 설정 (확인한 뒤에만 적용)
+    #[serde(flatten)]
+    pub settings: Settings,
+    pub commit: CommitRules,
+    /// 모든 프롬프트에 덧붙일 팀 지침
+    pub prompt: Option<String>,
+    /// 반드시 적용할 개인 정보 규칙 (사용자 규칙에 더해짐)
+    pub privacy: Option<PrivacyRules>,
+    /// AI에 보내지 않을 경로 (`.aiignore` 규칙에 더해짐)
+    pub exclude: Vec<String>,
+}
+
+/// 읽은 팀 설정 (파일이 없으면 None, 잘못되었으면 문제 목록)
+type LoadedTeamConfig = std::result::Result<Option<TeamConfig>, Vec<schema::Problem>>;
+
+/// 팀 설정의 적용 상태
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TeamTrust {
+    /// 실행에 영향을 주는 설정이 없음
+    NotNeeded,
+    Trusted,
+    Declined,
+    /// 아직 확인하지 않았거나 확인한 뒤 바뀜
+    Pending,
+}
+
+impl TeamConfig {
+    /// 프로젝트 루트의 팀 설정 읽기 (없으면 None, 스키마에 맞지 않으면 `ConfigError`, 한 번 읽은 파일은 다시 읽지 않음)
+    pub fn load_checked(root: &Path) -> Result<Option<Self>> {
+        static LOADED: Mutex<BTreeMap<PathBuf, LoadedTeamConfig>> = Mutex::new(BTreeMap::new());
+
+        let path = root.join(TEAM_CONFIG_FILE);
+        let loaded = LOADED.lock().unwrap().get(&path).cloned();
+        let loaded = loaded.unwrap_or_else(|| {
+            let loaded = match fs::read_to_string(&path) {
+                Ok(content) => match schema::validate_team(&content) {
+                    problems if problems.is_empty() => Ok(toml::from_str(&content).ok()),
+                    problems => Err(problems),
+                },
+                Err(_) => Ok(None),
+            };
+            LOADED.lock().unwrap().insert(path.clone(), loaded.clone());
+            loaded
+        });
+        loaded.map_err(|problems| ConfigError { path, problems }.into())
+    }
+
+    /// 실행에 영향을 주는 설정 (`key = value` 형식, 확인할 때 보여 줌)
+    pub fn gated_settings(&self) -> Vec<String> {
+        gated(&self.settings, &self.commit)
+    }
+
+    /// 실행에 영향을 주는 설정의 해시 (바뀌면 다시 확인)
+    pub fn digest(&self) -> String {
+        digest(&self.gated_settings())
+    }
+
+    /// 적용 상태 (`SecurityManager`의 확인 기록 기준)
+    pub fn trust(&self, root: &Path) -> TeamTrust {
+        trust(&root.join(TEAM_CONFIG_FILE), &self.gated_settings())
+    }
+
+    /// 확인하지 않은 설정을 뺀 팀 설정
+    fn honored(mut self, root: &Path) -> Self {
+        if !self.trust(root).is_applied() {
+            self.settings = Settings::default();
+            self.commit.template = None;
+        }
+        self
+    }
+}
+
+impl TeamTrust {
+    /// 실행에 영향을 주는 설정을 적용하는지
+    pub fn is_applied(self) -> bool {
+        matches!(self, TeamTrust::NotNeeded | TeamTrust::Trusted)
+    }
+}
+
+/// 클론한 리포지토리가 요청을 다른 곳으로 보내게 할 수 있는 설정 (백엔드, 주소, 모델, `commit.template`)
+pub(crate) fn gated(settings: &Settings, commit: &CommitRules) -> Vec<String> {
+    let mut gated: Vec<String> = KEYS
+        .iter()
+        .filter_map(|(key, _)| settings.get(key).map(|value| format!("{} = \"{}\"", key, value)))
+        .collect();
+    if let Some(template) = &commit.template {
+        gated.push(format!("commit.template = \"{}\"", template));
+    }
+    gated
+}
+
+/// 확인할 설정의 해시
+pub(crate) fn digest(gated: &[String]) -> String {
+    let hash = Sha256::digest(gated.join("\n").as_bytes());
+    hash.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// 설정 파일의 확인할 설정에 대한 적용 상태
+pub(crate) fn trust(file: &Path, gated: &[String]) -> TeamTrust {
+    if gated.is_empty() {
+        return TeamTrust::NotNeeded;
+    }
+    match SecurityManager::team_config_decision(file, &digest(gated)) {
+        Some(true) => TeamTrust::Trusted,
+        Some(false) => TeamTrust::Declined,
+        None => TeamTrust::Pending,
+    }
+}
+
+/// 현재 프로젝트에 적용할 팀 설정 (없거나 잘못되었으면 None, 확인하지 않은 설정은 뺌)
+pub fn active() -> Option<TeamConfig> {
+    let root = RepoConfig::discover_root()?;
+    active_in(&root)
+}
+
+/// 프로젝트 루트의 팀 설정 중 적용할 부분
+pub fn active_in(root: &Path) -> Option<TeamConfig> {
+    match TeamConfig::load_checked(root) {
+        Ok(team) => Some(team?.honored(root)),
+        Err(e) => {
+            tracing::warn!("Ignoring {}", e);
+            None
+        }
+    }
+}
+
+/// 팀 설정의 지침을 담은 프롬프트 섹션
+pub fn prompt_section() -> Option<String> {
+    let prompt = active()?.prompt?;
+    let prompt = prompt.trim();
+    (!prompt.is_empty()).then(|| format!("TEAM GUIDELINES for this project (follow them):\n{}", prompt))
+}
+
+/// 시작할 때 팀 설정과 리포지토리 설정 확인 (처음 보거나 바뀐 설정은 대화형이면 물어보고, 아니면 적용하지 않고 알림)
+pub fn review(interactive: bool) -> Result<()> {
+    let Some(root) = RepoConfig::discover_root() else {
+        return Ok(());
+    };
+    let mut files = Vec::new();
+    if let Some(team) = TeamConfig::load_checked(&root)? {
+        files.push((TEAM_CONFIG_FILE, team.gated_settings()));
+    }
+    if let Some(repo) = RepoConfig::load_checked(&root)? {
+        files.push((REPO_CONFIG_FILE, repo.gated_settings()));
+    }
+
+    for (name, gated) in files {
+        let file = root.join(name);
+        if trust(&file, &gated) != TeamTrust::Pending {
+            continue;
+        }
+        if interactive {
+            SecurityManager::prompt_team_config(&file, &gated, &digest(&gated))?;
+        } else {
+            println!("{}", tr!("team.ignoring", count = gated.len(), file = name));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gated_settings() {
+        let team: TeamConfig = toml::from_str(
+            "prompt = \"Mention the service\"\n[ollama]\nurl = \"http://ollama.internal:11434\"\n[commit]\ntypes = [\"feat\", \"fix\"]\ntemplate = \".ai-cli/commit.txt\"\n",
+        )
+        .unwrap();
+        assert_eq!(team.gated_settings(), vec!["ollama.url = \"http://ollama.internal:11434\"", "commit.template = \".ai-cli/commit.txt\""]);
+
+        // 지침이나 커밋 타입만 바뀌면 다시 묻지 않음
+        let mut edited = team.clone();
+        edited.prompt = Some("Mention the ticket".to_string());
+        edited.commit.types.push("docs".to_string());
+        assert_eq!(edited.digest(), team.digest());
+        edited.settings.set("ollama.url", "http://evil.example.com").unwrap();
+        assert_ne!(edited.digest(), team.digest());
+
+        let untrusted = team.clone().honored(Path::new("/nonexistent/ai-cli-team-test"));
+        assert!(untrusted.gated_settings().is_empty());
+        assert_eq!(untrusted.commit.types, vec!["feat", "fix"]);
+        assert_eq!(untrusted.prompt.as_deref(), Some("Mention the service"));
+    }
+}
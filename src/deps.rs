@@ -0,0 +1,313 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// 의존성 변경 분석 모듈
+/// 매니페스트(Cargo.toml, package.json, go.mod, requirements.txt)와 잠금 파일(Cargo.lock, package-lock.json)의
+/// 이전/새 버전을 파싱해 추가·삭제·버전 변경된 의존성 목록을 만듦
+/// 잠금 파일은 diff 원문 대신 이 요약만 프롬프트에 전달
+///
+/// 파일 하나에 표시할 최대 변경 수
+const MAX_CHANGES_PER_FILE: usize = 30;
+
+/// 잠금 파일 이름 (diff 원문을 보내지 않음)
+const LOCKFILES: &[&str] = &[
+    "Cargo.lock",
+    "package-lock.json",
+    "npm-shrinkwrap.json",
+    "yarn.lock",
+    "pnpm-lock.yaml",
+    "bun.lockb",
+    "poetry.lock",
+    "uv.lock",
+    "Pipfile.lock",
+    "go.sum",
+    "Gemfile.lock",
+    "composer.lock",
+];
+
+/// 의존성 변경 종류
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DependencyChange {
+    Added { name: String, version: String },
+    Removed { name: String, version: String },
+    Updated { name: String, from: String, to: String },
+}
+
+/// 잠금 파일인지 확인
+pub fn is_lockfile(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| LOCKFILES.contains(&name))
+}
+
+/// 의존성 목록을 파싱할 수 있는 파일인지 확인
+pub fn is_dependency_file(path: &Path) -> bool {
+    matches!(
+        path.file_name().and_then(|name| name.to_str()),
+        Some("Cargo.toml" | "Cargo.lock" | "package.json" | "package-lock.json" | "go.mod" | "requirements.txt")
+    )
+}
+
+/// 파일 내용에서 `의존성 이름 → 버전` 목록 파싱 (지원하지 않는 파일이거나 파싱에 실패하면 None)
+/// 매니페스트는 개발/빌드 의존성을 `name (dev)`처럼 구분
+pub fn parse_dependencies(path: &Path, content: &str) -> Option<BTreeMap<String, String>> {
+    match path.file_name()?.to_str()? {
+        "Cargo.toml" => parse_cargo_manifest(content),
+        "Cargo.lock" => parse_cargo_lock(content),
+        "package.json" => parse_package_json(content),
+        "package-lock.json" => parse_package_lock(content),
+        "go.mod" => Some(parse_go_mod(content)),
+        "requirements.txt" => Some(parse_requirements(content)),
+        _ => None,
+    }
+}
+
+fn parse_cargo_manifest(content: &str) -> Option<BTreeMap<String, String>> {
+    let manifest: toml::Table = content.parse().ok()?;
+    let mut dependencies = BTreeMap::new();
+
+    let mut add_table = |table: &toml::Table, suffix: &str| {
+        for (name, spec) in table {
+            let version = match spec {
+                toml::Value::String(version) => version.clone(),
+                toml::Value::Table(spec) => ["version", "path", "git"]
+                    .iter()
+                    .find_map(|key| spec.get(*key).and_then(|value| value.as_str()).map(|value| (*key, value)))
+                    .map(|(key, value)| if key == "version" { value.to_string() } else { format!("{} {}", key, value) })
+                    .or_else(|| spec.get("workspace").map(|_| "workspace".to_string()))
+                    .unwrap_or_default(),
+                _ => String::new(),
+            };
+            dependencies.insert(format!("{}{}", name, suffix), version);
+        }
+    };
+
+    for (section, suffix) in [("dependencies", ""), ("dev-dependencies", " (dev)"), ("build-dependencies", " (build)")] {
+        if let Some(table) = manifest.get(section).and_then(|value| value.as_table()) {
+            add_table(table, suffix);
+        }
+    }
+    if let Some(table) = manifest
+        .get("workspace")
+        .and_then(|workspace| workspace.get("dependencies"))
+        .and_then(|value| value.as_table())
+    {
+        add_table(table, " (workspace)");
+    }
+
+    Some(dependencies)
+}
+
+fn parse_cargo_lock(content: &str) -> Option<BTreeMap<String, String>> {
+    let lock: toml::Table = content.parse().ok()?;
+    let mut versions: BTreeMap<String, Vec<String>> = BTreeMap::new();
+
+    for package in lock.get("package")?.as_array()? {
+        let (Some(name), Some(version)) = (
+            package.get("name").and_then(|value| value.as_str()),
+            package.get("version").and_then(|value| value.as_str()),
+        ) else {
+            continue;
+        };
+        versions.entry(name.to_string()).or_default().push(version.to_string());
+    }
+
+    // 같은 패키지의 여러 버전은 하나로 합쳐 표시
+    Some(versions.into_iter().map(|(name, versions)| (name, versions.join(", "))).collect())
+}
+
+fn parse_package_json(content: &str) -> Option<BTreeMap<String, String>> {
+    let manifest: serde_json::Value = serde_json::from_str(content).ok()?;
+    let mut dependencies = BTreeMap::new();
+
+    for (section, suffix) in [
+        ("dependencies", ""),
+        ("devDependencies", " (dev)"),
+        ("peerDependencies", " (peer)"),
+        ("optionalDependencies", " (optional)"),
+    ] {
+        for (name, version) in manifest.get(section).and_then(|deps| deps.as_object()).into_iter().flatten() {
+            dependencies.insert(format!("{}{}", name, suffix), version.as_str().unwrap_or_default().to_string());
+        }
+    }
+
+    Some(dependencies)
+}
+
+fn parse_package_lock(content: &str) -> Option<BTreeMap<String, String>> {
+    let lock: serde_json::Value = serde_json::from_str(content).ok()?;
+    let mut dependencies = BTreeMap::new();
+
+    // lockfileVersion 2/3: "packages" 아래 "node_modules/<이름>" 항목
+    if let Some(packages) = lock.get("packages").and_then(|packages| packages.as_object()) {
+        for (key, package) in packages {
+            let Some(name) = key.rsplit("node_modules/").next().filter(|_| key.contains("node_modules/")) else {
+                continue;
+            };
+            if let Some(version) = package.get("version").and_then(|version| version.as_str()) {
+                dependencies.insert(name.to_string(), version.to_string());
+            }
+        }
+        return Some(dependencies);
+    }
+
+    // lockfileVersion 1: "dependencies" 아래 이름별 항목
+    for (name, package) in lock.get("dependencies").and_then(|deps| deps.as_object()).into_iter().flatten() {
+        if let Some(version) = package.get("version").and_then(|version| version.as_str()) {
+            dependencies.insert(name.clone(), version.to_string());
+        }
+    }
+    Some(dependencies)
+}
+
+fn parse_go_mod(content: &str) -> BTreeMap<String, String> {
+    let mut dependencies = BTreeMap::new();
+    let mut in_block = false;
+
+    for line in content.lines() {
+        let line = line.split("//").next().unwrap_or_default().trim();
+        let requirement = if in_block {
+            if line == ")" {
+                in_block = false;
+                continue;
+            }
+            line
+        } else if line == "require (" {
+            in_block = true;
+            continue;
+        } else if let Some(requirement) = line.strip_prefix("require ") {
+            requirement
+        } else {
+            continue;
+        };
+
+        let mut parts = requirement.split_whitespace();
+        if let (Some(module), Some(version)) = (parts.next(), parts.next()) {
+            dependencies.insert(module.to_string(), version.to_string());
+        }
+    }
+
+    dependencies
+}
+
+fn parse_requirements(content: &str) -> BTreeMap<String, String> {
+    content
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or_default().trim())
+        .filter(|line| !line.is_empty() && !line.starts_with('-'))
+        .map(|line| {
+            let split = line.find(['=', '<', '>', '~', '!', ';', '[', ' ']).unwrap_or(line.len());
+            let version = line[split..].split(';').next().unwrap_or_default().trim();
+            (line[..split].to_lowercase(), version.to_string())
+        })
+        .collect()
+}
+
+/// 이전/새 의존성 목록 비교
+pub fn diff_dependencies(old: &BTreeMap<String, String>, new: &BTreeMap<String, String>) -> Vec<DependencyChange> {
+    let mut changes = Vec::new();
+
+    for (name, version) in new {
+        match old.get(name) {
+            None => changes.push(DependencyChange::Added { name: name.clone(), version: version.clone() }),
+            Some(previous) if previous != version => changes.push(DependencyChange::Updated {
+                name: name.clone(),
+                from: previous.clone(),
+                to: version.clone(),
+            }),
+            Some(_) => {}
+        }
+    }
+    for (name, version) in old {
+        if !new.contains_key(name) {
+            changes.push(DependencyChange::Removed { name: name.clone(), version: version.clone() });
+        }
+    }
+
+    changes
+}
+
+/// 파일별 의존성 변경을 프롬프트 섹션으로 만듦 (변경이 없으면 None)
+pub fn format_dependency_changes(files: &[(String, Vec<DependencyChange>)]) -> Option<String> {
+    let files: Vec<&(String, Vec<DependencyChange>)> = files.iter().filter(|(_, changes)| !changes.is_empty()).collect();
+    if files.is_empty() {
+        return None;
+    }
+
+    let mut summary = String::from("Dependency changes (computed from manifests and lockfiles; lockfile diffs are omitted):\n");
+    for (path, changes) in files {
+        summary.push_str(&format!("- {}:\n", path));
+        for change in changes.iter().take(MAX_CHANGES_PER_FILE) {
+            let line = match change {
+                DependencyChange::Added { name, version } => format!("added {} {}", name, version),
+                DependencyChange::Removed { name, version } => format!("removed {} {}", name, version),
+                DependencyChange::Updated { name, from, to } => format!("updated {} {} -> {}", name, from, to),
+            };
+            summary.push_str(&format!("  - {}\n", line.trim_end()));
+        }
+        if changes.len() > MAX_CHANGES_PER_FILE {
+            summary.push_str(&format!("  - ... and {} more\n", changes.len() - MAX_CHANGES_PER_FILE));
+        }
+    }
+    summary.push_str("If the change only touches dependencies, use a build(deps): or chore(deps): commit type.\n");
+
+    Some(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cargo_manifest_changes() {
+        let old = "[dependencies]\nserde = \"1.0\"\nregex = { version = \"1.9\" }\nold-crate = \"0.1\"\n";
+        let new = "[dependencies]\nserde = \"1.0\"\nregex = { version = \"1.10\", features = [\"std\"] }\nlocal = { path = \"../local\" }\n\n[dev-dependencies]\ntempfile = \"3\"\n";
+
+        let old = parse_dependencies(Path::new("Cargo.toml"), old).unwrap();
+        let new = parse_dependencies(Path::new("crates/x/Cargo.toml"), new).unwrap();
+        assert_eq!(
+            diff_dependencies(&old, &new),
+            vec![
+                DependencyChange::Added { name: "local".to_string(), version: "path ../local".to_string() },
+                DependencyChange::Updated { name: "regex".to_string(), from: "1.9".to_string(), to: "1.10".to_string() },
+                DependencyChange::Added { name: "tempfile (dev)".to_string(), version: "3".to_string() },
+                DependencyChange::Removed { name: "old-crate".to_string(), version: "0.1".to_string() },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lockfile_parsing() {
+        let cargo_lock = "version = 3\n\n[[package]]\nname = \"syn\"\nversion = \"1.0.109\"\n\n[[package]]\nname = \"syn\"\nversion = \"2.0.48\"\n";
+        let parsed = parse_dependencies(Path::new("Cargo.lock"), cargo_lock).unwrap();
+        assert_eq!(parsed.get("syn").map(String::as_str), Some("1.0.109, 2.0.48"));
+
+        let package_lock = r#"{"lockfileVersion": 3, "packages": {"": {"version": "1.0.0"}, "node_modules/react": {"version": "18.2.0"}, "node_modules/a/node_modules/@types/b": {"version": "1.0.0"}}}"#;
+        let parsed = parse_dependencies(Path::new("package-lock.json"), package_lock).unwrap();
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed.get("@types/b").map(String::as_str), Some("1.0.0"));
+
+        let go_mod = "module x\n\nrequire github.com/a/b v1.2.0\n\nrequire (\n\tgithub.com/c/d v0.3.1 // indirect\n)\n";
+        let parsed = parse_dependencies(Path::new("go.mod"), go_mod).unwrap();
+        assert_eq!(parsed.get("github.com/c/d").map(String::as_str), Some("v0.3.1"));
+
+        let parsed = parse_dependencies(Path::new("requirements.txt"), "Django>=4.2 # web\n-r base.txt\nrequests\n").unwrap();
+        assert_eq!(parsed.get("django").map(String::as_str), Some(">=4.2"));
+        assert_eq!(parsed.get("requests").map(String::as_str), Some(""));
+
+        assert!(is_lockfile(Path::new("web/yarn.lock")));
+        assert!(!is_lockfile(Path::new("Cargo.toml")));
+    }
+
+    #[test]
+    fn test_format_dependency_changes() {
+        let changes = vec![(
+            "package.json".to_string(),
+            vec![DependencyChange::Updated { name: "react".to_string(), from: "^18.2.0".to_string(), to: "^18.3.0".to_string() }],
+        )];
+        let summary = format_dependency_changes(&changes).unwrap();
+        assert!(summary.contains("- package.json:\n  - updated react ^18.2.0 -> ^18.3.0\n"));
+
+        assert!(format_dependency_changes(&[("Cargo.lock".to_string(), Vec::new())]).is_none());
+    }
+}
@@ -0,0 +1,129 @@
+use anyhow::{Result, anyhow};
+use std::fs;
+use std::io::{self, IsTerminal, Write};
+use std::path::PathBuf;
+
+use crate::config;
+use crate::git_utils;
+use crate::lint;
+
+/// 커밋 메시지 편집 모듈
+/// 생성한 메시지를 `GIT_EDITOR`, `core.editor`, `VISUAL`, `EDITOR` 순으로 찾은 편집기에서 열고 (git의 `COMMIT_EDITMSG`처럼
+/// `#` 주석으로 안내, 스테이징한 파일, 커밋 규칙 위반을 보여 줌), 저장한 메시지를 커밋 규칙으로 다시 검사
+///
+/// 편집 파일 이름 (`.git` 디렉토리 안, 리포지토리가 없으면 임시 디렉토리)
+const EDIT_FILE: &str = "AI_CLI_EDITMSG";
+
+/// 편집기를 띄울 수 있는지 (표준 입력과 출력이 터미널)
+pub fn is_available() -> bool {
+    io::stdin().is_terminal() && io::stdout().is_terminal()
+}
+
+/// 커밋 메시지 편집기 (git과 같은 순서, 없으면 `VISUAL`/`EDITOR`/플랫폼 기본 편집기)
+fn editor() -> String {
+    std::env::var("GIT_EDITOR")
+        .ok()
+        .or_else(|| git_utils::open_repository().ok()?.config().ok()?.get_string("core.editor").ok())
+        .filter(|editor| !editor.trim().is_empty())
+        .unwrap_or_else(config::editor)
+}
+
+/// 편집 파일 경로
+fn edit_path() -> PathBuf {
+    git_utils::open_repository()
+        .map(|repo| repo.path().join(EDIT_FILE))
+        .unwrap_or_else(|_| std::env::temp_dir().join(format!("{}-{}", EDIT_FILE, std::process::id())))
+}
+
+/// 편집기에 보여 줄 내용 (메시지 다음에 `#` 주석)
+fn edit_template(message: &str, files: &[String], violations: &[lint::Violation]) -> String {
+    let mut text = format!("{}\n\n", message.trim_end());
+    for line in tr!("edit.help").lines() {
+        text.push_str(&format!("# {}\n", line));
+    }
+    if !files.is_empty() {
+        text.push_str(&format!("#\n# {}\n", tr!("edit.files")));
+        for file in files {
+            text.push_str(&format!("#\t{}\n", file));
+        }
+    }
+    if !violations.is_empty() {
+        text.push_str(&format!("#\n# {}\n", tr!("edit.rules")));
+        for violation in violations {
+            text.push_str(&format!("#   - {}\n", violation));
+        }
+    }
+    text
+}
+
+/// 주석 줄을 빼고 앞뒤 공백을 지운 메시지
+fn strip_comments(text: &str) -> String {
+    text.lines()
+        .filter(|line| !line.starts_with('#'))
+        .map(str::trim_end)
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string()
+}
+
+/// 메시지를 편집기에서 고침 (비우면 `None`, 규칙 위반이 남으면 다시 편집할지 물어봄)
+pub fn edit_commit_message(message: &str) -> Result<Option<String>> {
+    let rules = config::commit_rules();
+    let types = crate::ai_utils::allowed_commit_types(&rules);
+    let files = git_utils::get_staged_files().unwrap_or_default();
+    let path = edit_path();
+    let editor = editor();
+
+    let mut current = message.to_string();
+    let result = loop {
+        fs::write(&path, edit_template(&current, &files, &lint::lint_message(&current, &rules, &types)))?;
+        let status = config::editor_process(&editor, &path)
+            .status()
+            .map_err(|e| anyhow!("Failed to start editor '{}': {}", editor, e))?;
+        if !status.success() {
+            break Err(anyhow!("Editor '{}' exited with {}; commit cancelled", editor, status));
+        }
+
+        let edited = strip_comments(&fs::read_to_string(&path)?);
+        if edited.is_empty() {
+            break Ok(None);
+        }
+        let violations = lint::lint_message(&edited, &rules, &types);
+        if violations.is_empty() {
+            break Ok(Some(edited));
+        }
+
+        println!("\n{}", tr!("edit.violations", count = violations.len()));
+        println!("{}", lint::format_violations(&violations));
+        print!("{}", tr!("edit.choice"));
+        io::stdout().flush()?;
+        let mut response = String::new();
+        io::stdin().read_line(&mut response)?;
+        match response.trim().to_lowercase().as_str() {
+            "c" | "commit" => break Ok(Some(edited)),
+            "n" | "no" => break Ok(None),
+            _ => current = edited,
+        }
+    };
+    let _ = fs::remove_file(&path);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_edit_template() {
+        let violations = vec![lint::Violation { rule: "require_scope", message: "add a scope: feat(<scope>): ...".to_string() }];
+        let text = edit_template("feat: add parser\n", &["src/parser.rs".to_string()], &violations);
+        assert!(text.starts_with("feat: add parser\n\n# "));
+        assert!(text.contains("#\tsrc/parser.rs\n"));
+        assert!(text.contains("#   - [require_scope] add a scope"));
+
+        let edited = format!("feat(parser): add parser  \n\nHandles (a, b).\n{}", text.split_once("\n\n").unwrap().1);
+        assert_eq!(strip_comments(&edited), "feat(parser): add parser\n\nHandles (a, b).");
+        assert_eq!(strip_comments(&text.replace("feat: add parser", "")), "");
+    }
+}
@@ -0,0 +1,329 @@
+use anyhow::{Result, anyhow};
+use git2::BranchType;
+use std::io::{self, Write};
+use std::process::Command;
+
+use crate::ai_utils;
+use crate::audit;
+use crate::editor;
+use crate::git_utils;
+use crate::mcp::{Forge, MCPClientBuilder, McpRegistry, RemoteRepo, ToolManager};
+use crate::policy::{CommandDecision, Policy};
+use crate::security::{ApprovalOption, CommandType, SecurityManager};
+use crate::transcript;
+
+/// 커밋 후 작업 모듈
+/// 커밋한 뒤 한 번의 프롬프트로 브랜치 푸시, origin 서비스(GitHub/GitLab)에 MCP로 Pull/Merge Request 열기, 방금 만든 커밋 수정을 고를 수 있음
+/// 여러 개를 고르면 수정, 푸시, PR 순으로 실행하며, 푸시와 수정은 명령어 승인을, PR은 MCP 도구 승인을 거침
+/// 이미 열린 PR은 브랜치를 푸시하면 갱신됨. 대화형이 아니면(`--yes`, CI, 파이프) 묻지 않음
+///
+/// 고를 수 있는 작업 (실행 순서)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Action {
+    Amend,
+    Push,
+    Request,
+}
+
+/// 현재 브랜치의 상태
+struct Branch {
+    name: String,
+    has_upstream: bool,
+}
+
+/// 커밋한 뒤 다음 작업을 물어보고 고른 작업을 실행 (실패해도 커밋은 성공으로 끝나도록 알리기만 함)
+pub async fn offer(no_verify: bool) {
+    if ai_utils::is_non_interactive() {
+        return;
+    }
+    let Some(branch) = current_branch() else {
+        return;
+    };
+    let remote = RemoteRepo::origin();
+    let base = default_branch();
+
+    println!("\n{}", tr!("followup.title", branch = branch.name));
+    println!("{}", tr!("followup.amend"));
+    println!("{}", tr!("followup.push", command = push_command(&branch)));
+    if let Some(remote) = &remote {
+        println!("{}", tr!("followup.request", kind = request_kind(remote), base = base, forge = remote.forge.name()));
+    }
+    print!("{}", tr!("followup.choice"));
+    let _ = io::stdout().flush();
+    let mut input = String::new();
+    if io::stdin().read_line(&mut input).is_err() {
+        return;
+    }
+    let Some(mut actions) = parse_actions(&input) else {
+        println!("{}", tr!("followup.invalid", choice = input.trim()));
+        return;
+    };
+    if remote.is_none() {
+        actions.retain(|action| *action != Action::Request);
+    }
+    // PR을 열려면 브랜치가 리모트에 있어야 함
+    if actions.contains(&Action::Request) && !branch.has_upstream && !actions.contains(&Action::Push) {
+        actions.push(Action::Push);
+        actions.sort();
+    }
+
+    for action in actions {
+        let result = match action {
+            Action::Amend => amend(no_verify),
+            Action::Push => push(&branch),
+            Action::Request => match &remote {
+                Some(remote) => open_request(remote, &branch.name, &base).await,
+                None => Ok(()),
+            },
+        };
+        if let Err(e) = result {
+            println!("{}", tr!("followup.failed", action = format!("{:?}", action), error = e));
+            // 앞 작업이 실패하면 뒤 작업(수정 전 커밋 푸시, 푸시 안 된 브랜치로 PR)은 하지 않음
+            break;
+        }
+    }
+}
+
+/// 입력한 글자를 작업으로 (`pr`, `p r`, `A,P`; 빈 입력은 작업 없음, 모르는 글자가 있으면 `None`)
+fn parse_actions(input: &str) -> Option<Vec<Action>> {
+    let mut actions = Vec::new();
+    for c in input.chars().filter(|c| !c.is_whitespace() && *c != ',') {
+        let action = match c.to_ascii_lowercase() {
+            'a' => Action::Amend,
+            'p' => Action::Push,
+            'r' => Action::Request,
+            _ => return None,
+        };
+        if !actions.contains(&action) {
+            actions.push(action);
+        }
+    }
+    actions.sort();
+    Some(actions)
+}
+
+/// 현재 브랜치와 업스트림 여부 (분리된 HEAD면 `None`)
+fn current_branch() -> Option<Branch> {
+    let repo = git_utils::open_repository().ok()?;
+    let head = repo.head().ok()?;
+    if !head.is_branch() {
+        return None;
+    }
+    let name = head.shorthand()?.to_string();
+    let has_upstream = repo.find_branch(&name, BranchType::Local).ok()?.upstream().is_ok();
+    Some(Branch { name, has_upstream })
+}
+
+/// PR을 보낼 기본 브랜치 (`origin/HEAD`가 가리키는 브랜치, 모르면 `main`)
+fn default_branch() -> String {
+    git_utils::open_repository()
+        .ok()
+        .and_then(|repo| {
+            let reference = repo.find_reference("refs/remotes/origin/HEAD").ok()?;
+            let target = reference.symbolic_target()?.strip_prefix("refs/remotes/origin/")?.to_string();
+            Some(target)
+        })
+        .unwrap_or_else(|| "main".to_string())
+}
+
+fn request_kind(remote: &RemoteRepo) -> &'static str {
+    match remote.forge {
+        Forge::GitHub => "pull request",
+        Forge::GitLab => "merge request",
+    }
+}
+
+/// 푸시 명령어 (업스트림이 없으면 origin에 만들고 추적)
+fn push_command(branch: &Branch) -> String {
+    if branch.has_upstream {
+        "git push".to_string()
+    } else {
+        format!("git push -u origin {}", shell_quote(&branch.name))
+    }
+}
+
+/// 셸에 그대로 넘길 수 없는 글자가 있으면 작은따옴표로 감쌈
+fn shell_quote(value: &str) -> String {
+    if value.chars().all(|c| c.is_ascii_alphanumeric() || "._/-+@".contains(c)) {
+        value.to_string()
+    } else {
+        format!("'{}'", value.replace('\'', r"'\''"))
+    }
+}
+
+/// 브랜치 푸시 (명령어 승인 후 git을 직접 실행)
+/// SSH 에이전트, `GIT_ASKPASS`, 자격 증명 도우미가 동작하도록 환경 변수와 터미널을 그대로 넘기고, 승인은 한 번만 받음
+fn push(branch: &Branch) -> Result<()> {
+    let mut command = push_command(branch);
+    let mut security_manager = SecurityManager::default();
+    let args = loop {
+        if let CommandDecision::Deny(rule) = Policy::load()?.check_command(&command) {
+            return Err(anyhow!(tr!("command.denied", command = command, rule = rule)));
+        }
+        match security_manager.prompt_command_approval(&command, CommandType::GitWrite)? {
+            ApprovalOption::Yes | ApprovalOption::YesForSession => break git_push_args(&command)?,
+            ApprovalOption::No => return Err(anyhow!(tr!("command.cancelled"))),
+            ApprovalOption::EditAndRetry => {
+                command = read_line(tr!("command.modified"))?;
+                if command.is_empty() {
+                    return Err(anyhow!(tr!("command.empty")));
+                }
+            }
+        }
+    };
+
+    let status = Command::new("git").args(&args).status().map_err(|e| anyhow!("Failed to run git: {}", e));
+    let code = status.as_ref().ok().and_then(|status| status.code());
+    audit::record_command(&command, code);
+    transcript::command(&command, code, &status.as_ref().err().map(ToString::to_string).unwrap_or_default());
+    let status = status?;
+    if !status.success() {
+        return Err(anyhow!("'{}' exited with {}", command, status));
+    }
+    println!("{}", tr!("followup.pushed", branch = branch.name));
+    Ok(())
+}
+
+/// 승인한 푸시 명령어를 git 인자로 나눔 (작은따옴표, 큰따옴표 지원, `git push`가 아니면 거부)
+fn git_push_args(command: &str) -> Result<Vec<String>> {
+    let mut words = Vec::new();
+    let mut word: Option<String> = None;
+    let mut quote = None;
+    let mut chars = command.chars();
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), c) => word.get_or_insert_with(String::new).push(c),
+            (None, '\\') => word.get_or_insert_with(String::new).extend(chars.next()),
+            (None, '\'' | '"') => {
+                quote = Some(c);
+                word.get_or_insert_with(String::new);
+            }
+            (None, c) if c.is_whitespace() => words.extend(word.take()),
+            (None, c) => word.get_or_insert_with(String::new).push(c),
+        }
+    }
+    if quote.is_some() {
+        return Err(anyhow!("Unterminated quote in '{}'", command));
+    }
+    words.extend(word);
+
+    match words.split_first() {
+        Some((git, args)) if git == "git" && args.first().is_some_and(|arg| arg == "push") => Ok(args.to_vec()),
+        _ => Err(anyhow!("Only a 'git push' command can be run here: '{}'", command)),
+    }
+}
+
+/// 방금 만든 커밋의 메시지 수정 (편집기 또는 한 줄 입력, 승인 후 수정)
+fn amend(no_verify: bool) -> Result<()> {
+    let current = head_message()?;
+    let mut security_manager = SecurityManager::default();
+    let mut message = edit_message(&current)?;
+    loop {
+        let Some(new_message) = &message else {
+            return Err(anyhow!(tr!("commit.empty")));
+        };
+        let command = format!("git commit --amend -m \"{}\"", new_message.replace('"', "\\\""));
+        match security_manager.prompt_command_approval(&command, CommandType::GitCommit)? {
+            ApprovalOption::Yes | ApprovalOption::YesForSession => break,
+            ApprovalOption::No => return Err(anyhow!(tr!("command.cancelled"))),
+            ApprovalOption::EditAndRetry => message = edit_message(new_message)?,
+        }
+    }
+    let message = message.unwrap_or(current);
+    let result = git_utils::amend_commit(&message, no_verify);
+    let audited_command = format!("git commit --amend -m {:?}", message);
+    audit::record_command(&audited_command, Some(if result.is_ok() { 0 } else { 1 }));
+    transcript::command(&audited_command, Some(if result.is_ok() { 0 } else { 1 }), &result.as_ref().err().map(ToString::to_string).unwrap_or_default());
+    result.map_err(|e| anyhow!(e))?;
+    println!("{}", tr!("followup.amended"));
+    Ok(())
+}
+
+/// 새 메시지 입력 (편집기를 쓸 수 있으면 편집기, 비우면 `None`)
+fn edit_message(message: &str) -> Result<Option<String>> {
+    if editor::is_available() {
+        return editor::edit_commit_message(message);
+    }
+    let line = read_line(tr!("followup.message"))?;
+    Ok(Some(if line.is_empty() { message.to_string() } else { line }))
+}
+
+/// HEAD 커밋 메시지
+fn head_message() -> Result<String> {
+    let repo = git_utils::open_repository()?;
+    let commit = repo.head()?.peel_to_commit()?;
+    Ok(commit.message().unwrap_or_default().trim_end().to_string())
+}
+
+fn read_line(prompt: &str) -> Result<String> {
+    print!("{}", prompt);
+    io::stdout().flush()?;
+    let mut line = String::new();
+    io::stdin().read_line(&mut line)?;
+    Ok(line.trim().to_string())
+}
+
+/// origin 서비스의 MCP 서버로 PR/MR 열기 (제목은 커밋 제목, 본문은 나머지)
+async fn open_request(remote: &RemoteRepo, branch: &str, base: &str) -> Result<()> {
+    if branch == base {
+        return Err(anyhow!("{} is the base branch; create a feature branch first", branch));
+    }
+    if Policy::load().map(|policy| policy.is_local_only()).unwrap_or(true) {
+        return Err(anyhow!("local-only privacy mode does not allow MCP calls"));
+    }
+
+    let message = head_message()?;
+    let (title, body) = match message.split_once('\n') {
+        Some((title, body)) => (title.trim(), Some(body.trim()).filter(|body| !body.is_empty())),
+        None => (message.trim(), None),
+    };
+
+    let mut builder = MCPClientBuilder::new("ai-cli").version(env!("CARGO_PKG_VERSION"));
+    for (name, config) in McpRegistry::load().unwrap_or_default().servers() {
+        if remote.forge.matches_server(name, config) {
+            builder = builder.server(name.clone(), config.clone());
+        }
+    }
+    let client = builder.build();
+    for (server, e) in client.connect_servers().await {
+        println!("⚠️  MCP server '{}' is unavailable: {}", server, e);
+    }
+
+    let tools = ToolManager::new(client);
+    let result = tools.create_change_request(remote, title, body, branch, base).await;
+    tools.shutdown().await;
+    let text = result?;
+    println!("🔗 Opened a {} on {}: {}", request_kind(remote), remote.forge.name(), text.lines().next().unwrap_or_default().trim());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_actions() {
+        assert_eq!(parse_actions("rp"), Some(vec![Action::Push, Action::Request]));
+        assert_eq!(parse_actions(" P, a\n"), Some(vec![Action::Amend, Action::Push]));
+        assert_eq!(parse_actions("pp"), Some(vec![Action::Push]));
+        assert_eq!(parse_actions("\n"), Some(vec![]));
+        assert_eq!(parse_actions("x"), None);
+
+        let branch = Branch { name: "feature/login".to_string(), has_upstream: false };
+        assert_eq!(push_command(&branch), "git push -u origin feature/login");
+        assert_eq!(shell_quote("fix/it's"), "'fix/it'\\''s'");
+        assert_eq!(push_command(&Branch { has_upstream: true, ..branch }), "git push");
+    }
+
+    #[test]
+    fn test_git_push_args() {
+        assert_eq!(git_push_args("git push").unwrap(), vec!["push"]);
+        assert_eq!(git_push_args("git push -u origin 'fix/it'\\''s'").unwrap(), vec!["push", "-u", "origin", "fix/it's"]);
+        assert_eq!(git_push_args(&push_command(&Branch { name: "fix/it's".to_string(), has_upstream: false })).unwrap()[3], "fix/it's");
+        assert_eq!(git_push_args("git  push \"my remote\" ''").unwrap(), vec!["push", "my remote", ""]);
+        assert!(git_push_args("git push 'open").is_err());
+        assert!(git_push_args("git reset --hard").is_err());
+        assert!(git_push_args("rm -rf / ; git push").is_err());
+    }
+}
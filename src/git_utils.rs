@@ -1,10 +1,20 @@
-use git2::{Repository, Diff, DiffFormat, Tree, Oid};
+use git2::{Repository, Diff, DiffFormat, DiffLine, DiffOptions, IndexEntryExtendedFlag, Patch, Tree, Oid, ErrorCode};
 use anyhow::{Result, anyhow};
-use std::path::Path;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::fs;
+
+use crate::aiignore::AiIgnore;
+use crate::deps::{self, DependencyChange};
+use crate::injection;
+use crate::language::{self, FileLanguage};
+use crate::symbols::{self, AffectedSymbol};
+use crate::security;
 
 /// Git 리포지토리 유틸리티 모듈
 /// git2-rs를 사용하여 Git 작업을 안전하게 처리
-
+///
 /// 현재 디렉토리에서 Git 리포지토리 열기
 pub fn open_repository() -> Result<Repository> {
     Repository::open_from_env()
@@ -12,90 +22,476 @@ pub fn open_repository() -> Result<Repository> {
         .map_err(|_| anyhow!("Failed to open Git repository in current directory"))
 }
 
+/// Diff 생성 옵션
+#[derive(Debug, Clone)]
+pub struct DiffSettings {
+    /// 변경 라인 주변에 포함할 컨텍스트 라인 수 (git diff -U<n>)
+    pub context_lines: u32,
+    /// 라인 대신 단어 단위로 변경 표시 (git diff --word-diff)
+    pub word_diff: bool,
+    /// 공백만 바뀐 변경 무시 (git diff -w)
+    pub ignore_whitespace: bool,
+    /// 민감한 파일(.env, 개인 키 등)의 내용도 포함
+    pub include_sensitive: bool,
+    /// 잠금 파일(Cargo.lock 등)의 diff 원문도 포함 (기본: 의존성 변경 요약으로 대체)
+    pub include_lockfiles: bool,
+}
+
+impl Default for DiffSettings {
+    fn default() -> Self {
+        Self {
+            context_lines: 3,
+            word_diff: false,
+            ignore_whitespace: false,
+            include_sensitive: false,
+            include_lockfiles: false,
+        }
+    }
+}
+
+impl DiffSettings {
+    /// git2 diff 옵션으로 변환
+    fn to_diff_options(&self) -> DiffOptions {
+        let mut options = DiffOptions::new();
+        options
+            .context_lines(self.context_lines)
+            .ignore_whitespace(self.ignore_whitespace);
+        options
+    }
+}
+
 /// 스테이징된 변경 사항 가져오기 (git diff --cached)
 pub fn get_staged_diff() -> Result<String> {
+    get_staged_diff_with(&DiffSettings::default())
+}
+
+/// 옵션을 지정하여 스테이징된 변경 사항 가져오기
+pub fn get_staged_diff_with(settings: &DiffSettings) -> Result<String> {
+    let _span = tracing::debug_span!("git", op = "staged_diff").entered();
     let repo = open_repository()?;
 
-    let head = repo.head()?.peel_to_tree()
-        .map_err(|_| anyhow!("Could not find HEAD tree. Is the repository empty or no commits exist?"))?;
+    let head = head_tree(&repo)?;
 
     // 스테이징된 변경 사항(index)과 HEAD 트리 간의 diff 생성
-    let mut diff = repo.diff_tree_to_index(
-        Some(&head),
+    let diff = repo.diff_tree_to_index(
+        head.as_ref(),
         None, // None은 현재 인덱스(스테이징 영역)를 의미
-        None,
+        Some(&mut settings.to_diff_options()),
     )?;
 
-    diff_to_string(&diff)
+    render_diff(&repo, &diff, settings, false)
 }
 
 /// 워킹 디렉토리의 변경 사항 가져오기 (git diff)
 pub fn get_unstaged_diff() -> Result<String> {
+    get_unstaged_diff_with(&DiffSettings::default())
+}
+
+/// 옵션을 지정하여 워킹 디렉토리의 변경 사항 가져오기
+pub fn get_unstaged_diff_with(settings: &DiffSettings) -> Result<String> {
     let repo = open_repository()?;
 
-    let head = repo.head()?.peel_to_tree()
-        .map_err(|_| anyhow!("Could not find HEAD tree."))?;
+    let head = head_tree(&repo)?;
 
     // HEAD와 워킹 디렉토리 간의 diff 생성
-    let mut diff = repo.diff_tree_to_workdir(
-        Some(&head),
-        None,
+    let diff = repo.diff_tree_to_workdir(
+        head.as_ref(),
+        Some(&mut settings.to_diff_options()),
     )?;
 
-    diff_to_string(&diff)
+    render_diff(&repo, &diff, settings, true)
 }
 
 /// 특정 커밋의 변경 사항 가져오기
 pub fn get_commit_diff(commit_hash: &str) -> Result<String> {
+    get_commit_diff_with(commit_hash, &DiffSettings::default())
+}
+
+/// 옵션을 지정하여 특정 커밋의 변경 사항 가져오기
+pub fn get_commit_diff_with(commit_hash: &str, settings: &DiffSettings) -> Result<String> {
+    let _span = tracing::debug_span!("git", op = "commit_diff", commit = commit_hash).entered();
     let repo = open_repository()?;
 
     let oid = Oid::from_str(commit_hash)
         .map_err(|_| anyhow!("Invalid commit hash: {}", commit_hash))?;
 
-    let commit = repo.find_commit(oid)?;
+    let missing_object = |e: git2::Error| {
+        if e.code() == ErrorCode::NotFound && detect_checkout_shape(&repo).partial_clone {
+            anyhow!("Commit {} is not fully available locally (partial clone). Run 'git fetch' to download it first.", commit_hash)
+        } else {
+            anyhow!(e)
+        }
+    };
+
+    let commit = repo.find_commit(oid).map_err(missing_object)?;
     let parent_tree = if commit.parent_count() > 0 {
-        Some(commit.parent(0)?.tree()?)
+        Some(commit.parent(0).and_then(|parent| parent.tree()).map_err(missing_object)?)
     } else {
         None // 첫 커밋인 경우
     };
 
-    let commit_tree = commit.tree()?;
+    let commit_tree = commit.tree().map_err(missing_object)?;
 
-    let mut diff = repo.diff_tree_to_tree(
+    let diff = repo.diff_tree_to_tree(
         parent_tree.as_ref(),
         Some(&commit_tree),
-        None,
+        Some(&mut settings.to_diff_options()),
     )?;
 
-    diff_to_string(&diff)
+    render_diff(&repo, &diff, settings, false)
+}
+
+/// 두 커밋의 공통 조상에서 `head`까지의 diff (PR의 변경 사항, `git diff base...head`)
+pub fn get_range_diff_with(base: &str, head: &str, settings: &DiffSettings) -> Result<String> {
+    let _span = tracing::debug_span!("git", op = "range_diff", base, head).entered();
+    let repo = open_repository()?;
+
+    let find = |sha: &str| -> Result<git2::Commit> {
+        let oid = Oid::from_str(sha).map_err(|_| anyhow!("Invalid commit hash: {}", sha))?;
+        repo.find_commit(oid).map_err(|_| {
+            anyhow!("Commit {} is not available locally; fetch the full history first (e.g. actions/checkout with fetch-depth: 0)", sha)
+        })
+    };
+    let head_commit = find(head)?;
+    let base_commit = find(base)?;
+    let merge_base = repo.find_commit(repo.merge_base(base_commit.id(), head_commit.id())?)?;
+
+    let diff = repo.diff_tree_to_tree(
+        Some(&merge_base.tree()?),
+        Some(&head_commit.tree()?),
+        Some(&mut settings.to_diff_options()),
+    )?;
+
+    render_diff(&repo, &diff, settings, false)
+}
+
+/// 체크아웃 형태 (partial clone / sparse checkout)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CheckoutShape {
+    /// promisor 리모트가 있어 일부 객체가 로컬에 없을 수 있음
+    pub partial_clone: bool,
+    /// sparse-checkout으로 일부 파일만 워킹 디렉토리에 존재
+    pub sparse_checkout: bool,
+}
+
+/// 리포지토리가 partial clone 또는 sparse checkout인지 확인
+pub fn detect_checkout_shape(repo: &Repository) -> CheckoutShape {
+    let config = match repo.config() {
+        Ok(config) => config,
+        Err(_) => return CheckoutShape::default(),
+    };
+
+    let mut partial_clone = config.get_string("extensions.partialClone").is_ok();
+    if !partial_clone {
+        if let Ok(mut entries) = config.entries(Some(r"remote\..*\.promisor")) {
+            while let Some(Ok(entry)) = entries.next() {
+                if entry.value().map(|v| v.eq_ignore_ascii_case("true")).unwrap_or(false) {
+                    partial_clone = true;
+                    break;
+                }
+            }
+        }
+    }
+
+    CheckoutShape {
+        partial_clone,
+        sparse_checkout: config.get_bool("core.sparseCheckout").unwrap_or(false),
+    }
+}
+
+/// sparse checkout에서 워킹 디렉토리에 없는(skip-worktree) 경로 목록
+fn skip_worktree_paths(repo: &Repository) -> Result<HashSet<PathBuf>> {
+    let index = repo.index()?;
+    Ok(index
+        .iter()
+        .filter(|entry| IndexEntryExtendedFlag::from_bits_truncate(entry.flags_extended).is_skip_worktree())
+        .map(|entry| PathBuf::from(String::from_utf8_lossy(&entry.path).into_owned()))
+        .collect())
+}
+
+/// 설정에 따라 Diff를 문자열로 변환
+/// 민감한 파일(.env, 개인 키 등)과 `.aiignore` 대상 파일은 내용 대신 경로만 표시하고,
+/// 잠금 파일은 의존성 변경 요약으로 대신하므로 diff 원문을 생략하며,
+/// partial clone에서는 로컬에 없는 blob을 읽지 않고 주석으로 대체하며,
+/// `exclude_sparse`가 참이면 sparse checkout으로 제외된 파일을 건너뜀
+fn render_diff(repo: &Repository, diff: &Diff, settings: &DiffSettings, exclude_sparse: bool) -> Result<String> {
+    let shape = detect_checkout_shape(repo);
+    let exclude_sparse = exclude_sparse && shape.sparse_checkout;
+    let withhold_sensitive = !settings.include_sensitive
+        && diff.deltas().any(|delta| delta_path(&delta).is_some_and(|path| security::is_sensitive_file(&path)));
+    let aiignore = repo.workdir().map(AiIgnore::load);
+    let is_aiignored = |path: &Path| aiignore.as_ref().is_some_and(|rules| rules.is_ignored(path, false));
+    let withhold_ignored = diff.deltas().any(|delta| delta_path(&delta).is_some_and(|path| is_aiignored(&path)));
+    let withhold_lockfiles = !settings.include_lockfiles
+        && diff.deltas().any(|delta| delta_path(&delta).is_some_and(|path| deps::is_lockfile(&path)));
+    let mut writer = DiffWriter::new(settings.word_diff);
+
+    if !shape.partial_clone && !exclude_sparse && !withhold_sensitive && !withhold_ignored && !withhold_lockfiles {
+        diff.print(DiffFormat::Patch, |_, _, line| {
+            writer.push_line(&line);
+            true // 계속 진행
+        })?;
+        return writer.finish();
+    }
+
+    let odb = repo.odb()?;
+    let sparse_paths = if exclude_sparse {
+        skip_worktree_paths(repo)?
+    } else {
+        HashSet::new()
+    };
+
+    for (idx, delta) in diff.deltas().enumerate() {
+        let path = delta_path(&delta).unwrap_or_default();
+
+        if sparse_paths.contains(&path) {
+            tracing::debug!("Skipping sparse-checkout path: {}", path.display());
+            continue;
+        }
+
+        if is_aiignored(&path) {
+            // `.aiignore` 대상은 --include-sensitive로도 포함하지 않음
+            writer.push_note(&format!(
+                "diff --git a/{path} b/{path}\n# {status:?}: contents withheld (.aiignore)\n",
+                path = path.display(),
+                status = delta.status(),
+            ));
+            continue;
+        }
+
+        if withhold_sensitive && security::is_sensitive_file(&path) {
+            // 민감한 파일은 스테이징 여부와 관계없이 내용을 전송하지 않음
+            writer.push_note(&format!(
+                "diff --git a/{path} b/{path}\n# {status:?}: contents withheld (sensitive file type)\n",
+                path = path.display(),
+                status = delta.status(),
+            ));
+            continue;
+        }
+
+        if withhold_lockfiles && deps::is_lockfile(&path) {
+            // 잠금 파일 diff는 길고 잡음이 많아 의존성 변경 요약으로 대신함
+            writer.push_note(&format!(
+                "diff --git a/{path} b/{path}\n# {status:?}: lockfile diff omitted (see dependency changes)\n",
+                path = path.display(),
+                status = delta.status(),
+            ));
+            continue;
+        }
+
+        let missing = shape.partial_clone
+            && [delta.old_file(), delta.new_file()]
+                .iter()
+                .any(|file| !file.id().is_zero() && file.exists() && !odb.exists(file.id()));
+
+        if missing {
+            // blob을 읽으면 libgit2 내부에서 실패하므로 상태만 표시
+            writer.push_note(&format!(
+                "diff --git a/{path} b/{path}\n# {status:?}: file contents are not available locally (partial clone)\n",
+                path = path.display(),
+                status = delta.status(),
+            ));
+            continue;
+        }
+
+        if let Some(mut patch) = Patch::from_diff(diff, idx)? {
+            patch.print(&mut |_, _, line| {
+                writer.push_line(&line);
+                true // 계속 진행
+            })?;
+        }
+    }
+
+    writer.finish()
+}
+
+/// Diff 항목의 경로 (삭제된 파일은 이전 경로)
+fn delta_path(delta: &git2::DiffDelta) -> Option<PathBuf> {
+    delta.new_file().path()
+        .or_else(|| delta.old_file().path())
+        .map(Path::to_path_buf)
 }
 
 /// Diff 객체를 문자열로 변환
-fn diff_to_string(diff: &Diff) -> Result<String> {
-    let mut diff_text = String::new();
+pub fn diff_to_string(diff: &Diff) -> Result<String> {
+    let mut writer = DiffWriter::new(false);
 
     diff.print(DiffFormat::Patch, |_, _, line| {
-        diff_text.push_str(std::str::from_utf8(line.content()).unwrap_or(""));
+        writer.push_line(&line);
         true // 계속 진행
     })?;
 
-    if diff_text.is_empty() {
-        anyhow::bail!("No changes found to analyze.");
+    writer.finish()
+}
+
+/// Diff 라인을 모아 문자열로 만드는 출력기
+/// 단어 diff 모드에서는 삭제된 단어를 `[-...-]`, 추가된 단어를 `{+...+}`로 표시
+/// (git diff --word-diff=plain 형식)
+struct DiffWriter {
+    word_diff: bool,
+    text: String,
+    removed: Vec<String>,
+    added: Vec<String>,
+}
+
+impl DiffWriter {
+    fn new(word_diff: bool) -> Self {
+        Self {
+            word_diff,
+            text: String::new(),
+            removed: Vec::new(),
+            added: Vec::new(),
+        }
+    }
+
+    /// diff 라인 추가
+    fn push_line(&mut self, line: &DiffLine) {
+        let content = String::from_utf8_lossy(line.content()).into_owned();
+
+        if !self.word_diff {
+            // 추가/삭제/문맥 줄은 git diff처럼 앞에 `+`, `-`, 공백 표시
+            if matches!(line.origin(), '+' | '-' | ' ') {
+                self.text.push(line.origin());
+            }
+            self.text.push_str(&content);
+            return;
+        }
+
+        match line.origin() {
+            '-' => self.removed.push(content),
+            '+' => self.added.push(content),
+            _ => {
+                self.flush_word_diff();
+                self.text.push_str(&content);
+            }
+        }
+    }
+
+    /// 주석 텍스트 추가
+    fn push_note(&mut self, note: &str) {
+        self.flush_word_diff();
+        self.text.push_str(note);
+    }
+
+    /// 모아둔 삭제/추가 라인을 짝지어 단어 diff로 출력
+    fn flush_word_diff(&mut self) {
+        let count = self.removed.len().max(self.added.len());
+
+        for i in 0..count {
+            match (self.removed.get(i), self.added.get(i)) {
+                (Some(old), Some(new)) => self.text.push_str(&word_diff_line(old, new)),
+                (Some(old), None) => self.text.push_str(&format!("[-{}-]", old.trim_end_matches('\n'))),
+                (None, Some(new)) => self.text.push_str(&format!("{{+{}+}}", new.trim_end_matches('\n'))),
+                (None, None) => {}
+            }
+            self.text.push('\n');
+        }
+
+        self.removed.clear();
+        self.added.clear();
+    }
+
+    /// 최종 문자열 반환
+    fn finish(mut self) -> Result<String> {
+        self.flush_word_diff();
+
+        if self.text.is_empty() {
+            anyhow::bail!(tr!("commit.no_changes"));
+        }
+
+        Ok(self.text)
+    }
+}
+
+/// 한 라인 쌍의 단어 단위 차이 계산 (LCS 기반)
+fn word_diff_line(old: &str, new: &str) -> String {
+    let old_tokens = tokenize_words(old.trim_end_matches('\n'));
+    let new_tokens = tokenize_words(new.trim_end_matches('\n'));
+    let (n, m) = (old_tokens.len(), new_tokens.len());
+
+    // lcs[i][j] = old_tokens[i..]와 new_tokens[j..]의 최장 공통 부분열 길이
+    let mut lcs = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if old_tokens[i] == new_tokens[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
     }
 
-    Ok(diff_text)
+    let mut output = String::new();
+    let mut deleted = String::new();
+    let mut inserted = String::new();
+    let (mut i, mut j) = (0, 0);
+
+    while i < n || j < m {
+        if i < n && j < m && old_tokens[i] == new_tokens[j] {
+            push_word_changes(&mut output, &mut deleted, &mut inserted);
+            output.push_str(old_tokens[i]);
+            i += 1;
+            j += 1;
+        } else if j < m && (i == n || lcs[i][j + 1] >= lcs[i + 1][j]) {
+            inserted.push_str(new_tokens[j]);
+            j += 1;
+        } else {
+            deleted.push_str(old_tokens[i]);
+            i += 1;
+        }
+    }
+    push_word_changes(&mut output, &mut deleted, &mut inserted);
+
+    output
+}
+
+/// 누적된 삭제/추가 단어를 마커와 함께 출력
+fn push_word_changes(output: &mut String, deleted: &mut String, inserted: &mut String) {
+    if !deleted.is_empty() {
+        output.push_str(&format!("[-{}-]", deleted));
+        deleted.clear();
+    }
+    if !inserted.is_empty() {
+        output.push_str(&format!("{{+{}+}}", inserted));
+        inserted.clear();
+    }
+}
+
+/// 공백 경계를 기준으로 단어/공백 토큰 분리
+fn tokenize_words(line: &str) -> Vec<&str> {
+    let mut tokens = Vec::new();
+    let mut start = 0;
+    let mut in_whitespace = None;
+
+    for (index, ch) in line.char_indices() {
+        let is_whitespace = ch.is_whitespace();
+        if in_whitespace.is_some_and(|previous| previous != is_whitespace) {
+            tokens.push(&line[start..index]);
+            start = index;
+        }
+        in_whitespace = Some(is_whitespace);
+    }
+
+    if start < line.len() {
+        tokens.push(&line[start..]);
+    }
+
+    tokens
 }
 
 /// 스테이징된 파일 목록 가져오기
 pub fn get_staged_files() -> Result<Vec<String>> {
-    let repo = open_repository()?;
+    staged_files(&open_repository()?)
+}
+
+/// 리포지토리의 스테이징된 파일 목록
+pub fn staged_files(repo: &Repository) -> Result<Vec<String>> {
     let mut files = Vec::new();
 
-    let head = repo.head()?.peel_to_tree()
-        .map_err(|_| anyhow!("Could not find HEAD tree."))?;
+    let head = head_tree(repo)?;
 
-    let mut diff = repo.diff_tree_to_index(
-        Some(&head),
+    let diff = repo.diff_tree_to_index(
+        head.as_ref(),
         None,
         None,
     )?;
@@ -115,11 +511,418 @@ pub fn get_staged_files() -> Result<Vec<String>> {
     Ok(files)
 }
 
-/// 현재 브랜치 이름 가져오기
+/// 스테이징된 파일의 언어 감지
+pub fn get_staged_file_languages() -> Result<Vec<FileLanguage>> {
+    let repo = open_repository()?;
+
+    let head = head_tree(&repo)?;
+
+    let diff = repo.diff_tree_to_index(head.as_ref(), None, None)?;
+
+    Ok(diff_file_languages(&repo, &diff))
+}
+
+/// 특정 커밋에서 변경된 파일의 언어 감지
+pub fn get_commit_file_languages(commit_hash: &str) -> Result<Vec<FileLanguage>> {
+    let repo = open_repository()?;
+
+    let oid = Oid::from_str(commit_hash)
+        .map_err(|_| anyhow!("Invalid commit hash: {}", commit_hash))?;
+
+    let commit = repo.find_commit(oid)?;
+    let parent_tree = if commit.parent_count() > 0 {
+        Some(commit.parent(0)?.tree()?)
+    } else {
+        None
+    };
+
+    let diff = repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&commit.tree()?), None)?;
+
+    Ok(diff_file_languages(&repo, &diff))
+}
+
+/// Diff의 각 파일에 대해 확장자와 shebang으로 언어 판별
+fn diff_file_languages(repo: &Repository, diff: &Diff) -> Vec<FileLanguage> {
+    let odb = repo.odb().ok();
+
+    diff.deltas()
+        .filter_map(|delta| {
+            // 삭제된 파일은 이전 버전 기준으로 판별
+            let file = if delta.new_file().exists() { delta.new_file() } else { delta.old_file() };
+            let path = file.path()?;
+
+            // partial clone에서 로컬에 없는 blob은 읽지 않음
+            let first_line = odb
+                .as_ref()
+                .filter(|odb| odb.exists(file.id()))
+                .and_then(|_| repo.find_blob(file.id()).ok())
+                .and_then(|blob| {
+                    let content = blob.content();
+                    let end = content.iter().position(|&b| b == b'\n').unwrap_or(content.len());
+                    std::str::from_utf8(&content[..end]).ok().map(str::to_string)
+                });
+
+            language::detect_language(path, first_line.as_deref()).map(|language| FileLanguage {
+                path: path.to_string_lossy().to_string(),
+                language: language.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// 스테이징된 변경이 속한 함수/구조체 등 심볼 추출
+pub fn get_staged_symbols() -> Result<Vec<AffectedSymbol>> {
+    let repo = open_repository()?;
+    let diff = staged_analysis_diff(&repo)?;
+
+    diff_symbols(&repo, &diff)
+}
+
+/// 특정 커밋의 변경이 속한 심볼 추출
+pub fn get_commit_symbols(commit_hash: &str) -> Result<Vec<AffectedSymbol>> {
+    let repo = open_repository()?;
+    let diff = commit_analysis_diff(&repo, commit_hash)?;
+
+    diff_symbols(&repo, &diff)
+}
+
+/// 스테이징된 변경의 각 hunk를 감싸는 전체 정의 (정의 하나당 최대 max_lines줄)
+pub fn get_staged_enclosing_code(max_lines: usize) -> Result<Option<String>> {
+    let repo = open_repository()?;
+    let diff = staged_analysis_diff(&repo)?;
+
+    diff_enclosing_code(&repo, &diff, max_lines)
+}
+
+/// 특정 커밋의 각 hunk를 감싸는 전체 정의
+pub fn get_commit_enclosing_code(commit_hash: &str, max_lines: usize) -> Result<Option<String>> {
+    let repo = open_repository()?;
+    let diff = commit_analysis_diff(&repo, commit_hash)?;
+
+    diff_enclosing_code(&repo, &diff, max_lines)
+}
+
+/// 스테이징된 매니페스트·잠금 파일의 의존성 변경
+pub fn get_staged_dependency_changes() -> Result<Vec<(String, Vec<DependencyChange>)>> {
+    let repo = open_repository()?;
+    let diff = staged_analysis_diff(&repo)?;
+
+    diff_dependency_changes(&repo, &diff)
+}
+
+/// 특정 커밋의 의존성 변경
+pub fn get_commit_dependency_changes(commit_hash: &str) -> Result<Vec<(String, Vec<DependencyChange>)>> {
+    let repo = open_repository()?;
+    let diff = commit_analysis_diff(&repo, commit_hash)?;
+
+    diff_dependency_changes(&repo, &diff)
+}
+
+/// 변경된 매니페스트·잠금 파일의 이전/새 blob을 파싱해 파일별 의존성 변경 계산
+/// `.aiignore` 대상과 로컬에 없는 blob은 건너뜀
+fn diff_dependency_changes(repo: &Repository, diff: &Diff) -> Result<Vec<(String, Vec<DependencyChange>)>> {
+    let odb = repo.odb()?;
+    let aiignore = repo.workdir().map(AiIgnore::load);
+    let mut changes = Vec::new();
+
+    for delta in diff.deltas() {
+        let Some(path) = delta_path(&delta) else {
+            continue;
+        };
+        if !deps::is_dependency_file(&path) || aiignore.as_ref().is_some_and(|rules| rules.is_ignored(&path, false)) {
+            continue;
+        }
+
+        // 추가/삭제된 파일은 반대쪽을 빈 목록으로 비교
+        let mut versions = Vec::new();
+        for file in [delta.old_file(), delta.new_file()] {
+            if file.id().is_zero() {
+                versions.push(Some(Default::default()));
+            } else if odb.exists(file.id()) {
+                let blob = repo.find_blob(file.id())?;
+                versions.push(std::str::from_utf8(blob.content()).ok().and_then(|content| deps::parse_dependencies(&path, content)));
+            } else {
+                versions.push(None);
+            }
+        }
+
+        if let [Some(old), Some(new)] = versions.as_slice() {
+            changes.push((path.to_string_lossy().to_string(), deps::diff_dependencies(old, new)));
+        }
+    }
+
+    Ok(changes)
+}
+
+/// HEAD 트리 (아직 커밋이 없으면 None이며, diff는 빈 트리와 비교)
+fn head_tree(repo: &Repository) -> Result<Option<Tree<'_>>> {
+    match repo.head() {
+        Ok(head) => Ok(Some(head.peel_to_tree().map_err(|_| anyhow!("Could not find HEAD tree."))?)),
+        Err(e) if e.code() == ErrorCode::UnbornBranch => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// 분석용 스테이징 diff (HEAD 트리 대 인덱스)
+fn staged_analysis_diff(repo: &Repository) -> Result<Diff<'_>> {
+    let head = head_tree(repo)?;
+
+    Ok(repo.diff_tree_to_index(head.as_ref(), None, None)?)
+}
+
+/// 분석용 커밋 diff (첫 번째 부모 대 커밋)
+fn commit_analysis_diff<'r>(repo: &'r Repository, commit_hash: &str) -> Result<Diff<'r>> {
+    let oid = Oid::from_str(commit_hash)
+        .map_err(|_| anyhow!("Invalid commit hash: {}", commit_hash))?;
+
+    let commit = repo.find_commit(oid)?;
+    let parent_tree = if commit.parent_count() > 0 {
+        Some(commit.parent(0)?.tree()?)
+    } else {
+        None
+    };
+
+    Ok(repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&commit.tree()?), None)?)
+}
+
+/// 각 hunk의 변경 줄 범위를 새 버전(추가/수정)과 이전 버전(삭제)에서 파싱해 심볼 추출
+/// 민감 파일, `.aiignore` 대상, 로컬에 없는 blob은 건너뜀
+fn diff_symbols(repo: &Repository, diff: &Diff) -> Result<Vec<AffectedSymbol>> {
+    let odb = repo.odb()?;
+    let aiignore = repo.workdir().map(AiIgnore::load);
+    let mut found = Vec::new();
+
+    for idx in 0..diff.deltas().len() {
+        let Some(patch) = Patch::from_diff(diff, idx)? else {
+            continue;
+        };
+        let delta = patch.delta();
+        let Some(path) = delta_path(&delta) else {
+            continue;
+        };
+        if security::is_sensitive_file(&path) || aiignore.as_ref().is_some_and(|rules| rules.is_ignored(&path, false)) {
+            continue;
+        }
+
+        let mut new_ranges = Vec::new();
+        let mut old_ranges = Vec::new();
+        for hunk_idx in 0..patch.num_hunks() {
+            let (hunk, _) = patch.hunk(hunk_idx)?;
+            if hunk.new_lines() > 0 {
+                new_ranges.push((hunk.new_start() as usize, (hunk.new_start() + hunk.new_lines() - 1) as usize));
+            }
+            if hunk.old_lines() > 0 {
+                old_ranges.push((hunk.old_start() as usize, (hunk.old_start() + hunk.old_lines() - 1) as usize));
+            }
+        }
+
+        let path_str = path.to_string_lossy().to_string();
+        for (file, ranges) in [(delta.new_file(), new_ranges), (delta.old_file(), old_ranges)] {
+            if ranges.is_empty() || file.id().is_zero() || !odb.exists(file.id()) {
+                continue;
+            }
+            let blob = repo.find_blob(file.id())?;
+            if let Ok(source) = std::str::from_utf8(blob.content()) {
+                found.extend(symbols::symbols_in_ranges(&path_str, source, &ranges));
+            }
+        }
+    }
+
+    found.sort();
+    found.dedup();
+    Ok(found)
+}
+
+/// 확장 컨텍스트로 첨부하는 코드 전체의 최대 크기 (바이트)
+const MAX_ENCLOSING_CODE_BYTES: usize = 64 * 1024;
+
+/// 각 hunk(새 버전)를 감싸는 가장 안쪽 정의의 전체 코드를 프롬프트 섹션으로 만듦
+/// 긴 정의는 max_lines줄에서 자르고, 민감 파일과 `.aiignore` 대상은 건너뜀 (첨부할 코드가 없으면 None)
+fn diff_enclosing_code(repo: &Repository, diff: &Diff, max_lines: usize) -> Result<Option<String>> {
+    let odb = repo.odb()?;
+    let aiignore = repo.workdir().map(AiIgnore::load);
+    let mut blocks: Vec<String> = Vec::new();
+    let mut total_bytes = 0;
+
+    for idx in 0..diff.deltas().len() {
+        let Some(patch) = Patch::from_diff(diff, idx)? else {
+            continue;
+        };
+        let delta = patch.delta();
+        let Some(path) = delta_path(&delta) else {
+            continue;
+        };
+        let file = delta.new_file();
+        if security::is_sensitive_file(&path)
+            || aiignore.as_ref().is_some_and(|rules| rules.is_ignored(&path, false))
+            || file.id().is_zero()
+            || !odb.exists(file.id())
+        {
+            continue;
+        }
+
+        let ranges: Vec<(usize, usize)> = (0..patch.num_hunks())
+            .filter_map(|hunk_idx| patch.hunk(hunk_idx).ok())
+            .filter(|(hunk, _)| hunk.new_lines() > 0)
+            .map(|(hunk, _)| (hunk.new_start() as usize, (hunk.new_start() + hunk.new_lines() - 1) as usize))
+            .collect();
+        if ranges.is_empty() {
+            continue;
+        }
+
+        let blob = repo.find_blob(file.id())?;
+        let Ok(source) = std::str::from_utf8(blob.content()) else {
+            continue;
+        };
+        let lines: Vec<&str> = source.lines().collect();
+        let path_str = path.to_string_lossy().to_string();
+        let fence = language::detect_language(&path, lines.first().copied())
+            .map(str::to_lowercase)
+            .unwrap_or_default();
+
+        for definition in symbols::enclosing_definitions(&path_str, source, &ranges) {
+            let end = definition.end_line.min(lines.len()).min(definition.start_line + max_lines.max(1) - 1);
+            let mut body = lines[definition.start_line - 1..end].join("\n");
+            if end < definition.end_line {
+                body.push_str(&format!("\n// ... ({} more lines)", definition.end_line - end));
+            }
+
+            let block = format!(
+                "=== {}: {} {} (lines {}-{}) ===\n```{}\n{}\n```",
+                path_str,
+                definition.symbol.kind,
+                definition.symbol.name,
+                definition.start_line,
+                definition.end_line,
+                fence,
+                body
+            );
+            if total_bytes + block.len() > MAX_ENCLOSING_CODE_BYTES {
+                blocks.push("(remaining definitions omitted: size limit reached)".to_string());
+                return Ok(Some(format_enclosing_code(&blocks)));
+            }
+            total_bytes += block.len();
+            blocks.push(block);
+        }
+    }
+
+    Ok((!blocks.is_empty()).then(|| format_enclosing_code(&blocks)))
+}
+
+fn format_enclosing_code(blocks: &[String]) -> String {
+    format!(
+        "ENCLOSING CODE (the complete definition around each change, new version; the diff shows what changed):\n{}",
+        injection::wrap_untrusted("ENCLOSING CODE", &blocks.join("\n\n"))
+    )
+}
+
+/// 최근 커밋 검색 시 확인하는 최대 커밋 수
+const MAX_HISTORY_SCAN: usize = 500;
+
+/// 지정한 파일을 변경한 최근 커밋 제목 (최신순, 머지 커밋 제외, 파일 목록이 비면 모든 커밋)
+pub fn recent_commit_subjects(repo: &Repository, paths: &[String], limit: usize) -> Result<Vec<String>> {
+    let mut subjects = Vec::new();
+    if limit == 0 {
+        return Ok(subjects);
+    }
+
+    let mut revwalk = repo.revwalk()?;
+    if revwalk.push_head().is_err() {
+        return Ok(subjects); // 아직 커밋이 없음
+    }
+    revwalk.set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::TIME)?;
+
+    for oid in revwalk.take(MAX_HISTORY_SCAN) {
+        let commit = repo.find_commit(oid?)?;
+        if commit.parent_count() > 1 {
+            continue;
+        }
+
+        if !paths.is_empty() {
+            let parent_tree = match commit.parents().next() {
+                Some(parent) => Some(parent.tree()?),
+                None => None,
+            };
+            let mut options = DiffOptions::new();
+            for path in paths {
+                options.pathspec(path);
+            }
+            let diff = repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&commit.tree()?), Some(&mut options))?;
+            if diff.deltas().len() == 0 {
+                continue;
+            }
+        }
+
+        if let Some(summary) = commit.summary() {
+            subjects.push(summary.to_string());
+            if subjects.len() >= limit {
+                break;
+            }
+        }
+    }
+
+    Ok(subjects)
+}
+
+/// 스테이징된 파일을 변경한 최근 커밋 제목 (없으면 리포지토리 전체의 최근 커밋)
+pub fn get_recent_commit_subjects(limit: usize) -> Result<Vec<String>> {
+    let repo = open_repository()?;
+    let staged_files = get_staged_files().unwrap_or_default();
+
+    let subjects = recent_commit_subjects(&repo, &staged_files, limit)?;
+    if subjects.is_empty() && !staged_files.is_empty() {
+        return recent_commit_subjects(&repo, &[], limit);
+    }
+    Ok(subjects)
+}
+
+/// 프롬프트에 넣을 커밋 기록 섹션 (커밋 제목은 리포지토리 데이터이므로 구분자 블록으로 감쌈)
+pub fn format_commit_history(subjects: &[String]) -> Option<String> {
+    if subjects.is_empty() {
+        return None;
+    }
+
+    let list: Vec<String> = subjects.iter().map(|subject| format!("- {}", subject)).collect();
+    Some(format!(
+        "RECENT COMMIT SUBJECTS for these files (match their style, types and scopes):\n{}",
+        injection::wrap_untrusted("HISTORY", &list.join("\n"))
+    ))
+}
+
+/// 푸시할 커밋 (ID, 메시지), 오래된 것부터
+/// `remote_sha`가 없으면(새 브랜치) 리모트의 어느 브랜치에도 없는 커밋, 최대 `limit`개
+pub fn get_push_commits(local_sha: &str, remote_sha: Option<&str>, remote: &str, limit: usize) -> Result<Vec<(String, String)>> {
+    let repo = open_repository()?;
+    let mut revwalk = repo.revwalk()?;
+    revwalk.push(Oid::from_str(local_sha).map_err(|_| anyhow!("Invalid commit hash: {}", local_sha))?)?;
+    match remote_sha.and_then(|sha| Oid::from_str(sha).ok()) {
+        // 리모트 커밋이 로컬에 없으면(다른 사람이 푸시) 리모트 추적 브랜치 기준으로
+        Some(oid) if repo.find_commit(oid).is_ok() => revwalk.hide(oid)?,
+        _ => revwalk.hide_glob(&format!("refs/remotes/{}/*", remote))?,
+    }
+
+    let mut commits = Vec::new();
+    for oid in revwalk.take(limit) {
+        let commit = repo.find_commit(oid?)?;
+        commits.push((commit.id().to_string(), commit.message().unwrap_or_default().to_string()));
+    }
+    commits.reverse();
+    Ok(commits)
+}
+
+/// 현재 브랜치 이름 가져오기 (아직 커밋이 없으면 HEAD가 가리키는 브랜치)
 pub fn get_current_branch() -> Result<String> {
     let repo = open_repository()?;
 
-    let head = repo.head()?;
+    let head = match repo.head() {
+        Ok(head) => head,
+        Err(e) if e.code() == ErrorCode::UnbornBranch => {
+            let head = repo.find_reference("HEAD")?;
+            let target = head.symbolic_target().unwrap_or_default();
+            return Ok(target.strip_prefix("refs/heads/").unwrap_or(target).to_string());
+        }
+        Err(e) => return Err(e.into()),
+    };
     let branch_name = head.shorthand()
         .ok_or_else(|| anyhow!("Not on any branch (detached HEAD)"))?;
 
@@ -129,7 +932,7 @@ pub fn get_current_branch() -> Result<String> {
 /// 리포지토리 상태 확인
 pub fn get_repository_status() -> Result<GitStatus> {
     let repo = open_repository()?;
-    let mut statuses = repo.statuses(None)?;
+    let statuses = repo.statuses(None)?;
 
     let mut staged = 0;
     let mut modified = 0;
@@ -164,6 +967,382 @@ pub fn get_repository_status() -> Result<GitStatus> {
     })
 }
 
+/// 커밋 생성 오류
+#[derive(Debug, thiserror::Error)]
+pub enum CommitError {
+    #[error("{0} hook rejected the commit")]
+    HookRejected(String),
+    #[error("Nothing to commit: staged changes are identical to HEAD")]
+    NothingToCommit,
+    #[error("Git identity is not configured. Run 'git config user.name' and 'git config user.email' first")]
+    MissingSignature,
+    #[error("Failed to sign the commit (commit.gpgsign is set): {0}")]
+    Signing(String),
+    #[error("Git error: {0}")]
+    Git(#[from] git2::Error),
+    #[error("Failed to run hook: {0}")]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+/// 스테이징된 변경 사항으로 커밋 생성 (git commit -m)
+/// `git` 바이너리 없이 libgit2로 커밋하며, pre-commit / prepare-commit-msg / commit-msg / post-commit 훅은 직접 실행
+/// commit.gpgsign이 켜져 있으면 git과 같은 서명 프로그램으로 서명
+pub fn create_commit(message: &str, no_verify: bool) -> std::result::Result<Oid, CommitError> {
+    let _span = tracing::debug_span!("git", op = "commit", no_verify).entered();
+    let repo = open_repository()?;
+
+    if !no_verify {
+        run_hook(&repo, "pre-commit", &[])?;
+    }
+
+    let message = checked_message(&repo, message, no_verify)?;
+
+    // pre-commit 훅이 파일을 스테이징했을 수 있으므로 인덱스를 디스크에서 다시 로드
+    let mut index = repo.index()?;
+    index.read(true)?;
+    let tree = repo.find_tree(index.write_tree()?)?;
+
+    let parent = match repo.head() {
+        Ok(head) => Some(head.peel_to_commit()?),
+        Err(e) if e.code() == ErrorCode::UnbornBranch || e.code() == ErrorCode::NotFound => None,
+        Err(e) => return Err(e.into()),
+    };
+
+    if let Some(parent) = &parent {
+        if parent.tree_id() == tree.id() {
+            return Err(CommitError::NothingToCommit);
+        }
+    }
+
+    let author = env_signature(&repo, "AUTHOR")?;
+    let committer = env_signature(&repo, "COMMITTER")?;
+    let parents: Vec<&git2::Commit> = parent.iter().collect();
+
+    let oid = write_commit(&repo, &author, &committer, &message, &tree, &parents)?;
+    let reflog = if parent.is_some() { "commit" } else { "commit (initial)" };
+    update_head(&repo, oid, &format!("{}: {}", reflog, message.lines().next().unwrap_or_default()))?;
+
+    // post-commit 훅의 실패는 git과 마찬가지로 무시
+    if !no_verify {
+        if let Err(e) = run_hook(&repo, "post-commit", &[]) {
+            tracing::warn!("post-commit hook failed: {}", e);
+        }
+    }
+
+    Ok(oid)
+}
+
+/// 마지막 커밋 수정 (`git commit --amend`처럼 메시지를 바꾸고, 그 사이 스테이징한 변경도 포함)
+pub fn amend_commit(message: &str, no_verify: bool) -> std::result::Result<Oid, CommitError> {
+    let _span = tracing::debug_span!("git", op = "amend", no_verify).entered();
+    let repo = open_repository()?;
+
+    if !no_verify {
+        run_hook(&repo, "pre-commit", &[])?;
+    }
+    let message = checked_message(&repo, message, no_verify)?;
+
+    let mut index = repo.index()?;
+    index.read(true)?;
+    let tree = repo.find_tree(index.write_tree()?)?;
+    let head = repo.head()?.peel_to_commit()?;
+
+    // 작성자는 그대로 두고 커미터만 현재 사용자로
+    let committer = env_signature(&repo, "COMMITTER")?;
+    let parents: Vec<git2::Commit> = head.parents().collect();
+    let parents: Vec<&git2::Commit> = parents.iter().collect();
+
+    let oid = write_commit(&repo, &head.author(), &committer, &message, &tree, &parents)?;
+    update_head(&repo, oid, &format!("commit (amend): {}", message.lines().next().unwrap_or_default()))?;
+
+    if !no_verify {
+        if let Err(e) = run_hook(&repo, "post-commit", &[]) {
+            tracing::warn!("post-commit hook failed: {}", e);
+        }
+    }
+
+    Ok(oid)
+}
+
+/// prepare-commit-msg / commit-msg 훅을 거친 커밋 메시지 (훅은 메시지 파일을 수정할 수 있으므로 다시 읽음)
+/// git처럼 prepare-commit-msg는 --no-verify여도 실행하고, commit-msg만 건너뜀
+fn checked_message(repo: &Repository, message: &str, no_verify: bool) -> std::result::Result<String, CommitError> {
+    let message_file = repo.path().join("COMMIT_EDITMSG");
+    fs::write(&message_file, message)?;
+
+    let source: &Path = Path::new("message");
+    run_hook(repo, "prepare-commit-msg", &[message_file.as_path(), source])?;
+    if !no_verify {
+        run_hook(repo, "commit-msg", &[message_file.as_path()])?;
+    }
+    let message = fs::read_to_string(&message_file)?;
+
+    // --no-verify면 메시지를 그대로 기록하고, 아니면 `git commit -m`의 whitespace 정리만 적용
+    // ('#'으로 시작하는 줄도 -m 메시지에서는 주석이 아니므로 남김)
+    if no_verify {
+        Ok(message)
+    } else {
+        Ok(git2::message_prettify(&message, None)?)
+    }
+}
+
+/// 커밋 서명 생성 (git처럼 GIT_<ROLE>_NAME / _EMAIL / _DATE 환경 변수 우선, 없으면 user.name / user.email)
+fn env_signature(repo: &Repository, role: &str) -> std::result::Result<git2::Signature<'static>, CommitError> {
+    let config = repo.config()?;
+    let name = std::env::var(format!("GIT_{}_NAME", role))
+        .ok()
+        .or_else(|| config.get_string("user.name").ok());
+    let email = std::env::var(format!("GIT_{}_EMAIL", role))
+        .ok()
+        .or_else(|| config.get_string("user.email").ok())
+        .or_else(|| std::env::var("EMAIL").ok());
+
+    let (Some(name), Some(email)) = (name, email) else {
+        return Err(CommitError::MissingSignature);
+    };
+
+    let signature = match std::env::var(format!("GIT_{}_DATE", role)).ok() {
+        Some(date) => {
+            let time = parse_git_date(&date)
+                .ok_or_else(|| anyhow!("Invalid GIT_{}_DATE: {}", role, date))?;
+            git2::Signature::new(&name, &email, &time)
+        }
+        None => git2::Signature::now(&name, &email),
+    };
+    signature.map_err(|_| CommitError::MissingSignature)
+}
+
+/// GIT_*_DATE 파싱 (git 내부 형식 `[@]<초> <+hhmm>` 또는 RFC 3339)
+fn parse_git_date(date: &str) -> Option<git2::Time> {
+    let date = date.trim();
+    if let Some((seconds, offset)) = date.trim_start_matches('@').split_once(' ') {
+        if let (Ok(seconds), Some(minutes)) = (seconds.parse::<i64>(), parse_offset(offset)) {
+            return Some(git2::Time::new(seconds, minutes));
+        }
+    }
+    if let Ok(seconds) = date.trim_start_matches('@').parse::<i64>() {
+        return Some(git2::Time::new(seconds, 0));
+    }
+    let parsed = chrono::DateTime::parse_from_rfc3339(date).ok()?;
+    Some(git2::Time::new(parsed.timestamp(), parsed.offset().local_minus_utc() / 60))
+}
+
+/// `+0900` 형식의 시간대 오프셋을 분 단위로
+fn parse_offset(offset: &str) -> Option<i32> {
+    let (sign, digits) = match offset.as_bytes().first()? {
+        b'+' => (1, &offset[1..]),
+        b'-' => (-1, &offset[1..]),
+        _ => return None,
+    };
+    if digits.len() != 4 || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let hours: i32 = digits[..2].parse().ok()?;
+    let minutes: i32 = digits[2..].parse().ok()?;
+    Some(sign * (hours * 60 + minutes))
+}
+
+/// 커밋 객체 작성 (commit.gpgsign이 켜져 있으면 서명된 커밋으로)
+/// HEAD는 옮기지 않으며, 참조 갱신은 `update_head`에서
+fn write_commit(
+    repo: &Repository,
+    author: &git2::Signature,
+    committer: &git2::Signature,
+    message: &str,
+    tree: &Tree,
+    parents: &[&git2::Commit],
+) -> std::result::Result<Oid, CommitError> {
+    let config = repo.config()?;
+    if !config.get_bool("commit.gpgsign").unwrap_or(false) {
+        return Ok(repo.commit(None, author, committer, message, tree, parents)?);
+    }
+
+    let buffer = repo.commit_create_buffer(author, committer, message, tree, parents)?;
+    let content = buffer
+        .as_str()
+        .ok_or_else(|| CommitError::Signing("commit content is not valid UTF-8".to_string()))?;
+    let signature = sign_buffer(&config, content.as_bytes(), committer)?;
+    Ok(repo.commit_signed(content, &signature, None)?)
+}
+
+/// git과 같은 설정(gpg.format, gpg.program, gpg.ssh.program, user.signingkey)으로 커밋 내용 서명
+fn sign_buffer(config: &git2::Config, content: &[u8], committer: &git2::Signature) -> std::result::Result<String, CommitError> {
+    let format = config.get_string("gpg.format").unwrap_or_else(|_| "openpgp".to_string());
+    let signing_key = config.get_string("user.signingkey").ok();
+
+    let output = match format.as_str() {
+        "openpgp" | "x509" => {
+            let (program_key, default_program) = if format == "x509" {
+                ("gpg.x509.program", "gpgsm")
+            } else {
+                ("gpg.program", "gpg")
+            };
+            let program = config.get_string(program_key).unwrap_or_else(|_| default_program.to_string());
+            // 서명 키가 없으면 git처럼 커미터 ID로 키 선택
+            let key = signing_key.unwrap_or_else(|| {
+                format!("{} <{}>", committer.name().unwrap_or_default(), committer.email().unwrap_or_default())
+            });
+            run_signer(Command::new(&program).args(["--status-fd=2", "-bsau", &key]), content, &program)?
+        }
+        "ssh" => {
+            let program = config.get_string("gpg.ssh.program").unwrap_or_else(|_| "ssh-keygen".to_string());
+            let key = signing_key
+                .ok_or_else(|| CommitError::Signing("gpg.format is ssh but user.signingkey is not set".to_string()))?;
+
+            // 키 파일 경로 또는 `key::`/`ssh-` 리터럴 공개 키 (리터럴은 에이전트로 서명)
+            let literal = key.strip_prefix("key::").map(str::to_string)
+                .or_else(|| key.starts_with("ssh-").then(|| key.clone()));
+            let key_file = match &literal {
+                Some(public_key) => {
+                    let mut file = tempfile::NamedTempFile::new()?;
+                    std::io::Write::write_all(&mut file, public_key.as_bytes())?;
+                    Some(file)
+                }
+                None => None,
+            };
+            let key_path = key_file.as_ref().map(|file| file.path().to_path_buf()).unwrap_or_else(|| PathBuf::from(&key));
+
+            let mut command = Command::new(&program);
+            command.args(["-Y", "sign", "-n", "git", "-f"]).arg(&key_path);
+            if literal.is_some() {
+                command.arg("-U");
+            }
+            run_signer(&mut command, content, &program)?
+        }
+        other => return Err(CommitError::Signing(format!("unsupported gpg.format '{}'", other))),
+    };
+
+    let signature = String::from_utf8(output)
+        .map_err(|_| CommitError::Signing("signature is not valid UTF-8".to_string()))?;
+    if signature.trim().is_empty() {
+        return Err(CommitError::Signing("signing program produced no signature".to_string()));
+    }
+    Ok(signature)
+}
+
+/// 서명 프로그램에 커밋 내용을 stdin으로 넘기고 stdout의 서명을 받음
+fn run_signer(command: &mut Command, content: &[u8], program: &str) -> std::result::Result<Vec<u8>, CommitError> {
+    use std::io::Write;
+
+    let mut child = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| CommitError::Signing(format!("failed to run '{}': {}", program, e)))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(content)?;
+    }
+    let output = child.wait_with_output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let reason = stderr.lines().rev().find(|line| !line.trim().is_empty()).unwrap_or("no output");
+        return Err(CommitError::Signing(format!("'{}' failed: {}", program, reason.trim())));
+    }
+    Ok(output.stdout)
+}
+
+/// HEAD가 가리키는 브랜치(태어나지 않은 브랜치 포함)를 새 커밋으로 옮김 (detached HEAD면 HEAD 자체를)
+fn update_head(repo: &Repository, oid: Oid, reflog: &str) -> std::result::Result<(), CommitError> {
+    let head = repo.find_reference("HEAD")?;
+    match head.symbolic_target() {
+        Some(branch) => {
+            repo.reference(branch, oid, true, reflog)?;
+        }
+        None => repo.set_head_detached(oid)?,
+    }
+    Ok(())
+}
+
+/// Git 훅 실행 (core.hooksPath 설정 반영)
+/// 훅 출력은 실시간으로 터미널에 그대로 표시
+fn run_hook(repo: &Repository, name: &str, args: &[&Path]) -> std::result::Result<(), CommitError> {
+    let work_dir = repo.workdir().unwrap_or_else(|| repo.path()).to_path_buf();
+    let hook = hooks_dir(repo, &work_dir).join(name);
+
+    if !is_executable(&hook) {
+        return Ok(());
+    }
+
+    tracing::debug!("Running {} hook: {}", name, hook.display());
+
+    let status = hook_command(&hook)
+        .args(args)
+        .current_dir(&work_dir)
+        .stdin(Stdio::inherit())
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .status()?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(CommitError::HookRejected(name.to_string()))
+    }
+}
+
+/// 훅 실행 명령
+#[cfg(not(windows))]
+fn hook_command(hook: &Path) -> Command {
+    Command::new(hook)
+}
+
+/// Windows에서는 Git for Windows처럼 셔뱅(#!) 스크립트 훅을 `sh`로 실행
+#[cfg(windows)]
+fn hook_command(hook: &Path) -> Command {
+    let is_script = fs::read(hook).map(|bytes| bytes.starts_with(b"#!")).unwrap_or(false);
+    if !is_script {
+        return Command::new(hook);
+    }
+    let mut command = Command::new(windows_sh());
+    command.arg(hook);
+    command
+}
+
+/// Windows의 `sh` 위치 (PATH에 없으면 Git for Windows 설치 경로에서 찾음)
+#[cfg(windows)]
+fn windows_sh() -> PathBuf {
+    let path = std::env::var_os("PATH").unwrap_or_default();
+    let dirs: Vec<PathBuf> = std::env::split_paths(&path).collect();
+
+    if let Some(sh) = dirs.iter().map(|dir| dir.join("sh.exe")).find(|sh| sh.is_file()) {
+        return sh;
+    }
+    dirs.iter()
+        .filter(|dir| dir.join("git.exe").is_file())
+        .filter_map(|dir| dir.parent())
+        .flat_map(|root| [root.join("bin").join("sh.exe"), root.join("usr").join("bin").join("sh.exe")])
+        .find(|sh| sh.is_file())
+        .unwrap_or_else(|| PathBuf::from("sh"))
+}
+
+/// 훅 디렉토리 결정
+fn hooks_dir(repo: &Repository, work_dir: &Path) -> PathBuf {
+    repo.config()
+        .ok()
+        .and_then(|config| config.get_path("core.hooksPath").ok())
+        .map(|path| if path.is_relative() { work_dir.join(path) } else { path })
+        .unwrap_or_else(|| repo.path().join("hooks"))
+}
+
+/// 실행 가능한 훅 파일인지 확인
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    fs::metadata(path)
+        .map(|metadata| metadata.is_file() && metadata.permissions().mode() & 0o111 != 0)
+        .unwrap_or(false)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
+
 /// Git 상태 정보 구조체
 #[derive(Debug)]
 pub struct GitStatus {
@@ -177,11 +1356,130 @@ pub struct GitStatus {
 mod tests {
     use super::*;
     use tempfile::TempDir;
-    use std::process::Command;
+
+    #[test]
+    fn test_parse_git_date() {
+        let time = parse_git_date("@1700000000 +0900").unwrap();
+        assert_eq!((time.seconds(), time.offset_minutes()), (1700000000, 540));
+
+        let time = parse_git_date("1700000000 -0130").unwrap();
+        assert_eq!((time.seconds(), time.offset_minutes()), (1700000000, -90));
+
+        let time = parse_git_date("2023-11-15T07:13:20+09:00").unwrap();
+        assert_eq!((time.seconds(), time.offset_minutes()), (1700000000, 540));
+
+        assert!(parse_git_date("yesterday").is_none());
+    }
 
     #[test]
     fn test_diff_to_string() {
         // 이 테스트는 실제 Git 리포지토리가 필요
         // TODO: 임시 리포지토리 생성으로 테스트 개선
     }
+
+    #[test]
+    fn test_word_diff_line() {
+        let result = word_diff_line("let x = 1;\n", "let y = 1;\n");
+        assert_eq!(result, "let [-x-]{+y+} = 1;");
+
+        let unchanged = word_diff_line("same line", "same line");
+        assert_eq!(unchanged, "same line");
+    }
+
+    #[test]
+    fn test_enclosing_code() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = Repository::init(temp_dir.path()).unwrap();
+        let signature = git2::Signature::now("Test User", "test@example.com").unwrap();
+        let body: String = (1..=10).map(|n| format!("    let v{} = {};\n", n, n)).collect();
+        let write_and_stage = |content: &str| {
+            fs::write(temp_dir.path().join("lib.rs"), content).unwrap();
+            let mut index = repo.index().unwrap();
+            index.add_path(Path::new("lib.rs")).unwrap();
+            index.write().unwrap();
+            index.write_tree().unwrap()
+        };
+
+        let tree = repo.find_tree(write_and_stage(&format!("fn other() {{}}\n\nfn long() {{\n{}}}\n", body))).unwrap();
+        repo.commit(Some("HEAD"), &signature, &signature, "init", &tree, &[]).unwrap();
+        write_and_stage(&format!("fn other() {{}}\n\nfn long() {{\n{}}}\n", body.replace("v5 = 5", "v5 = 50")));
+
+        let diff = staged_analysis_diff(&repo).unwrap();
+        let section = diff_enclosing_code(&repo, &diff, 100).unwrap().unwrap();
+        assert!(section.contains("=== lib.rs: function long (lines 3-14) ===\n```rust\nfn long() {"));
+        assert!(section.contains("let v10 = 10;\n}\n```"));
+        assert!(!section.contains("fn other"));
+
+        // 정의가 max_lines보다 길면 잘라냄
+        let section = diff_enclosing_code(&repo, &diff, 4).unwrap().unwrap();
+        assert!(section.contains("let v3 = 3;\n// ... (8 more lines)"));
+    }
+
+    #[test]
+    fn test_dependency_changes_replace_lockfile_diff() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = Repository::init(temp_dir.path()).unwrap();
+        let signature = git2::Signature::now("Test User", "test@example.com").unwrap();
+        let stage = |manifest: &str, lock: &str| {
+            fs::write(temp_dir.path().join("Cargo.toml"), manifest).unwrap();
+            fs::write(temp_dir.path().join("Cargo.lock"), lock).unwrap();
+            let mut index = repo.index().unwrap();
+            index.add_path(Path::new("Cargo.toml")).unwrap();
+            index.add_path(Path::new("Cargo.lock")).unwrap();
+            index.write().unwrap();
+            index.write_tree().unwrap()
+        };
+        let lock = |version: &str| format!("version = 3\n\n[[package]]\nname = \"serde\"\nversion = \"{}\"\n", version);
+
+        let tree = repo.find_tree(stage("[dependencies]\nserde = \"1.0.1\"\n", &lock("1.0.1"))).unwrap();
+        repo.commit(Some("HEAD"), &signature, &signature, "init", &tree, &[]).unwrap();
+        stage("[dependencies]\nserde = \"1.0.2\"\n", &lock("1.0.2"));
+
+        let diff = staged_analysis_diff(&repo).unwrap();
+        let changes = diff_dependency_changes(&repo, &diff).unwrap();
+        let updated = DependencyChange::Updated { name: "serde".to_string(), from: "1.0.1".to_string(), to: "1.0.2".to_string() };
+        assert_eq!(changes, vec![("Cargo.lock".to_string(), vec![updated.clone()]), ("Cargo.toml".to_string(), vec![updated])]);
+
+        let rendered = render_diff(&repo, &diff, &DiffSettings::default(), false).unwrap();
+        assert!(rendered.contains("lockfile diff omitted"));
+        assert!(rendered.contains("-serde = \"1.0.1\"\n+serde = \"1.0.2\""));
+        assert!(!rendered.contains("version = \"1.0.2\""));
+
+        let settings = DiffSettings { include_lockfiles: true, ..DiffSettings::default() };
+        assert!(render_diff(&repo, &diff, &settings, false).unwrap().contains("version = \"1.0.2\""));
+    }
+
+    #[test]
+    fn test_render_diff_line_markers() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = Repository::init(temp_dir.path()).unwrap();
+        let signature = git2::Signature::now("Test User", "test@example.com").unwrap();
+        let stage = |content: &str| {
+            fs::write(temp_dir.path().join("notes.txt"), content).unwrap();
+            let mut index = repo.index().unwrap();
+            index.add_path(Path::new("notes.txt")).unwrap();
+            index.write().unwrap();
+            index.write_tree().unwrap()
+        };
+
+        let tree = repo.find_tree(stage("keep\nold\n")).unwrap();
+        repo.commit(Some("HEAD"), &signature, &signature, "init", &tree, &[]).unwrap();
+        stage("keep\nnew\n");
+
+        let diff = staged_analysis_diff(&repo).unwrap();
+        let rendered = render_diff(&repo, &diff, &DiffSettings::default(), false).unwrap();
+        assert!(rendered.contains("@@ -1,2 +1,2 @@"));
+        assert!(rendered.contains(" keep\n-old\n+new\n"));
+
+        // 단어 단위 diff는 줄 표시 없이 변경 부분만 표시
+        let settings = DiffSettings { word_diff: true, ..DiffSettings::default() };
+        let rendered = render_diff(&repo, &diff, &settings, false).unwrap();
+        assert!(rendered.contains("\nkeep\n[-old-]{+new+}\n"));
+    }
+
+    #[test]
+    fn test_tokenize_words() {
+        assert_eq!(tokenize_words("a  bc d"), vec!["a", "  ", "bc", " ", "d"]);
+        assert!(tokenize_words("").is_empty());
+    }
 }
\ No newline at end of file
@@ -0,0 +1,253 @@
+use std::io::{self, IsTerminal};
+use std::path::Path;
+
+use crate::language;
+
+/// diff 색칠 모듈
+/// 추가/삭제 줄을 배경색으로 구분하고, 파일 헤더(`+++ b/경로`)로 언어를 판별해 키워드·문자열·주석·숫자를 강조
+/// (bat/delta와 비슷한 모양을 외부 크레이트 없이 ANSI 색으로 그림)
+/// 출력이 터미널이 아니거나(파이프, 리다이렉트) `NO_COLOR`가 설정되면 색 없이 그대로 출력
+const KEYWORD: &str = "\x1b[35m";
+const STRING: &str = "\x1b[33m";
+const COMMENT: &str = "\x1b[90m";
+const NUMBER: &str = "\x1b[36m";
+/// 글자색만 되돌림 (줄 배경색은 유지)
+const RESET_FOREGROUND: &str = "\x1b[39m";
+const RESET: &str = "\x1b[0m";
+const ADDED_BACKGROUND: &str = "\x1b[48;5;22m";
+const REMOVED_BACKGROUND: &str = "\x1b[48;5;52m";
+
+/// 언어별 강조 규칙
+struct Syntax {
+    keywords: &'static [&'static str],
+    line_comments: &'static [&'static str],
+    quotes: &'static [char],
+}
+
+const RUST_KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern", "false", "fn", "for",
+    "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref", "return", "self", "Self", "static",
+    "struct", "super", "trait", "true", "type", "unsafe", "use", "where", "while",
+];
+const PYTHON_KEYWORDS: &[&str] = &[
+    "and", "as", "assert", "async", "await", "break", "class", "continue", "def", "del", "elif", "else", "except", "False",
+    "finally", "for", "from", "global", "if", "import", "in", "is", "lambda", "None", "nonlocal", "not", "or", "pass",
+    "raise", "return", "self", "True", "try", "while", "with", "yield",
+];
+const JAVASCRIPT_KEYWORDS: &[&str] = &[
+    "async", "await", "break", "case", "catch", "class", "const", "continue", "default", "delete", "do", "else", "enum",
+    "export", "extends", "false", "finally", "for", "from", "function", "if", "implements", "import", "in", "instanceof",
+    "interface", "let", "new", "null", "return", "static", "super", "switch", "this", "throw", "true", "try", "type",
+    "typeof", "undefined", "var", "void", "while", "yield",
+];
+const GO_KEYWORDS: &[&str] = &[
+    "break", "case", "chan", "const", "continue", "default", "defer", "else", "fallthrough", "false", "for", "func", "go",
+    "goto", "if", "import", "interface", "map", "nil", "package", "range", "return", "select", "struct", "switch", "true",
+    "type", "var",
+];
+/// C 계열 (C, C++, C#, Java, Kotlin, Swift, Scala, Dart, PHP)
+const C_FAMILY_KEYWORDS: &[&str] = &[
+    "auto", "bool", "break", "case", "catch", "char", "class", "const", "continue", "default", "delete", "do", "double",
+    "else", "enum", "extends", "extern", "false", "final", "float", "for", "fun", "func", "function", "if", "import",
+    "int", "interface", "let", "long", "namespace", "new", "null", "nullptr", "object", "override", "package", "private",
+    "protected", "public", "return", "short", "static", "struct", "switch", "template", "this", "throw", "true", "try",
+    "typedef", "union", "unsigned", "using", "val", "var", "virtual", "void", "volatile", "when", "while",
+];
+const RUBY_KEYWORDS: &[&str] = &[
+    "and", "begin", "class", "def", "do", "else", "elsif", "end", "ensure", "false", "if", "in", "module", "next", "nil",
+    "not", "or", "require", "rescue", "return", "self", "super", "then", "true", "unless", "until", "when", "while", "yield",
+];
+const SHELL_KEYWORDS: &[&str] = &[
+    "case", "do", "done", "elif", "else", "esac", "export", "fi", "for", "function", "if", "in", "local", "return", "then",
+    "until", "while",
+];
+const SQL_KEYWORDS: &[&str] = &[
+    "ALTER", "AND", "AS", "BY", "CREATE", "DELETE", "DROP", "FROM", "GROUP", "INDEX", "INSERT", "INTO", "JOIN", "KEY",
+    "LEFT", "LIMIT", "NOT", "NULL", "ON", "OR", "ORDER", "PRIMARY", "SELECT", "SET", "TABLE", "UPDATE", "VALUES", "WHERE",
+];
+const DATA_KEYWORDS: &[&str] = &["true", "false", "null"];
+
+/// 언어 이름(`language::detect_language` 결과)에 맞는 강조 규칙
+fn syntax_for(language: &str) -> Option<Syntax> {
+    let (keywords, line_comments, quotes): (&'static [&'static str], &'static [&'static str], &'static [char]) = match language {
+        "Rust" => (RUST_KEYWORDS, &["//"], &['"']),
+        "Python" => (PYTHON_KEYWORDS, &["#"], &['"', '\'']),
+        "Go" => (GO_KEYWORDS, &["//"], &['"', '`']),
+        "Ruby" => (RUBY_KEYWORDS, &["#"], &['"', '\'']),
+        "Shell" | "Fish" => (SHELL_KEYWORDS, &["#"], &['"', '\'']),
+        "SQL" => (SQL_KEYWORDS, &["--"], &['\'']),
+        "TOML" | "YAML" => (DATA_KEYWORDS, &["#"], &['"', '\'']),
+        "JSON" => (DATA_KEYWORDS, &[], &['"']),
+        "C" | "C++" | "C#" | "Java" | "Kotlin" | "Swift" | "Scala" | "Dart" | "PHP" => (C_FAMILY_KEYWORDS, &["//"], &['"', '\'']),
+        language if language.starts_with("JavaScript") || language.starts_with("TypeScript") => {
+            (JAVASCRIPT_KEYWORDS, &["//"], &['"', '\'', '`'])
+        }
+        _ => return None,
+    };
+    Some(Syntax { keywords, line_comments, quotes })
+}
+
+/// 색을 쓸지 (표준 출력이 터미널이고 `NO_COLOR`가 없고 `TERM`이 dumb가 아닌 경우)
+pub fn color_enabled() -> bool {
+    io::stdout().is_terminal()
+        && std::env::var_os("NO_COLOR").is_none()
+        && std::env::var("TERM").map(|term| term != "dumb").unwrap_or(true)
+}
+
+/// diff를 출력용으로 변환 (색을 쓸 수 없으면 그대로)
+pub fn render_diff(diff: &str) -> String {
+    if !color_enabled() {
+        return diff.to_string();
+    }
+    let mut highlighter = DiffHighlighter::default();
+    diff.lines()
+        .map(|line| {
+            highlighter.track(line);
+            highlighter.paint(line)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// diff를 줄 단위로 색칠 (파일 헤더를 지날 때마다 언어를 바꿈)
+#[derive(Default)]
+pub struct DiffHighlighter {
+    syntax: Option<Syntax>,
+}
+
+impl DiffHighlighter {
+    /// 원래 diff 줄을 보고 현재 파일의 언어 갱신 (화면에 보이지 않는 줄도 순서대로 넘겨야 함)
+    pub fn track(&mut self, line: &str) {
+        if let Some(path) = line.strip_prefix("+++ ") {
+            let path = path.strip_prefix("b/").unwrap_or(path);
+            self.syntax = language::detect_language(Path::new(path), None).and_then(syntax_for);
+        } else if line.starts_with("diff --git ") {
+            self.syntax = None;
+        }
+    }
+
+    /// 줄 색칠 (화면 너비에 맞게 자른 줄을 넘겨도 됨)
+    pub fn paint(&self, line: &str) -> String {
+        if line.starts_with("diff ") || line.starts_with("index ") || line.starts_with("+++") || line.starts_with("---") {
+            return format!("\x1b[1m{}{}", line, RESET);
+        }
+        if let Some(hunk) = line.strip_prefix("@@") {
+            // `@@ -1,2 +1,3 @@ fn name()`의 범위만 색칠
+            return match hunk.find("@@") {
+                Some(end) => format!("\x1b[36m@@{}@@{}{}", &hunk[..end], RESET, &hunk[end + 2..]),
+                None => format!("\x1b[36m{}{}", line, RESET),
+            };
+        }
+        let (background, code) = match line.chars().next() {
+            Some('+') => (ADDED_BACKGROUND, &line[1..]),
+            Some('-') => (REMOVED_BACKGROUND, &line[1..]),
+            Some(' ') => ("", &line[1..]),
+            _ => return line.to_string(),
+        };
+        let code = match &self.syntax {
+            Some(syntax) => paint_code(code, syntax),
+            None => code.to_string(),
+        };
+        // 배경색이 있으면 줄 끝까지 채움
+        let fill = if background.is_empty() { "" } else { "\x1b[K" };
+        format!("{}{}{}{}{}", background, &line[..1], code, fill, RESET)
+    }
+}
+
+/// 마크다운 코드 블록의 한 줄 색칠 (언어는 펜스의 정보 문자열 ```` ```rust ````나 확장자 ```` ```py ````, 모르는 언어면 그대로)
+pub fn paint_code_block_line(line: &str, info: &str) -> String {
+    let info = info.split_whitespace().next().unwrap_or("").to_lowercase();
+    let language = match info.as_str() {
+        "rust" => Some("Rust"),
+        "python" => Some("Python"),
+        "go" | "golang" => Some("Go"),
+        "ruby" => Some("Ruby"),
+        "sh" | "bash" | "zsh" | "shell" | "console" => Some("Shell"),
+        "sql" => Some("SQL"),
+        "json" => Some("JSON"),
+        "javascript" => Some("JavaScript"),
+        "typescript" => Some("TypeScript"),
+        "" => None,
+        extension => language::detect_language(Path::new(&format!("file.{}", extension)), None),
+    };
+    match language.and_then(syntax_for) {
+        Some(syntax) => paint_code(line, &syntax),
+        None => line.to_string(),
+    }
+}
+
+/// 한 줄의 코드를 토큰 단위로 색칠 (여러 줄에 걸친 문자열/주석은 줄마다 따로 봄)
+fn paint_code(code: &str, syntax: &Syntax) -> String {
+    let mut out = String::with_capacity(code.len() * 2);
+    let mut rest = code;
+    let is_word = |c: char| c.is_alphanumeric() || c == '_';
+
+    while let Some(c) = rest.chars().next() {
+        if syntax.line_comments.iter().any(|marker| rest.starts_with(marker)) {
+            out.push_str(&format!("{}{}{}", COMMENT, rest, RESET_FOREGROUND));
+            break;
+        }
+        let end = if syntax.quotes.contains(&c) {
+            // 닫는 따옴표까지 (역슬래시 이스케이프 건너뜀, 닫히지 않으면 줄 끝까지)
+            let mut escaped = false;
+            let close = rest.char_indices().skip(1).find(|&(_, ch)| {
+                let closes = ch == c && !escaped;
+                escaped = ch == '\\' && !escaped;
+                closes
+            });
+            let end = close.map(|(index, ch)| index + ch.len_utf8()).unwrap_or(rest.len());
+            out.push_str(&format!("{}{}{}", STRING, &rest[..end], RESET_FOREGROUND));
+            end
+        } else if is_word(c) {
+            let end = rest.find(|ch: char| !(is_word(ch) || (c.is_ascii_digit() && ch == '.'))).unwrap_or(rest.len());
+            let word = &rest[..end];
+            if c.is_ascii_digit() {
+                out.push_str(&format!("{}{}{}", NUMBER, word, RESET_FOREGROUND));
+            } else if syntax.keywords.contains(&word) {
+                out.push_str(&format!("{}{}{}", KEYWORD, word, RESET_FOREGROUND));
+            } else {
+                out.push_str(word);
+            }
+            end
+        } else {
+            out.push(c);
+            c.len_utf8()
+        };
+        rest = &rest[end..];
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strip_ansi(text: &str) -> String {
+        regex::Regex::new(r"\x1b\[[0-9;]*[A-Za-z]").unwrap().replace_all(text, "").into_owned()
+    }
+
+    #[test]
+    fn test_diff_highlighter() {
+        let diff = "diff --git a/src/lib.rs b/src/lib.rs\n--- a/src/lib.rs\n+++ b/src/lib.rs\n@@ -1,2 +1,2 @@ mod a\n-fn old() {}\n+fn new() -> u8 { 42 } // \"answer\"\n let s = \"a \\\" fn\";\n";
+        let mut highlighter = DiffHighlighter::default();
+        let lines: Vec<String> = diff
+            .lines()
+            .map(|line| {
+                highlighter.track(line);
+                highlighter.paint(line)
+            })
+            .collect();
+
+        assert_eq!(lines.iter().map(|line| strip_ansi(line)).collect::<Vec<_>>().join("\n") + "\n", diff);
+        assert_eq!(lines[3], "\x1b[36m@@ -1,2 +1,2 @@\x1b[0m mod a");
+        assert!(lines[4].starts_with(&format!("{}-{}fn{}", REMOVED_BACKGROUND, KEYWORD, RESET_FOREGROUND)));
+        assert!(lines[5].contains(&format!("{}42{}", NUMBER, RESET_FOREGROUND)));
+        assert!(lines[5].contains(&format!("{}// \"answer\"{}", COMMENT, RESET_FOREGROUND)));
+        // 문자열 안의 이스케이프된 따옴표와 키워드는 문자열로 처리
+        assert!(lines[6].contains(&format!("{}\"a \\\" fn\"{}", STRING, RESET_FOREGROUND)));
+
+        // 모르는 언어는 추가/삭제 색만
+        highlighter.track("+++ b/notes.unknown");
+        assert_eq!(highlighter.paint("+fn x"), format!("{}+fn x\x1b[K{}", ADDED_BACKGROUND, RESET));
+    }
+}
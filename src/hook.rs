@@ -0,0 +1,434 @@
+use anyhow::{Result, anyhow};
+use regex::Regex;
+use std::fmt;
+use std::fs;
+use std::io::{self, BufRead, IsTerminal};
+use std::sync::OnceLock;
+
+use crate::ai_utils::{self, ChatMessage};
+use crate::config::{self, CommitRules, CommitStyle};
+use crate::git_utils::{self, DiffSettings};
+use crate::highlight;
+use crate::injection;
+use crate::issues;
+use crate::lint;
+use crate::output;
+use crate::redaction;
+
+/// Git 훅 모듈
+/// `.git/hooks`의 훅 스크립트에서 부르는 모드 (`exec ai-cli hook commit-msg "$1"`, `git commit --no-verify`로 건너뛸 수 있음)
+/// - `commit-msg`: AI가 만든 메시지와 직접 쓴 메시지 모두 `[commit]` 규칙으로 검사하고, 어기면 모델 없이 고친 메시지를 diff로 보여 주며 커밋을 막음
+/// - `pre-push`: 푸시할 커밋(훅 표준 입력의 범위)에서 비밀 정보, 충돌 표시, 디버그 코드, 커밋 규칙 위반을 찾고 (`--model`이면 모델 검토도),
+///   기준(`--fail-on`, `AI_CLI_PRE_PUSH_FAIL_ON`, 기본 error) 이상인 문제가 있으면 푸시를 막음 (`AI_CLI_SKIP_PRE_PUSH=1`이면 알리고 푸시)
+///
+/// git이 만든 메시지 (병합, 되돌리기, fixup/squash)는 검사하지 않음
+const GENERATED_PREFIXES: [&str; 5] = ["Merge ", "Revert \"", "fixup! ", "squash! ", "amend! "];
+
+/// 푸시를 막지 않고 통과시키는 환경 변수
+pub const SKIP_PRE_PUSH_ENV: &str = "AI_CLI_SKIP_PRE_PUSH";
+
+/// 푸시를 막는 심각도 기준 환경 변수 (`--fail-on`이 우선)
+pub const PRE_PUSH_FAIL_ON_ENV: &str = "AI_CLI_PRE_PUSH_FAIL_ON";
+
+/// pre-push에서 검사할 최대 커밋 수 (새 리포지토리를 처음 푸시하는 경우 등)
+const MAX_PUSH_COMMITS: usize = 200;
+
+/// 모델 검토에 보낼 diff의 최대 길이 (바이트)
+const MAX_REVIEW_DIFF_BYTES: usize = 40_000;
+
+/// 푸시하지 않는 쪽의 커밋 ID (브랜치 삭제, 새 브랜치)
+const ZERO_SHA: &str = "0000000000000000000000000000000000000000";
+
+/// 남으면 안 되는 디버그 코드 (설명, 정규식)
+const DEBUG_PATTERNS: &[(&str, &str)] = &[
+    ("dbg!", r"\bdbg!\("),
+    ("console.log", r"\bconsole\.(?:log|debug)\("),
+    ("debugger", r"^\s*debugger\s*;?\s*$"),
+    ("breakpoint", r"\bpdb\.set_trace\(|^\s*breakpoint\(\)"),
+    ("binding.pry", r"\bbinding\.pry\b|^\s*byebug\b"),
+    ("var_dump", r"\bvar_dump\("),
+];
+
+/// 검사 결과의 심각도
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+/// `--fail-on` 값
+pub const SEVERITIES: [&str; 3] = ["info", "warning", "error"];
+
+impl Severity {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "info" => Some(Self::Info),
+            "warning" => Some(Self::Warning),
+            "error" => Some(Self::Error),
+            _ => None,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Info => "info",
+            Self::Warning => "warning",
+            Self::Error => "error",
+        }
+    }
+
+    fn icon(&self) -> &'static str {
+        match self {
+            Self::Info => "ℹ",
+            Self::Warning => "⚠️ ",
+            Self::Error => "❌",
+        }
+    }
+}
+
+/// pre-push 검사 결과
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Finding {
+    severity: Severity,
+    /// 짧은 커밋 ID (모델 검토는 `review`)
+    commit: String,
+    /// `파일:줄` 또는 `message`
+    location: String,
+    message: String,
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} [{}] {} {}: {}", self.severity.icon(), self.severity.as_str(), self.commit, self.location, self.message)
+    }
+}
+
+/// commit-msg 훅: 메시지 파일 검사 (규칙을 지키면 `true`)
+pub fn commit_msg(path: &str) -> Result<bool> {
+    let text = fs::read_to_string(path).map_err(|e| anyhow!("Failed to read {}: {}", path, e))?;
+    let message = lint::strip_comments(&text);
+    if GENERATED_PREFIXES.iter().any(|prefix| message.starts_with(prefix)) {
+        output::record(serde_json::json!({ "valid": true, "skipped": true }));
+        return Ok(true);
+    }
+
+    let rules = config::commit_rules();
+    let types = ai_utils::allowed_commit_types(&rules);
+    let violations = lint::lint_message(&message, &rules, &types);
+    let branch = git_utils::get_current_branch().ok();
+    let suggestion = (!violations.is_empty())
+        .then(|| suggest_fix(&message, &rules, &types, branch.as_deref()))
+        .filter(|suggestion| *suggestion != message);
+    output::record(serde_json::json!({
+        "valid": violations.is_empty(),
+        "violations": violations.iter().map(|violation| serde_json::json!({ "rule": violation.rule, "message": violation.message })).collect::<Vec<_>>(),
+        "suggestion": suggestion,
+    }));
+    if violations.is_empty() {
+        return Ok(true);
+    }
+
+    println!("❌ Commit message breaks {} rule(s):\n{}", violations.len(), lint::format_violations(&violations));
+    if let Some(suggestion) = &suggestion {
+        let remaining: Vec<&str> = lint::lint_message(suggestion, &rules, &types).iter().map(|violation| violation.rule).collect();
+        if remaining.is_empty() {
+            println!("\n💡 Suggested fix:");
+        } else {
+            println!("\n💡 Suggested fix (still needs {}):", remaining.join(", "));
+        }
+        println!("{}", highlight::render_diff(&line_diff(&message, suggestion).join("\n")));
+    }
+    println!("\nEdit and retry with: git commit -e -F {}  (or skip the check with --no-verify)", path);
+    Ok(false)
+}
+
+/// 규칙에 맞게 고친 메시지 (허용하지 않는 타입, 형식, 제목과 본문 사이 빈 줄, 브랜치 이름의 티켓 번호)
+/// scope나 본문처럼 내용을 알아야 고칠 수 있는 규칙은 그대로 둠
+fn suggest_fix(message: &str, rules: &CommitRules, types: &[String], branch: Option<&str>) -> String {
+    let (subject, body) = match message.split_once('\n') {
+        Some((subject, body)) => (subject.trim_end(), body.trim()),
+        None => (message.trim_end(), ""),
+    };
+    let mut subject = subject.to_string();
+    let mut body = body.to_string();
+
+    // 허용하지 않는 타입은 대소문자만 다르면 허용하는 타입으로, 아니면 설명으로 추측한 타입으로 (추측한 타입도 허용하지 않으면 첫 타입)
+    if matches!(rules.style(), CommitStyle::Conventional) {
+        if let Some(header) = lint::header_regex().captures(&subject) {
+            let commit_type = &header["type"];
+            if !types.iter().any(|allowed| allowed == commit_type) {
+                let guessed = ai_utils::guess_commit_type(&header["description"]);
+                let replacement = types
+                    .iter()
+                    .find(|allowed| allowed.eq_ignore_ascii_case(commit_type))
+                    .or_else(|| types.iter().find(|allowed| *allowed == guessed))
+                    .or(types.first())
+                    .map_or(guessed, String::as_str);
+                subject = format!("{}{}", replacement, &subject[commit_type.len()..]);
+            }
+        }
+    }
+
+    // 티켓 번호가 없으면 브랜치 이름의 티켓을 트레일러로
+    if let Some(ticket) = rules.ticket_pattern.as_deref().and_then(|pattern| Regex::new(pattern).ok()) {
+        let found = branch.and_then(issues::ticket_from_branch).filter(|found| ticket.is_match(&found.key));
+        if let (false, Some(found)) = (ticket.is_match(message), found) {
+            body = if body.is_empty() { format!("Refs: {}", found.key) } else { format!("{}\n\nRefs: {}", body, found.key) };
+        }
+    }
+
+    let fixed = if body.is_empty() { subject } else { format!("{}\n\n{}", subject, body) };
+    ai_utils::refine_commit_message(&fixed, rules)
+}
+
+/// 두 메시지의 줄 단위 diff (` `, `-`, `+`로 시작하는 줄)
+fn line_diff(old: &str, new: &str) -> Vec<String> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+    // common[i][j]: old[i..]와 new[j..]의 가장 긴 공통 줄 수
+    let mut common = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            common[i][j] = if old[i] == new[j] { common[i + 1][j + 1] + 1 } else { common[i + 1][j].max(common[i][j + 1]) };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    let mut lines = Vec::new();
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            lines.push(format!(" {}", old[i]));
+            i += 1;
+            j += 1;
+        } else if i < old.len() && (j == new.len() || common[i + 1][j] >= common[i][j + 1]) {
+            lines.push(format!("-{}", old[i]));
+            i += 1;
+        } else {
+            lines.push(format!("+{}", new[j]));
+            j += 1;
+        }
+    }
+    lines
+}
+
+/// pre-push 훅: 푸시할 커밋 검사 (푸시해도 되면 `true`)
+/// 표준 입력은 git이 주는 `<로컬 ref> <로컬 sha> <리모트 ref> <리모트 sha>` 줄 (터미널에서 직접 실행하면 현재 HEAD)
+pub async fn pre_push(remote: Option<&str>, fail_on: Option<&str>, model: Option<&str>) -> Result<bool> {
+    let remote = remote.unwrap_or("origin");
+    let fail_on = match fail_on.map(str::to_string).or_else(|| std::env::var(PRE_PUSH_FAIL_ON_ENV).ok()) {
+        Some(value) => Severity::parse(&value)
+            .ok_or_else(|| anyhow!("{} must be one of {} (got '{}')", PRE_PUSH_FAIL_ON_ENV, SEVERITIES.join(", "), value))?,
+        None => Severity::Error,
+    };
+
+    let updates: Vec<(String, Option<String>)> = if io::stdin().is_terminal() {
+        vec![(git_utils::open_repository()?.head()?.peel_to_commit()?.id().to_string(), None)]
+    } else {
+        io::stdin().lock().lines().map_while(Result::ok).filter_map(|line| parse_push_line(&line)).collect()
+    };
+
+    let mut commits = Vec::new();
+    for (local, remote_sha) in &updates {
+        for commit in git_utils::get_push_commits(local, remote_sha.as_deref(), remote, MAX_PUSH_COMMITS)? {
+            if !commits.contains(&commit) {
+                commits.push(commit);
+            }
+        }
+    }
+    if commits.is_empty() {
+        output::record(serde_json::json!({ "commits": 0, "findings": [], "blocked": false }));
+        return Ok(true);
+    }
+    println!("🔍 Reviewing {} commit(s) before pushing to {}...", commits.len(), remote);
+
+    let rules = config::commit_rules();
+    let types = ai_utils::allowed_commit_types(&rules);
+    let scan_settings = DiffSettings { context_lines: 0, include_sensitive: true, ..DiffSettings::default() };
+    let mut findings = Vec::new();
+    let mut review_diff = String::new();
+    for (id, message) in &commits {
+        let short = id[..7.min(id.len())].to_string();
+        let message = lint::strip_comments(message);
+        if !GENERATED_PREFIXES.iter().any(|prefix| message.starts_with(prefix)) {
+            findings.extend(lint::lint_message(&message, &rules, &types).into_iter().map(|violation| Finding {
+                severity: Severity::Warning,
+                commit: short.clone(),
+                location: "message".to_string(),
+                message: violation.to_string(),
+            }));
+        }
+        findings.extend(scan_diff(&short, &git_utils::get_commit_diff_with(id, &scan_settings)?));
+        if model.is_some() && review_diff.len() < MAX_REVIEW_DIFF_BYTES {
+            review_diff.push_str(&format!("commit {}\n{}\n\n", short, git_utils::get_commit_diff_with(id, &DiffSettings::default())?));
+        }
+    }
+
+    if let Some(model) = model {
+        match review_with_model(model, &review_diff).await {
+            Ok(reviewed) => findings.extend(reviewed),
+            // 모델을 쓸 수 없어도 규칙 검사 결과로 판단
+            Err(e) => println!("⚠️  Model review skipped: {}", e),
+        }
+    }
+
+    let blocking = findings.iter().filter(|finding| finding.severity >= fail_on).count();
+    let skipped = std::env::var(SKIP_PRE_PUSH_ENV).is_ok_and(|value| !value.is_empty() && value != "0");
+    output::record(serde_json::json!({
+        "commits": commits.len(),
+        "findings": findings.iter().map(|finding| serde_json::json!({
+            "severity": finding.severity.as_str(),
+            "commit": finding.commit,
+            "location": finding.location,
+            "message": finding.message,
+        })).collect::<Vec<_>>(),
+        "fail_on": fail_on.as_str(),
+        "blocked": blocking > 0 && !skipped,
+    }));
+    for finding in &findings {
+        println!("{}", finding);
+    }
+
+    if blocking == 0 {
+        println!("✅ No findings at or above '{}' ({} in total); pushing", fail_on.as_str(), findings.len());
+        Ok(true)
+    } else if skipped {
+        println!("⚠️  {} finding(s) at or above '{}', pushing anyway because {} is set", blocking, fail_on.as_str(), SKIP_PRE_PUSH_ENV);
+        Ok(true)
+    } else {
+        println!(
+            "🚫 Push blocked: {} finding(s) at or above '{}'. Fix them, or push anyway with {}=1 git push",
+            blocking,
+            fail_on.as_str(),
+            SKIP_PRE_PUSH_ENV
+        );
+        Ok(false)
+    }
+}
+
+/// 훅 표준 입력 한 줄 → (로컬 sha, 리모트 sha), 브랜치 삭제는 `None`
+fn parse_push_line(line: &str) -> Option<(String, Option<String>)> {
+    let fields: Vec<&str> = line.split_whitespace().collect();
+    let [_, local, _, remote] = fields.as_slice() else {
+        return None;
+    };
+    if *local == ZERO_SHA {
+        return None;
+    }
+    Some((local.to_string(), (*remote != ZERO_SHA).then(|| remote.to_string())))
+}
+
+/// 추가한 줄에서 비밀 정보, 충돌 표시, 디버그 코드 찾기
+fn scan_diff(commit: &str, diff: &str) -> Vec<Finding> {
+    static DEBUG: OnceLock<Vec<(&str, Regex)>> = OnceLock::new();
+    let debug = DEBUG.get_or_init(|| {
+        DEBUG_PATTERNS.iter().map(|(name, pattern)| (*name, Regex::new(pattern).expect("invalid debug pattern"))).collect()
+    });
+
+    let mut findings = Vec::new();
+    let mut file = String::new();
+    let mut line_number = 0usize;
+    for line in diff.lines() {
+        if let Some(path) = line.strip_prefix("+++ ") {
+            file = path.strip_prefix("b/").unwrap_or(path).to_string();
+            continue;
+        }
+        if let Some(hunk) = line.strip_prefix("@@ ") {
+            // `@@ -a,b +c,d @@`의 c
+            line_number = hunk
+                .split_whitespace()
+                .find_map(|range| range.strip_prefix('+'))
+                .and_then(|range| range.split(',').next()?.parse().ok())
+                .unwrap_or(0);
+            continue;
+        }
+        let Some(added) = line.strip_prefix('+') else {
+            if line.starts_with(' ') {
+                line_number += 1;
+            }
+            continue;
+        };
+        let location = format!("{}:{}", file, line_number);
+        let mut found = |severity, message: String| {
+            findings.push(Finding { severity, commit: commit.to_string(), location: location.clone(), message })
+        };
+        let (_, report) = redaction::redact_secrets(added);
+        for kind in report.counts().keys() {
+            found(Severity::Error, format!("possible {} added", kind));
+        }
+        if ["<<<<<<< ", ">>>>>>> "].iter().any(|marker| added.starts_with(marker)) || added == "=======" {
+            found(Severity::Error, "merge conflict marker".to_string());
+        }
+        if let Some((name, _)) = debug.iter().find(|(_, pattern)| pattern.is_match(added)) {
+            found(Severity::Warning, format!("leftover debug statement ({})", name));
+        }
+        line_number += 1;
+    }
+    findings
+}
+
+/// 모델로 diff 검토 (`- [error|warning|info] <파일>: <문제>` 줄만 결과로)
+async fn review_with_model(model: &str, diff: &str) -> Result<Vec<Finding>> {
+    static LINE: OnceLock<Regex> = OnceLock::new();
+    let line = LINE.get_or_init(|| Regex::new(r"(?i)^\s*[-*]?\s*\[(error|warning|info)\]\s*(?:(\S+?):\s+)?(.+)$").expect("invalid review pattern"));
+
+    let backend = ai_utils::get_ai_backend(&config::resolve_model(Some(model)))?;
+    let system = format!(
+        "You review commits right before they are pushed. Report only likely leaked secrets or credentials, \
+         leftover debug code, accidental files, and commit messages or code that break the project's conventions. \
+         Write one finding per line as '- [error|warning|info] <file>: <problem>'. If there is nothing to report, reply 'LGTM'.\n\n{}",
+        injection::UNTRUSTED_CONTENT_RULES
+    );
+    let diff: String = diff.chars().take(MAX_REVIEW_DIFF_BYTES).collect();
+    let messages = [ChatMessage { role: "user".to_string(), content: injection::wrap_untrusted("DIFF", &diff) }];
+    let response = ai_utils::generate_chat(&backend, Some(&system), &messages, 500, Some(0.2)).await?;
+
+    Ok(response
+        .content
+        .lines()
+        .filter_map(|text| line.captures(text))
+        .map(|captures| Finding {
+            severity: Severity::parse(&captures[1]).unwrap_or(Severity::Warning),
+            commit: "review".to_string(),
+            location: captures.get(2).map_or_else(|| "-".to_string(), |file| file.as_str().to_string()),
+            message: captures[3].trim().to_string(),
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_suggest_fix() {
+        let types: Vec<String> = ["feat", "fix", "chore"].iter().map(|t| t.to_string()).collect();
+        let rules = CommitRules { ticket_pattern: Some("[A-Z]+-[0-9]+".to_string()), ..CommitRules::default() };
+
+        let fixed = suggest_fix("docs: add parser guide\nCovers tuples.", &rules, &types, Some("feature/PROJ-12-guide"));
+        assert_eq!(fixed, "feat: add parser guide\n\nCovers tuples.\n\nRefs: PROJ-12");
+        assert!(lint::lint_message(&fixed, &rules, &types).is_empty());
+        assert_eq!(suggest_fix("tidy up", &rules, &types, None), "chore: tidy up");
+        assert_eq!(suggest_fix("Fix(parser): handle tabs", &rules, &types, None), "fix(parser): handle tabs");
+
+        assert_eq!(
+            line_diff("docs: add guide\nCovers tuples.", "feat: add guide\n\nCovers tuples."),
+            vec!["-docs: add guide", "+feat: add guide", "+", " Covers tuples."]
+        );
+    }
+
+    #[test]
+    fn test_scan_diff() {
+        let diff = "diff --git a/src/main.rs b/src/main.rs\n--- a/src/main.rs\n+++ b/src/main.rs\n@@ -10,0 +11,3 @@\n+    dbg!(&config);\n+    let key = \"sk-abcdefghijklmnopqrstuvwxyz123456\";\n+    println!(\"done\");\n";
+        let findings = scan_diff("abc1234", diff);
+        assert_eq!(findings.len(), 2);
+        assert_eq!(findings[0].to_string(), "⚠️  [warning] abc1234 src/main.rs:11: leftover debug statement (dbg!)");
+        assert_eq!((findings[1].severity, findings[1].location.as_str()), (Severity::Error, "src/main.rs:12"));
+        assert_eq!(findings[1].message, "possible openai_key added");
+
+        let local = "1".repeat(40);
+        assert_eq!(parse_push_line(&format!("refs/heads/x {} refs/heads/x {}", local, ZERO_SHA)), Some((local.clone(), None)));
+        assert_eq!(parse_push_line(&format!("(delete) {} refs/heads/x {}", ZERO_SHA, local)), None);
+        assert!(Severity::parse("Warning") < Some(Severity::Error));
+    }
+}
@@ -0,0 +1,189 @@
+use fluent_bundle::concurrent::FluentBundle;
+use fluent_bundle::{FluentArgs, FluentResource};
+use fluent_syntax::ast::Entry;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::OnceLock;
+
+/// 출력 언어 모듈
+/// 프롬프트, 승인 요청, init/config 안내, 명령어 거부·커밋 실패 안내를 영어와 한국어로 보여 줌 (그 밖의 오류와 진단 출력은 영어)
+/// 언어는 `language` 설정(`AI_CLI_LANG`), `LC_ALL`, `LC_MESSAGES`, `LANG` 순으로 정하고 모르는 값이면 영어
+/// 메시지는 `locales/<언어>.ftl`의 Fluent 메시지이며 `tr!("id")`, 변수가 있으면 `tr!("id", name = value)`로 가져옴 (`{ $name }`을 값으로 바꿈)
+///
+/// 지원하는 언어
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Locale {
+    En,
+    Ko,
+}
+
+/// `language` 설정에 쓸 수 있는 값
+pub const LANGUAGES: [&str; 2] = ["en", "ko"];
+
+/// 언어별 Fluent 메시지 (`locales/*.ftl`)
+const EN_FTL: &str = include_str!("../locales/en.ftl");
+const KO_FTL: &str = include_str!("../locales/ko.ftl");
+
+/// 한 언어의 메시지 묶음 (변수가 없는 메시지는 미리 만들어 `&'static str`로 빌려줌)
+struct Catalog {
+    bundle: FluentBundle<FluentResource>,
+    plain: HashMap<String, String>,
+}
+
+impl Catalog {
+    fn new(source: &str, language: &str) -> Self {
+        let resource = FluentResource::try_new(source.to_string())
+            .unwrap_or_else(|(_, errors)| panic!("invalid {}.ftl: {:?}", language, errors));
+        let ids: Vec<String> = resource
+            .entries()
+            .filter_map(|entry| match entry {
+                Entry::Message(message) => Some(message.id.name.to_string()),
+                _ => None,
+            })
+            .collect();
+        let mut bundle = FluentBundle::new_concurrent(vec![language.parse().expect("invalid language id")]);
+        // 터미널에 보이지 않는 방향 격리 문자(FSI/PDI)를 넣지 않음
+        bundle.set_use_isolating(false);
+        bundle
+            .add_resource(resource)
+            .unwrap_or_else(|errors| panic!("duplicate messages in {}.ftl: {:?}", language, errors));
+
+        let mut catalog = Self { bundle, plain: HashMap::new() };
+        for id in ids {
+            if let Some(text) = catalog.format(&id, None) {
+                catalog.plain.insert(id, text);
+            }
+        }
+        catalog
+    }
+
+    /// Fluent 메시지 만들기 (없는 메시지거나 변수가 빠졌으면 `None`)
+    fn format(&self, id: &str, args: Option<&FluentArgs>) -> Option<String> {
+        let pattern = self.bundle.get_message(id)?.value()?;
+        let mut errors = Vec::new();
+        let text = self.bundle.format_pattern(pattern, args, &mut errors).into_owned();
+        errors.is_empty().then_some(text)
+    }
+}
+
+/// 언어별 메시지 묶음 (처음 쓸 때 한 번 읽음)
+fn catalog(locale: Locale) -> &'static Catalog {
+    static EN: OnceLock<Catalog> = OnceLock::new();
+    static KO: OnceLock<Catalog> = OnceLock::new();
+    match locale {
+        Locale::En => EN.get_or_init(|| Catalog::new(EN_FTL, "en")),
+        Locale::Ko => KO.get_or_init(|| Catalog::new(KO_FTL, "ko")),
+    }
+}
+
+/// `tr!` 아이디를 Fluent 아이디로 (Fluent 아이디에는 `.`을 쓸 수 없어 `-`로 씀)
+fn fluent_id(id: &str) -> String {
+    id.replace('.', "-")
+}
+
+/// 로케일 값 해석 (`ko_KR.UTF-8` → 한국어, 모르는 값은 `None`)
+fn parse(value: &str) -> Option<Locale> {
+    let value = value.trim().to_lowercase();
+    if value.starts_with("ko") {
+        Some(Locale::Ko)
+    } else if value.starts_with("en") || value == "c" || value.starts_with("c.") || value == "posix" {
+        Some(Locale::En)
+    } else {
+        None
+    }
+}
+
+/// 현재 언어 (`AI_CLI_LANG`, `LC_ALL`, `LC_MESSAGES`, `LANG` 중 처음 설정된 값)
+pub fn locale() -> Locale {
+    ["AI_CLI_LANG", "LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|value| !value.trim().is_empty())
+        .and_then(|value| parse(&value))
+        .unwrap_or(Locale::En)
+}
+
+/// 언어별 메시지 (없는 아이디면 아이디 그대로)
+fn lookup(id: &'static str, locale: Locale) -> &'static str {
+    catalog(locale).plain.get(&fluent_id(id)).map_or(id, String::as_str)
+}
+
+/// 언어별 변수를 채운 메시지 (없는 아이디면 아이디 그대로)
+fn fill(id: &str, locale: Locale, args: &[(&str, &dyn Display)]) -> String {
+    let mut fluent_args = FluentArgs::new();
+    for (name, value) in args {
+        // 숫자도 Fluent 숫자 서식 대신 `Display` 그대로 씀
+        fluent_args.set(*name, value.to_string());
+    }
+    catalog(locale).format(&fluent_id(id), Some(&fluent_args)).unwrap_or_else(|| id.to_string())
+}
+
+/// 현재 언어의 메시지
+pub fn message(id: &'static str) -> &'static str {
+    lookup(id, locale())
+}
+
+/// 변수를 채운 메시지
+pub fn format_message(id: &'static str, args: &[(&str, &dyn Display)]) -> String {
+    fill(id, locale(), args)
+}
+
+/// 현재 언어의 메시지 (`tr!("commit.success")`, `tr!("commit.analyzing", lines = 42)`)
+#[macro_export]
+macro_rules! tr {
+    ($id:literal) => {
+        $crate::i18n::message($id)
+    };
+    ($id:literal, $($name:ident = $value:expr),+ $(,)?) => {
+        $crate::i18n::format_message($id, &[$((stringify!($name), &$value as &dyn std::fmt::Display)),+])
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `.ftl` 파일의 메시지 아이디와 쓰는 변수
+    fn messages(source: &str) -> Vec<(String, Vec<String>)> {
+        let variable = regex::Regex::new(r"\{ *\$([a-z_]+) *\}").unwrap();
+        let mut found: Vec<(String, Vec<String>)> = Vec::new();
+        for line in source.lines() {
+            if let Some((id, _)) = line.split_once(" =").filter(|_| !line.starts_with([' ', '#'])) {
+                found.push((id.to_string(), Vec::new()));
+            }
+            if let Some((_, variables)) = found.last_mut() {
+                variables.extend(variable.captures_iter(line).map(|captures| captures[1].to_string()));
+                variables.sort();
+            }
+        }
+        found
+    }
+
+    #[test]
+    fn test_catalog() {
+        let en = messages(EN_FTL);
+        let ko = messages(KO_FTL);
+        assert_eq!(en.iter().map(|(id, _)| id).collect::<Vec<_>>(), ko.iter().map(|(id, _)| id).collect::<Vec<_>>());
+        for ((id, en), (_, ko)) in en.iter().zip(&ko) {
+            assert_eq!(en, ko, "variables differ in {}", id);
+        }
+        // 모든 메시지가 Fluent 문법에 맞고 아이디가 겹치지 않으며 변수 없이 만들 수 있는 메시지는 미리 만듦
+        for (locale, found) in [(Locale::En, &en), (Locale::Ko, &ko)] {
+            let plain = found.iter().filter(|(_, variables)| variables.is_empty()).count();
+            assert_eq!(catalog(locale).plain.len(), plain);
+        }
+
+        assert_eq!(parse("ko_KR.UTF-8"), Some(Locale::Ko));
+        assert_eq!(parse("en_US.UTF-8"), Some(Locale::En));
+        assert_eq!(parse("C.UTF-8"), Some(Locale::En));
+        assert_eq!(parse("fr_FR"), None);
+        assert_eq!(lookup("commit.success", Locale::Ko), "✅ 커밋했습니다!");
+        assert_eq!(lookup("missing.id", Locale::Ko), "missing.id");
+        assert_eq!(lookup("approval.no", Locale::En), "  [N]o      - Cancel execution");
+        assert_eq!(lookup("trust.prompt", Locale::En), "Do you want to trust this folder? [Y/n] ");
+        assert_eq!(fill("commit.hook_rejected", Locale::Ko, &[("hook", &"pre-commit")]), "❌ pre-commit 훅이 커밋을 거부했습니다!");
+        assert_eq!(fill("commit.analyzing", Locale::En, &[("lines", &1234)]), "📝 Analyzing 1234 lines of changes...");
+        assert_eq!(lookup("sampling.prompt", Locale::Ko), "이 요청을 허용하시겠습니까? [y/N] ");
+        assert_eq!(fill("team.ignoring", Locale::En, &[("count", &2), ("file", &".ai-cli.toml")]).lines().count(), 1);
+    }
+}
@@ -0,0 +1,492 @@
+use anyhow::{Result, anyhow};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::aiignore::AiIgnore;
+use crate::ai_utils::{self, AIBackend};
+use crate::context;
+use crate::security;
+
+/// 시맨틱 인덱스 모듈
+/// 리포지토리 파일을 줄 단위 조각으로 나눠 백엔드 임베딩 API로 벡터화하고 `.ai-cli/index.json`에 저장
+/// 질의도 같은 모델로 임베딩한 뒤 코사인 유사도로 가장 가까운 조각을 찾음
+///
+/// 조각 하나의 줄 수
+const CHUNK_LINES: usize = 40;
+
+/// 인덱싱하지 않는 파일 크기 (바이트)
+const MAX_FILE_BYTES: u64 = 256 * 1024;
+
+/// 인덱스 파일 형식 버전
+const INDEX_VERSION: u32 = 1;
+
+/// 검색 결과 조각당 기본 미리보기 줄 수
+pub const DEFAULT_SNIPPET_LINES: usize = 8;
+
+/// 인덱싱된 조각
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexedChunk {
+    pub path: String,
+    pub start_line: usize,
+    pub end_line: usize,
+    pub text: String,
+    pub embedding: Vec<f32>,
+}
+
+/// 검색 결과
+#[derive(Debug, Clone)]
+pub struct SearchHit<'a> {
+    pub score: f32,
+    pub chunk: &'a IndexedChunk,
+}
+
+/// 출력/도구 응답용 검색 결과 (조각 위치와 미리보기)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SearchMatch {
+    pub path: String,
+    pub start_line: usize,
+    pub end_line: usize,
+    pub score: f32,
+    /// 미리보기 첫 줄 번호
+    pub snippet_line: usize,
+    pub snippet: String,
+}
+
+impl SearchHit<'_> {
+    /// 질의 단어가 가장 많이 나오는 `max_lines`줄 구간을 미리보기로 만듦 (일치하는 단어가 없으면 조각의 첫 부분)
+    pub fn to_match(&self, query: &str, max_lines: usize) -> SearchMatch {
+        let keywords: Vec<String> = query
+            .split(|c: char| !c.is_alphanumeric() && c != '_')
+            .filter(|word| word.len() > 1)
+            .map(str::to_lowercase)
+            .collect();
+        let lines: Vec<&str> = self.chunk.text.lines().collect();
+        let window = max_lines.max(1).min(lines.len().max(1));
+
+        // 가장 많이 일치하는 줄을 가운데에 둠
+        let best_line = lines
+            .iter()
+            .enumerate()
+            .map(|(i, line)| {
+                let line = line.to_lowercase();
+                (i, keywords.iter().filter(|keyword| line.contains(keyword.as_str())).count())
+            })
+            .filter(|(_, score)| *score > 0)
+            .max_by(|a, b| a.1.cmp(&b.1).then(b.0.cmp(&a.0)))
+            .map(|(i, _)| i);
+        let start = best_line
+            .map(|i| i.saturating_sub(window / 2).min(lines.len().saturating_sub(window)))
+            .unwrap_or(0);
+        let end = (start + window).min(lines.len());
+
+        SearchMatch {
+            path: self.chunk.path.clone(),
+            start_line: self.chunk.start_line,
+            end_line: self.chunk.end_line,
+            score: self.score,
+            snippet_line: self.chunk.start_line + start,
+            snippet: lines[start..end].join("\n"),
+        }
+    }
+}
+
+/// 디스크에 저장되는 시맨틱 인덱스
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SemanticIndex {
+    version: u32,
+    /// 임베딩에 사용한 백엔드 (`local` | `openai`)
+    pub backend: String,
+    pub model: String,
+    /// 파일별 내용 해시 (변경된 파일만 다시 임베딩)
+    files: HashMap<String, String>,
+    chunks: Vec<IndexedChunk>,
+}
+
+/// 인덱스 갱신 결과
+#[derive(Debug, Default)]
+pub struct IndexStats {
+    pub files: usize,
+    pub embedded_files: usize,
+    pub chunks: usize,
+    pub embedded_chunks: usize,
+}
+
+impl SemanticIndex {
+    /// 인덱스 파일 경로
+    pub fn path(project_root: &Path) -> PathBuf {
+        project_root.join(".ai-cli").join("index.json")
+    }
+
+    /// 저장된 인덱스 로드 (없거나 형식이 다르면 None)
+    pub fn load(project_root: &Path) -> Result<Option<Self>> {
+        let path = Self::path(project_root);
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let index: SemanticIndex = serde_json::from_str(&fs::read_to_string(&path)?)
+            .map_err(|e| anyhow!("Failed to parse {}: {}", path.display(), e))?;
+        Ok((index.version == INDEX_VERSION).then_some(index))
+    }
+
+    /// 인덱스 저장
+    pub fn save(&self, project_root: &Path) -> Result<()> {
+        // 인덱스는 커밋하지 않음
+        let path = context::local_cache_path(project_root, "index.json")?;
+        // --watch로 갱신하는 중에도 다른 명령어가 반쯤 쓴 파일을 읽지 않도록 임시 파일에 쓴 뒤 교체
+        let temp_path = path.with_extension("json.tmp");
+        fs::write(&temp_path, serde_json::to_string(self)?)?;
+        fs::rename(temp_path, path)?;
+        Ok(())
+    }
+
+    /// 인덱스 생성 또는 갱신 (같은 모델로 만든 기존 인덱스가 있으면 변경된 파일만 임베딩)
+    pub async fn build(project_root: &Path, backend_name: &str, rebuild: bool) -> Result<(Self, IndexStats)> {
+        let backend = ai_utils::get_ai_backend(backend_name)?;
+        let model = ai_utils::embedding_model(&backend)?;
+
+        let previous = if rebuild { None } else { Self::load(project_root)? }
+            .filter(|index| index.backend == backend_name && index.model == model);
+
+        let mut stats = IndexStats::default();
+        let mut files = HashMap::new();
+        let mut chunks = Vec::new();
+        let mut pending: Vec<IndexedChunk> = Vec::new();
+
+        for relative in indexable_files(project_root)? {
+            let Ok(content) = fs::read_to_string(project_root.join(&relative)) else {
+                continue; // 바이너리 또는 읽을 수 없는 파일
+            };
+            let hash = content_hash(&content);
+            stats.files += 1;
+
+            let unchanged = previous
+                .as_ref()
+                .filter(|index| index.files.get(&relative) == Some(&hash));
+            if let Some(index) = unchanged {
+                chunks.extend(index.chunks.iter().filter(|chunk| chunk.path == relative).cloned());
+            } else {
+                stats.embedded_files += 1;
+                pending.extend(chunk_file(&relative, &content));
+            }
+            files.insert(relative, hash);
+        }
+
+        if !pending.is_empty() {
+            println!("🧮 Embedding {} chunk(s) from {} file(s) with {} ({})...", pending.len(), stats.embedded_files, backend.display_name(), model);
+            let inputs: Vec<String> = pending.iter().map(embedding_input).collect();
+            let embeddings = ai_utils::embed_texts(&inputs, &backend).await?;
+            for (chunk, embedding) in pending.iter_mut().zip(embeddings) {
+                chunk.embedding = embedding;
+            }
+        }
+
+        stats.embedded_chunks = pending.len();
+        chunks.extend(pending);
+        stats.chunks = chunks.len();
+
+        let index = SemanticIndex {
+            version: INDEX_VERSION,
+            backend: backend_name.to_string(),
+            model,
+            files,
+            chunks,
+        };
+        Ok((index, stats))
+    }
+
+    /// 질의 벡터와 가장 유사한 조각 top-k
+    pub fn search(&self, query_embedding: &[f32], top_k: usize) -> Vec<SearchHit<'_>> {
+        let mut hits: Vec<SearchHit> = self
+            .chunks
+            .iter()
+            .map(|chunk| SearchHit { score: cosine_similarity(query_embedding, &chunk.embedding), chunk })
+            .collect();
+
+        hits.sort_by(|a, b| b.score.total_cmp(&a.score));
+        hits.truncate(top_k);
+        hits
+    }
+
+    /// 질의 텍스트를 인덱스와 같은 모델로 임베딩한 뒤 검색
+    pub async fn query(&self, query: &str, top_k: usize) -> Result<Vec<SearchHit<'_>>> {
+        let backend = self.embedding_backend()?;
+        let embedding = ai_utils::embed_texts(&[query.to_string()], &backend)
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| anyhow!("No embedding returned for query"))?;
+
+        Ok(self.search(&embedding, top_k))
+    }
+
+    /// 인덱스를 만든 임베딩 백엔드 (현재 설정된 모델과 다르면 오류)
+    pub fn embedding_backend(&self) -> Result<AIBackend> {
+        let backend = ai_utils::get_ai_backend(&self.backend)?;
+        let model = ai_utils::embedding_model(&backend)?;
+        if model != self.model {
+            return Err(anyhow!(
+                "Index was built with embedding model '{}' but '{}' is configured. Run 'ai-cli index --rebuild'",
+                self.model,
+                model
+            ));
+        }
+        Ok(backend)
+    }
+
+    /// 인덱싱된 조각 수
+    pub fn len(&self) -> usize {
+        self.chunks.len()
+    }
+
+    /// 인덱싱된 조각이 없는지
+    pub fn is_empty(&self) -> bool {
+        self.chunks.is_empty()
+    }
+}
+
+/// 프로젝트의 시맨틱 인덱스에서 질의와 가까운 조각 검색
+/// 인덱스가 없으면 `ai-cli index`를 안내하는 오류, 인덱싱 후 `.aiignore`에 추가된 파일은 결과에서 제외
+pub async fn search_project(project_root: &Path, query: &str, top_k: usize, snippet_lines: usize) -> Result<Vec<SearchMatch>> {
+    let index = SemanticIndex::load(project_root)?
+        .ok_or_else(|| anyhow!("No semantic index found in {}. Run 'ai-cli index' first", project_root.display()))?;
+    let aiignore = AiIgnore::load(project_root);
+
+    let hits = index.query(query, top_k).await?;
+    Ok(hits
+        .iter()
+        .filter(|hit| !aiignore.is_ignored(Path::new(&hit.chunk.path), false))
+        .map(|hit| hit.to_match(query, snippet_lines))
+        .collect())
+}
+
+/// 인덱스를 다시 만들어야 하는지 판단하는 작업 트리 지문
+/// HEAD가 가리키는 커밋/브랜치와 인덱싱 대상 파일의 경로·크기·수정 시각으로 계산 (파일 내용은 읽지 않음)
+pub fn workspace_fingerprint(project_root: &Path) -> Result<String> {
+    let repo = git2::Repository::open(project_root)?;
+    let head = repo.head().ok();
+    let mut hasher = Sha256::new();
+    hasher.update(head.as_ref().and_then(|head| head.name()).unwrap_or("").as_bytes());
+    hasher.update(head.as_ref().and_then(|head| head.target()).map(|oid| oid.to_string()).unwrap_or_default().as_bytes());
+
+    for relative in indexable_files(project_root)? {
+        let Ok(meta) = fs::metadata(project_root.join(&relative)) else {
+            continue;
+        };
+        let modified = meta
+            .modified()
+            .ok()
+            .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
+            .map(|duration| duration.as_nanos())
+            .unwrap_or_default();
+        hasher.update(format!("{}\0{}\0{}\n", relative, meta.len(), modified).as_bytes());
+    }
+
+    Ok(hasher.finalize().iter().map(|byte| format!("{:02x}", byte)).collect())
+}
+
+/// 파일 시스템과 Git ref를 주기적으로 확인해 바뀌면 인덱스를 갱신 (Ctrl-C로 종료)
+/// 변경이 `interval` 동안 멈춘 뒤에 갱신해 저장 중간의 편집이나 체크아웃마다 임베딩하지 않음
+pub async fn watch(project_root: &Path, backend_name: &str, interval: std::time::Duration) -> Result<()> {
+    println!("👀 Watching {} for changes (Ctrl-C to stop)...", project_root.display());
+    let mut indexed = workspace_fingerprint(project_root)?;
+    let mut pending: Option<String> = None;
+
+    loop {
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => {
+                println!("\n👋 Stopped watching");
+                return Ok(());
+            }
+            _ = tokio::time::sleep(interval) => {}
+        }
+
+        let current = match workspace_fingerprint(project_root) {
+            Ok(fingerprint) => fingerprint,
+            Err(e) => {
+                tracing::warn!("Could not scan {}: {}", project_root.display(), e);
+                continue;
+            }
+        };
+
+        // 변경이 이어지는 동안은 기다림
+        if current == indexed || pending.as_ref() != Some(&current) {
+            pending = (current != indexed).then_some(current);
+            continue;
+        }
+        pending = None;
+
+        match SemanticIndex::build(project_root, backend_name, false).await {
+            Ok((index, stats)) => {
+                index.save(project_root)?;
+                indexed = current;
+                if stats.embedded_files > 0 {
+                    println!(
+                        "🔄 [{}] Re-embedded {} file(s); {} chunk(s) indexed",
+                        chrono::Local::now().format("%H:%M:%S"),
+                        stats.embedded_files,
+                        stats.chunks
+                    );
+                }
+                // README/docs 요약 캐시도 함께 갱신
+                let _ = context::ContextEngine::new().load_contexts(project_root);
+            }
+            Err(e) => println!("⚠️  Index update failed: {}", e),
+        }
+    }
+}
+
+/// 인덱싱할 파일 목록 (Git 인덱스에 있는 파일, 민감 파일·`.aiignore` 대상·큰 파일 제외)
+fn indexable_files(project_root: &Path) -> Result<Vec<String>> {
+    let repo = git2::Repository::open(project_root)
+        .map_err(|e| anyhow!("Semantic indexing requires a Git repository: {}", e))?;
+    let git_index = repo.index()?;
+    let aiignore = AiIgnore::load(project_root);
+
+    let mut files: Vec<String> = git_index
+        .iter()
+        .filter_map(|entry| String::from_utf8(entry.path).ok())
+        .filter(|path| !security::is_sensitive_file(Path::new(path)))
+        .filter(|path| !aiignore.is_ignored(Path::new(path), false))
+        .filter(|path| {
+            fs::metadata(project_root.join(path))
+                .map(|meta| meta.is_file() && meta.len() <= MAX_FILE_BYTES)
+                .unwrap_or(false)
+        })
+        .collect();
+
+    files.sort();
+    files.dedup();
+    Ok(files)
+}
+
+/// 파일을 CHUNK_LINES 줄 단위 조각으로 나눔 (빈 조각 제외)
+fn chunk_file(path: &str, content: &str) -> Vec<IndexedChunk> {
+    let lines: Vec<&str> = content.lines().collect();
+
+    lines
+        .chunks(CHUNK_LINES)
+        .enumerate()
+        .filter(|(_, window)| window.iter().any(|line| !line.trim().is_empty()))
+        .map(|(i, window)| IndexedChunk {
+            path: path.to_string(),
+            start_line: i * CHUNK_LINES + 1,
+            end_line: i * CHUNK_LINES + window.len(),
+            text: window.join("\n"),
+            embedding: Vec::new(),
+        })
+        .collect()
+}
+
+/// 임베딩 입력 (파일 경로를 함께 넣어 위치 정보도 반영)
+fn embedding_input(chunk: &IndexedChunk) -> String {
+    format!("{}\n{}", chunk.path, chunk.text)
+}
+
+fn content_hash(content: &str) -> String {
+    Sha256::digest(content.as_bytes())
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// 코사인 유사도 (길이가 다르거나 영벡터면 0)
+pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() || a.is_empty() {
+        return 0.0;
+    }
+
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a: f32 = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b: f32 = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+
+    if norm_a == 0.0 || norm_b == 0.0 {
+        0.0
+    } else {
+        dot / (norm_a * norm_b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cosine_similarity() {
+        assert!((cosine_similarity(&[1.0, 0.0], &[2.0, 0.0]) - 1.0).abs() < 1e-6);
+        assert!(cosine_similarity(&[1.0, 0.0], &[0.0, 1.0]).abs() < 1e-6);
+        assert_eq!(cosine_similarity(&[1.0], &[1.0, 2.0]), 0.0);
+        assert_eq!(cosine_similarity(&[0.0, 0.0], &[1.0, 1.0]), 0.0);
+    }
+
+    #[test]
+    fn test_chunk_file() {
+        let content: String = (1..=90).map(|n| format!("line {}\n", n)).collect();
+        let chunks = chunk_file("src/lib.rs", &content);
+
+        let ranges: Vec<_> = chunks.iter().map(|c| (c.start_line, c.end_line)).collect();
+        assert_eq!(ranges, vec![(1, 40), (41, 80), (81, 90)]);
+        assert!(chunks[2].text.starts_with("line 81"));
+    }
+
+    #[test]
+    fn test_search_ranks_by_similarity() {
+        let chunk = |path: &str, embedding: Vec<f32>| IndexedChunk {
+            path: path.to_string(),
+            start_line: 1,
+            end_line: 1,
+            text: String::new(),
+            embedding,
+        };
+        let index = SemanticIndex {
+            version: INDEX_VERSION,
+            backend: "local".to_string(),
+            model: "test".to_string(),
+            files: HashMap::new(),
+            chunks: vec![chunk("a.rs", vec![0.0, 1.0]), chunk("b.rs", vec![1.0, 0.1]), chunk("c.rs", vec![1.0, 1.0])],
+        };
+
+        let hits = index.search(&[1.0, 0.0], 2);
+        let paths: Vec<_> = hits.iter().map(|hit| hit.chunk.path.as_str()).collect();
+        assert_eq!(paths, vec!["b.rs", "c.rs"]);
+    }
+
+    #[test]
+    fn test_workspace_fingerprint() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let repo = git2::Repository::init(root).unwrap();
+        fs::write(root.join("lib.rs"), "fn a() {}\n").unwrap();
+        let mut git_index = repo.index().unwrap();
+        git_index.add_path(Path::new("lib.rs")).unwrap();
+        git_index.write().unwrap();
+
+        let before = workspace_fingerprint(root).unwrap();
+        assert_eq!(workspace_fingerprint(root).unwrap(), before);
+
+        fs::write(root.join("lib.rs"), "fn a() {}\nfn b() {}\n").unwrap();
+        assert_ne!(workspace_fingerprint(root).unwrap(), before);
+    }
+
+    #[test]
+    fn test_search_match_snippet() {
+        let content: String = (1..=40)
+            .map(|n| if n == 25 { "fn retry_request(attempts: u32) {\n".to_string() } else { format!("line {}\n", n) })
+            .collect();
+        let mut chunks = chunk_file("src/net.rs", &content);
+        chunks[0].start_line = 41;
+        let hit = SearchHit { score: 0.5, chunk: &chunks[0] };
+
+        let found = hit.to_match("where do we retry a request?", 3);
+        assert_eq!(found.snippet_line, 41 + 23);
+        assert!(found.snippet.contains("fn retry_request"));
+        assert_eq!(found.snippet.lines().count(), 3);
+
+        // 일치하는 단어가 없으면 조각의 첫 부분
+        let found = hit.to_match("database", 2);
+        assert_eq!((found.snippet_line, found.snippet.as_str()), (41, "line 1\nline 2"));
+    }
+}
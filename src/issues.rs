@@ -0,0 +1,283 @@
+use anyhow::{Result, anyhow};
+use regex::Regex;
+use serde_json::Value;
+use std::sync::OnceLock;
+
+use crate::injection;
+use crate::mcp::{ClientInfo, MCPClientBuilder, McpRegistry, ServerConfig, ServerSession, ToolManager};
+
+/// 이슈 트래커 모듈
+/// 브랜치 이름에 티켓 번호(`PROJ-123`, `issue-42`)가 있으면 설정한 MCP 서버(Jira, GitHub Issues 등)로
+/// 티켓 제목과 설명을 가져와 커밋 프롬프트에 실제 요구 사항으로 전달
+///
+/// ```sh
+/// export AI_CLI_ISSUE_MCP="github"   # ~/.ai-cli/mcp.json의 서버 이름 또는 서버 실행 명령어 (설정해야 동작)
+/// export AI_CLI_ISSUE_TOOL="get_issue"                                     # 기본값
+/// export AI_CLI_ISSUE_ARGS='{"owner": "{owner}", "repo": "{repo}", "issue_number": "{number}"}'   # 기본값
+/// ```
+/// 인자 템플릿의 `{key}`(PROJ-123 또는 #42), `{number}`, `{owner}`, `{repo}`(origin 리모트)는 실제 값으로 치환되고,
+/// 값이 `"{number}"` 하나뿐인 문자열은 숫자로 전달됨
+///
+/// `AI_CLI_JIRA_MCP`를 설정하면 커밋한 뒤 브랜치의 Jira 티켓에 커밋을 댓글로 남기고,
+/// `AI_CLI_JIRA_TRANSITION`도 설정하면 티켓을 그 상태로 옮김 (변경 도구이므로 승인 필요)
+///
+/// 기본 티켓 조회 도구 (GitHub MCP 서버)
+const DEFAULT_ISSUE_TOOL: &str = "get_issue";
+
+/// 기본 도구 인자 템플릿
+const DEFAULT_ISSUE_ARGS: &str = r#"{"owner": "{owner}", "repo": "{repo}", "issue_number": "{number}"}"#;
+
+/// 프롬프트에 넣는 티켓 내용의 최대 길이 (문자)
+const MAX_TICKET_CHARS: usize = 4000;
+
+/// 브랜치 이름에서 찾은 티켓
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TicketRef {
+    /// 표시용 키 (`PROJ-123`, `#42`)
+    pub key: String,
+    /// 티켓 번호 (`123`, `42`)
+    pub number: String,
+}
+
+/// 브랜치 이름에서 티켓 찾기
+/// Jira 키(대문자 프로젝트 키, `feature/PROJ-123-login`)를 먼저 찾고, 없으면 이슈 번호(`issue-42`, `gh-42`, `42-fix-login`)
+pub fn ticket_from_branch(branch: &str) -> Option<TicketRef> {
+    static JIRA: OnceLock<Regex> = OnceLock::new();
+    static ISSUE: OnceLock<Regex> = OnceLock::new();
+
+    let jira = JIRA.get_or_init(|| Regex::new(r"(?:^|[^A-Za-z0-9])([A-Z][A-Z0-9]+)-(\d+)(?:$|[^0-9])").expect("invalid ticket pattern"));
+    if let Some(captures) = jira.captures(branch) {
+        return Some(TicketRef {
+            key: format!("{}-{}", &captures[1], &captures[2]),
+            number: captures[2].to_string(),
+        });
+    }
+
+    let issue = ISSUE.get_or_init(|| {
+        Regex::new(r"(?i)(?:^|/)(?:issues?[-_/]?|gh-|#)?(\d+)(?:$|[-_/])").expect("invalid issue pattern")
+    });
+    issue.captures(branch).map(|captures| TicketRef {
+        key: format!("#{}", &captures[1]),
+        number: captures[1].to_string(),
+    })
+}
+
+/// 도구 인자 템플릿에 티켓과 리포지토리 값 채우기
+fn render_arguments(template: &str, ticket: &TicketRef, owner: &str, repo: &str) -> Result<Value> {
+    let template: Value = serde_json::from_str(template)
+        .map_err(|e| anyhow!("AI_CLI_ISSUE_ARGS is not valid JSON: {}", e))?;
+
+    fn render(value: Value, ticket: &TicketRef, owner: &str, repo: &str) -> Value {
+        match value {
+            Value::String(text) if text == "{number}" => ticket
+                .number
+                .parse::<u64>()
+                .map(Value::from)
+                .unwrap_or(Value::String(ticket.number.clone())),
+            Value::String(text) => Value::String(
+                text.replace("{key}", &ticket.key)
+                    .replace("{number}", &ticket.number)
+                    .replace("{owner}", owner)
+                    .replace("{repo}", repo),
+            ),
+            Value::Object(map) => Value::Object(
+                map.into_iter().map(|(key, value)| (key, render(value, ticket, owner, repo))).collect(),
+            ),
+            Value::Array(items) => Value::Array(items.into_iter().map(|item| render(item, ticket, owner, repo)).collect()),
+            other => other,
+        }
+    }
+
+    Ok(render(template, ticket, owner, repo))
+}
+
+/// 리모트 URL에서 `(owner, repo)` 추출 (`git@github.com:acme/app.git`, `https://github.com/acme/app`)
+fn owner_and_repo(url: &str) -> Option<(String, String)> {
+    let path = url.trim_end_matches('/').trim_end_matches(".git");
+    let mut parts = path.rsplit(['/', ':']);
+    let repo = parts.next()?;
+    let owner = parts.next()?;
+    (!owner.is_empty() && !repo.is_empty()).then(|| (owner.to_string(), repo.to_string()))
+}
+
+/// `AI_CLI_ISSUE_MCP` 값의 서버 설정 (레지스트리의 서버 이름이 아니면 서버 실행 명령어로 취급)
+fn issue_server_config(server: &str, registry: &McpRegistry) -> Result<ServerConfig> {
+    if let Some(config) = registry.get(server.trim()) {
+        return Ok(config.clone());
+    }
+
+    let mut words = server.split_whitespace();
+    let program = words.next().ok_or_else(|| anyhow!("AI_CLI_ISSUE_MCP is empty"))?;
+    Ok(ServerConfig {
+        command: Some(program.to_string()),
+        args: words.map(str::to_string).collect(),
+        ..ServerConfig::default()
+    })
+}
+
+/// 설정한 MCP 서버로 티켓 내용 가져오기
+async fn fetch_ticket(server: &str, ticket: &TicketRef) -> Result<String> {
+    let config = issue_server_config(server, &McpRegistry::load()?)?;
+
+    let (owner, repo) = crate::git_utils::open_repository()
+        .ok()
+        .and_then(|repo| repo.find_remote("origin").ok()?.url().and_then(owner_and_repo))
+        .unwrap_or_default();
+    let tool = std::env::var("AI_CLI_ISSUE_TOOL").unwrap_or_else(|_| DEFAULT_ISSUE_TOOL.to_string());
+    let template = std::env::var("AI_CLI_ISSUE_ARGS").unwrap_or_else(|_| DEFAULT_ISSUE_ARGS.to_string());
+    let arguments = render_arguments(&template, ticket, &owner, &repo)?;
+
+    let client_info = ClientInfo { name: "ai-cli".to_string(), version: env!("CARGO_PKG_VERSION").to_string() };
+    let session = ServerSession::connect(server.trim(), &config, client_info).await?;
+    let result = session.call_tool(&tool, Some(arguments)).await;
+    session.close().await;
+    let result = result?;
+
+    let text = result.text();
+    if result.is_error() {
+        return Err(anyhow!("'{}' failed: {}", tool, text.trim()));
+    }
+    if text.trim().is_empty() {
+        return Err(anyhow!("'{}' returned no text for {}", tool, ticket.key));
+    }
+    Ok(text)
+}
+
+/// 현재 브랜치의 티켓을 프롬프트 섹션으로 만듦
+/// `AI_CLI_ISSUE_MCP`가 없거나, 브랜치에 티켓이 없거나, 로컬 전용 모드거나, 가져오지 못하면 None
+pub async fn ticket_section(branch: &str) -> Option<String> {
+    let server = std::env::var("AI_CLI_ISSUE_MCP").ok().filter(|server| !server.trim().is_empty())?;
+    let ticket = ticket_from_branch(branch)?;
+
+    if crate::policy::Policy::load().map(|policy| policy.is_local_only()).unwrap_or(true) {
+        println!("🔒 Skipping issue lookup for {}: local-only privacy mode", ticket.key);
+        return None;
+    }
+
+    println!("🎫 Fetching {} from the issue tracker...", ticket.key);
+    match fetch_ticket(&server, &ticket).await {
+        Ok(text) => {
+            let text: String = text.trim().chars().take(MAX_TICKET_CHARS).collect();
+            Some(format!(
+                "LINKED TICKET {} (fetched from the issue tracker for branch '{}'; reference the requirement it describes):\n{}",
+                ticket.key,
+                branch,
+                injection::wrap_untrusted("TICKET", &text)
+            ))
+        }
+        Err(e) => {
+            println!("⚠️  Could not fetch {}: {}", ticket.key, e);
+            None
+        }
+    }
+}
+
+/// 커밋 댓글 (`abc1234 on feature/PROJ-1-login: Add login form`)
+fn commit_comment(branch: &str) -> Result<String> {
+    let repo = crate::git_utils::open_repository()?;
+    let commit = repo.head()?.peel_to_commit()?;
+    let id = commit.id().to_string();
+    Ok(format!("Commit {} on {}: {}", &id[..7], branch, commit.summary().unwrap_or_default()))
+}
+
+/// Jira 서버에 연결해 티켓에 커밋 댓글을 남기고 상태 변경
+async fn update_jira_ticket(server: &str, ticket: &TicketRef, comment: &str, status: Option<&str>) -> Result<()> {
+    let config = issue_server_config(server, &McpRegistry::load()?)?;
+    let client = MCPClientBuilder::new("ai-cli")
+        .version(env!("CARGO_PKG_VERSION"))
+        .server(server.trim(), config)
+        .build();
+    if let Some((_, e)) = client.connect_servers().await.into_iter().next() {
+        return Err(e);
+    }
+
+    let tools = ToolManager::new(client);
+    let result = async {
+        tools.comment_jira_issue(&ticket.key, comment).await?;
+        if let Some(status) = status {
+            tools.transition_jira_issue(&ticket.key, status).await?;
+        }
+        Ok(())
+    }
+    .await;
+    tools.shutdown().await;
+    result
+}
+
+/// 커밋한 뒤 현재 브랜치의 Jira 티켓에 커밋 연결
+/// `AI_CLI_JIRA_MCP`가 없거나, 브랜치에 Jira 키가 없거나, 로컬 전용 모드면 아무것도 하지 않음
+pub async fn link_commit(branch: &str) {
+    let Some(server) = std::env::var("AI_CLI_JIRA_MCP").ok().filter(|server| !server.trim().is_empty()) else {
+        return;
+    };
+    let Some(ticket) = ticket_from_branch(branch).filter(|ticket| !ticket.key.starts_with('#')) else {
+        return;
+    };
+
+    if crate::policy::Policy::load().map(|policy| policy.is_local_only()).unwrap_or(true) {
+        println!("🔒 Not updating {}: local-only privacy mode", ticket.key);
+        return;
+    }
+
+    let status = std::env::var("AI_CLI_JIRA_TRANSITION").ok().filter(|status| !status.trim().is_empty());
+    let result = match commit_comment(branch) {
+        Ok(comment) => update_jira_ticket(&server, &ticket, &comment, status.as_deref()).await,
+        Err(e) => Err(e),
+    };
+    match (result, status) {
+        (Ok(()), Some(status)) => println!("🎫 Linked the commit to {} and moved it to {}", ticket.key, status),
+        (Ok(()), None) => println!("🎫 Linked the commit to {}", ticket.key),
+        (Err(e), _) => println!("⚠️  Could not update {}: {}", ticket.key, e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ticket_from_branch() {
+        let ticket = ticket_from_branch("feature/PROJ-123-login-form").unwrap();
+        assert_eq!(ticket, TicketRef { key: "PROJ-123".to_string(), number: "123".to_string() });
+        assert_eq!(ticket_from_branch("ABC2-7").unwrap().key, "ABC2-7");
+
+        assert_eq!(ticket_from_branch("fix/42-null-check").unwrap().key, "#42");
+        assert_eq!(ticket_from_branch("issue-17").unwrap().number, "17");
+        assert_eq!(ticket_from_branch("gh-8/cleanup").unwrap().number, "8");
+
+        assert!(ticket_from_branch("main").is_none());
+        assert!(ticket_from_branch("release-v2").is_none());
+        assert!(ticket_from_branch("fix-login").is_none());
+    }
+
+    #[test]
+    fn test_render_arguments() {
+        let ticket = TicketRef { key: "#42".to_string(), number: "42".to_string() };
+        let arguments = render_arguments(DEFAULT_ISSUE_ARGS, &ticket, "acme", "app").unwrap();
+        assert_eq!(arguments, serde_json::json!({"owner": "acme", "repo": "app", "issue_number": 42}));
+
+        let ticket = TicketRef { key: "PROJ-9".to_string(), number: "9".to_string() };
+        let arguments = render_arguments(r#"{"issue_key": "{key}", "fields": ["summary"]}"#, &ticket, "", "").unwrap();
+        assert_eq!(arguments, serde_json::json!({"issue_key": "PROJ-9", "fields": ["summary"]}));
+
+        assert!(render_arguments("{not json", &ticket, "", "").is_err());
+    }
+
+    #[test]
+    fn test_owner_and_repo() {
+        assert_eq!(owner_and_repo("git@github.com:acme/app.git"), Some(("acme".to_string(), "app".to_string())));
+        assert_eq!(owner_and_repo("https://github.com/acme/app/"), Some(("acme".to_string(), "app".to_string())));
+        assert_eq!(owner_and_repo("app"), None);
+    }
+
+    #[test]
+    fn test_issue_server_config() {
+        let mut registry = McpRegistry::default();
+        registry.insert("github", ServerConfig { url: Some("https://mcp.example.com/mcp".to_string()), ..ServerConfig::default() });
+
+        assert_eq!(issue_server_config("github", &registry).unwrap().url.as_deref(), Some("https://mcp.example.com/mcp"));
+        let command = issue_server_config("jira-mcp --site acme", &registry).unwrap();
+        assert_eq!(command.command.as_deref(), Some("jira-mcp"));
+        assert_eq!(command.args, vec!["--site".to_string(), "acme".to_string()]);
+    }
+}
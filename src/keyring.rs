@@ -0,0 +1,31 @@
+use anyhow::{Result, anyhow};
+use ::keyring::{Entry, Error};
+
+/// OS 키체인 모듈
+/// `keyring` 크레이트로 macOS 키체인, Windows 자격 증명 관리자, Linux Secret Service에 비밀 값을 저장
+/// 비밀 값은 명령줄 인자로 넘기지 않으며, 키체인을 쓸 수 없으면 실패하므로 호출하는 쪽에서 파일로 대체
+pub fn get(service: &str, account: &str) -> Option<String> {
+    match Entry::new(service, account).and_then(|entry| entry.get_password()) {
+        Ok(secret) => Some(secret),
+        Err(Error::NoEntry) => None,
+        Err(e) => {
+            tracing::debug!("Could not read {} from the OS keychain: {}", account, e);
+            None
+        }
+    }
+}
+
+/// 비밀 값 저장 (같은 항목이 있으면 덮어씀)
+pub fn set(service: &str, account: &str, secret: &str) -> Result<()> {
+    Entry::new(service, account)
+        .and_then(|entry| entry.set_password(secret))
+        .map_err(|e| anyhow!("OS keychain unavailable: {}", e))
+}
+
+/// 비밀 값 삭제 (없으면 무시)
+pub fn delete(service: &str, account: &str) {
+    match Entry::new(service, account).and_then(|entry| entry.delete_credential()) {
+        Ok(()) | Err(Error::NoEntry) => {}
+        Err(e) => tracing::debug!("Could not delete {} from the OS keychain: {}", account, e),
+    }
+}
@@ -0,0 +1,180 @@
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// 언어 감지 모듈
+/// 변경된 파일의 확장자와 shebang으로 프로그래밍 언어를 판별하여 프롬프트에 제공
+///
+/// 파일별 언어 정보
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FileLanguage {
+    pub path: String,
+    pub language: String,
+}
+
+/// 확장자 → 언어 매핑
+const EXTENSION_LANGUAGES: &[(&str, &str)] = &[
+    ("rs", "Rust"),
+    ("py", "Python"),
+    ("pyi", "Python"),
+    ("js", "JavaScript"),
+    ("mjs", "JavaScript"),
+    ("cjs", "JavaScript"),
+    ("jsx", "JavaScript (JSX)"),
+    ("ts", "TypeScript"),
+    ("tsx", "TypeScript (TSX)"),
+    ("go", "Go"),
+    ("java", "Java"),
+    ("kt", "Kotlin"),
+    ("kts", "Kotlin"),
+    ("swift", "Swift"),
+    ("c", "C"),
+    ("h", "C"),
+    ("cc", "C++"),
+    ("cpp", "C++"),
+    ("cxx", "C++"),
+    ("hpp", "C++"),
+    ("cs", "C#"),
+    ("rb", "Ruby"),
+    ("php", "PHP"),
+    ("scala", "Scala"),
+    ("dart", "Dart"),
+    ("lua", "Lua"),
+    ("r", "R"),
+    ("sh", "Shell"),
+    ("bash", "Shell"),
+    ("zsh", "Shell"),
+    ("fish", "Fish"),
+    ("ps1", "PowerShell"),
+    ("sql", "SQL"),
+    ("html", "HTML"),
+    ("htm", "HTML"),
+    ("css", "CSS"),
+    ("scss", "SCSS"),
+    ("vue", "Vue"),
+    ("svelte", "Svelte"),
+    ("md", "Markdown"),
+    ("json", "JSON"),
+    ("yaml", "YAML"),
+    ("yml", "YAML"),
+    ("toml", "TOML"),
+    ("xml", "XML"),
+    ("proto", "Protocol Buffers"),
+    ("tf", "Terraform"),
+    ("nix", "Nix"),
+];
+
+/// 확장자 없이 파일 이름으로 판별하는 언어
+const FILENAME_LANGUAGES: &[(&str, &str)] = &[
+    ("Dockerfile", "Dockerfile"),
+    ("Makefile", "Makefile"),
+    ("CMakeLists.txt", "CMake"),
+    ("Rakefile", "Ruby"),
+    ("Gemfile", "Ruby"),
+    ("Jenkinsfile", "Groovy"),
+];
+
+/// shebang 인터프리터 → 언어 매핑
+const INTERPRETER_LANGUAGES: &[(&str, &str)] = &[
+    ("python", "Python"),
+    ("node", "JavaScript"),
+    ("deno", "TypeScript"),
+    ("ruby", "Ruby"),
+    ("perl", "Perl"),
+    ("php", "PHP"),
+    ("bash", "Shell"),
+    ("sh", "Shell"),
+    ("zsh", "Shell"),
+    ("fish", "Fish"),
+    ("lua", "Lua"),
+    ("pwsh", "PowerShell"),
+];
+
+/// 파일 경로와 첫 줄(shebang)로 언어 판별
+pub fn detect_language(path: &Path, first_line: Option<&str>) -> Option<&'static str> {
+    if let Some(file_name) = path.file_name().and_then(|name| name.to_str()) {
+        if let Some((_, language)) = FILENAME_LANGUAGES.iter().find(|(name, _)| *name == file_name) {
+            return Some(language);
+        }
+    }
+
+    if let Some(extension) = path.extension().and_then(|ext| ext.to_str()) {
+        let extension = extension.to_lowercase();
+        if let Some((_, language)) = EXTENSION_LANGUAGES.iter().find(|(ext, _)| *ext == extension) {
+            return Some(language);
+        }
+    }
+
+    first_line.and_then(detect_shebang)
+}
+
+/// shebang 라인에서 언어 판별 (예: `#!/usr/bin/env python3`)
+fn detect_shebang(line: &str) -> Option<&'static str> {
+    let command = line.strip_prefix("#!")?.trim();
+    let mut parts = command.split_whitespace();
+    let mut interpreter = parts.next()?.rsplit('/').next()?;
+
+    if interpreter == "env" {
+        interpreter = parts.find(|part| !part.starts_with('-'))?;
+    }
+
+    // python3.12 → python
+    let interpreter = interpreter.trim_end_matches(|c: char| c.is_ascii_digit() || c == '.');
+
+    INTERPRETER_LANGUAGES
+        .iter()
+        .find(|(name, _)| *name == interpreter)
+        .map(|(_, language)| *language)
+}
+
+/// 프롬프트에 넣을 언어 요약 생성
+pub fn format_language_summary(files: &[FileLanguage]) -> Option<String> {
+    if files.is_empty() {
+        return None;
+    }
+
+    let mut by_language: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    for file in files {
+        by_language.entry(&file.language).or_default().push(&file.path);
+    }
+
+    let mut summary = String::from("Changed files by language:\n");
+    for (language, paths) in by_language {
+        summary.push_str(&format!("- {}: {}\n", language, paths.join(", ")));
+    }
+    summary.push_str("Use the idioms and terminology of these languages.\n");
+
+    Some(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_language_by_extension() {
+        assert_eq!(detect_language(Path::new("src/main.rs"), None), Some("Rust"));
+        assert_eq!(detect_language(Path::new("app/Component.TSX"), None), Some("TypeScript (TSX)"));
+        assert_eq!(detect_language(Path::new("Dockerfile"), None), Some("Dockerfile"));
+        assert_eq!(detect_language(Path::new("LICENSE"), None), None);
+    }
+
+    #[test]
+    fn test_detect_language_by_shebang() {
+        assert_eq!(detect_language(Path::new("bin/run"), Some("#!/usr/bin/env python3")), Some("Python"));
+        assert_eq!(detect_language(Path::new("bin/setup"), Some("#!/bin/bash -e")), Some("Shell"));
+        assert_eq!(detect_language(Path::new("bin/tool"), Some("echo hi")), None);
+    }
+
+    #[test]
+    fn test_format_language_summary() {
+        let files = vec![
+            FileLanguage { path: "src/main.rs".to_string(), language: "Rust".to_string() },
+            FileLanguage { path: "src/cli.rs".to_string(), language: "Rust".to_string() },
+        ];
+
+        let summary = format_language_summary(&files).unwrap();
+        assert!(summary.contains("- Rust: src/main.rs, src/cli.rs"));
+        assert!(format_language_summary(&[]).is_none());
+    }
+}
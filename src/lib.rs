@@ -0,0 +1,44 @@
+#[macro_use]
+pub mod i18n;
+pub mod agent;
+pub mod aiignore;
+pub mod ascii;
+pub mod cli;
+pub mod checklist;
+pub mod ci;
+pub mod clipboard;
+pub mod git_utils;
+pub mod highlight;
+pub mod hook;
+pub mod ai_utils;
+pub mod audit;
+pub mod context;
+pub mod deps;
+pub mod editor;
+pub mod followup;
+pub mod index;
+pub mod injection;
+pub mod issues;
+pub mod language;
+pub mod notify;
+pub mod output;
+pub mod pager;
+pub mod redaction;
+pub mod security;
+pub mod spinner;
+pub mod stats;
+pub mod symbols;
+pub mod tips;
+pub mod transcript;
+pub mod policy;
+pub mod preview;
+pub mod project;
+pub mod sandbox;
+pub mod mcp;
+pub mod config;
+pub mod keyring;
+pub mod lint;
+pub mod map_reduce;
+pub mod markdown;
+pub mod logging;
+pub mod review;
@@ -0,0 +1,159 @@
+use regex::Regex;
+use std::fmt;
+use std::sync::OnceLock;
+
+use crate::config::{CommitRules, CommitStyle};
+
+/// 커밋 메시지 규칙 검사 모듈
+/// 설정의 `[commit]` 규칙(형식, 허용 타입, scope 필수, 제목 길이, 본문 필수, 티켓 번호)으로 커밋 메시지를 검사
+/// 제목 형식은 `commit.style`을 따름 (gitmoji는 이모지로 시작, plain과 custom-template은 형식 검사 안 함)
+/// `#`으로 시작하는 줄(git 커밋 편집기의 주석)은 무시
+///
+/// 규칙 위반
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    /// 위반한 규칙 (`commit.` 아래 설정 키 이름, 형식 문제는 `format`)
+    pub rule: &'static str,
+    pub message: String,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] {}", self.rule, self.message)
+    }
+}
+
+/// Conventional Commit 제목 (`type(scope)!: description`)
+pub(crate) fn header_regex() -> &'static Regex {
+    static HEADER: OnceLock<Regex> = OnceLock::new();
+    HEADER.get_or_init(|| Regex::new(r"^(?P<type>[\w-]+)(?:\((?P<scope>[^()]*)\))?!?: (?P<description>\S.*)$").expect("invalid header pattern"))
+}
+
+/// gitmoji 제목인지 (`✨ add ...` 또는 `:sparkles: add ...`)
+pub(crate) fn is_gitmoji_subject(subject: &str) -> bool {
+    static GITMOJI: OnceLock<Regex> = OnceLock::new();
+    GITMOJI
+        .get_or_init(|| Regex::new(r"^(?::[a-z0-9_+-]+:|[^\x00-\x7F\p{L}\p{N}]+)\s+\S").expect("invalid gitmoji pattern"))
+        .is_match(subject)
+}
+
+/// 주석 줄을 뺀 메시지
+pub(crate) fn strip_comments(message: &str) -> String {
+    message.lines().filter(|line| !line.starts_with('#')).collect::<Vec<_>>().join("\n").trim().to_string()
+}
+
+/// 커밋 메시지를 규칙으로 검사 (`types`는 허용하는 커밋 타입)
+pub fn lint_message(message: &str, rules: &CommitRules, types: &[String]) -> Vec<Violation> {
+    let mut violations = Vec::new();
+    let message = strip_comments(message);
+    let mut lines = message.lines();
+    let subject = lines.next().unwrap_or("").trim_end();
+
+    if subject.is_empty() {
+        violations.push(Violation { rule: "format", message: "the message is empty".to_string() });
+        return violations;
+    }
+
+    match rules.style() {
+        CommitStyle::Conventional => check_conventional_header(subject, rules, types, &mut violations),
+        CommitStyle::Gitmoji if !is_gitmoji_subject(subject) => violations.push(Violation {
+            rule: "style",
+            message: "the subject must start with a gitmoji, like '✨ add tuple support'".to_string(),
+        }),
+        CommitStyle::Gitmoji | CommitStyle::Plain | CommitStyle::CustomTemplate => {}
+    }
+
+    let length = subject.chars().count();
+    if length > rules.max_subject_length() {
+        violations.push(Violation {
+            rule: "max_subject_length",
+            message: format!("the subject is {} characters long (at most {})", length, rules.max_subject_length()),
+        });
+    }
+
+    let rest: Vec<&str> = lines.collect();
+    if rest.first().is_some_and(|line| !line.trim().is_empty()) {
+        violations.push(Violation { rule: "format", message: "separate the subject from the body with a blank line".to_string() });
+    }
+    if rules.require_body == Some(true) && rest.iter().all(|line| line.trim().is_empty()) {
+        violations.push(Violation { rule: "require_body", message: "add a body explaining what changed and why".to_string() });
+    }
+
+    if let Some(pattern) = &rules.ticket_pattern {
+        match Regex::new(pattern) {
+            Ok(ticket) if !ticket.is_match(&message) => violations.push(Violation {
+                rule: "ticket_pattern",
+                message: format!("reference a ticket matching '{}'", pattern),
+            }),
+            Ok(_) => {}
+            Err(e) => tracing::warn!("Ignoring invalid commit.ticket_pattern '{}': {}", pattern, e),
+        }
+    }
+    violations
+}
+
+/// Conventional Commit 제목 검사 (형식, 허용 타입, scope)
+fn check_conventional_header(subject: &str, rules: &CommitRules, types: &[String], violations: &mut Vec<Violation>) {
+    match header_regex().captures(subject) {
+        Some(header) => {
+            let commit_type = &header["type"];
+            if !types.iter().any(|allowed| allowed == commit_type) {
+                violations.push(Violation {
+                    rule: "types",
+                    message: format!("type '{}' is not allowed (use one of {})", commit_type, types.join(", ")),
+                });
+            }
+            let scope = header.name("scope").map(|scope| scope.as_str().trim()).unwrap_or("");
+            if rules.require_scope == Some(true) && scope.is_empty() {
+                violations.push(Violation { rule: "require_scope", message: format!("add a scope: {}(<scope>): ...", commit_type) });
+            }
+        }
+        None => violations.push(Violation {
+            rule: "format",
+            message: "the subject must look like '<type>(<scope>): <description>'".to_string(),
+        }),
+    }
+}
+
+/// 위반 목록을 출력용 문자열로
+pub fn format_violations(violations: &[Violation]) -> String {
+    violations.iter().map(|violation| format!("  - {}", violation)).collect::<Vec<_>>().join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn types() -> Vec<String> {
+        vec!["feat".to_string(), "fix".to_string()]
+    }
+
+    #[test]
+    fn test_lint_message() {
+        let rules = CommitRules::default();
+        assert!(lint_message("feat(parser): add tuple support\n\nParses (a, b).\n# comment", &rules, &types()).is_empty());
+
+        let violations = lint_message("docs: update readme\nmore text", &rules, &types());
+        let found: Vec<&str> = violations.iter().map(|violation| violation.rule).collect();
+        assert_eq!(found, vec!["types", "format"]);
+        assert_eq!(lint_message("update readme", &rules, &types())[0].rule, "format");
+
+        let strict = CommitRules {
+            require_scope: Some(true),
+            max_subject_length: Some(20),
+            require_body: Some(true),
+            ticket_pattern: Some("[A-Z]+-[0-9]+".to_string()),
+            ..CommitRules::default()
+        };
+        let found: Vec<&'static str> = lint_message("fix: handle empty input files", &strict, &types()).iter().map(|violation| violation.rule).collect();
+        assert_eq!(found, vec!["require_scope", "max_subject_length", "require_body", "ticket_pattern"]);
+        assert!(lint_message("fix(io): empty input\n\nRefs ABC-12", &strict, &types()).is_empty());
+
+        let gitmoji = CommitRules { style: Some("gitmoji".to_string()), ..CommitRules::default() };
+        assert!(lint_message("✨ add tuple support", &gitmoji, &types()).is_empty());
+        assert!(lint_message(":bug: fix empty input", &gitmoji, &types()).is_empty());
+        assert_eq!(lint_message("feat: add tuple support", &gitmoji, &types())[0].rule, "style");
+        let plain = CommitRules { style: Some("plain".to_string()), ..CommitRules::default() };
+        assert!(lint_message("Add tuple support", &plain, &types()).is_empty());
+    }
+}
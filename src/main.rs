@@ -1,158 +1,1227 @@
-use clap::{Parser, Subcommand};
-use anyhow::Result;
+use clap::Parser;
+use anyhow::{Result, anyhow};
+use std::time::Instant;
 
-mod cli;
-mod git_utils;
-mod ai_utils;
-mod context;
-mod security;
-mod mcp;
+use ai_cli::{tr, agent, ascii, cli, ci, clipboard, git_utils, highlight, hook, ai_utils, audit, context, deps, followup, index, issues, language, notify, output, pager, security, spinner, stats, symbols, tips, transcript, project, mcp, config, lint, map_reduce, markdown, logging, review};
 
 use cli::*;
 use git_utils::*;
 use ai_utils::*;
 
+/// 프롬프트에 추가할 시맨틱 검색 결과 수
+const RETRIEVAL_TOP_K: usize = 5;
+
 #[tokio::main]
 async fn main() -> Result<()> {
-    // 로깅 초기화
-    tracing_subscriber::fmt::init();
-
     let cli = Cli::parse();
 
+    // --ascii: 그림 문자를 글자로 바꾸고 색, 스피너, 전체 화면 없이 한 줄씩 출력 (--json 결과는 그대로)
+    if ascii::is_requested(cli.ascii) {
+        std::process::exit(ascii::run_as_child(cli.json)?);
+    }
+
+    // 로깅 초기화 (--log-level, --log-file)
+    logging::init(cli.log_level.as_deref(), cli.log_file);
+
+    // --json: 사람이 읽는 출력은 표준 에러로, 표준 출력에는 결과 JSON만
+    if cli.json && !output::is_json() {
+        std::process::exit(output::run_as_child()?);
+    }
+    let (command, features) = tips::features(&cli);
+    let no_tips = cli.no_tips;
+    let result = run(cli).await;
+    match &result {
+        Ok(()) => tips::after_command(command, &features, no_tips),
+        Err(e) => output::record_error(e),
+    }
+    result
+}
+
+async fn run(cli: Cli) -> Result<()> {
+    // 설정 파일과 선택한 프로필 적용 (이미 설정된 환경 변수가 우선)
+    if let Some(profile) = &cli.profile {
+        config::select_profile(profile);
+    }
+    // 이전 버전처럼 환경 변수로만 설정했다면 처음 실행할 때 설정 파일로 옮길지 물어봄
+    if config::needs_migration()
+        && !ai_utils::is_non_interactive()
+        && !matches!(cli.command, Commands::Init { .. } | Commands::Config { action: Some(_), .. })
+    {
+        if let Err(e) = config::offer_migration() {
+            println!("⚠️  Could not migrate settings: {}", e);
+        }
+    }
+    // 클론한 리포지토리의 팀 설정과 `.ai-cli.toml` 중 요청을 보낼 곳을 바꾸는 설정은 사용자가 확인한 뒤에만 적용
+    if !matches!(cli.command, Commands::Config { action: Some(_), .. }) {
+        if let Err(e) = config::team::review(!ai_utils::is_non_interactive()) {
+            tracing::warn!("{}", e);
+        }
+    }
+    if let Err(e) = config::Config::load().and_then(|settings| settings.apply_to_env()) {
+        // init과 config는 잘못된 설정을 고치거나 새 프로필을 만들 수 있어야 하므로 계속 진행
+        if !matches!(cli.command, Commands::Init { .. } | Commands::Config { .. }) {
+            return Err(e);
+        }
+        tracing::warn!("{}; using environment variables only", e);
+    }
+
+    // 프롬프트, 응답, 승인, 실행한 명령어를 세션 기록으로 남김 (transcripts = "off"면 남기지 않음)
+    transcript::start();
+    // 실행한 명령 수 (stats = "on"일 때만)
+    if let Some(name) = cli::subcommand_name(&std::env::args().collect::<Vec<_>>()) {
+        stats::command(&name);
+    }
+
+    // 로컬 전용 모드 (루프백 외 호스트로의 전송 차단)
+    if cli.local_only {
+        std::env::set_var("AI_CLI_PRIVACY", "local-only");
+    }
+    // 스피너와 진행 줄 숨기기
+    if cli.quiet {
+        std::env::set_var("AI_CLI_QUIET", "1");
+    }
+    // 긴 출력도 페이저 없이 출력
+    if cli.no_pager {
+        std::env::set_var(pager::NO_PAGER_ENV, "1");
+    }
+
     match &cli.command {
-        Commands::Commit { message, all } => {
-            println!("🤖 AI is generating your commit message...");
+        Commands::Commit { message, all, model, yes, no_verify, history, show_payload, no_review, copy, diff: diff_args, context: context_args } => {
+            // 확인 프롬프트 생략 (원격 전송은 egress.max_bytes 상한만 적용)
+            if *yes {
+                std::env::set_var("AI_CLI_ASSUME_YES", "1");
+            }
+
+            // 신뢰 폴더 확인 (신뢰하지 않는 폴더에서는 읽기 전용)
+            let mut security_manager = security::SecurityManager::default();
+            let security_level = security_manager.ensure_security_level(&std::env::current_dir()?)?;
+
+            if !*show_payload {
+                println!("{}", tr!("commit.generating"));
+            }
 
             // 모든 변경 사항 스테이징 (옵션)
-            if *all {
-                println!("📋 Staging all changes...");
+            if *all && !security_level.allows_writes() {
+                println!("{}", tr!("commit.skip_staging"));
+            } else if *all {
+                println!("{}", tr!("commit.staging"));
                 // TODO: git add -A 구현
             }
 
-            // 스테이징된 diff 읽기
-            let diff = get_staged_diff()?;
-            println!("📝 Analyzing {} lines of changes...", diff.lines().count());
+            // 스테이징된 파일의 최근 커밋 제목 (리포지토리의 커밋 스타일 참고용)
+            let history_section = format_commit_history(&get_recent_commit_subjects(*history).unwrap_or_default());
+            // 선택한 프로필의 커밋 규칙과 팀 지침
+            let conventions_section = config::commit_conventions_section();
+            let team_section = config::team::prompt_section();
+            let project_profile = project::detect_current_project().summary();
+            let layers = context_args.layers();
+            let project_section = context::project_context_section(&std::env::current_dir()?, &layers);
+            // 최근 셸 명령어 (AI_CLI_SHELL_HISTORY로 켠 경우만, 비밀 정보 제거)
+            let shell_section = context::shell_history_section(&layers);
+
+            // 브랜치 이름의 티켓 내용 (AI_CLI_ISSUE_MCP로 이슈 트래커 MCP 서버를 설정한 경우)
+            let ticket_section = match get_current_branch() {
+                Ok(branch) => issues::ticket_section(&branch).await,
+                Err(_) => None,
+            };
+
+            // --model이나 설정의 default_model, 승인 프롬프트나 검토 화면에서 고른 모델
+            // (어디에서도 정하지 않았으면 로컬 모델, 실패 시 OpenAI)
+            let mut commit_model: Option<String> = config::configured_model(model.as_deref());
+            // 다시 생성할 때마다 바꾸는 온도
+            let mut temperature = COMMIT_TEMPERATURES[0];
+            // 지금까지 생성한 후보 메시지 (메시지, 모델)와 보여 줄 후보
+            let mut candidates: Vec<(String, String)> = Vec::new();
+            let mut current = 0;
+            let mut generate = true;
+
+            loop {
+                // 스테이징된 diff 읽기 (훅이 파일을 수정했을 수 있으므로 매번 다시 읽음)
+                let diff = get_staged_diff_with(&diff_args.settings())?;
+                if generate {
+                    println!("{}", tr!("commit.analyzing", lines = diff.lines().count()));
+                    let reading = spinner::Spinner::start(format!("reading diff… {} lines", spinner::format_count(diff.lines().count())));
+
+                    // 사용자 지시와 변경 파일 언어를 프롬프트 컨텍스트로 전달
+                    let mut prompt_context = PromptContext::new();
+                    if let Some(instructions) = message {
+                        prompt_context.add(instructions.as_str());
+                        if let Some(files) = context::file_reference_section(instructions, &std::env::current_dir()?) {
+                            prompt_context.add(files);
+                        }
+                    }
+                    let languages = get_staged_file_languages().unwrap_or_default();
+                    if let Some(summary) = language::format_language_summary(&languages) {
+                        prompt_context.add(summary);
+                    }
+                    if let Some(summary) = symbols::format_symbol_summary(&get_staged_symbols().unwrap_or_default()) {
+                        prompt_context.add(summary);
+                    }
+                    if let Some(summary) = deps::format_dependency_changes(&get_staged_dependency_changes().unwrap_or_default()) {
+                        prompt_context.add(summary);
+                    }
+                    if let Some(max_lines) = diff_args.expand_context {
+                        if let Some(code) = get_staged_enclosing_code(max_lines).unwrap_or_default() {
+                            prompt_context.add(code);
+                        }
+                    }
+                    if let Some(profile) = &project_profile {
+                        prompt_context.add(profile.as_str());
+                    }
+                    if let Some(section) = &project_section {
+                        prompt_context.add(section.as_str());
+                    }
+                    if let Some(section) = &shell_section {
+                        prompt_context.add(section.as_str());
+                    }
+                    if let Some(section) = &ticket_section {
+                        prompt_context.add(section.as_str());
+                    }
+                    if let Some(section) = &history_section {
+                        prompt_context.add(section.as_str());
+                    }
+                    if let Some(section) = &conventions_section {
+                        prompt_context.add(section.as_str());
+                    }
+                    if let Some(section) = &team_section {
+                        prompt_context.add(section.as_str());
+                    }
+                    // 검색 인덱스 갱신은 확인 프롬프트를 띄울 수 있으므로 그 전에 스피너를 지움
+                    drop(reading);
+                    if let Some(code) = context::relevant_code_section(&std::env::current_dir()?, &diff, RETRIEVAL_TOP_K, !*show_payload).await {
+                        prompt_context.add(code);
+                    }
+
+                    // 백엔드로 전송될 내용만 출력하고 종료 (폴백 백엔드가 있으면 그 요청도)
+                    if *show_payload {
+                        let payloads = commit_payloads(&diff, prompt_context.render().as_deref(), commit_model.as_deref())?;
+                        for (i, payload) in payloads.iter().enumerate() {
+                            if i > 0 {
+                                println!("\nℹ️  If {} fails, this request goes to {} instead:", payloads[i - 1].destination, payload.destination);
+                            }
+                            print_payload(&payload.prompt, &format!("{} ({})", payload.destination, payload.url));
+                        }
+                        let records: Vec<_> = payloads
+                            .iter()
+                            .map(|payload| serde_json::json!({ "payload": payload.prompt, "destination": payload.destination, "url": payload.url }))
+                            .collect();
+                        output::record(serde_json::json!({
+                            "payload": payloads[0].prompt,
+                            "destination": payloads[0].destination,
+                            "url": payloads[0].url,
+                            "requests": records,
+                        }));
+                        break;
+                    }
 
-            // 커밋 메시지 생성
-            let commit_message = generate_commit_message(&diff).await?;
+                    // 커밋 메시지 생성
+                    let started = Instant::now();
+                    let commit_message = match &commit_model {
+                        Some(model) => generate_commit_message_with(&diff, prompt_context.render().as_deref(), model, temperature).await?,
+                        None => generate_commit_message(&diff, prompt_context.render().as_deref(), temperature).await?,
+                    };
+                    notify::finished(started, tr!("notify.commit"));
 
-            // 사용자 승인 및 커밋 실행
-            security::prompt_and_commit(&commit_message)?;
+                    if *copy {
+                        clipboard::copy_or_warn(&commit_message);
+                    }
+                    candidates.push((commit_message, commit_model.clone().unwrap_or_else(|| "auto".to_string())));
+                    current = candidates.len() - 1;
+                }
+                generate = true;
+                let (commit_message, model) = &candidates[current];
+
+                // 신뢰하지 않는 폴더에서는 메시지만 출력
+                if !security_level.allows_writes() {
+                    println!("\n{}\n{}", tr!("commit.generated"), commit_message);
+                    println!("\n{}", tr!("commit.read_only"));
+                    output::record(serde_json::json!({ "message": commit_message, "committed": false, "reason": "untrusted folder" }));
+                    break;
+                }
+
+                // 정제로 고치지 못한 커밋 규칙 위반 알림 (승인 전에 고치거나 다시 생성)
+                let rules = config::commit_rules();
+                let violations = lint::lint_message(commit_message, &rules, &allowed_commit_types(&rules));
+                let notice = (!violations.is_empty())
+                    .then(|| format!("⚠️  The generated message breaks {} commit rule(s):\n{}", violations.len(), lint::format_violations(&violations)));
+                let review = review::Review {
+                    diff: &diff,
+                    message: commit_message,
+                    model,
+                    notice: notice.as_deref(),
+                    candidate: (current + 1, candidates.len()),
+                };
+                let use_review = !*no_review && review::is_available();
+                if let (Some(notice), false) = (&notice, use_review) {
+                    println!("\n{}", notice);
+                }
+
+                // 사용자 승인 및 커밋 실행
+                let outcome = security::prompt_and_commit(commit_message, *no_verify, &review, use_review)?;
+                // 편집기나 훅을 거쳐 실제로 커밋한 메시지를 기록 (커밋하지 않았으면 생성한 메시지)
+                let committed = match &outcome {
+                    security::CommitOutcome::Committed(message) => Some(message.as_str()),
+                    _ => None,
+                };
+                output::record(serde_json::json!({
+                    "message": committed.unwrap_or(commit_message).trim_end(),
+                    "model": review.model,
+                    "committed": committed.is_some(),
+                    "violations": violations.iter().map(|violation| violation.to_string()).collect::<Vec<_>>(),
+                }));
+                if !matches!(outcome, security::CommitOutcome::Committed(_) | security::CommitOutcome::Cancelled) {
+                    tips::used("commit.regenerate");
+                }
+                match outcome {
+                    security::CommitOutcome::Regenerate => {
+                        temperature = next_temperature(temperature);
+                        println!("\n{}", tr!("commit.regenerating_temperature", temperature = temperature));
+                    }
+                    security::CommitOutcome::ChangeModel(model) => {
+                        println!("\n{}", tr!("commit.regenerating_with", model = model));
+                        commit_model = Some(model);
+                    }
+                    // 이미 생성한 후보 사이를 오갈 때는 다시 생성하지 않음
+                    security::CommitOutcome::Previous | security::CommitOutcome::Next => {
+                        current = if outcome == security::CommitOutcome::Next {
+                            (current + 1) % candidates.len()
+                        } else {
+                            (current + candidates.len() - 1) % candidates.len()
+                        };
+                        generate = false;
+                        println!("\n{}", tr!("commit.candidate", index = current + 1, count = candidates.len()));
+                    }
+                    security::CommitOutcome::Committed(_) => {
+                        // 브랜치의 Jira 티켓에 커밋 연결 (AI_CLI_JIRA_MCP를 설정한 경우)
+                        if let Ok(branch) = get_current_branch() {
+                            issues::link_commit(&branch).await;
+                        }
+                        // 푸시, PR 열기, 커밋 수정
+                        followup::offer(*no_verify).await;
+                        break;
+                    }
+                    security::CommitOutcome::Cancelled => break,
+                }
+            }
         }
-        Commands::Explain { hash, model, detailed, format } => {
-            println!("🔍 AI is analyzing the changes...");
+        Commands::Explain { hash, model, detailed, show_diff, format, yes, show_payload, notify, copy, per_file, diff: diff_args, context: context_args } => {
+            if *yes {
+                std::env::set_var("AI_CLI_ASSUME_YES", "1");
+            }
+
+            // 신뢰 폴더 확인 (설명은 읽기 전용 작업이므로 신뢰하지 않아도 계속 진행)
+            security::SecurityManager::default().ensure_security_level(&std::env::current_dir()?)?;
+
+            println!("{}", tr!("explain.analyzing"));
 
             // diff 또는 특정 커밋 분석
-            let diff = if let Some(commit_hash) = hash {
-                get_commit_diff(commit_hash)?
+            let diff_settings = diff_args.settings();
+            let reading = spinner::Spinner::start("reading diff…");
+            let (diff, languages, affected_symbols) = if let Some(commit_hash) = hash {
+                (
+                    get_commit_diff_with(commit_hash, &diff_settings)?,
+                    get_commit_file_languages(commit_hash).unwrap_or_default(),
+                    get_commit_symbols(commit_hash).unwrap_or_default(),
+                )
             } else {
-                get_staged_diff()?
+                (
+                    get_staged_diff_with(&diff_settings)?,
+                    get_staged_file_languages().unwrap_or_default(),
+                    get_staged_symbols().unwrap_or_default(),
+                )
             };
+            drop(reading);
+
+            // 분석할 diff 표시 (JSON 출력에는 섞지 않음)
+            if *show_diff && format != "json" {
+                println!("{}\n", highlight::render_diff(&diff));
+            }
 
             // AI 백엔드 선택
-            let backend = get_ai_backend(model)?;
+            let backend = get_ai_backend(&config::resolve_model(model.as_deref()))?;
+
+            // 변경 파일 언어를 프롬프트 컨텍스트로 전달
+            let reading = spinner::Spinner::start(format!("gathering context… {} lines", spinner::format_count(diff.lines().count())));
+            let mut prompt_context = PromptContext::new();
+            if let Some(summary) = language::format_language_summary(&languages) {
+                prompt_context.add(summary);
+            }
+            if let Some(summary) = symbols::format_symbol_summary(&affected_symbols) {
+                prompt_context.add(summary);
+            }
+            let dependency_changes = match hash {
+                Some(commit_hash) => get_commit_dependency_changes(commit_hash),
+                None => get_staged_dependency_changes(),
+            };
+            if let Some(summary) = deps::format_dependency_changes(&dependency_changes.unwrap_or_default()) {
+                prompt_context.add(summary);
+            }
+            if let Some(max_lines) = diff_args.expand_context {
+                let enclosing = match hash {
+                    Some(commit_hash) => get_commit_enclosing_code(commit_hash, max_lines),
+                    None => get_staged_enclosing_code(max_lines),
+                };
+                if let Some(code) = enclosing.unwrap_or_default() {
+                    prompt_context.add(code);
+                }
+            }
+            if let Some(profile) = project::detect_current_project().summary() {
+                prompt_context.add(profile);
+            }
+            let layers = context_args.layers();
+            if let Some(section) = context::project_context_section(&std::env::current_dir()?, &layers) {
+                prompt_context.add(section);
+            }
+            if let Some(section) = context::shell_history_section(&layers) {
+                prompt_context.add(section);
+            }
+            if let Some(section) = config::team::prompt_section() {
+                prompt_context.add(section);
+            }
+            drop(reading);
+            if let Some(code) = context::relevant_code_section(&std::env::current_dir()?, &diff, RETRIEVAL_TOP_K, !*show_payload).await {
+                prompt_context.add(code);
+            }
+
+            // 백엔드로 전송될 내용만 출력하고 종료
+            if *show_payload {
+                if !backend.is_remote() {
+                    println!("ℹ️  {} runs locally; nothing leaves this machine.", backend.display_name());
+                }
+                if map_reduce::should_split(&diff, *per_file) {
+                    println!("ℹ️  Each file would first be summarized in a separate request; showing the combined request instead.");
+                }
+                let prompt = build_explain_prompt(&diff, *detailed, prompt_context.render().as_deref(), &backend)?;
+                print_payload(&prompt, backend.display_name());
+                output::record(serde_json::json!({ "payload": prompt, "destination": backend.display_name() }));
+                return Ok(());
+            }
 
             // 변경 사항 설명 생성
-            let explanation = generate_explanation(&diff, *detailed, &backend).await?;
+            let started = Instant::now();
+            // 큰 diff는 파일별 요약을 모아 설명
+            let analyzed = if map_reduce::should_split(&diff, *per_file) {
+                map_reduce::summarize_files(&diff, &backend).await?
+            } else {
+                diff.clone()
+            };
+            let explanation = generate_explanation(&analyzed, *detailed, prompt_context.render().as_deref(), &backend).await?;
+            notify::finished(started, tr!("notify.explain"));
+            if let Some(usage) = &explanation.usage {
+                output::add_usage(usage);
+            }
+            output::record(serde_json::json!({
+                "analysis": explanation.content,
+                "model": explanation.model,
+                "detailed": detailed,
+                "languages": languages,
+                "symbols": affected_symbols,
+            }));
 
             match format.as_str() {
                 "json" => {
                     let output = serde_json::json!({
-                        "analysis": explanation,
-                        "model": backend,
-                        "detailed": detailed
+                        "analysis": explanation.content,
+                        "model": explanation.model,
+                        "detailed": detailed,
+                        "languages": languages,
+                        "symbols": affected_symbols
                     });
                     println!("{}", serde_json::to_string_pretty(&output)?);
                 }
                 "markdown" => {
-                    println!("## Code Change Analysis\n\n{}", explanation);
+                    pager::page(&format!("## Code Change Analysis\n\n{}", explanation.content));
                 }
                 _ => {
-                    println!("\n📄 AI Analysis:\n{}", explanation);
+                    pager::page(&format!("\n{}\n{}", tr!("explain.result"), markdown::render_for_terminal(&explanation.content)));
                 }
             }
+
+            if *copy {
+                clipboard::copy_or_warn(&explanation.content);
+            }
+
+            // 설명을 Slack 채널에 공유 (승인 후 게시)
+            if let Some(channel) = notify {
+                let target = hash.as_deref().map(|hash| format!("commit {}", hash)).unwrap_or_else(|| "staged changes".to_string());
+                notify::share_on_slack(channel, &format!("*Change analysis* ({})\n\n{}", target, explanation.content)).await;
+            }
         }
         Commands::Init { model, openai_key, anthropic_key, ollama_url } => {
-            println!("🔧 Initializing AI CLI configuration...");
+            println!("{}", tr!("init.start"));
 
-            // 환경 변수 설정 안내
+            // 설정 파일에 저장 (환경 변수는 계속 우선)
+            let mut config_file = config::Config::load()?;
+            let profile = config_file.active_profile_name();
+            let settings = config_file.settings_mut(profile.as_deref());
             if let Some(m) = model {
-                println!("✓ Default model set to: {}", m);
-                std::env::set_var("AI_CLI_DEFAULT_MODEL", m);
+                settings.set("default_model", m)?;
+                println!("{}", tr!("init.model", model = m));
             }
 
             if let Some(key) = openai_key {
-                println!("✓ OpenAI API key configured");
-                std::env::set_var("OPENAI_API_KEY", key);
+                settings.set("openai.api_key", key)?;
+                println!("{}", tr!("init.openai"));
             }
 
             if let Some(key) = anthropic_key {
-                println!("✓ Anthropic API key configured");
-                std::env::set_var("ANTHROPIC_API_KEY", key);
+                settings.set("anthropic.api_key", key)?;
+                println!("{}", tr!("init.anthropic"));
             }
 
             if ollama_url != "http://localhost:11434" {
-                println!("✓ Ollama URL set to: {}", ollama_url);
-                std::env::set_var("AI_CLI_OLLAMA_URL", ollama_url);
+                settings.set("ollama.url", ollama_url)?;
+                println!("{}", tr!("init.ollama", url = ollama_url));
+            }
+
+            let settings_path = config_file.save()?;
+            match &profile {
+                Some(name) => println!("{}", tr!("init.saved_profile", profile = name, path = settings_path.display())),
+                None => println!("{}", tr!("init.saved", path = settings_path.display())),
             }
 
             // 기본 설정 파일 생성
             let current_dir = std::env::current_dir()?;
             if let Ok(config_path) = context::create_default_project_config(&current_dir) {
-                println!("✓ Created PROJECT.md at: {}", config_path.display());
+                println!("{}", tr!("init.project_config", path = config_path.display()));
             }
 
             if let Ok(config_path) = context::create_default_global_config() {
-                println!("✓ Created global config at: {}", config_path.display());
+                println!("{}", tr!("init.global_config", path = config_path.display()));
             }
 
-            // MCP 클라이언트 초기화 테스트
-            let mcp_client = mcp::MCPClientBuilder::new("ai-cli")
-                .version("0.1.0")
-                .server_url("stdio://")
-                .build();
+            // MCP 서버 연결 테스트 (~/.ai-cli/mcp.json에 등록한 서버)
+            let registry = mcp::McpRegistry::load()?;
+            if registry.is_empty() {
+                println!("{}", tr!("init.no_mcp", file = mcp::registry::REGISTRY_FILE));
+            } else {
+                let mcp_client = mcp::MCPClientBuilder::new("ai-cli")
+                    .version("0.1.0")
+                    .registry(registry)
+                    .build();
 
-            match mcp_client.initialize().await {
-                Ok(()) => {
-                    println!("✓ MCP client initialized successfully");
-                    let tools = mcp_client.list_tools();
-                    if !tools.is_empty() {
-                        println!("✓ Available MCP tools: {}", tools.join(", "));
+                match mcp_client.initialize().await {
+                    Ok(()) => {
+                        println!("{}", tr!("init.mcp_ready"));
+                        let tools = mcp_client.list_tools();
+                        if !tools.is_empty() {
+                            println!("{}", tr!("init.mcp_tools", tools = tools.join(", ")));
+                        }
+                        mcp_client.shutdown().await;
+                    }
+                    Err(e) => {
+                        println!("{}", tr!("init.mcp_failed", error = e));
                     }
                 }
-                Err(e) => {
-                    println!("⚠ MCP client initialization failed: {}", e);
-                    println!("  This is normal if no MCP server is installed.");
+            }
+
+            println!("\n{}", tr!("init.done"));
+            println!("{}", tr!("init.next"));
+        }
+        Commands::Index { model, rebuild, watch, interval, yes } => {
+            if *yes {
+                std::env::set_var("AI_CLI_ASSUME_YES", "1");
+            }
+
+            // 인덱스 파일을 리포지토리에 쓰므로 신뢰 폴더에서만 실행
+            let current_dir = std::env::current_dir()?;
+            let security_level = security::SecurityManager::default().ensure_security_level(&current_dir)?;
+            if !security_level.allows_writes() {
+                println!("🔒 Not indexing: this folder is not trusted (read-only mode)");
+                return Ok(());
+            }
+
+            let project_root = context::ContextEngine::new().find_project_root(&current_dir)?;
+            println!("📚 Indexing {}...", project_root.display());
+
+            let (semantic_index, stats) = index::SemanticIndex::build(&project_root, model, *rebuild).await?;
+            semantic_index.save(&project_root)?;
+
+            println!(
+                "✅ Indexed {} chunk(s) from {} file(s) ({} re-embedded, {} unchanged) with {}",
+                stats.chunks,
+                stats.files,
+                stats.embedded_files,
+                stats.files - stats.embedded_files,
+                semantic_index.model
+            );
+            println!("   Saved to {}", index::SemanticIndex::path(&project_root).display());
+
+            if *watch {
+                index::watch(&project_root, model, std::time::Duration::from_secs((*interval).max(1))).await?;
+            }
+        }
+        Commands::Search { query, top_k, lines, format, yes } => {
+            if *yes {
+                std::env::set_var("AI_CLI_ASSUME_YES", "1");
+            }
+
+            let query = query.join(" ");
+            let project_root = context::ContextEngine::new().find_project_root(&std::env::current_dir()?)?;
+            let matches = index::search_project(&project_root, &query, *top_k, *lines).await?;
+            output::record(serde_json::to_value(&matches)?);
+
+            if format == "json" {
+                println!("{}", serde_json::to_string_pretty(&matches)?);
+                return Ok(());
+            }
+
+            if matches.is_empty() {
+                println!("🔍 No matches for \"{}\"", query);
+                return Ok(());
+            }
+
+            println!("🔍 {} match(es) for \"{}\":", matches.len(), query);
+            for (rank, found) in matches.iter().enumerate() {
+                println!("\n{}. {}:{}-{} (similarity {:.2})", rank + 1, found.path, found.start_line, found.end_line, found.score);
+                for (offset, line) in found.snippet.lines().enumerate() {
+                    println!("   {:>5} │ {}", found.snippet_line + offset, line);
                 }
             }
+        }
+        Commands::Lint { message, file } => {
+            let text = match (message, file) {
+                (Some(message), _) => message.clone(),
+                (None, Some(path)) => std::fs::read_to_string(path).map_err(|e| anyhow!("Failed to read {}: {}", path, e))?,
+                (None, None) => open_repository()?.head()?.peel_to_commit()?.message().unwrap_or_default().to_string(),
+            };
+            let rules = config::commit_rules();
+            let violations = lint::lint_message(&text, &rules, &allowed_commit_types(&rules));
+            output::record(serde_json::json!({
+                "valid": violations.is_empty(),
+                "violations": violations.iter().map(|violation| serde_json::json!({ "rule": violation.rule, "message": violation.message })).collect::<Vec<_>>(),
+            }));
+            if violations.is_empty() {
+                println!("✅ Commit message follows the commit rules");
+            } else {
+                println!("❌ Commit message breaks {} rule(s):\n{}", violations.len(), lint::format_violations(&violations));
+                std::process::exit(1);
+            }
+        }
+        Commands::Ci { action } => match action {
+            CiAction::PrDescribe { pr, model, no_review, dry_run } => {
+                ci::pr_describe(*pr, model.as_deref(), *no_review, *dry_run).await?;
+            }
+        },
+        Commands::Hook { action } => match action {
+            HookAction::CommitMsg { file } => {
+                if !hook::commit_msg(file)? {
+                    std::process::exit(1);
+                }
+            }
+            HookAction::PrePush { remote, fail_on, model, .. } => {
+                if !hook::pre_push(remote.as_deref(), fail_on.as_deref(), model.as_deref()).await? {
+                    std::process::exit(1);
+                }
+            }
+        },
+        Commands::Audit { action } => {
+            let log = audit::AuditLog::open_default()?;
 
-            println!("\n🎉 AI CLI initialization complete!");
-            println!("Run 'ai-cli commit' to generate your first AI-powered commit message.");
+            match action {
+                AuditAction::Show { limit } => {
+                    let entries = log.entries()?;
+                    if entries.is_empty() {
+                        println!("No audit entries recorded yet ({})", log.path().display());
+                    }
+
+                    let skip = limit.map(|n| entries.len().saturating_sub(n)).unwrap_or(0);
+                    let mut lines = Vec::new();
+                    for entry in entries.iter().skip(skip) {
+                        match &entry.event {
+                            audit::AuditEvent::AiRequest { backend, model, prompt_sha256, bytes_sent, files } => {
+                                lines.push(format!(
+                                    "#{} {} AI request  {} ({}) {} bytes, prompt sha256 {}",
+                                    entry.seq, entry.timestamp, backend, model, bytes_sent, &prompt_sha256[..12]
+                                ));
+                                if !files.is_empty() {
+                                    lines.push(format!("     files: {}", files.join(", ")));
+                                }
+                            }
+                            audit::AuditEvent::Command { command, exit_code } => {
+                                let code = exit_code.map(|c| c.to_string()).unwrap_or_else(|| "-".to_string());
+                                lines.push(format!("#{} {} Command     {} (exit {})", entry.seq, entry.timestamp, command, code));
+                            }
+                        }
+                    }
+                    if !lines.is_empty() {
+                        pager::page(&lines.join("\n"));
+                    }
+                }
+                AuditAction::Verify => {
+                    match log.verify() {
+                        Ok(count) => println!("✅ Audit log intact: {} entries verified ({})", count, log.path().display()),
+                        Err(e) => {
+                            println!("❌ Audit log verification failed: {}", e);
+                            std::process::exit(1);
+                        }
+                    }
+                }
+            }
         }
-        Commands::Config { verbose } => {
-            println!("⚙️  AI CLI Configuration");
+        Commands::Replay { transcript: name, full } => match name {
+            Some(name) => {
+                let path = transcript::resolve(name)?;
+                let entries = transcript::load(&path)?;
+                output::record(serde_json::json!({"path": path.display().to_string(), "entries": entries}));
+                if entries.is_empty() {
+                    println!("ℹ {} has no readable entries", path.display());
+                } else {
+                    pager::page(&transcript::render(&entries, *full).join("\n"));
+                }
+            }
+            None => {
+                let sessions = transcript::recent(20);
+                if sessions.is_empty() {
+                    let dir = transcript::transcript_dir().map(|dir| dir.display().to_string()).unwrap_or_default();
+                    println!("No transcripts recorded yet ({})", dir);
+                    return Ok(());
+                }
+                println!("🗂  Recent sessions (newest first; 'ai-cli replay <name>' or 'ai-cli replay last' to review):");
+                for (path, entries) in &sessions {
+                    let name = path.file_stem().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default();
+                    let (command, at) = match entries.first() {
+                        Some(transcript::Entry { at, event: transcript::Event::Session { command, .. }, .. }) => (command.clone(), transcript::local_time(at)),
+                        _ => (String::new(), String::new()),
+                    };
+                    println!("  {}  {}  {} event(s)  {}", name, at, entries.len().saturating_sub(1), command);
+                }
+                output::record(serde_json::json!(sessions
+                    .iter()
+                    .map(|(path, entries)| serde_json::json!({"path": path.display().to_string(), "events": entries.len().saturating_sub(1)}))
+                    .collect::<Vec<_>>()));
+            }
+        },
+        Commands::Stats { dashboard, reset } => {
+            if *reset {
+                if stats::reset()? {
+                    println!("✅ Usage stats deleted");
+                } else {
+                    println!("ℹ No usage stats recorded");
+                }
+                return Ok(());
+            }
+            let recorded = stats::load();
+            output::record(serde_json::to_value(&recorded)?);
+            if !stats::is_enabled() {
+                println!("ℹ Usage stats are off; turn them on with 'ai-cli config set stats on' (kept only in ~/.ai-cli/stats.json)");
+                if recorded == stats::Stats::default() {
+                    return Ok(());
+                }
+            }
+            let lines = if *dashboard { stats::dashboard(&recorded) } else { stats::summary(&recorded) };
+            println!("{}", lines.join("\n"));
+        }
+        Commands::Mcp { action } => match action {
+            McpAction::List => {
+                let registry = mcp::McpRegistry::load()?;
+                if registry.all_servers().next().is_none() {
+                    println!("ℹ No MCP servers configured. Add one with `ai-cli mcp add <name> --command ...` or `--url ...`.");
+                    return Ok(());
+                }
+
+                println!("🔌 MCP servers:");
+                for (name, config) in registry.all_servers() {
+                    // 설정에 적힌 그대로 표시 (${VAR}를 치환하지 않으므로 토큰이 드러나지 않음)
+                    let target = match (&config.command, &config.url) {
+                        (Some(command), None) => format!("stdio: {} {}", command, config.args.join(" ")),
+                        (None, Some(url)) => format!("http: {}", url),
+                        _ => "invalid: set either 'command' or 'url'".to_string(),
+                    };
+                    let disabled = if config.disabled { " (disabled)" } else { "" };
+                    println!("  • {}{} — {}", name, disabled, target.trim_end());
+                }
+            }
+            McpAction::Add { name, command, url, env, cwd, headers, force, args } => {
+                let path = mcp::McpRegistry::path().ok_or_else(|| anyhow!("Could not determine home directory"))?;
+                let entry = mcp::registry::server_entry(command.as_deref(), args, env, cwd.as_deref(), url.as_deref(), headers)?;
+                mcp::McpRegistry::add_to(&path, name, entry, *force)?;
+                println!("✅ Added MCP server '{}' to {}", name, path.display());
+                println!("   Check it with `ai-cli mcp test {}`", name);
+            }
+            McpAction::Remove { name } => {
+                let path = mcp::McpRegistry::path().ok_or_else(|| anyhow!("Could not determine home directory"))?;
+                if !mcp::McpRegistry::remove_from(&path, name)? {
+                    return Err(anyhow!("MCP server '{}' is not configured", name));
+                }
+                println!("🗑️  Removed MCP server '{}'", name);
+            }
+            McpAction::Test { name } => {
+                let registry = mcp::McpRegistry::load()?;
+                let config = registry.get(name).ok_or_else(|| anyhow!("MCP server '{}' is not configured or is disabled", name))?;
+                let mcp_client = mcp::MCPClientBuilder::new("ai-cli")
+                    .version(env!("CARGO_PKG_VERSION"))
+                    .server(name.clone(), config.clone())
+                    .build();
+
+                println!("🔌 Connecting to '{}'...", name);
+                mcp_client.connect_servers().await;
+                let status = mcp_client.health_check().await.into_iter().next();
+                mcp_client.shutdown().await;
+
+                match status {
+                    Some(status) if status.state == mcp::ConnectionState::Connected => {
+                        let latency = status.latency_ms.map(|latency| format!(", ping {}ms", latency)).unwrap_or_default();
+                        println!("✅ '{}' works ({}): {} tool(s){}", name, status.transport, status.tools, latency);
+                        if let Some(error) = &status.last_error {
+                            println!("   ⚠️  {}", error);
+                        }
+                    }
+                    status => {
+                        let error = status.and_then(|status| status.last_error).unwrap_or_else(|| "unknown error".to_string());
+                        return Err(anyhow!("MCP server '{}' is not working: {}", name, error));
+                    }
+                }
+            }
+            McpAction::Tools { name, format } => {
+                let registry = mcp::McpRegistry::load()?;
+                let config = registry.get(name).ok_or_else(|| anyhow!("MCP server '{}' is not configured or is disabled", name))?;
+                let mcp_client = mcp::MCPClientBuilder::new("ai-cli")
+                    .version(env!("CARGO_PKG_VERSION"))
+                    .server(name.clone(), config.clone())
+                    .build();
+                let failures = mcp_client.connect_servers().await;
+                let tools: Vec<mcp::Tool> = mcp_client.list_tools().iter().filter_map(|tool| mcp_client.get_tool(tool)).collect();
+                mcp_client.shutdown().await;
+                if let Some((_, e)) = failures.into_iter().next() {
+                    return Err(anyhow!("Could not connect to MCP server '{}': {}", name, e));
+                }
+
+                if format == "json" || output::is_json() {
+                    let tools: Vec<_> = tools
+                        .iter()
+                        .map(|tool| serde_json::json!({
+                            "name": tool.name,
+                            "description": tool.description,
+                            "access": mcp::approval::classify_tool(config, tool),
+                            "inputSchema": tool.input_schema,
+                        }))
+                        .collect();
+                    output::record(serde_json::json!(tools));
+                    if format == "json" {
+                        println!("{}", serde_json::to_string_pretty(&tools)?);
+                        return Ok(());
+                    }
+                }
+
+                println!("🔧 Tools of '{}' ({}):", name, tools.len());
+                for tool in &tools {
+                    let access = match mcp::approval::classify_tool(config, tool) {
+                        mcp::approval::ToolAccess::ReadOnly => "read-only",
+                        mcp::approval::ToolAccess::Mutating => "needs approval",
+                    };
+                    println!("  • {} [{}]", tool.name, access);
+                    if let Some(description) = tool.description.lines().map(str::trim).find(|line| !line.is_empty()) {
+                        println!("      {}", description);
+                    }
+                }
+            }
+            McpAction::Status { format } => {
+                let registry = mcp::McpRegistry::load()?;
+                if registry.is_empty() {
+                    println!("ℹ No MCP servers configured. Add them to ~/.ai-cli/{}.", mcp::registry::REGISTRY_FILE);
+                    return Ok(());
+                }
+
+                let mcp_client = mcp::MCPClientBuilder::new("ai-cli")
+                    .version(env!("CARGO_PKG_VERSION"))
+                    .registry(registry)
+                    .build();
+                // 연결하지 못한 서버도 상태 목록에 오류와 함께 표시됨
+                mcp_client.connect_servers().await;
+                let statuses = mcp_client.health_check().await;
+                mcp_client.shutdown().await;
+                output::record(serde_json::to_value(&statuses)?);
+
+                if format == "json" {
+                    println!("{}", serde_json::to_string_pretty(&statuses)?);
+                    return Ok(());
+                }
+
+                println!("🔌 MCP servers:");
+                for status in &statuses {
+                    let icon = match status.state {
+                        mcp::ConnectionState::Connected => "✓",
+                        mcp::ConnectionState::Disconnected => "⚠",
+                        mcp::ConnectionState::Failed => "✗",
+                    };
+                    let mut line = format!("  {} {} ({}): {} tool(s)", icon, status.name, status.transport, status.tools);
+                    if let Some(latency) = status.latency_ms {
+                        line.push_str(&format!(", ping {}ms", latency));
+                    }
+                    if status.reconnects > 0 {
+                        line.push_str(&format!(", {} reconnect(s)", status.reconnects));
+                    }
+                    println!("{}", line);
+                    if let Some(error) = &status.last_error {
+                        println!("      {}", error);
+                    }
+                }
+            }
+            McpAction::Run { task, model, max_steps, no_shell_history } => {
+                // 에이전트는 도구로 명령어를 실행하고 외부 상태를 바꾸므로 신뢰 폴더에서만 실행
+                let security_level = security::SecurityManager::default().ensure_security_level(&std::env::current_dir()?)?;
+                if !security_level.allows_writes() {
+                    println!("🔒 Not running the agent: this folder is not trusted (read-only mode)");
+                    return Ok(());
+                }
+
+                let backend = get_ai_backend(&config::resolve_model(model.as_deref()))?;
+                let mcp_client = std::sync::Arc::new(mcp::MCPClientBuilder::new("ai-cli")
+                    .version(env!("CARGO_PKG_VERSION"))
+                    .registry(mcp::McpRegistry::load()?)
+                    .build());
+                mcp_client.initialize().await?;
+
+                // 리포지토리 정보 (PR/이슈 도구 인자에 필요)
+                let mut repo_context = Vec::new();
+                if let Ok(branch) = get_current_branch() {
+                    repo_context.push(format!("Current branch: {}", branch));
+                }
+                if let Some(url) = open_repository().ok().and_then(|repo| repo.find_remote("origin").ok()?.url().map(str::to_string)) {
+                    repo_context.push(format!("Origin remote: {}", url));
+                }
+                let disabled = if *no_shell_history { vec![context::ContextLayer::ShellHistory] } else { Vec::new() };
+                if let Some(section) = context::shell_history_section(&context::ContextLayers::load(&disabled)) {
+                    repo_context.push(section);
+                }
+                let repo_context = (!repo_context.is_empty()).then(|| repo_context.join("\n"));
+
+                println!("🤖 Working on it with {} ({} MCP tool(s))...", backend.display_name(), mcp_client.list_tools().len());
+                let started = Instant::now();
+                let result = agent::run(&backend, &mcp_client, &task.join(" "), repo_context.as_deref(), *max_steps).await;
+                mcp_client.shutdown().await;
+                notify::finished(started, tr!("notify.agent"));
+                let run = result?;
+
+                if !run.calls.is_empty() {
+                    println!("\n🔧 Tool calls:");
+                    for call in &run.calls {
+                        println!("  {} {}", if call.succeeded { "✓" } else { "✗" }, call.tool);
+                    }
+                }
+                println!("\n{}", run.answer);
+            }
+            McpAction::Logout { server } => {
+                let registry = mcp::McpRegistry::load()?;
+                let config = registry
+                    .get(server)
+                    .ok_or_else(|| anyhow!("MCP server '{}' is not configured", server))?;
+                let mcp::registry::ServerTransport::Http { url, .. } = config.transport()? else {
+                    return Err(anyhow!("MCP server '{}' is a local server and does not use OAuth", server));
+                };
+                if mcp::oauth::logout(&url)? {
+                    println!("✅ Signed out of MCP server '{}'", server);
+                } else {
+                    println!("ℹ No stored token for MCP server '{}'", server);
+                }
+            }
+        },
+        Commands::Context { action } => match action {
+            ContextAction::Add { note, tag, notes } => {
+                let current_dir = std::env::current_dir()?;
+                let project_root = context::ContextEngine::new()
+                    .find_project_root(&current_dir)
+                    .unwrap_or(current_dir);
+
+                let path = context::append_project_note(&project_root, &note.join(" "), tag.as_deref(), *notes)?;
+                println!("📝 Added note to {}", path.display());
+            }
+        },
+        Commands::Config { action: Some(action), .. } => match action {
+            ConfigAction::Set { key, value } => {
+                let mut config_file = config::Config::load()?;
+                let profile = config_file.active_profile_name();
+                config_file.settings_mut(profile.as_deref()).set(key, value)?;
+                let mut path = config_file.save()?;
+                if config::is_secret(key) {
+                    path = config_file.credentials_path(&path);
+                }
+                let shown = if config::is_secret(key) { "********" } else { value.trim() };
+                match &profile {
+                    Some(name) => println!("{}", tr!("config.set_profile", key = key, value = shown, profile = name, path = path.display())),
+                    None => println!("{}", tr!("config.set", key = key, value = shown, path = path.display())),
+                }
+                if config::is_overridden(key) {
+                    println!("{}", tr!("config.overridden", var = config::env_var(key).unwrap_or(key)));
+                }
+            }
+            ConfigAction::Unset { key } => {
+                let mut config_file = config::Config::load()?;
+                let profile = config_file.active_profile_name();
+                if config_file.settings_mut(profile.as_deref()).unset(key)? {
+                    let mut path = config_file.save()?;
+                    if config::is_secret(key) {
+                        path = config_file.credentials_path(&path);
+                    }
+                    match &profile {
+                        Some(name) => println!("{}", tr!("config.removed_profile", key = key, profile = name, path = path.display())),
+                        None => println!("{}", tr!("config.removed", key = key, path = path.display())),
+                    }
+                } else {
+                    println!("{}", tr!("config.not_set", key = key));
+                }
+            }
+            // 잘못된 설정 파일도 고칠 수 있도록 읽지 않고 바로 편집기로 엶
+            ConfigAction::Edit { repo } => {
+                let path = if *repo {
+                    context::ContextEngine::new().find_project_root(&std::env::current_dir()?)?.join(config::REPO_CONFIG_FILE)
+                } else {
+                    config::config_path().ok_or_else(|| anyhow!("Cannot determine the home directory for the config file"))?
+                };
+                config::edit(&path, *repo)?;
+            }
+            ConfigAction::Migrate => {
+                let migrated = config::migrate_env()?;
+                if migrated.is_empty() {
+                    println!("{}", tr!("config.no_env"));
+                }
+                for (key, location) in migrated {
+                    println!("✓ {} → {}", key, location);
+                }
+            }
+            ConfigAction::Team => {
+                let root = context::ContextEngine::new().find_project_root(&std::env::current_dir()?)?;
+                let path = root.join(config::team::TEAM_CONFIG_FILE);
+                let team = config::TeamConfig::load_checked(&root)?;
+                let repo = config::RepoConfig::load_checked(&root)?;
+                let mut reviews = Vec::new();
+                match &team {
+                    Some(team) => {
+                        println!("{}", tr!("team.config", path = path.display()));
+                        if !team.commit.types.is_empty() {
+                            println!("{}", tr!("team.types", types = team.commit.types.join(", ")));
+                        }
+                        if team.commit.conventions.is_some() {
+                            println!("{}", tr!("team.conventions"));
+                        }
+                        if team.prompt.is_some() {
+                            println!("{}", tr!("team.guidelines"));
+                        }
+                        if team.privacy.is_some() {
+                            println!("{}", tr!("team.privacy"));
+                        }
+                        if !team.exclude.is_empty() {
+                            println!("{}", tr!("team.excluded", paths = team.exclude.join(", ")));
+                        }
+                        reviews.push((path, team.gated_settings(), team.digest(), team.trust(&root)));
+                    }
+                    None => println!("{}", tr!("team.none", path = path.display())),
+                }
+                // 리포지토리 설정도 요청 대상을 바꾸는 설정은 같은 방식으로 확인
+                if let Some(repo) = &repo {
+                    let path = root.join(config::REPO_CONFIG_FILE);
+                    println!("{}", tr!("team.repo_config", path = path.display()));
+                    reviews.push((path, repo.gated_settings(), repo.digest(), repo.trust(&root)));
+                }
+
+                for (path, gated, digest, trust) in reviews {
+                    let name = path.strip_prefix(&root).unwrap_or(&path).display().to_string();
+                    if gated.is_empty() {
+                        println!("\n{}", tr!("team.nothing_gated", file = name));
+                        continue;
+                    }
+                    let status = match trust {
+                        config::team::TeamTrust::Trusted => tr!("team.status_applied"),
+                        config::team::TeamTrust::Declined => tr!("team.status_ignored"),
+                        _ => tr!("team.status_pending"),
+                    };
+                    println!("\n{}", tr!("team.gated", file = name, status = status));
+                    for setting in &gated {
+                        println!("  {}", setting);
+                    }
+                    security::SecurityManager::prompt_team_config(&path, &gated, &digest)?;
+                }
+            }
+            ConfigAction::Where => {
+                let config_file = config::Config::load()?;
+                let repo_root = config::RepoConfig::discover_root();
+                let repo_config = match &repo_root {
+                    Some(root) => config::RepoConfig::load_checked(root)?.map(|repo| repo.honored(root)),
+                    None => None,
+                };
+                let team_config = repo_root.as_deref().and_then(config::team::active_in);
+
+                println!("🔎 Where settings come from (CLI flag > env var > repo config > team config > profile > global config > default)");
+                if let Some(path) = config::config_path() {
+                    println!("  Global config: {}{}", path.display(), if path.exists() { "" } else { " (not created)" });
+                }
+                if let (Some(root), Some(_)) = (&repo_root, &repo_config) {
+                    println!("  Repo config:   {}", root.join(config::REPO_CONFIG_FILE).display());
+                }
+                if let (Some(root), Some(_)) = (&repo_root, &team_config) {
+                    println!("  Team config:   {}", root.join(config::team::TEAM_CONFIG_FILE).display());
+                }
+                println!();
+                let mut settings = Vec::new();
+                for row in config_file.provenance(team_config.as_ref(), repo_config.as_ref())? {
+                    settings.push(serde_json::json!({
+                        "key": row.key,
+                        "value": row.value.as_ref().map(|value| if config::is_secret(row.key) { "********" } else { value.as_str() }),
+                        "source": row.value.as_ref().map(|_| row.source.to_string()),
+                        "overrides": row.shadowed.iter().map(ToString::to_string).collect::<Vec<_>>(),
+                    }));
+                    let value = match &row.value {
+                        Some(_) if config::is_secret(row.key) => "********".to_string(),
+                        Some(value) => value.clone(),
+                        None => "(not set)".to_string(),
+                    };
+                    let source = if row.value.is_some() { row.source.to_string() } else { "-".to_string() };
+                    let shadowed = if row.shadowed.is_empty() {
+                        String::new()
+                    } else {
+                        let sources: Vec<String> = row.shadowed.iter().map(ToString::to_string).collect();
+                        format!("  (overrides {})", sources.join(", "))
+                    };
+                    println!("  {:<18} {:<30} {}{}", row.key, value, source, shadowed);
+                }
+                println!("\nℹ A command's --model flag overrides default_model for that run");
+                output::record(serde_json::json!({ "settings": settings }));
+            }
+            ConfigAction::Export { redact_secrets, output } => {
+                let bundle = config::bundle::Bundle::collect(*redact_secrets)?;
+                let json = bundle.to_json()?;
+                match output {
+                    Some(path) => {
+                        // 비밀 값이 들어 있으면 소유자만 읽을 수 있게 저장
+                        let mut options = std::fs::OpenOptions::new();
+                        options.write(true).create(true).truncate(true);
+                        #[cfg(unix)]
+                        if !*redact_secrets {
+                            use std::os::unix::fs::OpenOptionsExt;
+                            options.mode(0o600);
+                        }
+                        std::io::Write::write_all(&mut options.open(path)?, json.as_bytes())?;
+                        println!("{}", tr!("config.exported", servers = bundle.mcp_servers.len(), files = bundle.files.len(), path = path));
+                        if *redact_secrets {
+                            println!("{}", tr!("config.redacted", count = bundle.redacted.len()));
+                        } else {
+                            println!("{}", tr!("config.contains_keys"));
+                        }
+                    }
+                    None => print!("{}", json),
+                }
+            }
+            ConfigAction::Sync { remote, pull, push } => {
+                let mode = match (pull, push) {
+                    (true, _) => config::sync::SyncMode::Pull,
+                    (_, true) => config::sync::SyncMode::Push,
+                    _ => config::sync::SyncMode::Auto,
+                };
+                let report = config::sync::sync(remote.as_deref(), mode)?;
+                if let Some(pulled) = &report.pulled {
+                    println!("{}", tr!("config.pulled", remote = report.remote));
+                    for server in &pulled.servers {
+                        println!("{}", tr!("config.server", server = server));
+                    }
+                    for path in &pulled.files {
+                        println!("  ✓ {}", path.display());
+                    }
+                    println!("{}", tr!("config.not_synced"));
+                }
+                if report.pushed {
+                    println!("{}", tr!("config.pushed", remote = report.remote));
+                }
+                if report.pulled.is_none() && !report.pushed {
+                    println!("{}", tr!("config.up_to_date", remote = report.remote));
+                }
+            }
+            ConfigAction::Import { file, force } => {
+                let text = std::fs::read_to_string(file).map_err(|e| anyhow!("Failed to read {}: {}", file, e))?;
+                let bundle = config::bundle::Bundle::parse(&text)?;
+                let report = bundle.apply(*force)?;
+                if let Some(path) = &report.config_path {
+                    println!("{}", tr!("config.merged", path = path.display()));
+                }
+                for server in &report.servers {
+                    println!("{}", tr!("config.imported_server", server = server));
+                }
+                for path in &report.files {
+                    println!("✓ {}", path.display());
+                }
+                if !report.skipped.is_empty() {
+                    println!("\n{}", tr!("config.kept"));
+                    for skipped in &report.skipped {
+                        println!("  - {}", skipped);
+                    }
+                }
+                if !bundle.redacted.is_empty() {
+                    println!("\n{}", tr!("config.set_secrets"));
+                    for key in &bundle.redacted {
+                        println!("  - {}", key);
+                    }
+                }
+            }
+        },
+        Commands::Config { verbose, action: None } => {
+            println!("{}", tr!("config.title"));
+            output::record(serde_json::json!({
+                "config_file": config::config_path(),
+                "profile": config::Config::load().ok().and_then(|settings| settings.active_profile_name()),
+                "default_model": config::default_model(),
+            }));
 
             if *verbose {
                 // 현재 설정 상세 출력
-                println!("\nEnvironment Variables:");
+                if let Some(path) = config::config_path() {
+                    let status = if path.exists() { "✓" } else { "not created (run 'ai-cli init')" };
+                    println!("\nConfig File: {} {}", path.display(), status);
+                    if let Ok(config_file) = config::Config::load() {
+                        let credentials = config_file.credentials_path(&path);
+                        if credentials.exists() {
+                            println!("  Credentials File: ✓ {}", credentials.display());
+                        }
+                    }
+                }
+                if let Ok(root) = context::ContextEngine::new().find_project_root(&std::env::current_dir()?) {
+                    let repo_config = root.join(config::REPO_CONFIG_FILE);
+                    if repo_config.is_file() {
+                        println!("  Repository Config: ✓ {}", repo_config.display());
+                    }
+                }
+                if let Some(profile) = config::Config::load().ok().and_then(|settings| settings.active_profile_name()) {
+                    println!("  Profile: {}", profile);
+                }
+                println!("  Default Model: {}", config::default_model());
+
+                println!("\nEnvironment Variables (config file values are applied unless already set):");
                 if let Ok(model) = std::env::var("AI_CLI_LOCAL_MODEL") {
                     println!("  Local Model: {}", model);
                 }
                 if let Ok(url) = std::env::var("AI_CLI_OLLAMA_URL") {
                     println!("  Ollama URL: {}", url);
                 }
-                if let Ok(key) = std::env::var("OPENAI_API_KEY") {
+                if std::env::var_os("OPENAI_API_KEY").is_some() {
                     println!("  OpenAI API: ✓ configured");
                 }
-                if let Ok(key) = std::env::var("ANTHROPIC_API_KEY") {
+                if std::env::var_os("ANTHROPIC_API_KEY").is_some() {
                     println!("  Anthropic API: ✓ configured");
                 }
 
                 // 컨텍스트 파일 상태
                 let current_dir = std::env::current_dir()?;
-                let project_config = current_dir.join("PROJECT.md");
-                if project_config.exists() {
-                    println!("  Project Context: ✓ {}", project_config.display());
+                let context_files: Vec<String> = context::context_file_names()
+                    .into_iter()
+                    .filter(|name| current_dir.join(name).is_file())
+                    .collect();
+                if !context_files.is_empty() {
+                    println!("  Project Context: ✓ {}", context_files.join(", "));
+                } else if current_dir.join(".ai-cli").join("docs-summary.md").exists() {
+                    println!("  Project Context: README/docs summary (no PROJECT.md)");
+                }
+                if let Ok(Some(semantic_index)) = index::SemanticIndex::load(&current_dir) {
+                    println!("  Semantic Index: ✓ {} chunks ({})", semantic_index.len(), semantic_index.model);
+                }
+
+                let profile = project::ProjectProfile::detect(&current_dir);
+                if !profile.is_empty() {
+                    println!("  Project Profile: {}", profile.languages.join(", "));
+                    if !profile.frameworks.is_empty() {
+                        println!("    Frameworks: {}", profile.frameworks.join(", "));
+                    }
+                    if !profile.build_tools.is_empty() {
+                        println!("    Build Tools: {}", profile.build_tools.join(", "));
+                    }
                 }
 
                 let home_config = dirs::home_dir()
@@ -162,9 +1231,22 @@ async fn main() -> Result<()> {
                         println!("  Global Context: ✓ {}", config_path.display());
                     }
                 }
+
+                let layers = context::ContextLayers::load(&[]);
+                let precedence: Vec<&str> = layers.precedence().iter().map(context::ContextLayer::as_str).collect();
+                println!("  Context Precedence: {}", precedence.join(" > "));
+
+                match mcp::McpRegistry::load() {
+                    Ok(registry) if !registry.is_empty() => {
+                        let servers: Vec<&str> = registry.servers().map(|(name, _)| name.as_str()).collect();
+                        println!("  MCP Servers: {}", servers.join(", "));
+                    }
+                    Ok(_) => {}
+                    Err(e) => println!("  MCP Servers: ⚠ {}", e),
+                }
             } else {
-                println!("Use --verbose for detailed configuration");
-                println!("Run 'ai-cli init' to configure");
+                println!("{}", tr!("config.verbose_hint"));
+                println!("{}", tr!("config.init_hint"));
             }
         }
     }
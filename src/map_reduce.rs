@@ -0,0 +1,184 @@
+use anyhow::Result;
+use futures::stream::{self, StreamExt};
+
+use crate::ai_utils::{self, AIBackend, ChatMessage};
+use crate::audit;
+use crate::checklist::Checklist;
+use crate::injection;
+use crate::output;
+use crate::transcript;
+
+/// 파일별 요약 모듈 (map-reduce)
+/// diff가 크거나(`MAP_REDUCE_BYTES` 초과) `explain --per-file`이면 파일마다 따로 요약을 받은 뒤(map),
+/// 각 파일의 diff 본문을 요약으로 바꾼 diff로 전체 설명을 생성(reduce)
+/// 원격 백엔드면 모든 파일을 한 번에 마스킹하고 전송량을 한 번만 확인하며, 진행 상황은 체크리스트로 표시
+/// 실패한 파일은 한 번 다시 시도하고, 그래도 실패하면 건너뛰고 요약 대신 건너뛴 이유를 남김
+///
+/// 파일별로 나눠 요약할 diff 크기
+pub const MAP_REDUCE_BYTES: usize = 40_000;
+
+/// 파일 하나를 요약하는 최대 시도 횟수
+const ATTEMPTS: usize = 2;
+
+/// 파일 요약 응답 토큰 상한
+const SUMMARY_TOKENS: u32 = 300;
+
+/// 동시에 보낼 원격 요청 수 (로컬 Ollama는 한 번에 하나)
+const REMOTE_CONCURRENCY: usize = 3;
+
+const SUMMARY_SYSTEM: &str = "You are summarizing one file of a larger code change so that another step can explain the whole change.
+Describe in 2-5 short bullet points what changed in this file and why it matters. Mention renamed or removed public items.
+Do not guess about files you cannot see.";
+
+/// 파일별로 나눠 요약해야 하는지 (파일이 두 개 이상인 경우만)
+pub fn should_split(diff: &str, forced: bool) -> bool {
+    split_by_file(diff).len() >= 2 && (forced || diff.len() > MAP_REDUCE_BYTES)
+}
+
+/// diff를 파일별로 나눔 (경로, 그 파일의 diff)
+pub fn split_by_file(diff: &str) -> Vec<(String, String)> {
+    let mut files: Vec<(String, String)> = Vec::new();
+    for line in diff.split_inclusive('\n') {
+        if let Some(rest) = line.strip_prefix("diff --git a/") {
+            files.push((header_path(rest.trim_end()).to_string(), String::new()));
+        }
+        match files.last_mut() {
+            Some((_, section)) => section.push_str(line),
+            // 첫 파일 머리 앞의 줄은 버리지 않고 이름 없는 조각으로
+            None => files.push((String::new(), line.to_string())),
+        }
+    }
+    files
+}
+
+/// `diff --git a/경로 b/경로` 머리의 경로 (경로에 ` b/`가 들어 있어도 앞뒤가 같으면 그대로)
+fn header_path(rest: &str) -> &str {
+    let half = rest.len().saturating_sub(3) / 2;
+    match (rest.get(..half), rest.get(half..)) {
+        (Some(old), Some(new)) if new.strip_prefix(" b/") == Some(old) => old,
+        _ => rest.rsplit(" b/").next().unwrap_or(rest),
+    }
+}
+
+/// 파일마다 요약을 받아, 본문을 요약으로 바꾼 diff를 돌려줌
+pub async fn summarize_files(diff: &str, backend: &AIBackend) -> Result<String> {
+    injection::warn_on_injection("diff", diff);
+    let files = split_by_file(diff);
+
+    // 마스킹과 전송량 확인은 모든 파일을 합쳐 한 번만
+    let sections: Vec<String> = files.iter().map(|(_, section)| section.clone()).collect();
+    let sections = if backend.is_remote() {
+        ai_utils::redact_texts_for_remote(sections, backend.display_name())?
+    } else {
+        sections
+    };
+    let prompts: Vec<String> = sections.iter().map(|section| summary_prompt(section)).collect();
+    if backend.is_remote() {
+        ai_utils::confirm_outbound(&prompts.concat(), backend.display_name())?;
+    }
+
+    let checklist = Checklist::start(
+        format!("📝 Summarizing {} file(s) with {}", files.len(), backend.display_name()),
+        files.iter().map(|(path, _)| if path.is_empty() { "(header)".to_string() } else { path.clone() }).collect(),
+    );
+    let concurrency = if backend.is_remote() { REMOTE_CONCURRENCY } else { 1 };
+    let mut summaries: Vec<(usize, Result<String, String>)> = stream::iter(prompts.iter().enumerate())
+        .map(|(index, prompt)| {
+            let checklist = &checklist;
+            async move { (index, summarize_one(index, prompt, backend, checklist).await) }
+        })
+        .buffer_unordered(concurrency)
+        .collect()
+        .await;
+    drop(checklist);
+    summaries.sort_by_key(|(index, _)| *index);
+
+    Ok(files
+        .iter()
+        .zip(summaries)
+        .map(|((path, _), (_, summary))| reduced_section(path, summary))
+        .collect())
+}
+
+/// 파일 하나 요약 (실패하면 한 번 더 시도)
+async fn summarize_one(index: usize, prompt: &str, backend: &AIBackend, checklist: &Checklist) -> Result<String, String> {
+    audit::record_ai_request(backend.display_name(), backend.model(), prompt);
+    transcript::prompt(backend.display_name(), backend.model(), prompt);
+    let messages = [ChatMessage { role: "user".to_string(), content: prompt.to_string() }];
+    checklist.running(index);
+    let mut error = String::new();
+    for attempt in 1..=ATTEMPTS {
+        if attempt > 1 {
+            checklist.retrying(index, &error);
+        }
+        match ai_utils::send_chat(backend, Some(SUMMARY_SYSTEM), &messages, SUMMARY_TOKENS, Some(0.3)).await {
+            Ok(response) => {
+                if let Some(usage) = &response.usage {
+                    output::add_usage(usage);
+                }
+                checklist.done(index, response.usage.map(|usage| usage.total_tokens));
+                transcript::response(backend.display_name(), &response.content);
+                return Ok(response.content);
+            }
+            Err(e) => {
+                tracing::debug!("Summarizing file {} failed (attempt {}): {:#}", index, attempt, e);
+                error = format!("{:#}", e);
+            }
+        }
+    }
+    checklist.failed(index, &error);
+    Err(error)
+}
+
+/// 파일 하나를 요약하는 요청
+fn summary_prompt(section: &str) -> String {
+    format!("{}\n\nFILE DIFF:\n{}", injection::UNTRUSTED_CONTENT_RULES, ai_utils::wrap_diff(section))
+}
+
+/// reduce 단계에 보낼 파일 조각 (diff 머리와 `#` 주석으로 단 요약)
+fn reduced_section(path: &str, summary: Result<String, String>) -> String {
+    let body = match summary {
+        Ok(summary) => format!("# summary of this file's changes:\n{}", comment(&summary)),
+        Err(error) => format!("# summary unavailable, file skipped: {}", error.lines().next().unwrap_or_default()),
+    };
+    if path.is_empty() {
+        format!("{}\n", body)
+    } else {
+        format!("diff --git a/{path} b/{path}\n{body}\n")
+    }
+}
+
+/// 여러 줄을 `# ` 주석 줄로
+fn comment(text: &str) -> String {
+    text.trim().lines().map(|line| format!("# {}", line).trim_end().to_string()).collect::<Vec<_>>().join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_and_reduce() {
+        let diff = "diff --git a/src/a.rs b/src/a.rs\n+fn a() {}\ndiff --git a/docs/my b/notes.md b/docs/my b/notes.md\n+text\n";
+        let files = split_by_file(diff);
+        assert_eq!(
+            files,
+            vec![
+                ("src/a.rs".to_string(), "diff --git a/src/a.rs b/src/a.rs\n+fn a() {}\n".to_string()),
+                ("docs/my b/notes.md".to_string(), "diff --git a/docs/my b/notes.md b/docs/my b/notes.md\n+text\n".to_string()),
+            ]
+        );
+        assert!(should_split(diff, true));
+        assert!(!should_split(diff, false));
+        assert!(!should_split("diff --git a/x b/x\n+1\n", true));
+
+        assert_eq!(
+            reduced_section("src/a.rs", Ok("- adds `a`\n\n- tests".to_string())),
+            "diff --git a/src/a.rs b/src/a.rs\n# summary of this file's changes:\n# - adds `a`\n#\n# - tests\n"
+        );
+        assert_eq!(
+            reduced_section("src/b.rs", Err("timeout\ndetails".to_string())),
+            "diff --git a/src/b.rs b/src/b.rs\n# summary unavailable, file skipped: timeout\n"
+        );
+    }
+}
@@ -0,0 +1,348 @@
+use std::io::{self, IsTerminal};
+
+use crate::highlight;
+use crate::review;
+
+/// 마크다운 출력 모듈
+/// AI가 쓴 마크다운 설명을 터미널 너비에 맞춰 그림 (termimad와 비슷한 모양을 외부 크레이트 없이 ANSI 색으로)
+/// 제목은 굵은 색 글씨, 목록은 `•`와 내어쓰기, 인용은 `│`, 코드 블록은 들여 쓰고 언어별로 강조 (줄바꿈하지 않음), 제목과 문단은 단어 단위로 줄바꿈
+/// 색을 쓸 수 없으면 `**`, `#` 같은 표시만 지운 평문으로 출력 (`--format markdown`은 원문 그대로)
+const HEADING: &str = "\x1b[1;36m";
+const BOLD: &str = "\x1b[1m";
+const CODE: &str = "\x1b[33m";
+const QUOTE: &str = "\x1b[90m";
+const RESET: &str = "\x1b[0m";
+
+/// 줄바꿈 너비를 모를 때(파이프) 구분선 길이
+const RULE_WIDTH: usize = 40;
+
+/// 글자 모양
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Style {
+    Plain,
+    Bold,
+    Code,
+}
+
+/// 공백 없이 이어진 한 단어 (모양이 다른 조각으로 나뉠 수 있음, 예: `**bar**,`)
+type Word = Vec<(String, Style)>;
+
+/// 터미널 출력용 렌더링 (표준 출력이 터미널이면 그 너비로 줄바꿈, 아니면 줄바꿈하지 않음)
+pub fn render_for_terminal(text: &str) -> String {
+    let width = if io::stdout().is_terminal() {
+        review::terminal_size()
+            .map(|(_, columns)| columns)
+            .or_else(|| std::env::var("COLUMNS").ok()?.parse().ok())
+    } else {
+        None
+    };
+    render(text, width.unwrap_or(usize::MAX), highlight::color_enabled())
+}
+
+/// 마크다운을 너비 `width`에 맞춘 줄들로 변환
+pub fn render(text: &str, width: usize, color: bool) -> String {
+    let mut out: Vec<String> = Vec::new();
+    // 이어지는 줄을 모으는 중인 문단 (첫 줄 앞머리, 다음 줄 앞머리, 내용)
+    let mut paragraph: Option<(String, String, String)> = None;
+    let mut fence: Option<String> = None;
+
+    let flush = |paragraph: &mut Option<(String, String, String)>, out: &mut Vec<String>| {
+        if let Some((first, next, text)) = paragraph.take() {
+            out.extend(wrap(&words(&text, color), width, &first, &next, color));
+        }
+    };
+
+    for line in text.lines() {
+        let trimmed = line.trim_start();
+
+        // 코드 블록 (줄바꿈 없이 들여 쓰고 강조)
+        if let Some(info) = &fence {
+            if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+                fence = None;
+            } else if color {
+                out.push(format!("  {}{}", highlight::paint_code_block_line(line, info), RESET));
+            } else {
+                out.push(format!("  {}", line));
+            }
+            continue;
+        }
+        if let Some(info) = trimmed.strip_prefix("```").or_else(|| trimmed.strip_prefix("~~~")) {
+            flush(&mut paragraph, &mut out);
+            fence = Some(info.trim().to_string());
+            continue;
+        }
+
+        if trimmed.is_empty() {
+            flush(&mut paragraph, &mut out);
+            if out.last().is_some_and(|last| !last.is_empty()) {
+                out.push(String::new());
+            }
+            continue;
+        }
+
+        if let Some((level, heading)) = heading(trimmed) {
+            flush(&mut paragraph, &mut out);
+            for plain in wrap(&words(heading, false), width, "", "", false) {
+                let heading = if level == 1 { plain.to_uppercase() } else { plain };
+                out.push(if color { format!("{}{}{}", HEADING, heading, RESET) } else { heading });
+            }
+            continue;
+        }
+
+        if is_rule(trimmed) {
+            flush(&mut paragraph, &mut out);
+            out.push("─".repeat(if width == usize::MAX { RULE_WIDTH } else { width.min(RULE_WIDTH * 2) }));
+            continue;
+        }
+
+        // 표는 줄 모양을 그대로 둠
+        if trimmed.starts_with('|') {
+            flush(&mut paragraph, &mut out);
+            out.push(line.to_string());
+            continue;
+        }
+
+        if let Some(rest) = trimmed.strip_prefix('>') {
+            flush(&mut paragraph, &mut out);
+            let bar = if color { format!("{}│{} ", QUOTE, RESET) } else { "│ ".to_string() };
+            paragraph = Some((bar.clone(), bar, rest.trim().to_string()));
+            continue;
+        }
+
+        if let Some((marker, item)) = list_item(trimmed) {
+            flush(&mut paragraph, &mut out);
+            let indent = " ".repeat((line.len() - trimmed.len()) / 2 * 2);
+            let first = format!("{}{} ", indent, marker);
+            let next = " ".repeat(review::text_width(&first));
+            paragraph = Some((first, next, item.to_string()));
+            continue;
+        }
+
+        // 문단이나 목록 항목의 이어지는 줄
+        match &mut paragraph {
+            Some((_, _, text)) => {
+                text.push(' ');
+                text.push_str(trimmed);
+            }
+            None => paragraph = Some((String::new(), String::new(), trimmed.to_string())),
+        }
+    }
+    flush(&mut paragraph, &mut out);
+    while out.last().is_some_and(|last| last.is_empty()) {
+        out.pop();
+    }
+    out.join("\n")
+}
+
+/// `# 제목` (수준, 내용)
+fn heading(line: &str) -> Option<(usize, &str)> {
+    let level = line.chars().take_while(|c| *c == '#').count();
+    let rest = &line[level..];
+    ((1..=6).contains(&level) && (rest.is_empty() || rest.starts_with(' '))).then(|| (level, rest.trim().trim_end_matches('#').trim_end()))
+}
+
+/// `---`, `***`, `___` 구분선
+fn is_rule(line: &str) -> bool {
+    let marks: String = line.chars().filter(|c| !c.is_whitespace()).collect();
+    marks.len() >= 3 && ["-", "*", "_"].iter().any(|mark| marks.chars().all(|c| c.to_string() == *mark))
+}
+
+/// 목록 항목 (표시할 기호, 내용): `- a`, `* a`, `+ a`는 `•`, `1. a`와 `1) a`는 번호 그대로
+fn list_item(line: &str) -> Option<(String, &str)> {
+    for bullet in ["- ", "* ", "+ "] {
+        if let Some(item) = line.strip_prefix(bullet) {
+            return Some(("•".to_string(), item.trim()));
+        }
+    }
+    let digits = line.chars().take_while(char::is_ascii_digit).count();
+    let rest = &line[digits..];
+    if digits == 0 || digits > 3 {
+        return None;
+    }
+    let item = rest.strip_prefix(". ").or_else(|| rest.strip_prefix(") "))?;
+    Some((format!("{}.", &line[..digits]), item.trim()))
+}
+
+/// 줄 안의 표시(`**굵게**`, `` `코드` ``, `[글](주소)`)를 풀어 단어로 나눔
+/// 색을 쓰지 않으면 코드는 역따옴표를 남기고 굵은 글씨는 평문으로
+fn words(text: &str, color: bool) -> Vec<Word> {
+    let mut spans: Vec<(String, Style)> = Vec::new();
+    let mut plain = String::new();
+    let mut rest = text;
+    let push = |plain: &mut String, spans: &mut Vec<(String, Style)>, span: Option<(String, Style)>| {
+        if !plain.is_empty() {
+            spans.push((std::mem::take(plain), Style::Plain));
+        }
+        spans.extend(span);
+    };
+
+    while let Some(c) = rest.chars().next() {
+        if c == '`' {
+            if let Some(end) = rest[1..].find('`') {
+                let code = &rest[1..1 + end];
+                let span = if color { (code.to_string(), Style::Code) } else { (format!("`{}`", code), Style::Plain) };
+                push(&mut plain, &mut spans, Some(span));
+                rest = &rest[end + 2..];
+                continue;
+            }
+        } else if rest.starts_with("**") || rest.starts_with("__") {
+            let marker = &rest[..2];
+            if let Some(end) = rest[2..].find(marker).filter(|end| *end > 0) {
+                let style = if color { Style::Bold } else { Style::Plain };
+                push(&mut plain, &mut spans, Some((rest[2..2 + end].to_string(), style)));
+                rest = &rest[end + 4..];
+                continue;
+            }
+        } else if c == '[' {
+            if let Some((label, url, len)) = link(rest) {
+                plain.push_str(label);
+                if url != label {
+                    plain.push_str(&format!(" ({})", url));
+                }
+                rest = &rest[len..];
+                continue;
+            }
+        }
+        plain.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+    push(&mut plain, &mut spans, None);
+
+    // 공백에서 단어를 나누고, 공백 없이 붙은 조각은 한 단어로
+    let mut words: Vec<Word> = Vec::new();
+    let mut current: Word = Vec::new();
+    for (text, style) in spans {
+        for c in text.chars() {
+            if c.is_whitespace() {
+                if !current.is_empty() {
+                    words.push(std::mem::take(&mut current));
+                }
+                continue;
+            }
+            match current.last_mut() {
+                Some((piece, piece_style)) if *piece_style == style => piece.push(c),
+                _ => current.push((c.to_string(), style)),
+            }
+        }
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+/// `[글](주소)` (글, 주소, 표시 길이)
+fn link(text: &str) -> Option<(&str, &str, usize)> {
+    let close = text.find("](")?;
+    let end = text[close + 2..].find(')')?;
+    let label = &text[1..close];
+    (!label.contains('[')).then(|| (label, &text[close + 2..close + 2 + end], close + 3 + end))
+}
+
+/// 단어를 너비에 맞춰 줄로 나눔 (첫 줄은 `first`, 다음 줄은 `next`를 앞에 붙임)
+fn wrap(words: &[Word], width: usize, first: &str, next: &str, color: bool) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut line = first.to_string();
+    let mut used = review::text_width(&strip_ansi(first));
+    let mut empty = true;
+    for word in words {
+        let word_width: usize = word.iter().map(|(text, _)| review::text_width(text)).sum();
+        if !empty && used + 1 + word_width > width {
+            lines.push(std::mem::replace(&mut line, next.to_string()));
+            used = review::text_width(&strip_ansi(next));
+            empty = true;
+        }
+        if !empty {
+            line.push(' ');
+            used += 1;
+        }
+        for (text, style) in word {
+            match (color, style) {
+                (true, Style::Bold) => line.push_str(&format!("{}{}{}", BOLD, text, RESET)),
+                (true, Style::Code) => line.push_str(&format!("{}{}{}", CODE, text, RESET)),
+                _ => line.push_str(text),
+            }
+        }
+        used += word_width;
+        empty = false;
+    }
+    lines.push(line);
+    lines
+}
+
+/// 앞머리의 색 코드 제거 (너비 계산용)
+fn strip_ansi(text: &str) -> String {
+    let mut out = String::new();
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            chars.by_ref().find(|c| c.is_ascii_alphabetic());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_markdown() {
+        let text = "## Summary\n\nAdds **tuple** parsing to the `parser` module so that nested values work.\n\n\
+                    - First item that is long enough to wrap\n  - nested\n1. Step one\n\n> quoted text\n\n\
+                    ```rust\nfn main() {}\n```\n---\nSee [docs](https://example.com).";
+        let rendered = render(text, 30, false);
+        assert_eq!(
+            rendered.lines().collect::<Vec<_>>(),
+            vec![
+                "Summary",
+                "",
+                "Adds tuple parsing to the",
+                "`parser` module so that nested",
+                "values work.",
+                "",
+                "• First item that is long",
+                "  enough to wrap",
+                "  • nested",
+                "1. Step one",
+                "",
+                "│ quoted text",
+                "",
+                "  fn main() {}",
+                "──────────────────────────────",
+                "See docs",
+                "(https://example.com).",
+            ]
+        );
+
+        let colored = render("# Title\n**bold**, `code`", 80, true);
+        assert_eq!(colored, "\x1b[1;36mTITLE\x1b[0m\n\x1b[1mbold\x1b[0m, \x1b[33mcode\x1b[0m");
+        assert_eq!(render("한글 문장을 좁은 폭에 맞춤", 12, false), "한글 문장을\n좁은 폭에\n맞춤");
+    }
+
+    #[test]
+    fn test_render_wraps_to_width() {
+        // 제목: 단어 단위로 줄바꿈하고 1단계 제목은 줄마다 대문자
+        assert_eq!(render("# Parser changes for tuples", 12, false), "PARSER\nCHANGES FOR\nTUPLES");
+        assert_eq!(render("### Breaking changes", 10, true), "\x1b[1;36mBreaking\x1b[0m\n\x1b[1;36mchanges\x1b[0m");
+
+        // 목록: 다음 줄은 기호 너비만큼 내어 쓰고, 색 코드와 넓은 글자도 너비에 맞춤
+        let text = "- top level item wraps\n  - nested item wraps too\n10. numbered item wraps\n- 한글 항목도 줄을 바꿈";
+        assert_eq!(
+            render(text, 14, false).lines().collect::<Vec<_>>(),
+            vec!["• top level", "  item wraps", "  • nested", "    item wraps", "    too", "10. numbered", "    item wraps", "• 한글 항목도", "  줄을 바꿈"]
+        );
+        let colored = render("- use `wrap` **here**", 8, true);
+        assert_eq!(colored, "• use\n  \x1b[33mwrap\x1b[0m\n  \x1b[1mhere\x1b[0m");
+        assert!(colored.lines().all(|line| review::text_width(&strip_ansi(line)) <= 8));
+
+        // 코드 블록: 너비를 넘어도 줄바꿈하지 않고 들여 씀, 안의 `#`과 `-`는 마크다운으로 보지 않음
+        let text = "Run:\n\n```sh\n# comment line that is long\n- not a bullet\n```\nDone.";
+        assert_eq!(
+            render(text, 10, false).lines().collect::<Vec<_>>(),
+            vec!["Run:", "", "  # comment line that is long", "  - not a bullet", "Done."]
+        );
+    }
+}
@@ -0,0 +1,155 @@
+use anyhow::{Result, anyhow};
+use globset::Glob;
+use serde::{Deserialize, Serialize};
+
+use super::protocol::Tool;
+use super::registry::ServerConfig;
+use crate::ai_utils;
+use crate::audit;
+use crate::security::{ApprovalOption, CommandType, SecurityManager};
+
+/// MCP 도구 호출 승인
+///
+/// 모든 `tools/call`은 `SecurityManager`를 거칩니다. 도구는 레지스트리의 `toolAccess` 설정,
+/// 없으면 서버가 알려 준 `readOnlyHint` 주석으로 읽기 전용/변경으로 분류하며 (둘 다 없으면 변경),
+/// 변경 도구는 인자를 보여 주고 승인을 받은 뒤 호출합니다. 세션 승인은 도구 이름 단위입니다.
+///
+/// ```json
+/// "github": {
+///   "command": "npx",
+///   "toolAccess": { "get_*": "read-only", "list_*": "read-only", "merge_pull_request": "mutating" }
+/// }
+/// ```
+///
+/// 승인 프롬프트에 보여 주는 인자의 최대 줄 수
+const MAX_ARGUMENT_LINES: usize = 20;
+
+/// 도구가 외부 상태를 바꾸는지
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ToolAccess {
+    ReadOnly,
+    Mutating,
+}
+
+impl ToolAccess {
+    /// 승인 단위가 되는 명령어 종류
+    pub fn command_type(self) -> CommandType {
+        match self {
+            ToolAccess::ReadOnly => CommandType::McpRead,
+            ToolAccess::Mutating => CommandType::McpWrite,
+        }
+    }
+}
+
+/// 도구 분류: `toolAccess`의 정확한 이름, 글롭 패턴, 서버의 `readOnlyHint` 순으로 확인하고 없으면 변경으로 간주
+pub fn classify_tool(config: &ServerConfig, tool: &Tool) -> ToolAccess {
+    if let Some(access) = config.tool_access.get(&tool.name) {
+        return *access;
+    }
+    let by_pattern = config.tool_access.iter().find_map(|(pattern, access)| {
+        let matcher = Glob::new(pattern).ok()?.compile_matcher();
+        matcher.is_match(&tool.name).then_some(*access)
+    });
+    if let Some(access) = by_pattern {
+        return access;
+    }
+
+    match tool.annotations.as_ref().and_then(|annotations| annotations.read_only_hint) {
+        Some(true) => ToolAccess::ReadOnly,
+        _ => ToolAccess::Mutating,
+    }
+}
+
+/// 승인 프롬프트에 보여 줄 인자 (보기 좋게 정렬하고 길면 자름)
+pub fn format_arguments(arguments: Option<&serde_json::Value>) -> String {
+    let Some(arguments) = arguments.filter(|arguments| !arguments.is_null()) else {
+        return "(none)".to_string();
+    };
+    let pretty = serde_json::to_string_pretty(arguments).unwrap_or_else(|_| arguments.to_string());
+    let lines: Vec<&str> = pretty.lines().collect();
+    if lines.len() <= MAX_ARGUMENT_LINES {
+        return pretty;
+    }
+    format!("{}\n… ({} more lines)", lines[..MAX_ARGUMENT_LINES].join("\n"), lines.len() - MAX_ARGUMENT_LINES)
+}
+
+/// 도구 호출 허용 여부 확인 (읽기 전용 도구는 바로 허용, 변경 도구는 세션 승인이 없으면 물어봄)
+/// 비대화형 실행에서는 물어볼 수 없으므로 세션 승인이 없는 변경 도구는 거부
+pub fn authorize_tool_call(server: &str, tool: &Tool, access: ToolAccess, arguments: Option<&serde_json::Value>) -> Result<()> {
+    if access == ToolAccess::ReadOnly {
+        return Ok(());
+    }
+
+    let destructive = tool.annotations.as_ref().and_then(|annotations| annotations.destructive_hint).unwrap_or(false);
+    authorize_external_action(&format!("{}/{}", server, tool.name), arguments, destructive)
+}
+
+/// 외부 상태를 바꾸는 작업(변경 도구, 웹훅 전송) 승인 (`qualified`는 `서버/도구` 형식의 승인 단위)
+pub fn authorize_external_action(qualified: &str, arguments: Option<&serde_json::Value>, destructive: bool) -> Result<()> {
+    let command_type = ToolAccess::Mutating.command_type();
+    let mut security_manager = SecurityManager::default();
+    if ai_utils::is_non_interactive() && !security_manager.is_tool_approved(qualified, command_type) {
+        audit::record_command(&format!("mcp {}", qualified), None);
+        crate::transcript::approval(qualified, "no (needs interactive approval)");
+        return Err(anyhow!(
+            "MCP tool '{}' changes external state and needs interactive approval (approve it for the session first)",
+            qualified
+        ));
+    }
+
+    let arguments = format_arguments(arguments);
+    match security_manager.prompt_tool_approval(qualified, &arguments, command_type, destructive)? {
+        ApprovalOption::Yes | ApprovalOption::YesForSession => Ok(()),
+        ApprovalOption::No | ApprovalOption::EditAndRetry => {
+            audit::record_command(&format!("mcp {}", qualified), None);
+            Err(anyhow!("MCP tool call '{}' cancelled by user", qualified))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tool(value: serde_json::Value) -> Tool {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_classify_tool() {
+        let search = tool(serde_json::json!({
+            "name": "search_issues",
+            "inputSchema": {"type": "object"},
+            "annotations": {"readOnlyHint": true}
+        }));
+        let create = tool(serde_json::json!({"name": "create_issue", "inputSchema": {"type": "object"}}));
+        let list = tool(serde_json::json!({"name": "list_commits", "inputSchema": {"type": "object"}}));
+
+        let config = ServerConfig::default();
+        assert_eq!(classify_tool(&config, &search), ToolAccess::ReadOnly);
+        assert_eq!(classify_tool(&config, &create), ToolAccess::Mutating);
+        assert_eq!(classify_tool(&config, &list), ToolAccess::Mutating);
+
+        // 설정이 주석보다 우선하고, 정확한 이름이 글롭보다 우선
+        let config: ServerConfig = serde_json::from_value(serde_json::json!({
+            "command": "server",
+            "toolAccess": {"list_*": "read-only", "search_*": "read-only", "search_issues": "mutating"}
+        }))
+        .unwrap();
+        assert_eq!(classify_tool(&config, &search), ToolAccess::Mutating);
+        assert_eq!(classify_tool(&config, &list), ToolAccess::ReadOnly);
+        assert_eq!(classify_tool(&config, &create), ToolAccess::Mutating);
+    }
+
+    #[test]
+    fn test_format_arguments() {
+        assert_eq!(format_arguments(None), "(none)");
+        assert_eq!(format_arguments(Some(&serde_json::json!({"title": "Bug"}))), "{\n  \"title\": \"Bug\"\n}");
+
+        let long: Vec<u32> = (0..40).collect();
+        let formatted = format_arguments(Some(&serde_json::json!(long)));
+        assert_eq!(formatted.lines().count(), MAX_ARGUMENT_LINES + 1);
+        assert!(formatted.ends_with("(22 more lines)"));
+    }
+}
@@ -0,0 +1,155 @@
+//! 도구 결과 캐시
+//!
+//! 읽기 전용 도구(목록 조회, 검색 등)의 성공한 결과를 도구와 인자 기준으로 잠시 보관해, 에이전트가 같은 조회를
+//! 반복해도 서버(와 그 뒤의 GitHub, Jira 같은 서비스 API)를 다시 호출하지 않습니다. 보관 시간은 서버 설정의
+//! `cacheTtl`(초, 기본 60, 0이면 사용 안 함)이고, 같은 서버의 변경 도구를 호출하면 그 서버의 캐시를 비웁니다.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use super::approval::ToolAccess;
+use super::protocol::{CallToolResult, Tool};
+use super::registry::ServerConfig;
+
+/// 기본 보관 시간
+pub const DEFAULT_CACHE_TTL: Duration = Duration::from_secs(60);
+
+/// 서버의 캐시 보관 시간 (0이면 None)
+pub fn cache_ttl(config: &ServerConfig) -> Option<Duration> {
+    let ttl = config.cache_ttl.map(Duration::from_secs).unwrap_or(DEFAULT_CACHE_TTL);
+    (!ttl.is_zero()).then_some(ttl)
+}
+
+/// 결과를 캐시해도 되는 도구인지 (읽기 전용이고 서버가 멱등이 아니라고 표시하지 않음)
+pub fn is_cacheable(tool: &Tool, access: ToolAccess) -> bool {
+    access == ToolAccess::ReadOnly
+        && tool.annotations.as_ref().and_then(|annotations| annotations.idempotent_hint) != Some(false)
+}
+
+/// 캐시 키 (인자 객체의 키 순서가 달라도 같은 키)
+fn cache_key(tool: &str, arguments: Option<&serde_json::Value>) -> String {
+    let mut key = format!("{}\0", tool);
+    if let Some(arguments) = arguments {
+        write_canonical(arguments, &mut key);
+    }
+    key
+}
+
+/// 객체 키를 정렬해 JSON으로 직렬화
+fn write_canonical(value: &serde_json::Value, out: &mut String) {
+    match value {
+        serde_json::Value::Object(map) => {
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+            out.push('{');
+            for (i, (name, value)) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                out.push_str(&serde_json::Value::String(name.clone()).to_string());
+                out.push(':');
+                write_canonical(value, out);
+            }
+            out.push('}');
+        }
+        serde_json::Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_canonical(item, out);
+            }
+            out.push(']');
+        }
+        value => out.push_str(&value.to_string()),
+    }
+}
+
+/// 서버 이름 → (캐시 키 → (저장 시각, 결과))
+#[derive(Default)]
+pub struct ToolResultCache {
+    entries: HashMap<String, HashMap<String, (Instant, CallToolResult)>>,
+}
+
+impl ToolResultCache {
+    /// 보관 시간이 지나지 않은 결과
+    pub fn get(&mut self, server: &str, tool: &str, arguments: Option<&serde_json::Value>, ttl: Duration) -> Option<CallToolResult> {
+        let entries = self.entries.get_mut(server)?;
+        let key = cache_key(tool, arguments);
+        match entries.get(&key) {
+            Some((stored, result)) if stored.elapsed() < ttl => Some(result.clone()),
+            Some(_) => {
+                entries.remove(&key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    /// 성공한 결과 저장 (도구 오류 결과는 저장하지 않음)
+    pub fn insert(&mut self, server: &str, tool: &str, arguments: Option<&serde_json::Value>, result: &CallToolResult) {
+        if result.is_error() {
+            return;
+        }
+        self.entries
+            .entry(server.to_string())
+            .or_default()
+            .insert(cache_key(tool, arguments), (Instant::now(), result.clone()));
+    }
+
+    /// 서버의 캐시 비우기 (변경 도구를 호출한 뒤)
+    pub fn invalidate(&mut self, server: &str) {
+        self.entries.remove(server);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(text: &str, is_error: bool) -> CallToolResult {
+        serde_json::from_value(serde_json::json!({
+            "content": [{"type": "text", "text": text}],
+            "isError": is_error
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_tool_result_cache() {
+        let mut cache = ToolResultCache::default();
+        let ttl = Duration::from_secs(60);
+        let arguments = serde_json::json!({"state": "open", "repo": "ai-cli"});
+
+        cache.insert("github", "list_issues", Some(&arguments), &result("#1 Bug", false));
+        let reordered = serde_json::json!({"repo": "ai-cli", "state": "open"});
+        assert_eq!(cache.get("github", "list_issues", Some(&reordered), ttl).unwrap().text(), "#1 Bug");
+        assert!(cache.get("github", "list_issues", None, ttl).is_none());
+        assert!(cache.get("github", "list_issues", Some(&arguments), Duration::ZERO).is_none());
+
+        cache.insert("github", "get_issue", None, &result("Not Found", true));
+        assert!(cache.get("github", "get_issue", None, ttl).is_none());
+
+        cache.insert("github", "list_issues", Some(&arguments), &result("#1 Bug", false));
+        cache.invalidate("github");
+        assert!(cache.get("github", "list_issues", Some(&arguments), ttl).is_none());
+    }
+
+    #[test]
+    fn test_is_cacheable() {
+        let tool = |annotations: serde_json::Value| -> Tool {
+            serde_json::from_value(serde_json::json!({
+                "name": "search", "inputSchema": {"type": "object"}, "annotations": annotations
+            }))
+            .unwrap()
+        };
+        assert!(is_cacheable(&tool(serde_json::json!({})), ToolAccess::ReadOnly));
+        assert!(!is_cacheable(&tool(serde_json::json!({})), ToolAccess::Mutating));
+        assert!(!is_cacheable(&tool(serde_json::json!({"idempotentHint": false})), ToolAccess::ReadOnly));
+
+        let config: ServerConfig = serde_json::from_value(serde_json::json!({"command": "server", "cacheTtl": 0})).unwrap();
+        assert_eq!(cache_ttl(&config), None);
+        assert_eq!(cache_ttl(&ServerConfig::default()), Some(DEFAULT_CACHE_TTL));
+    }
+}
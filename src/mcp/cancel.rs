@@ -0,0 +1,98 @@
+//! 요청 취소 (`notifications/cancelled`)
+//!
+//! 도구 호출 중에 Ctrl-C를 누르면 서버에 취소 알림을 보내 서버 쪽 작업도 멈추게 하고, 호출은 `Cancelled` 오류로 끝납니다.
+//! Ctrl-C 처리기는 한 번 등록되면 프로세스가 끝날 때까지 남으므로, 진행 중인 호출이 없을 때 누르면 평소처럼 종료(130)합니다.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Once, OnceLock};
+use tokio::sync::Notify;
+
+/// 취소 알림 메서드
+pub const CANCELLED_NOTIFICATION: &str = "notifications/cancelled";
+
+/// 서버에 알리는 취소 이유
+const CANCEL_REASON: &str = "User cancelled the request (Ctrl-C)";
+
+/// Ctrl-C로 종료할 때의 종료 코드 (128 + SIGINT)
+const INTERRUPTED_EXIT_CODE: i32 = 130;
+
+/// 진행 중인 도구 호출 수
+static IN_FLIGHT: AtomicUsize = AtomicUsize::new(0);
+
+/// Ctrl-C 감시 작업 시작 여부
+static WATCHER: Once = Once::new();
+
+/// 사용자가 요청을 취소함
+#[derive(Debug, thiserror::Error)]
+#[error("MCP request cancelled by user")]
+pub struct Cancelled;
+
+/// 오류가 사용자 취소 때문인지
+pub fn is_cancelled(error: &anyhow::Error) -> bool {
+    error.chain().any(|cause| cause.downcast_ref::<Cancelled>().is_some())
+}
+
+/// 취소 알림 메시지
+pub fn notification(request_id: &str) -> serde_json::Value {
+    serde_json::json!({
+        "jsonrpc": super::protocol::MCPMessage::JSONRPC_VERSION,
+        "method": CANCELLED_NOTIFICATION,
+        "params": {"requestId": request_id, "reason": CANCEL_REASON},
+    })
+}
+
+fn cancel_requests() -> &'static Notify {
+    static CANCEL: OnceLock<Notify> = OnceLock::new();
+    CANCEL.get_or_init(Notify::new)
+}
+
+/// 진행 중인 도구 호출 표시 (버리면 해제)
+pub struct InFlight(());
+
+impl InFlight {
+    /// 호출 시작 (처음이면 Ctrl-C 감시 작업도 시작)
+    pub fn start() -> Self {
+        WATCHER.call_once(|| {
+            tokio::spawn(async {
+                // Ctrl-C를 받는 곳은 여기 한 곳뿐이므로 호출 종료와 경합하지 않음
+                while tokio::signal::ctrl_c().await.is_ok() {
+                    if IN_FLIGHT.load(Ordering::SeqCst) == 0 {
+                        eprintln!();
+                        std::process::exit(INTERRUPTED_EXIT_CODE);
+                    }
+                    cancel_requests().notify_waiters();
+                }
+            });
+        });
+        IN_FLIGHT.fetch_add(1, Ordering::SeqCst);
+        Self(())
+    }
+
+    /// 사용자가 Ctrl-C를 누를 때까지 대기
+    pub async fn cancelled(&self) {
+        cancel_requests().notified().await;
+    }
+}
+
+impl Drop for InFlight {
+    fn drop(&mut self) {
+        IN_FLIGHT.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_notification() {
+        let message = notification("42");
+        assert_eq!(message["method"], "notifications/cancelled");
+        assert_eq!(message["params"]["requestId"], "42");
+        assert!(message.get("id").is_none());
+
+        let error = anyhow::Error::new(Cancelled).context("tools/call failed");
+        assert!(is_cancelled(&error));
+        assert!(!is_cancelled(&anyhow::anyhow!("timeout")));
+    }
+}
@@ -4,202 +4,929 @@
 
 use anyhow::{Result, anyhow};
 use serde_json;
-use std::collections::HashMap;
-use std::process::{Command, Stdio};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use tokio::process::{Command as TokioCommand};
 use tokio::io::{AsyncBufReadExt, BufReader};
-use futures::StreamExt;
+use tracing::Instrument;
 
+use super::approval;
+use super::cache::{self, ToolResultCache};
+use super::cancel;
+use super::connection::{ConnectionLost, ServerConnection, ServerStatus};
+use super::dispatch::Dispatcher;
+use super::logging;
+use super::negotiation;
+use super::oauth;
+use super::progress;
 use super::protocol::*;
+use super::sampling;
+use super::schema;
+use super::registry::{McpRegistry, ServerConfig, ServerTransport, REGISTRY_FILE};
+use super::roots;
+use super::sse::SseSession;
+use super::streamable::{HttpStatusError, StreamableSession, STREAMABLE_PROTOCOL_VERSION};
+use crate::audit;
+use crate::policy::Policy;
+use crate::sandbox::{Sandbox, SandboxBackend};
+use crate::transcript;
+
+/// 동시에 실행하는 도구 호출 수 기본값
+pub const DEFAULT_CONCURRENCY: usize = 4;
+
+/// 동시에 실행하는 도구 호출 수 (`AI_CLI_MCP_CONCURRENCY`, 1이면 한 번에 하나씩)
+pub fn concurrency_limit() -> usize {
+    std::env::var("AI_CLI_MCP_CONCURRENCY")
+        .ok()
+        .and_then(|value| value.trim().parse::<usize>().ok())
+        .filter(|limit| *limit > 0)
+        .unwrap_or(DEFAULT_CONCURRENCY)
+}
 
 /// MCP 클라이언트
+/// 레지스트리(`~/.ai-cli/mcp.json`)의 모든 서버에 연결해 도구를 한 목록으로 모으고, 호출은 도구를 제공한 서버로 보냄
+/// 서버별 연결은 `ServerConnection`이 관리하며 끊기면 다시 연결함
 pub struct MCPClient {
     name: String,
     version: String,
-    server_url: String,
-    tools: Arc<Mutex<HashMap<String, Tool>>>,
+    registry: McpRegistry,
+    /// 도구 이름 → (서버 이름, 도구 정의)
+    tools: Arc<Mutex<HashMap<String, (String, Tool)>>>,
     initialized: Arc<Mutex<bool>>,
+    connections: tokio::sync::Mutex<BTreeMap<String, Arc<tokio::sync::Mutex<ServerConnection>>>>,
+    /// 읽기 전용 도구 결과 캐시
+    cache: Mutex<ToolResultCache>,
+    /// 동시에 실행하는 도구 호출 수 제한
+    calls: tokio::sync::Semaphore,
+    /// 도구 호출에 적용할 정책 (없으면 호출할 때마다 로드)
+    pub(super) policy: Option<Policy>,
+}
+
+/// 승인까지 마쳐 서버에 보낼 도구 호출
+struct PreparedCall {
+    server: String,
+    tool: Tool,
+    arguments: Option<serde_json::Value>,
+    access: approval::ToolAccess,
+    cache_ttl: Option<Duration>,
+}
+
+/// 도구 호출 준비 결과
+enum CallPlan {
+    /// 캐시에 있던 결과 (서버를 호출하지 않음)
+    Cached(CallToolResult),
+    Ready(Box<PreparedCall>),
 }
 
 impl MCPClient {
     /// 새 MCP 클라이언트 생성
-    pub fn new(name: String, version: String, server_url: String) -> Self {
+    pub fn new(name: String, version: String, registry: McpRegistry) -> Self {
         Self {
             name,
             version,
-            server_url,
+            registry,
             tools: Arc::new(Mutex::new(HashMap::new())),
             initialized: Arc::new(Mutex::new(false)),
+            connections: tokio::sync::Mutex::new(BTreeMap::new()),
+            cache: Mutex::new(ToolResultCache::default()),
+            calls: tokio::sync::Semaphore::new(concurrency_limit()),
+            policy: None,
         }
     }
 
-    /// MCP 서버에 연결 및 초기화
+    /// 레지스트리의 서버에 연결 및 초기화
+    /// 일부 서버에 연결하지 못해도 나머지 서버의 도구는 사용할 수 있고, 하나도 연결하지 못하면 오류
     pub async fn initialize(&self) -> Result<()> {
-        // stdio 방식의 서버 연결
-        if self.server_url.starts_with("stdio://") {
-            self.initialize_stdio().await
-        } else if self.server_url.starts_with("http://") || self.server_url.starts_with("https://") {
-            self.initialize_http().await
-        } else {
-            Err(anyhow!("Unsupported server URL format: {}", self.server_url))
+        if self.registry.is_empty() {
+            return Err(anyhow!("No MCP servers configured (add them to ~/.ai-cli/{})", REGISTRY_FILE));
+        }
+
+        let failures = self.connect_servers().await;
+        for (server, e) in &failures {
+            println!("⚠️  MCP server '{}' is unavailable: {}", server, e);
+        }
+
+        if !self.is_initialized() {
+            let names: Vec<&str> = failures.iter().map(|(server, _)| server.as_str()).collect();
+            return Err(anyhow!("Could not connect to any MCP server ({})", names.join(", ")));
+        }
+        Ok(())
+    }
+
+    /// 모든 서버에 연결하고 연결하지 못한 서버와 오류 반환 (출력 없음)
+    pub async fn connect_servers(&self) -> Vec<(String, anyhow::Error)> {
+        let client_info = ClientInfo { name: self.name.clone(), version: self.version.clone() };
+        let mut connections = self.connections.lock().await;
+        let mut failures = Vec::new();
+        for (server, config) in self.registry.servers() {
+            let mut connection = ServerConnection::new(server, config.clone(), client_info.clone());
+            match connection.connect().await {
+                Ok(server_tools) => {
+                    self.register_tools(server, server_tools);
+                    tracing::info!("Connected to MCP server '{}'", server);
+                }
+                Err(e) => failures.push((server.clone(), e)),
+            }
+            connections.insert(server.clone(), Arc::new(tokio::sync::Mutex::new(connection)));
+        }
+
+        if failures.len() < connections.len() {
+            *self.initialized.lock().unwrap() = true;
+        }
+        failures
+    }
+
+    /// 서버의 도구 등록
+    /// 다른 서버가 이미 같은 이름의 도구를 등록했으면 `서버.도구` 이름으로 등록
+    fn register_tools(&self, server: &str, server_tools: &[Tool]) {
+        let mut tools = self.tools.lock().unwrap();
+        for tool in server_tools {
+            let name = if tools.contains_key(&tool.name) {
+                let qualified = format!("{}.{}", server, tool.name);
+                tracing::warn!("MCP tool '{}' from '{}' is registered as '{}'", tool.name, server, qualified);
+                qualified
+            } else {
+                tool.name.clone()
+            };
+            tracing::debug!("Loaded MCP tool: {}", name);
+            tools.insert(name, (server.to_string(), tool.clone()));
+        }
+    }
+
+    /// 도구 호출 (도구를 제공한 서버로 전달, 연결이 끊겼으면 다시 연결한 뒤 호출)
+    /// 변경 도구는 `SecurityManager` 승인을 받은 뒤 호출하고, 모든 호출을 감사 로그에 기록
+    /// 읽기 전용 도구는 캐시 시간 안에 같은 인자로 호출한 결과가 있으면 서버를 호출하지 않고 그 결과를 반환
+    pub async fn call_tool(&self, tool_name: &str, arguments: Option<serde_json::Value>) -> Result<CallToolResult> {
+        match self.prepare_call(tool_name, arguments)? {
+            CallPlan::Cached(result) => Ok(result),
+            CallPlan::Ready(call) => self.execute(call).await,
+        }
+    }
+
+    /// 서로 관계없는 도구 호출 여러 개를 동시에 실행하고 결과를 호출 순서대로 반환
+    /// 승인은 호출 순서대로 하나씩 받고, 승인된 호출만 `AI_CLI_MCP_CONCURRENCY`개까지 동시에 서버에 보냄
+    /// (같은 서버에 보낸 요청도 요청 ID로 응답을 구분하므로 한 세션에서 동시에 처리됨)
+    pub async fn call_tools(self: &Arc<Self>, calls: Vec<(String, Option<serde_json::Value>)>) -> Vec<Result<CallToolResult>> {
+        let mut tasks = Vec::new();
+        for (tool_name, arguments) in calls {
+            let plan = self.prepare_call(&tool_name, arguments);
+            let client = Arc::clone(self);
+            let task = tokio::spawn(async move {
+                match plan? {
+                    CallPlan::Cached(result) => Ok(result),
+                    CallPlan::Ready(call) => client.execute(call).await,
+                }
+            });
+            tasks.push((tool_name, task));
+        }
+
+        let mut results = Vec::new();
+        for (tool_name, task) in tasks {
+            results.push(task.await.unwrap_or_else(|e| Err(anyhow!("MCP tool '{}' call failed: {}", tool_name, e))));
         }
+        results
     }
 
-    /// stdio를 통한 서버 초기화
-    async fn initialize_stdio(&self) -> Result<()> {
-        // GitHub MCP 서버 예시 (실제로는 설치된 서버 실행)
-        let mut child = TokioCommand::new("npx")
-            .args(["@modelcontextprotocol/server-github"])
+    /// 호출할 도구를 찾아 인자를 검증하고, 캐시를 확인한 뒤 보안 승인
+    fn prepare_call(&self, tool_name: &str, arguments: Option<serde_json::Value>) -> Result<CallPlan> {
+        if !*self.initialized.lock().unwrap() {
+            return Err(anyhow!("MCP client not initialized"));
+        }
+
+        let (server, tool) = self
+            .tools
+            .lock()
+            .unwrap()
+            .get(tool_name)
+            .cloned()
+            .ok_or_else(|| anyhow!("Tool '{}' not found", tool_name))?;
+        // 모델이 만든 잘못된 인자는 승인을 묻거나 서버에 보내기 전에 거부
+        schema::validate_arguments(&tool, arguments.as_ref())?;
+
+        let config = self
+            .registry
+            .get(&server)
+            .ok_or_else(|| anyhow!("MCP server '{}' is not configured", server))?;
+        let access = approval::classify_tool(config, &tool);
+        let cache_ttl = cache::cache_ttl(config).filter(|_| cache::is_cacheable(&tool, access));
+        if let Some(ttl) = cache_ttl {
+            if let Some(result) = self.cache.lock().unwrap().get(&server, &tool.name, arguments.as_ref(), ttl) {
+                tracing::debug!("Using cached result for MCP tool '{}/{}'", server, tool.name);
+                return Ok(CallPlan::Cached(result));
+            }
+        }
+        // 파일을 바꾸는 도구가 `paths.writable` 밖에 쓰려 하면 승인을 묻기 전에 거부
+        if access == approval::ToolAccess::Mutating {
+            let policy = match &self.policy {
+                Some(policy) => policy.clone(),
+                None => Policy::load()?,
+            };
+            policy
+                .check_writable_arguments(arguments.as_ref())
+                .map_err(|e| anyhow!("MCP tool '{}/{}' refused: {}", server, tool.name, e))?;
+        }
+        approval::authorize_tool_call(&server, &tool, access, arguments.as_ref())?;
+        Ok(CallPlan::Ready(Box::new(PreparedCall { server, tool, arguments, access, cache_ttl })))
+    }
+
+    /// 준비한 호출을 서버에 보내고 캐시와 감사 로그 갱신 (동시 호출 수 제한 안에서 실행)
+    async fn execute(&self, call: Box<PreparedCall>) -> Result<CallToolResult> {
+        let PreparedCall { server, tool, arguments, access, cache_ttl } = *call;
+        let _permit = self.calls.acquire().await.map_err(|e| anyhow!("MCP client is shutting down: {}", e))?;
+        let connection = self
+            .connections
+            .lock()
+            .await
+            .get(&server)
+            .cloned()
+            .ok_or_else(|| anyhow!("MCP server '{}' is not configured", server))?;
+        let result = ServerConnection::call_tool(&connection, &tool.name, arguments.clone()).await;
+        match &result {
+            Ok(result) if cache_ttl.is_some() => self.cache.lock().unwrap().insert(&server, &tool.name, arguments.as_ref(), result),
+            // 변경 도구를 호출하면(실패했더라도) 서버 상태가 바뀌었을 수 있으므로 캐시를 비움
+            _ if access == approval::ToolAccess::Mutating => self.cache.lock().unwrap().invalidate(&server),
+            _ => {}
+        }
+        let exit_code = match &result {
+            Ok(result) if !result.is_error() => 0,
+            _ => 1,
+        };
+        audit::record_command(&format!("mcp {}/{}", server, tool.name), Some(exit_code));
+        let text = match &result {
+            Ok(result) => result.text(),
+            Err(e) => e.to_string(),
+        };
+        transcript::tool_call(&format!("{}/{}", server, tool.name), arguments.as_ref(), &text, exit_code != 0);
+        result
+    }
+
+    /// 서버별 상태 확인 (연결된 서버는 ping으로 응답 시간 측정)
+    pub async fn health_check(&self) -> Vec<ServerStatus> {
+        let mut statuses = Vec::new();
+        for connection in self.connections.lock().await.values() {
+            statuses.push(connection.lock().await.health_check().await);
+        }
+        statuses
+    }
+
+    /// 사용 가능한 도구 목록 반환
+    pub fn list_tools(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.tools.lock().unwrap().keys().cloned().collect();
+        names.sort();
+        names
+    }
+
+    /// 도구 정보 조회
+    pub fn get_tool(&self, name: &str) -> Option<Tool> {
+        self.tools.lock().unwrap()
+            .get(name)
+            .map(|(_, tool)| tool.clone())
+    }
+
+    /// 조건에 맞는 서버가 제공하는 도구의 등록 이름 (`tool`은 서버가 알려 준 원래 도구 이름)
+    pub fn find_server_tool(&self, tool: &str, server: impl Fn(&str, &ServerConfig) -> bool) -> Option<String> {
+        let tools = self.tools.lock().unwrap();
+        let mut names: Vec<&String> = tools
+            .iter()
+            .filter(|(_, (server_name, definition))| {
+                definition.name == tool && self.registry.get(server_name).is_some_and(|config| server(server_name, config))
+            })
+            .map(|(name, _)| name)
+            .collect();
+        names.sort();
+        names.first().map(|name| name.to_string())
+    }
+
+    /// 클라이언트가 초기화되었는지 확인
+    pub fn is_initialized(&self) -> bool {
+        *self.initialized.lock().unwrap()
+    }
+
+    /// 모든 서버 연결 종료 (stdio 서버는 종료되고, Streamable HTTP 세션은 서버에 종료를 알림)
+    pub async fn shutdown(&self) {
+        for connection in self.connections.lock().await.values() {
+            connection.lock().await.close().await;
+        }
+        *self.initialized.lock().unwrap() = false;
+    }
+}
+
+/// 연결된 MCP 서버 세션
+pub enum ServerSession {
+    Stdio(StdioSession),
+    Http(HttpSession),
+}
+
+impl ServerSession {
+    /// 레지스트리 설정으로 서버에 연결 (원격 서버는 외부 전송 허용 목록을 따름)
+    /// `name`은 샘플링 승인 프롬프트 등에서 서버를 가리키는 이름
+    /// OAuth가 필요한 원격 서버는 저장한 토큰을 사용하고, 없거나 거부되면 브라우저로 로그인
+    pub async fn connect(name: &str, config: &ServerConfig, client_info: ClientInfo) -> Result<Self> {
+        let log_level = logging::requested_level(name, config);
+        match config.transport()? {
+            ServerTransport::Stdio { command, args, env, cwd } => {
+                Ok(Self::Stdio(StdioSession::connect(name, &command, &args, &env, cwd.as_deref(), client_info, log_level).await?))
+            }
+            ServerTransport::Http { url, mut headers } => {
+                // 직접 지정한 인증 헤더가 없으면 저장한 OAuth 토큰을 사용하고, 401이면 로그인 후 한 번 재시도
+                if headers.iter().any(|(name, _)| name.eq_ignore_ascii_case("authorization")) {
+                    return Ok(Self::Http(HttpSession::connect(name, &url, &headers, client_info, log_level).await?));
+                }
+                let mut with_token = headers.clone();
+                if let Some(token) = oauth::access_token(&url).await? {
+                    with_token.push(("Authorization".to_string(), format!("Bearer {}", token)));
+                }
+                match HttpSession::connect(name, &url, &with_token, client_info.clone(), log_level).await {
+                    Err(e) if oauth::is_unauthorized(&e) => {
+                        let token = oauth::authorize(name, &url, oauth::authenticate_header(&e)).await?;
+                        headers.push(("Authorization".to_string(), format!("Bearer {}", token)));
+                        Ok(Self::Http(HttpSession::connect(name, &url, &headers, client_info, log_level).await?))
+                    }
+                    session => Ok(Self::Http(session?)),
+                }
+            }
+        }
+    }
+
+    /// 서버의 모든 도구 목록
+    pub async fn list_tools(&self) -> Result<Vec<Tool>> {
+        match self {
+            Self::Stdio(session) => session.list_tools().await,
+            Self::Http(session) => session.list_tools().await,
+        }
+    }
+
+    /// 도구 호출 (서버가 보낸 진행 알림은 호출 중에 진행 줄로 표시)
+    pub async fn call_tool(&self, tool_name: &str, arguments: Option<serde_json::Value>) -> Result<CallToolResult> {
+        let result = match self {
+            Self::Stdio(session) => session.call_tool(tool_name, arguments).await,
+            Self::Http(session) => session.call_tool(tool_name, arguments).await,
+        };
+        progress::finish();
+        result
+    }
+
+    /// 연결 확인 요청
+    pub async fn ping(&self) -> Result<()> {
+        match self {
+            Self::Stdio(session) => session.ping().await,
+            Self::Http(session) => session.ping().await,
+        }
+    }
+
+    /// 알림 전송 (응답을 기다리지 않음)
+    pub async fn notify(&self, message: &serde_json::Value) -> Result<()> {
+        match self {
+            Self::Stdio(session) => session.send(message).await,
+            Self::Http(session) => session.notify(message).await,
+        }
+    }
+
+    /// 세션이 아직 쓸 수 있는지 (stdio 서버 프로세스나 SSE 스트림이 끝났으면 false, Streamable HTTP는 요청해 봐야 알 수 있음)
+    pub fn is_alive(&self) -> bool {
+        match self {
+            Self::Stdio(session) => session.is_alive(),
+            Self::Http(session) => session.is_alive(),
+        }
+    }
+
+    /// 세션 종료 (stdio 서버 프로세스는 세션을 버릴 때 종료됨)
+    pub async fn close(&self) {
+        if let Self::Http(session) = self {
+            session.close().await;
+        }
+    }
+}
+
+/// stdio 세션에서 요청 하나의 응답을 기다리는 시간 (진행 알림을 받으면 다시 시작)
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// stdio MCP 서버 세션
+/// 지정한 명령어로 서버를 실행해 초기화하고, JSON-RPC 요청과 응답을 한 줄씩 주고받음 (세션을 버리면 서버도 종료)
+/// 서버 출력은 읽기 작업이 받아 요청 ID별로 나눠 주므로 여러 요청을 동시에 보낼 수 있음
+pub struct StdioSession {
+    server: String,
+    child: Mutex<tokio::process::Child>,
+    stdin: Arc<tokio::sync::Mutex<tokio::process::ChildStdin>>,
+    dispatcher: Arc<Dispatcher>,
+    reader: tokio::task::JoinHandle<()>,
+}
+
+impl StdioSession {
+    /// 서버 실행 및 초기화 핸드셰이크 (`env`는 서버에 추가로 전달할 환경 변수, `cwd`는 작업 디렉토리)
+    /// 서버가 `logging` 기능을 알리면 `log_level` 이상의 로그를 보내도록 요청
+    pub async fn connect(
+        server: &str,
+        program: &str,
+        args: &[String],
+        env: &[(String, String)],
+        cwd: Option<&Path>,
+        client_info: ClientInfo,
+        log_level: &str,
+    ) -> Result<Self> {
+        // 서버도 에이전트 명령어처럼 샌드박스에서 실행 (쓰기는 프로젝트와 서버별 캐시 디렉토리로 제한,
+        // 환경 변수는 허용 목록, 프록시/인증서 변수, `env`만)
+        let mut sandbox = Sandbox::for_current_project()?.with_network_env();
+        if let Some(cwd) = cwd {
+            if !cwd.is_dir() {
+                return Err(anyhow!("Working directory '{}' for MCP server '{}' does not exist", cwd.display(), server));
+            }
+            sandbox = sandbox.with_working_dir(cwd);
+        }
+        let mut cache_env = Vec::new();
+        if matches!(sandbox.backend(), SandboxBackend::Bubblewrap | SandboxBackend::Firejail) {
+            if let Some(cache_dir) = server_cache_dir(server) {
+                std::fs::create_dir_all(&cache_dir)
+                    .map_err(|e| anyhow!("Failed to create cache directory '{}' for MCP server '{}': {}", cache_dir.display(), server, e))?;
+                cache_env = cache_env_vars(&cache_dir);
+                sandbox = sandbox.with_writable_dir(cache_dir);
+            }
+        }
+        let mut child = TokioCommand::from(sandbox.command(program, args))
+            .envs(cache_env.iter().map(|(name, value)| (name, value)))
+            .envs(env.iter().map(|(name, value)| (name, value)))
             .stdin(Stdio::piped())
             .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
+            .stderr(Stdio::null())
+            .kill_on_drop(true)
             .spawn()
-            .map_err(|e| anyhow!("Failed to start MCP server: {}", e))?;
+            .map_err(|e| anyhow!("Failed to start MCP server '{}': {}", program, e))?;
 
-        let mut stdin = child.stdin.take()
-            .ok_or_else(|| anyhow!("Failed to get stdin handle"))?;
-        let mut stdout = BufReader::new(child.stdout.take()
-            .ok_or_else(|| anyhow!("Failed to get stdout handle"))?);
+        let stdin = Arc::new(tokio::sync::Mutex::new(child.stdin.take()
+            .ok_or_else(|| anyhow!("Failed to get stdin handle"))?));
+        let stdout = child.stdout.take()
+            .ok_or_else(|| anyhow!("Failed to get stdout handle"))?;
+        let dispatcher = Dispatcher::new(server);
+        let reader = tokio::spawn(read_stdio_messages(server.to_string(), stdout, Arc::clone(&stdin), Arc::clone(&dispatcher)));
+        let session = Self { server: server.to_string(), child: Mutex::new(child), stdin, dispatcher, reader };
 
-        // 초기화 메시지 전송
-        let init_message = MCPMessage::Initialize {
+        let result = session.request(MCPMessage::Initialize {
             jsonrpc: MCPMessage::JSONRPC_VERSION.to_string(),
             id: MCPMessage::new_request_id(),
             params: InitializeParams {
                 protocol_version: MCP_PROTOCOL_VERSION.to_string(),
-                capabilities: ClientCapabilities {
-                    tools: Some(ToolsCapability {
-                        list_changed: Some(true),
-                    }),
-                },
-                client_info: ClientInfo {
-                    name: self.name.clone(),
-                    version: self.version.clone(),
-                },
+                capabilities: ClientCapabilities::current(),
+                client_info,
             },
-        };
+        }).await?;
+        let result = negotiation::negotiate(server, MCP_PROTOCOL_VERSION, result)?;
+        tracing::info!("MCP server initialized: {} {}", result.server_info.name, result.server_info.version);
 
-        let init_json = serde_json::to_string(&init_message)?;
-        use tokio::io::AsyncWriteExt;
-        stdin.write_all(init_json.as_bytes()).await?;
-        stdin.write_all(b"\n").await?;
-        stdin.flush().await?;
+        session.send(&serde_json::json!({
+            "jsonrpc": MCPMessage::JSONRPC_VERSION,
+            "method": "notifications/initialized",
+        })).await?;
 
-        // 응답 수신
-        let mut response_line = String::new();
-        stdout.read_line(&mut response_line).await?;
+        if result.capabilities.logging.is_some() {
+            if let Err(e) = session.request(logging::set_level_message(log_level)).await {
+                tracing::debug!("MCP server '{}' did not accept log level {}: {}", server, log_level, e);
+            }
+        }
+        Ok(session)
+    }
 
-        let response: MCPMessage = serde_json::from_str(&response_line.trim())
-            .map_err(|e| anyhow!("Failed to parse MCP response: {}", e))?;
+    /// 서버의 모든 도구 목록 (페이지 단위로 이어서 요청)
+    pub async fn list_tools(&self) -> Result<Vec<Tool>> {
+        let mut tools = Vec::new();
+        let mut cursor = None;
 
-        match response {
-            MCPMessage::InitializeResult { result, .. } => {
-                tracing::info!("MCP server initialized: {} {}", result.server_info.name, result.server_info.version);
+        loop {
+            let result = self.request(MCPMessage::ToolsList {
+                jsonrpc: MCPMessage::JSONRPC_VERSION.to_string(),
+                id: MCPMessage::new_request_id(),
+                params: ToolsListParams { cursor },
+            }).await?;
+
+            let page = parse_tools_page(result)?;
+            tools.extend(page.0);
+            cursor = page.1;
+            if cursor.is_none() {
+                return Ok(tools);
+            }
+        }
+    }
+
+    /// 연결 확인 요청
+    pub async fn ping(&self) -> Result<()> {
+        self.request(MCPMessage::Ping {
+            jsonrpc: MCPMessage::JSONRPC_VERSION.to_string(),
+            id: MCPMessage::new_request_id(),
+        }).await?;
+        Ok(())
+    }
 
-                // 도구 목록 로드
-                self.load_tools_stdio(&mut stdin, &mut stdout).await?;
+    /// 서버 프로세스가 아직 실행 중이고 출력을 읽을 수 있는지
+    pub fn is_alive(&self) -> bool {
+        !self.dispatcher.is_closed() && matches!(self.child.lock().unwrap().try_wait(), Ok(None))
+    }
 
-                *self.initialized.lock().unwrap() = true;
-                Ok(())
+    /// 도구 호출
+    /// Ctrl-C를 누르면 서버에 취소 알림을 보내고 `Cancelled` 오류 반환
+    pub async fn call_tool(&self, tool_name: &str, arguments: Option<serde_json::Value>) -> Result<CallToolResult> {
+        let id = MCPMessage::new_request_id();
+        let in_flight = cancel::InFlight::start();
+        let result = tokio::select! {
+            result = self.request(tool_call_message(&id, tool_name, arguments)) => result?,
+            _ = in_flight.cancelled() => {
+                self.send(&cancel::notification(&id)).await?;
+                return Err(cancel::Cancelled.into());
             }
-            _ => Err(anyhow!("Unexpected MCP response format"))
+        };
+
+        serde_json::from_value(result).map_err(|e| anyhow!("Failed to parse tool call response: {}", e))
+    }
+
+    /// 메시지 한 줄 전송
+    async fn send(&self, message: &impl serde::Serialize) -> Result<()> {
+        write_stdio_message(&self.server, &self.stdin, message).await
+    }
+
+    /// 요청을 보내고 같은 ID 응답의 `result` 반환
+    async fn request(&self, message: MCPMessage) -> Result<serde_json::Value> {
+        let message = serde_json::to_value(message)?;
+        let id = message["id"].clone();
+        async {
+            let pending = self.dispatcher.register(&id)?;
+            self.send(&message).await?;
+            response_result(pending.response(REQUEST_TIMEOUT).await?)
         }
+        .instrument(request_span(&self.server, &message))
+        .await
+    }
+}
+
+impl Drop for StdioSession {
+    fn drop(&mut self) {
+        self.reader.abort();
     }
+}
+
+/// 샌드박스에서 실행하는 stdio 서버의 쓰기 가능한 캐시 디렉토리 (`~/.ai-cli/mcp-cache/<서버>`)
+/// 홈 디렉토리는 읽기 전용이므로 npx/uvx가 패키지를 받을 곳을 서버마다 따로 둠
+fn server_cache_dir(server: &str) -> Option<PathBuf> {
+    let name: String = server
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect();
+    dirs::home_dir().map(|home| home.join(".ai-cli").join("mcp-cache").join(name))
+}
+
+/// 패키지 관리자가 캐시 디렉토리를 쓰도록 하는 환경 변수 (npm/npx, 그리고 XDG를 따르는 uv, pip 등)
+fn cache_env_vars(cache_dir: &Path) -> Vec<(String, String)> {
+    vec![
+        ("npm_config_cache".to_string(), cache_dir.join("npm").display().to_string()),
+        ("XDG_CACHE_HOME".to_string(), cache_dir.display().to_string()),
+    ]
+}
+
+/// MCP 요청 로그 span (서버, 메서드, ID만 기록하고 인자와 결과는 남기지 않음)
+fn request_span(server: &str, message: &serde_json::Value) -> tracing::Span {
+    let tool = message["params"]["name"].as_str().filter(|_| message["method"] == "tools/call");
+    tracing::debug_span!(
+        "mcp_request",
+        server,
+        method = message["method"].as_str().unwrap_or(""),
+        id = %message["id"],
+        tool,
+    )
+}
+
+/// stdio 서버에 메시지 한 줄 쓰기
+async fn write_stdio_message(
+    server: &str,
+    stdin: &tokio::sync::Mutex<tokio::process::ChildStdin>,
+    message: &impl serde::Serialize,
+) -> Result<()> {
+    use tokio::io::AsyncWriteExt;
+    let mut line = serde_json::to_string(message)?;
+    line.push('\n');
+    let mut stdin = stdin.lock().await;
+    let written = async {
+        stdin.write_all(line.as_bytes()).await?;
+        stdin.flush().await
+    };
+    written
+        .await
+        .map_err(|e| ConnectionLost(format!("Failed to write to MCP server '{}': {}", server, e)).into())
+}
+
+/// stdio 서버 출력 읽기 작업
+/// 서버가 보낸 요청(ping, 샘플링)에는 따로 응답하고, 응답과 알림은 기다리는 요청에 나눠 줌
+async fn read_stdio_messages(
+    server: String,
+    stdout: tokio::process::ChildStdout,
+    stdin: Arc<tokio::sync::Mutex<tokio::process::ChildStdin>>,
+    dispatcher: Arc<Dispatcher>,
+) {
+    let mut lines = BufReader::new(stdout).lines();
+    let reason = loop {
+        let line = match lines.next_line().await {
+            Ok(Some(line)) => line,
+            Ok(None) => break format!("MCP server '{}' closed the connection", server),
+            Err(e) => break format!("Failed to read from MCP server '{}': {}", server, e),
+        };
+        let Ok(message) = serde_json::from_str::<serde_json::Value>(line.trim()) else {
+            continue;
+        };
+
+        if is_server_request(&message) {
+            // 샘플링은 사용자 승인과 모델 생성을 기다리므로 다른 응답을 막지 않게 따로 처리
+            let (server, stdin) = (server.clone(), Arc::clone(&stdin));
+            tokio::spawn(async move {
+                let reply = reply_to_server_request(&server, &message).await;
+                if let Err(e) = write_stdio_message(&server, &stdin, &reply).await {
+                    tracing::warn!("{}", e);
+                }
+            });
+            continue;
+        }
+        dispatcher.route(message);
+    };
+    dispatcher.close(reason);
+}
 
-    /// HTTP를 통한 서버 초기화 (추후 구현)
-    async fn initialize_http(&self) -> Result<()> {
-        // TODO: HTTP 기반 MCP 서버 연동 구현
-        Err(anyhow!("HTTP MCP client not yet implemented"))
+/// HTTP 전송 방식 (Streamable HTTP 세션은 상태가 커서 상자에 담음)
+enum HttpTransport {
+    Streamable(Box<StreamableSession>),
+    Sse(SseSession),
+}
+
+/// HTTP MCP 서버 세션
+/// Streamable HTTP로 먼저 초기화하고, 서버가 POST를 받지 않으면(400/404/405) 이전 HTTP+SSE 전송으로 다시 연결
+pub struct HttpSession {
+    server: String,
+    transport: HttpTransport,
+    client_info: ClientInfo,
+    /// 서버가 `logging` 기능을 알리면 요청할 로그 수준
+    log_level: String,
+    /// 만료된 Streamable HTTP 세션을 한 요청만 다시 초기화하도록 막음
+    renewing: tokio::sync::Mutex<()>,
+}
+
+impl HttpSession {
+    /// 서버에 연결하고 초기화 핸드셰이크
+    pub async fn connect(
+        server: &str,
+        url: &str,
+        headers: &[(String, String)],
+        client_info: ClientInfo,
+        log_level: &str,
+    ) -> Result<Self> {
+        let mut session = Self {
+            server: server.to_string(),
+            transport: HttpTransport::Streamable(Box::new(StreamableSession::open(server, url, headers)?)),
+            client_info,
+            log_level: log_level.to_string(),
+            renewing: tokio::sync::Mutex::new(()),
+        };
+
+        match session.initialize().await {
+            Ok(()) => Ok(session),
+            Err(e) if e.downcast_ref::<HttpStatusError>().is_some_and(HttpStatusError::is_legacy_server) => {
+                tracing::info!("{} does not support Streamable HTTP; falling back to HTTP+SSE", url);
+                session.transport = HttpTransport::Sse(SseSession::open(server, url, headers).await?);
+                session.initialize().await?;
+                Ok(session)
+            }
+            Err(e) => Err(e),
+        }
     }
 
-    /// stdio를 통해 도구 목록 로드
-    async fn load_tools_stdio(
-        &self,
-        stdin: &mut tokio::process::ChildStdin,
-        stdout: &mut BufReader<tokio::process::ChildStdout>
-    ) -> Result<()> {
-        let tools_request = MCPMessage::ToolsList {
+    /// 초기화 요청과 `notifications/initialized` 전송 (서버가 `logging` 기능을 알리면 로그 수준도 설정)
+    async fn initialize(&self) -> Result<()> {
+        let protocol_version = match self.transport {
+            HttpTransport::Streamable(_) => STREAMABLE_PROTOCOL_VERSION,
+            HttpTransport::Sse(_) => MCP_PROTOCOL_VERSION,
+        };
+        let message = serde_json::to_value(MCPMessage::Initialize {
             jsonrpc: MCPMessage::JSONRPC_VERSION.to_string(),
             id: MCPMessage::new_request_id(),
-            params: ToolsListParams { cursor: None },
+            params: InitializeParams {
+                protocol_version: protocol_version.to_string(),
+                capabilities: ClientCapabilities::current(),
+                client_info: self.client_info.clone(),
+            },
+        })?;
+        let response = match &self.transport {
+            HttpTransport::Streamable(session) => session.request(&message).await?,
+            HttpTransport::Sse(session) => session.request(&message).await?,
         };
+        let result = negotiation::negotiate(&self.server, protocol_version, response_result(response)?)?;
+        tracing::info!("MCP server initialized: {} {}", result.server_info.name, result.server_info.version);
 
-        let request_json = serde_json::to_string(&tools_request)?;
-        use tokio::io::AsyncWriteExt;
-        stdin.write_all(request_json.as_bytes()).await?;
-        stdin.write_all(b"\n").await?;
-        stdin.flush().await?;
+        let initialized = serde_json::json!({
+            "jsonrpc": MCPMessage::JSONRPC_VERSION,
+            "method": "notifications/initialized",
+        });
+        match &self.transport {
+            HttpTransport::Streamable(session) => {
+                session.set_protocol_version(&result.protocol_version);
+                session.notify(&initialized).await?;
+            }
+            HttpTransport::Sse(session) => session.notify(&initialized).await?,
+        }
 
-        let mut response_line = String::new();
-        stdout.read_line(&mut response_line).await?;
+        if result.capabilities.logging.is_some() {
+            let message = serde_json::to_value(logging::set_level_message(&self.log_level))?;
+            let response = match &self.transport {
+                HttpTransport::Streamable(session) => session.request(&message).await,
+                HttpTransport::Sse(session) => session.request(&message).await,
+            };
+            if let Err(e) = response.and_then(response_result) {
+                tracing::debug!("MCP server '{}' did not accept log level {}: {}", self.server, self.log_level, e);
+            }
+        }
+        Ok(())
+    }
 
-        let response: MCPMessage = serde_json::from_str(&response_line.trim())
-            .map_err(|e| anyhow!("Failed to parse tools list response: {}", e))?;
+    /// 알림 전송
+    async fn notify(&self, message: &serde_json::Value) -> Result<()> {
+        match &self.transport {
+            HttpTransport::Streamable(session) => session.notify(message).await,
+            HttpTransport::Sse(session) => session.notify(message).await,
+        }
+    }
+
+    /// 요청을 보내고 `result` 반환 (Streamable HTTP 세션이 만료되면 다시 초기화한 뒤 한 번 재시도)
+    async fn request(&self, message: MCPMessage) -> Result<serde_json::Value> {
+        let message = serde_json::to_value(message)?;
+        let span = request_span(&self.server, &message);
+        self.request_value(&message).instrument(span).await
+    }
 
-        match response {
-            MCPMessage::ToolsListResult { result, .. } => {
-                let mut tools = self.tools.lock().unwrap();
-                for tool in result.tools {
-                    tracing::debug!("Loaded MCP tool: {}", tool.name);
-                    tools.insert(tool.name.clone(), tool);
+    /// JSON 요청을 보내고 `result` 반환
+    async fn request_value(&self, message: &serde_json::Value) -> Result<serde_json::Value> {
+        let response = match &self.transport {
+            HttpTransport::Streamable(session) => {
+                let session_id = session.session_id();
+                match session.request(message).await {
+                    Err(e) if e.downcast_ref::<HttpStatusError>().is_some_and(HttpStatusError::is_session_expired) => {
+                        self.renew(session, session_id).await?;
+                        session.request(message).await?
+                    }
+                    response => response?,
                 }
-                tracing::info!("Loaded {} MCP tools", tools.len());
-                Ok(())
             }
-            _ => Err(anyhow!("Unexpected tools list response format"))
-        }
+            HttpTransport::Sse(session) => session.request(message).await?,
+        };
+        response_result(response)
     }
 
-    /// 도구 호출
-    pub async fn call_tool(&self, tool_name: &str, arguments: Option<serde_json::Value>) -> Result<CallToolResult> {
-        if !*self.initialized.lock().unwrap() {
-            return Err(anyhow!("MCP client not initialized"));
+    /// 만료된 세션 대신 새 세션 시작 (동시에 만료를 알게 된 다른 요청이 이미 새로 시작했으면 그 세션 사용)
+    async fn renew(&self, session: &StreamableSession, expired: Option<String>) -> Result<()> {
+        let _renewing = self.renewing.lock().await;
+        if session.session_id() != expired {
+            return Ok(());
         }
+        tracing::info!("MCP session expired; starting a new session");
+        session.reset();
+        self.initialize().await
+    }
 
-        let tools = self.tools.lock().unwrap();
-        if !tools.contains_key(tool_name) {
-            return Err(anyhow!("Tool '{}' not found", tool_name));
+    /// 서버의 모든 도구 목록 (페이지 단위로 이어서 요청)
+    pub async fn list_tools(&self) -> Result<Vec<Tool>> {
+        let mut tools = Vec::new();
+        let mut cursor = None;
+
+        loop {
+            let result = self.request(MCPMessage::ToolsList {
+                jsonrpc: MCPMessage::JSONRPC_VERSION.to_string(),
+                id: MCPMessage::new_request_id(),
+                params: ToolsListParams { cursor },
+            }).await?;
+
+            let page = parse_tools_page(result)?;
+            tools.extend(page.0);
+            cursor = page.1;
+            if cursor.is_none() {
+                return Ok(tools);
+            }
         }
-        drop(tools);
+    }
 
-        let call_request = MCPMessage::ToolsCall {
+    /// 연결 확인 요청
+    pub async fn ping(&self) -> Result<()> {
+        self.request(MCPMessage::Ping {
             jsonrpc: MCPMessage::JSONRPC_VERSION.to_string(),
             id: MCPMessage::new_request_id(),
-            params: CallToolParams {
-                name: tool_name.to_string(),
-                arguments,
-            },
+        }).await?;
+        Ok(())
+    }
+
+    /// 도구 호출
+    /// Ctrl-C를 누르면 서버에 취소 알림을 보내고 `Cancelled` 오류 반환
+    pub async fn call_tool(&self, tool_name: &str, arguments: Option<serde_json::Value>) -> Result<CallToolResult> {
+        let id = MCPMessage::new_request_id();
+        let in_flight = cancel::InFlight::start();
+        let result = tokio::select! {
+            result = self.request(tool_call_message(&id, tool_name, arguments)) => result?,
+            _ = in_flight.cancelled() => {
+                self.notify(&cancel::notification(&id)).await?;
+                return Err(cancel::Cancelled.into());
+            }
         };
 
-        // TODO: 실제 도구 호출 구현
-        // 현재는 모의 응답 반환
-        Ok(CallToolResult {
-            content: vec![Content::Text {
-                text: format!("Tool '{}' called successfully", tool_name),
-            }],
-            is_error: Some(false),
-        })
+        serde_json::from_value(result).map_err(|e| anyhow!("Failed to parse tool call response: {}", e))
     }
 
-    /// 사용 가능한 도구 목록 반환
-    pub fn list_tools(&self) -> Vec<String> {
-        self.tools.lock().unwrap()
-            .keys()
-            .cloned()
-            .collect()
+    /// HTTP+SSE 이벤트 스트림이 끊기지 않았는지 (Streamable HTTP는 요청해 봐야 알 수 있음)
+    fn is_alive(&self) -> bool {
+        match &self.transport {
+            HttpTransport::Streamable(_) => true,
+            HttpTransport::Sse(session) => session.is_alive(),
+        }
     }
 
-    /// 도구 정보 조회
-    pub fn get_tool(&self, name: &str) -> Option<Tool> {
-        self.tools.lock().unwrap()
-            .get(name)
-            .cloned()
+    /// 세션 종료
+    pub async fn close(&self) {
+        if let HttpTransport::Streamable(session) = &self.transport {
+            session.close().await;
+        }
     }
+}
 
-    /// 클라이언트가 초기화되었는지 확인
-    pub fn is_initialized(&self) -> bool {
-        *self.initialized.lock().unwrap()
+/// 서버가 보낸 알림 처리 (진행 알림은 진행 줄로, 로그 알림은 tracing 이벤트로)
+pub(super) fn handle_server_notification(server: &str, message: &serde_json::Value) {
+    progress::handle_notification(message);
+    logging::handle_notification(server, message);
+}
+
+/// 서버가 클라이언트에 보낸 요청인지 (응답이나 알림이 아님)
+pub(super) fn is_server_request(message: &serde_json::Value) -> bool {
+    message.get("method").is_some() && message.get("id").is_some()
+}
+
+/// 서버가 보낸 요청 처리 (`sampling/createMessage`는 사용자 승인 후 설정한 모델로 생성)
+pub(super) async fn reply_to_server_request(server: &str, request: &serde_json::Value) -> serde_json::Value {
+    let id = request.get("id").cloned().unwrap_or_default();
+    let method = request.get("method").and_then(|method| method.as_str()).unwrap_or_default();
+    if method != sampling::SAMPLING_METHOD || !sampling::is_enabled() {
+        return server_request_reply(&id, method);
+    }
+
+    let params = request.get("params").cloned().unwrap_or_default();
+    match sampling::create_message(server, params).await {
+        Ok(result) => serde_json::json!({"jsonrpc": MCPMessage::JSONRPC_VERSION, "id": id, "result": result}),
+        Err(e) => {
+            let code = if e.downcast_ref::<sampling::SamplingRejected>().is_some() { sampling::USER_REJECTED } else { INTERNAL_ERROR };
+            serde_json::json!({
+                "jsonrpc": MCPMessage::JSONRPC_VERSION,
+                "id": id,
+                "error": {"code": code, "message": e.to_string()},
+            })
+        }
+    }
+}
+
+/// 서버가 클라이언트에 보낸 요청에 대한 응답 (`ping`, `roots/list`만 지원하고 나머지는 Method not found)
+fn server_request_reply(id: &serde_json::Value, method: &str) -> serde_json::Value {
+    match method {
+        "ping" => serde_json::json!({"jsonrpc": MCPMessage::JSONRPC_VERSION, "id": id, "result": {}}),
+        roots::ROOTS_LIST_METHOD => serde_json::json!({
+            "jsonrpc": MCPMessage::JSONRPC_VERSION,
+            "id": id,
+            "result": roots::list_result(&roots::current_roots()),
+        }),
+        _ => serde_json::json!({
+            "jsonrpc": MCPMessage::JSONRPC_VERSION,
+            "id": id,
+            "error": {"code": METHOD_NOT_FOUND, "message": format!("Method not found: {}", method)},
+        }),
+    }
+}
+
+/// 도구 호출 요청 (요청 ID를 progress token으로도 사용)
+fn tool_call_message(id: &str, tool_name: &str, arguments: Option<serde_json::Value>) -> MCPMessage {
+    MCPMessage::ToolsCall {
+        jsonrpc: MCPMessage::JSONRPC_VERSION.to_string(),
+        id: id.to_string(),
+        params: CallToolParams {
+            name: tool_name.to_string(),
+            arguments,
+            meta: Some(RequestMeta { progress_token: id.to_string() }),
+        },
+    }
+}
+
+/// `tools/list` 결과의 도구 목록과 다음 페이지 커서
+fn parse_tools_page(result: serde_json::Value) -> Result<(Vec<Tool>, Option<String>)> {
+    let cursor = result.get("nextCursor").and_then(|c| c.as_str()).map(str::to_string);
+    let page: ToolsListResult = serde_json::from_value(result)
+        .map_err(|e| anyhow!("Failed to parse tools list response: {}", e))?;
+    Ok((page.tools, cursor))
+}
+
+/// JSON-RPC 응답의 `result` (오류 응답이면 코드와 메시지를 담은 `JsonRpcError`)
+pub(super) fn response_result(response: serde_json::Value) -> Result<serde_json::Value> {
+    let response: JsonRpcResponse =
+        serde_json::from_value(response).map_err(|e| anyhow!("Invalid JSON-RPC response from MCP server: {}", e))?;
+    match (response.error, response.result) {
+        (Some(error), _) => Err(error.into()),
+        (None, Some(result)) => Ok(result),
+        (None, None) => Err(anyhow!("MCP response has neither a result nor an error")),
     }
 }
 
@@ -237,6 +964,7 @@ pub fn create_github_tools() -> Vec<Tool> {
                 },
                 required: vec!["title".to_string(), "head".to_string(), "base".to_string()],
             },
+            annotations: None,
         },
         Tool {
             name: "create_issue".to_string(),
@@ -259,6 +987,89 @@ pub fn create_github_tools() -> Vec<Tool> {
                 },
                 required: vec!["title".to_string()],
             },
+            annotations: None,
         },
     ]
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_server_cache_dir() {
+        let dir = server_cache_dir("team/github tools").unwrap();
+        assert!(dir.ends_with(Path::new(".ai-cli").join("mcp-cache").join("team_github_tools")));
+
+        let env = cache_env_vars(&dir);
+        assert!(env.contains(&("npm_config_cache".to_string(), dir.join("npm").display().to_string())));
+        assert!(env.contains(&("XDG_CACHE_HOME".to_string(), dir.display().to_string())));
+    }
+
+    #[test]
+    fn test_server_request_reply() {
+        let id = serde_json::json!(7);
+        assert_eq!(server_request_reply(&id, "ping"), serde_json::json!({"jsonrpc": "2.0", "id": 7, "result": {}}));
+
+        let reply = server_request_reply(&id, "roots/list");
+        assert_eq!(reply["id"], id);
+        assert!(reply["result"]["roots"].is_array());
+
+        let reply = server_request_reply(&id, "elicitation/create");
+        assert_eq!(reply["id"], id);
+        assert_eq!(reply["error"]["code"], METHOD_NOT_FOUND);
+    }
+
+    #[test]
+    fn test_response_result() {
+        let ok = serde_json::json!({"jsonrpc": "2.0", "id": "a1", "result": {"tools": []}});
+        assert_eq!(response_result(ok).unwrap(), serde_json::json!({"tools": []}));
+
+        let failed = serde_json::json!({
+            "jsonrpc": "2.0", "id": "a1",
+            "error": {"code": -32602, "message": "Unknown tool: create_isue", "data": "did you mean create_issue?"}
+        });
+        let error = response_result(failed).unwrap_err();
+        assert!(JsonRpcError::has_code(&error, INVALID_PARAMS));
+        assert_eq!(
+            error.to_string(),
+            "MCP server error -32602 (invalid params): Unknown tool: create_isue (did you mean create_issue?)"
+        );
+
+        let custom = serde_json::json!({"jsonrpc": "2.0", "id": "a1", "error": {"code": -32001, "message": "Rate limited"}});
+        assert_eq!(response_result(custom).unwrap_err().to_string(), "MCP server error -32001 (server error): Rate limited");
+        assert!(response_result(serde_json::json!({"jsonrpc": "2.0", "id": "a1"})).is_err());
+    }
+
+    #[test]
+    fn test_prepare_call_checks_writable_paths() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let policy_file = dir.path().join("policy.toml");
+        std::fs::write(&policy_file, "[paths]\nwritable = [\"src/**\"]\n").unwrap();
+        let policy = Policy::load_from(Some(&policy_file), None, Some(dir.path().to_path_buf()), false).unwrap();
+
+        let config: ServerConfig = serde_json::from_value(serde_json::json!({"toolAccess": {"read_file": "read-only"}})).unwrap();
+        let client = super::super::MCPClientBuilder::new("test").server("fs", config).policy(policy).build();
+        *client.initialized.lock().unwrap() = true;
+        for name in ["write_file", "read_file"] {
+            let tool: Tool = serde_json::from_value(serde_json::json!({"name": name, "inputSchema": {"type": "object"}})).unwrap();
+            client.tools.lock().unwrap().insert(name.to_string(), ("fs".to_string(), tool));
+        }
+
+        let outside = serde_json::json!({"path": dir.path().join("Cargo.toml").display().to_string(), "content": "x"});
+        let error = client.prepare_call("write_file", Some(outside.clone())).err().unwrap().to_string();
+        assert!(error.contains("paths.writable"), "{}", error);
+        // 읽기 전용 도구는 쓰기 허용 경로와 관계없이 호출
+        assert!(client.prepare_call("read_file", Some(outside)).is_ok());
+    }
+
+    #[test]
+    fn test_is_response_to() {
+        let id = serde_json::json!("a1");
+        assert!(JsonRpcResponse::is_response_to(&serde_json::json!({"id": "a1", "result": {}}), &id));
+        assert!(!JsonRpcResponse::is_response_to(&serde_json::json!({"id": "b2", "result": {}}), &id));
+        assert!(!JsonRpcResponse::is_response_to(&serde_json::json!({"id": "a1", "method": "ping"}), &id));
+        // 서버가 요청을 해석하지 못한 오류 응답
+        assert!(JsonRpcResponse::is_response_to(&serde_json::json!({"id": null, "error": {"code": -32700, "message": "Parse error"}}), &id));
+        assert!(!JsonRpcResponse::is_response_to(&serde_json::json!({"method": "notifications/progress"}), &id));
+    }
+}
@@ -0,0 +1,296 @@
+//! MCP 서버 연결 관리
+//!
+//! 서버마다 연결 관리자를 두어 서버 프로세스 종료나 끊긴 연결을 감지하면 지수 백오프로 다시 연결하고,
+//! `mcp status`에 보여 줄 상태(도구 수, 재연결 횟수, 응답 시간, 마지막 오류)를 기록합니다.
+//! 서버에 알린 프로젝트 루트가 작업 디렉토리 변경으로 달라지면 도구 호출 전에 변경 알림을 보냅니다.
+//! 도구 호출은 준비된 세션을 받아 연결 관리자를 잠그지 않고 수행하므로 한 서버에 여러 호출을 동시에 보낼 수 있습니다.
+
+use anyhow::{Result, anyhow};
+use serde::Serialize;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use super::client::ServerSession;
+use super::oauth;
+use super::protocol::{CallToolResult, ClientInfo, JsonRpcError, MCPMessage, Tool, METHOD_NOT_FOUND};
+use super::registry::ServerConfig;
+use super::roots::{self, Root};
+
+/// 연결이 끊겼을 때 다시 연결을 시도하는 최대 횟수
+pub const MAX_RECONNECT_ATTEMPTS: u32 = 4;
+
+/// 첫 재연결 대기 시간 (시도마다 두 배)
+const INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+
+/// 재연결 대기 시간 상한
+const MAX_BACKOFF: Duration = Duration::from_secs(8);
+
+/// 상태 확인(ping) 응답 대기 시간
+const PING_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// 서버 연결이 끊김 (프로세스 종료, 끊긴 파이프, 닫힌 스트림)
+#[derive(Debug, thiserror::Error)]
+#[error("{0}")]
+pub struct ConnectionLost(pub String);
+
+/// `attempt`번째(0부터) 재연결 전 대기 시간
+pub fn backoff_delay(attempt: u32) -> Duration {
+    INITIAL_BACKOFF.saturating_mul(2u32.saturating_pow(attempt)).min(MAX_BACKOFF)
+}
+
+/// 오류가 연결 끊김 때문인지
+pub fn is_connection_lost(error: &anyhow::Error) -> bool {
+    error.chain().any(|cause| cause.downcast_ref::<ConnectionLost>().is_some())
+}
+
+/// 서버 연결 상태
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ConnectionState {
+    Connected,
+    /// 연결이 끊겨 다음 호출 때 다시 연결함
+    Disconnected,
+    /// 연결하지 못함
+    Failed,
+}
+
+/// 서버별 상태 (`mcp status` 출력용)
+#[derive(Debug, Clone, Serialize)]
+pub struct ServerStatus {
+    pub name: String,
+    pub transport: &'static str,
+    pub state: ConnectionState,
+    pub tools: usize,
+    pub reconnects: u32,
+    /// 마지막 상태 확인(ping) 응답 시간
+    pub latency_ms: Option<u64>,
+    pub last_error: Option<String>,
+}
+
+/// 서버 하나의 연결 관리자
+pub struct ServerConnection {
+    name: String,
+    config: ServerConfig,
+    client_info: ClientInfo,
+    session: Option<Arc<ServerSession>>,
+    tools: Vec<Tool>,
+    state: ConnectionState,
+    reconnects: u32,
+    latency: Option<Duration>,
+    last_error: Option<String>,
+    /// 서버에 마지막으로 알린 루트
+    roots: Vec<Root>,
+}
+
+impl ServerConnection {
+    pub fn new(name: &str, config: ServerConfig, client_info: ClientInfo) -> Self {
+        Self {
+            name: name.to_string(),
+            config,
+            client_info,
+            session: None,
+            tools: Vec::new(),
+            state: ConnectionState::Failed,
+            reconnects: 0,
+            latency: None,
+            last_error: None,
+            roots: Vec::new(),
+        }
+    }
+
+    /// 연결하고 도구 목록을 가져옴 (실패하면 상태에 오류 기록)
+    pub async fn connect(&mut self) -> Result<&[Tool]> {
+        match self.open().await {
+            Ok(()) => Ok(&self.tools),
+            Err(e) => {
+                self.state = ConnectionState::Failed;
+                self.last_error = Some(e.to_string());
+                Err(e)
+            }
+        }
+    }
+
+    /// 세션을 새로 열고 도구 목록 갱신
+    async fn open(&mut self) -> Result<()> {
+        self.roots = roots::current_roots();
+        let session = ServerSession::connect(&self.name, &self.config, self.client_info.clone()).await?;
+        self.tools = session.list_tools().await?;
+        self.session = Some(Arc::new(session));
+        self.state = ConnectionState::Connected;
+        self.last_error = None;
+        Ok(())
+    }
+
+    /// 끊긴 세션을 정리하고 백오프하며 다시 연결
+    async fn reconnect(&mut self) -> Result<()> {
+        if let Some(session) = self.session.take() {
+            session.close().await;
+        }
+
+        for attempt in 0..MAX_RECONNECT_ATTEMPTS {
+            tokio::time::sleep(backoff_delay(attempt)).await;
+            match self.open().await {
+                Ok(()) => {
+                    self.reconnects += 1;
+                    tracing::info!("Reconnected to MCP server '{}'", self.name);
+                    return Ok(());
+                }
+                Err(e) => {
+                    tracing::warn!("Reconnecting to MCP server '{}' failed (attempt {}): {}", self.name, attempt + 1, e);
+                    self.last_error = Some(e.to_string());
+                }
+            }
+        }
+
+        self.state = ConnectionState::Failed;
+        Err(anyhow!(
+            "MCP server '{}' could not be reconnected after {} attempts: {}",
+            self.name,
+            MAX_RECONNECT_ATTEMPTS,
+            self.last_error.as_deref().unwrap_or("unknown error")
+        ))
+    }
+
+    /// 연결이 끊긴 것으로 표시 (다음 호출 때 다시 연결)
+    /// 동시에 보낸 다른 호출이 이미 다시 연결했으면 끊긴 세션은 이미 교체되었으므로 그대로 둠
+    fn mark_lost(&mut self, session: &Arc<ServerSession>, error: &anyhow::Error) {
+        if !self.session.as_ref().is_some_and(|current| Arc::ptr_eq(current, session)) {
+            return;
+        }
+        tracing::warn!("Lost connection to MCP server '{}': {}", self.name, error);
+        self.state = ConnectionState::Disconnected;
+        self.last_error = Some(error.to_string());
+        self.session = None;
+    }
+
+    /// 도구 호출에 쓸 세션 (끊긴 것을 알면 다시 연결하고, 바뀐 루트를 알린 뒤 반환)
+    async fn ready_session(&mut self) -> Result<Arc<ServerSession>> {
+        if !self.session.as_ref().is_some_and(|session| session.is_alive()) {
+            self.reconnect().await?;
+        }
+        self.sync_roots().await;
+        self.session.clone().ok_or_else(|| anyhow!("MCP server '{}' is not connected", self.name))
+    }
+
+    /// 토큰이 거부된 세션 대신 새 세션 (다른 호출이 이미 다시 연결했으면 그 세션 사용)
+    async fn renew_session(&mut self, rejected: &Arc<ServerSession>) -> Result<Arc<ServerSession>> {
+        if self.session.as_ref().is_none_or(|current| Arc::ptr_eq(current, rejected)) {
+            tracing::info!("MCP server '{}' rejected the access token; reconnecting", self.name);
+            self.reconnect().await?;
+        }
+        self.ready_session().await
+    }
+
+    /// 도구 호출 (연결 관리자는 세션을 준비하는 동안에만 잠금)
+    /// 호출 전에 끊긴 것을 알면 다시 연결한 뒤 호출하고, 호출 중에 끊기면 서버가 요청을 처리했는지 알 수 없으므로
+    /// 다시 보내지 않고 오류를 반환 (다음 호출 때 다시 연결)
+    /// 토큰이 만료되어 401을 받으면 서버가 요청을 처리하지 않았으므로 다시 연결(토큰 갱신)한 뒤 한 번 재시도
+    pub async fn call_tool(
+        connection: &tokio::sync::Mutex<Self>,
+        tool_name: &str,
+        arguments: Option<serde_json::Value>,
+    ) -> Result<CallToolResult> {
+        let mut session = connection.lock().await.ready_session().await?;
+        let mut result = session.call_tool(tool_name, arguments.clone()).await;
+        if matches!(&result, Err(e) if oauth::is_unauthorized(e)) {
+            session = connection.lock().await.renew_session(&session).await?;
+            result = session.call_tool(tool_name, arguments).await;
+        }
+
+        match result {
+            Err(e) if is_connection_lost(&e) => {
+                let mut connection = connection.lock().await;
+                connection.mark_lost(&session, &e);
+                Err(anyhow!("MCP server '{}' disconnected during '{}'; the call may not have completed: {}", connection.name, tool_name, e))
+            }
+            result => result,
+        }
+    }
+
+    /// 루트가 바뀌었으면 서버에 변경 알림 (서버는 `roots/list`로 새 목록을 요청함)
+    async fn sync_roots(&mut self) {
+        let current = roots::current_roots();
+        if current == self.roots {
+            return;
+        }
+        let Some(session) = self.session.as_ref() else { return };
+        let notification = serde_json::json!({
+            "jsonrpc": MCPMessage::JSONRPC_VERSION,
+            "method": roots::ROOTS_CHANGED_NOTIFICATION,
+        });
+        match session.notify(&notification).await {
+            Ok(()) => self.roots = current,
+            Err(e) => tracing::warn!("Failed to notify MCP server '{}' of changed roots: {}", self.name, e),
+        }
+    }
+
+    /// 상태 확인: 연결되어 있으면 ping으로 응답 시간을 재고, 연결 중에 끊겼으면 한 번 다시 연결
+    pub async fn health_check(&mut self) -> ServerStatus {
+        if self.state != ConnectionState::Failed && !self.session.as_ref().is_some_and(|session| session.is_alive()) {
+            if let Err(e) = self.open().await {
+                self.state = ConnectionState::Disconnected;
+                self.last_error = Some(e.to_string());
+            } else {
+                self.reconnects += 1;
+            }
+        }
+
+        if let Some(session) = self.session.clone() {
+            let started = Instant::now();
+            match tokio::time::timeout(PING_TIMEOUT, session.ping()).await {
+                Ok(Ok(())) => self.latency = Some(started.elapsed()),
+                Ok(Err(e)) if is_connection_lost(&e) => self.mark_lost(&session, &e),
+                // ping을 지원하지 않는 서버도 있으므로 연결 상태는 유지 (Method not found는 응답한 것이므로 정상)
+                Ok(Err(e)) if JsonRpcError::has_code(&e, METHOD_NOT_FOUND) => self.latency = Some(started.elapsed()),
+                Ok(Err(e)) => self.last_error = Some(e.to_string()),
+                Err(_) => self.last_error = Some(format!("No ping response within {} seconds", PING_TIMEOUT.as_secs())),
+            }
+        }
+        self.status()
+    }
+
+    /// 현재 상태
+    pub fn status(&self) -> ServerStatus {
+        let transport = match (self.config.validate(), &self.config.url) {
+            (Err(_), _) => "invalid",
+            (Ok(()), Some(_)) => "http",
+            (Ok(()), None) => "stdio",
+        };
+        ServerStatus {
+            name: self.name.clone(),
+            transport,
+            state: self.state,
+            tools: self.tools.len(),
+            reconnects: self.reconnects,
+            latency_ms: self.latency.map(|latency| latency.as_millis() as u64),
+            last_error: self.last_error.clone(),
+        }
+    }
+
+    /// 연결 종료
+    pub async fn close(&mut self) {
+        if let Some(session) = self.session.take() {
+            session.close().await;
+        }
+        self.state = ConnectionState::Disconnected;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_delay() {
+        assert_eq!(backoff_delay(0), Duration::from_millis(500));
+        assert_eq!(backoff_delay(2), Duration::from_secs(2));
+        assert_eq!(backoff_delay(10), MAX_BACKOFF);
+    }
+
+    #[test]
+    fn test_is_connection_lost() {
+        let lost = anyhow::Error::new(ConnectionLost("MCP server closed the connection".to_string()));
+        assert!(is_connection_lost(&lost.context("tools/call failed")));
+        assert!(!is_connection_lost(&anyhow!("Tool 'x' not found")));
+    }
+}
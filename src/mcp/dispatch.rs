@@ -0,0 +1,169 @@
+use anyhow::{Result, anyhow};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+use super::client::handle_server_notification;
+use super::connection::ConnectionLost;
+use super::progress::PROGRESS_NOTIFICATION;
+
+/// 요청 다중화
+///
+/// 한 세션(stdio, HTTP+SSE)에서 여러 요청을 동시에 보낼 수 있도록, 세션의 읽기 작업이 받은 응답을
+/// 요청 ID로 기다리는 호출에 전달합니다. 도구 호출의 진행 알림은 progress token(요청 ID)이 같은 호출에도
+/// 전달해 그 호출의 응답 대기 시간을 다시 시작합니다. 연결이 끊기면 기다리던 모든 호출이 `ConnectionLost`로 끝납니다.
+///
+/// 응답을 기다리는 요청 (요청 ID → 받은 메시지를 넘길 채널)과 연결이 끊긴 이유
+#[derive(Default)]
+struct DispatchState {
+    pending: HashMap<String, mpsc::UnboundedSender<serde_json::Value>>,
+    closed: Option<String>,
+}
+
+/// 세션이 받은 메시지를 요청별로 나눠 주는 분배기
+pub struct Dispatcher {
+    /// 알림을 기록할 때 쓰는 서버 이름
+    server: String,
+    state: Mutex<DispatchState>,
+}
+
+/// 요청 ID를 분배기 키로 변환 (문자열과 숫자 ID를 구분)
+fn request_key(id: &serde_json::Value) -> String {
+    id.to_string()
+}
+
+impl Dispatcher {
+    pub fn new(server: &str) -> Arc<Self> {
+        Arc::new(Self { server: server.to_string(), state: Mutex::new(DispatchState::default()) })
+    }
+
+    /// 요청을 보내기 전에 응답 받을 자리 등록 (연결이 이미 끊겼으면 오류)
+    pub fn register(self: &Arc<Self>, id: &serde_json::Value) -> Result<PendingRequest> {
+        let mut state = self.state.lock().unwrap();
+        if let Some(reason) = &state.closed {
+            return Err(ConnectionLost(reason.clone()).into());
+        }
+        let key = request_key(id);
+        let (sender, receiver) = mpsc::unbounded_channel();
+        state.pending.insert(key.clone(), sender);
+        Ok(PendingRequest { key, dispatcher: Arc::clone(self), receiver })
+    }
+
+    /// 서버가 보낸 응답이나 알림 전달 (서버가 보낸 요청은 세션이 직접 응답, 알림은 진행 줄과 로그로도 처리)
+    pub fn route(&self, message: serde_json::Value) {
+        let state = self.state.lock().unwrap();
+        match message.get("id") {
+            // 요청을 해석하지 못해 ID 없이 보낸 오류는 기다리는 요청이 하나뿐일 때만 그 요청의 응답
+            Some(serde_json::Value::Null) => match state.pending.values().collect::<Vec<_>>().as_slice() {
+                [sender] => {
+                    let _ = sender.send(message);
+                }
+                _ => tracing::warn!("Ignoring MCP error response without a request id: {}", message),
+            },
+            Some(id) => match state.pending.get(&request_key(id)) {
+                Some(sender) => {
+                    let _ = sender.send(message);
+                }
+                None => tracing::debug!("Ignoring MCP response to unknown request {}", id),
+            },
+            None => {
+                let token = message
+                    .get("method")
+                    .filter(|method| method.as_str() == Some(PROGRESS_NOTIFICATION))
+                    .and_then(|_| message.pointer("/params/progressToken"));
+                if let Some(sender) = token.and_then(|token| state.pending.get(&request_key(token))) {
+                    let _ = sender.send(message.clone());
+                }
+                drop(state);
+                handle_server_notification(&self.server, &message);
+            }
+        }
+    }
+
+    /// 연결이 끊김 (기다리던 요청과 이후 요청은 `ConnectionLost`로 끝남)
+    pub fn close(&self, reason: String) {
+        let mut state = self.state.lock().unwrap();
+        state.pending.clear();
+        state.closed.get_or_insert(reason);
+    }
+
+    /// 연결이 끊겼는지
+    pub fn is_closed(&self) -> bool {
+        self.state.lock().unwrap().closed.is_some()
+    }
+
+    fn closed_reason(&self) -> String {
+        self.state.lock().unwrap().closed.clone().unwrap_or_else(|| "MCP connection closed".to_string())
+    }
+}
+
+/// 응답을 기다리는 요청 (버리면 등록 해제)
+pub struct PendingRequest {
+    key: String,
+    dispatcher: Arc<Dispatcher>,
+    receiver: mpsc::UnboundedReceiver<serde_json::Value>,
+}
+
+impl PendingRequest {
+    /// 응답 메시지 대기 (진행 알림을 받으면 대기 시간을 다시 시작)
+    pub async fn response(mut self, timeout: Duration) -> Result<serde_json::Value> {
+        loop {
+            let message = tokio::time::timeout(timeout, self.receiver.recv())
+                .await
+                .map_err(|_| anyhow!("MCP server did not respond within {} seconds", timeout.as_secs()))?;
+            match message {
+                Some(message) if message.get("method").is_some() => continue,
+                Some(message) => return Ok(message),
+                None => return Err(ConnectionLost(self.dispatcher.closed_reason()).into()),
+            }
+        }
+    }
+}
+
+impl Drop for PendingRequest {
+    fn drop(&mut self) {
+        self.dispatcher.state.lock().unwrap().pending.remove(&self.key);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::connection::is_connection_lost;
+
+    const TIMEOUT: Duration = Duration::from_secs(1);
+
+    #[tokio::test]
+    async fn test_route_by_id() {
+        let dispatcher = Dispatcher::new("github");
+        let first = dispatcher.register(&serde_json::json!("a1")).unwrap();
+        let second = dispatcher.register(&serde_json::json!("b2")).unwrap();
+
+        // 응답 순서가 요청 순서와 달라도 각 요청이 자기 응답을 받음
+        dispatcher.route(serde_json::json!({"jsonrpc": "2.0", "id": "b2", "result": {"n": 2}}));
+        dispatcher.route(serde_json::json!({
+            "jsonrpc": "2.0", "method": "notifications/progress", "params": {"progressToken": "a1", "progress": 1}
+        }));
+        dispatcher.route(serde_json::json!({"jsonrpc": "2.0", "id": "a1", "result": {"n": 1}}));
+        assert_eq!(second.response(TIMEOUT).await.unwrap()["result"]["n"], 2);
+        assert_eq!(first.response(TIMEOUT).await.unwrap()["result"]["n"], 1);
+        assert!(dispatcher.state.lock().unwrap().pending.is_empty());
+
+        let only = dispatcher.register(&serde_json::json!("c3")).unwrap();
+        dispatcher.route(serde_json::json!({"jsonrpc": "2.0", "id": null, "error": {"code": -32700, "message": "Parse error"}}));
+        assert_eq!(only.response(TIMEOUT).await.unwrap()["error"]["code"], -32700);
+    }
+
+    #[tokio::test]
+    async fn test_close() {
+        let dispatcher = Dispatcher::new("github");
+        let pending = dispatcher.register(&serde_json::json!("a1")).unwrap();
+        dispatcher.close("MCP server 'github' closed the connection".to_string());
+
+        let error = pending.response(TIMEOUT).await.unwrap_err();
+        assert!(is_connection_lost(&error));
+        assert_eq!(error.to_string(), "MCP server 'github' closed the connection");
+        assert!(dispatcher.register(&serde_json::json!("b2")).is_err());
+    }
+}
@@ -0,0 +1,128 @@
+//! 서버 로그 (`logging/setLevel`, `notifications/message`)
+//!
+//! `logging` 기능을 알린 서버에는 초기화 직후 보낼 로그 수준을 알려 주고, 서버가 보낸 로그 메시지는
+//! `mcp_server` 대상의 tracing 이벤트로 기록합니다(서버 이름과 로거 이름은 필드로 붙음).
+//! 수준은 서버 설정의 `logLevel`, 없으면 `RUST_LOG=mcp_server=debug`일 때 `debug`, 그 외에는 `warning`입니다.
+
+use serde::Deserialize;
+
+use super::protocol::{MCPMessage, SetLevelParams};
+use super::registry::ServerConfig;
+
+/// 서버 로그 알림 메서드
+pub const LOG_MESSAGE_NOTIFICATION: &str = "notifications/message";
+
+/// 서버 로그를 기록하는 tracing 대상
+pub const LOG_TARGET: &str = "mcp_server";
+
+/// MCP 로그 수준 (syslog 순서, 낮은 것부터)
+pub const LOG_LEVELS: &[&str] = &["debug", "info", "notice", "warning", "error", "critical", "alert", "emergency"];
+
+/// 기본으로 요청하는 수준 (서버의 일상적인 info 로그가 출력에 섞이지 않게 함)
+const DEFAULT_LEVEL: &str = "warning";
+
+/// 서버 로그 메시지의 최대 길이 (문자)
+const MAX_MESSAGE_CHARS: usize = 2000;
+
+/// 서버 로그 알림 인자
+#[derive(Debug, Clone, Deserialize)]
+pub struct LogMessageParams {
+    pub level: String,
+    #[serde(default)]
+    pub logger: Option<String>,
+    #[serde(default)]
+    pub data: serde_json::Value,
+}
+
+/// 서버에 요청할 로그 수준
+pub fn requested_level(server: &str, config: &ServerConfig) -> &'static str {
+    if let Some(level) = config.log_level.as_deref() {
+        match LOG_LEVELS.iter().find(|known| known.eq_ignore_ascii_case(level.trim())) {
+            Some(level) => return level,
+            None => tracing::warn!(
+                "Ignoring logLevel '{}' for MCP server '{}' (expected one of {})",
+                level,
+                server,
+                LOG_LEVELS.join(", ")
+            ),
+        }
+    }
+    if tracing::enabled!(target: LOG_TARGET, tracing::Level::DEBUG) { "debug" } else { DEFAULT_LEVEL }
+}
+
+/// 로그 수준 설정 요청
+pub fn set_level_message(level: &str) -> MCPMessage {
+    MCPMessage::LoggingSetLevel {
+        jsonrpc: MCPMessage::JSONRPC_VERSION.to_string(),
+        id: MCPMessage::new_request_id(),
+        params: SetLevelParams { level: level.to_string() },
+    }
+}
+
+/// MCP 로그 수준에 해당하는 tracing 수준 (notice는 info, critical 이상은 error)
+pub fn tracing_level(level: &str) -> tracing::Level {
+    match level {
+        "debug" => tracing::Level::DEBUG,
+        "info" | "notice" => tracing::Level::INFO,
+        "warning" => tracing::Level::WARN,
+        _ => tracing::Level::ERROR,
+    }
+}
+
+/// 로그 내용 (문자열은 그대로, 그 외에는 JSON, 제어 문자는 제거하고 길면 자름)
+fn message_text(data: &serde_json::Value) -> String {
+    let text = match data {
+        serde_json::Value::String(text) => text.clone(),
+        data => data.to_string(),
+    };
+    text.chars()
+        .map(|c| if c == '\n' || c == '\t' { ' ' } else { c })
+        .filter(|c| !c.is_control())
+        .take(MAX_MESSAGE_CHARS)
+        .collect()
+}
+
+/// 서버 알림 처리 (로그 알림이면 tracing 이벤트로 기록)
+pub fn handle_notification(server: &str, message: &serde_json::Value) {
+    if message.get("method").and_then(|method| method.as_str()) != Some(LOG_MESSAGE_NOTIFICATION) {
+        return;
+    }
+    let Some(params) = message.get("params").and_then(|params| LogMessageParams::deserialize(params).ok()) else {
+        return;
+    };
+    let logger = params.logger.as_deref();
+    let text = message_text(&params.data);
+    match tracing_level(&params.level) {
+        tracing::Level::DEBUG => tracing::debug!(target: LOG_TARGET, server, logger, "{}", text),
+        tracing::Level::INFO => tracing::info!(target: LOG_TARGET, server, logger, "{}", text),
+        tracing::Level::WARN => tracing::warn!(target: LOG_TARGET, server, logger, "{}", text),
+        _ => tracing::error!(target: LOG_TARGET, server, logger, "{}", text),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_requested_level() {
+        let config: ServerConfig = serde_json::from_value(serde_json::json!({"command": "server", "logLevel": "Debug"})).unwrap();
+        assert_eq!(requested_level("github", &config), "debug");
+
+        let config: ServerConfig = serde_json::from_value(serde_json::json!({"command": "server", "logLevel": "verbose"})).unwrap();
+        assert_eq!(requested_level("github", &config), DEFAULT_LEVEL);
+
+        let message = serde_json::to_value(set_level_message("info")).unwrap();
+        assert_eq!(message["method"], "logging/setLevel");
+        assert_eq!(message["params"], serde_json::json!({"level": "info"}));
+    }
+
+    #[test]
+    fn test_tracing_level() {
+        assert_eq!(tracing_level("notice"), tracing::Level::INFO);
+        assert_eq!(tracing_level("warning"), tracing::Level::WARN);
+        assert_eq!(tracing_level("emergency"), tracing::Level::ERROR);
+        assert_eq!(message_text(&serde_json::json!("disk\nfull\x1b[31m")), "disk full[31m");
+        assert_eq!(message_text(&serde_json::json!({"error": "timeout"})), r#"{"error":"timeout"}"#);
+    }
+}
@@ -3,19 +3,37 @@
 //! MCP는 AI 에이전트가 외부 도구와 상호작용하기 위한 개방형 표준입니다.
 //! 이 모듈은 MCP 클라이언트의 기본 구조를 제공합니다.
 
+pub mod approval;
+pub mod cache;
+pub mod cancel;
 pub mod client;
+pub mod connection;
+pub mod dispatch;
+pub mod logging;
+pub mod negotiation;
+pub mod oauth;
+pub mod progress;
 pub mod protocol;
+pub mod registry;
+pub mod roots;
+pub mod sampling;
+pub mod schema;
+pub mod sse;
+pub mod streamable;
 pub mod tools;
 
-pub use client::MCPClient;
+pub use client::{MCPClient, ServerSession};
+pub use connection::{ConnectionState, ServerStatus};
 pub use protocol::*;
+pub use registry::{McpRegistry, ServerConfig};
 pub use tools::*;
 
 /// MCP 클라이언트 생성을 위한 빌더
 pub struct MCPClientBuilder {
     name: String,
     version: String,
-    server_url: Option<String>,
+    registry: McpRegistry,
+    policy: Option<crate::policy::Policy>,
 }
 
 impl MCPClientBuilder {
@@ -24,7 +42,8 @@ impl MCPClientBuilder {
         Self {
             name: name.into(),
             version: "0.1.0".to_string(),
-            server_url: None,
+            registry: McpRegistry::default(),
+            policy: None,
         }
     }
 
@@ -34,18 +53,28 @@ impl MCPClientBuilder {
         self
     }
 
-    /// MCP 서버 URL 설정
-    pub fn server_url(mut self, url: impl Into<String>) -> Self {
-        self.server_url = Some(url.into());
+    /// 연결할 서버 목록 설정 (보통 `McpRegistry::load()`)
+    pub fn registry(mut self, registry: McpRegistry) -> Self {
+        self.registry = registry;
+        self
+    }
+
+    /// 연결할 서버 추가
+    pub fn server(mut self, name: impl Into<String>, config: ServerConfig) -> Self {
+        self.registry.insert(name, config);
+        self
+    }
+
+    /// 도구 호출에 적용할 정책 (없으면 호출할 때마다 `Policy::load()`)
+    pub fn policy(mut self, policy: crate::policy::Policy) -> Self {
+        self.policy = Some(policy);
         self
     }
 
     /// MCP 클라이언트 생성
     pub fn build(self) -> MCPClient {
-        MCPClient::new(
-            self.name,
-            self.version,
-            self.server_url.unwrap_or_else(|| "stdio://".to_string()),
-        )
+        let mut client = MCPClient::new(self.name, self.version, self.registry);
+        client.policy = self.policy;
+        client
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,93 @@
+//! 프로토콜 버전 협상
+//!
+//! `initialize` 응답의 프로토콜 버전과 서버 기능을 ai-cli가 지원하는 범위와 비교합니다. 서버가 요청과 다른 버전으로
+//! 답하면 ai-cli도 지원하는 버전일 때만 그 버전으로 낮춰 계속하고, 필요한 기능(`tools`)이 없으면 이후 요청에서
+//! 알기 어려운 파싱 오류가 나는 대신 빠진 기능을 알려 주는 오류로 연결을 끝냅니다.
+//! 선택 기능(`resources`, `prompts`, `logging`)은 서버가 알린 경우에만 사용합니다.
+
+use anyhow::{Result, anyhow};
+
+use super::protocol::InitializeResult;
+
+/// ai-cli가 지원하는 프로토콜 버전 (최신순)
+pub const SUPPORTED_PROTOCOL_VERSIONS: &[&str] = &["2025-03-26", "2024-11-05"];
+
+/// ai-cli가 사용하려면 서버에 꼭 있어야 하는 기능
+const REQUIRED_CAPABILITIES: &[&str] = &["tools"];
+
+/// `initialize` 응답 확인 (`requested`는 요청한 프로토콜 버전)
+pub fn negotiate(server: &str, requested: &str, result: serde_json::Value) -> Result<InitializeResult> {
+    let version = result
+        .get("protocolVersion")
+        .and_then(|version| version.as_str())
+        .ok_or_else(|| anyhow!("MCP server '{}' did not report a protocol version in its initialize response", server))?;
+    if !SUPPORTED_PROTOCOL_VERSIONS.contains(&version) {
+        return Err(anyhow!(
+            "MCP server '{}' uses protocol version {}, which ai-cli does not support (supported: {})",
+            server,
+            version,
+            SUPPORTED_PROTOCOL_VERSIONS.join(", ")
+        ));
+    }
+    if version != requested {
+        tracing::info!("MCP server '{}' negotiated protocol version {} (requested {})", server, version, requested);
+    }
+
+    let offered: Vec<&str> = result
+        .get("capabilities")
+        .and_then(|capabilities| capabilities.as_object())
+        .map(|capabilities| capabilities.keys().map(String::as_str).collect())
+        .unwrap_or_default();
+    let missing: Vec<&str> = REQUIRED_CAPABILITIES.iter().copied().filter(|name| !offered.contains(name)).collect();
+    if !missing.is_empty() {
+        let offered = if offered.is_empty() { "none".to_string() } else { offered.join(", ") };
+        return Err(anyhow!(
+            "MCP server '{}' is missing required capabilities: {} (it offers: {})",
+            server,
+            missing.join(", "),
+            offered
+        ));
+    }
+
+    serde_json::from_value(result).map_err(|e| anyhow!("Failed to parse initialize response from MCP server '{}': {}", server, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn initialize_result(version: &str, capabilities: serde_json::Value) -> serde_json::Value {
+        serde_json::json!({
+            "protocolVersion": version,
+            "capabilities": capabilities,
+            "serverInfo": {"name": "github", "version": "1.0"}
+        })
+    }
+
+    #[test]
+    fn test_negotiate_version() {
+        // 요청보다 낮지만 지원하는 버전으로 답하면 그 버전으로 계속
+        let result = negotiate("github", "2025-03-26", initialize_result("2024-11-05", serde_json::json!({"tools": {}}))).unwrap();
+        assert_eq!(result.protocol_version, "2024-11-05");
+        assert!(result.capabilities.resources.is_none());
+
+        let error = negotiate("github", "2025-03-26", initialize_result("2023-01-01", serde_json::json!({"tools": {}}))).unwrap_err();
+        assert!(error.to_string().contains("protocol version 2023-01-01"));
+
+        let error = negotiate("github", "2024-11-05", serde_json::json!({"capabilities": {}})).unwrap_err();
+        assert!(error.to_string().contains("did not report a protocol version"));
+    }
+
+    #[test]
+    fn test_negotiate_capabilities() {
+        let error = negotiate("docs", "2024-11-05", initialize_result("2024-11-05", serde_json::json!({"resources": {}, "prompts": {}})))
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "MCP server 'docs' is missing required capabilities: tools (it offers: resources, prompts)"
+        );
+
+        let error = negotiate("docs", "2024-11-05", serde_json::json!({"protocolVersion": "2024-11-05"})).unwrap_err();
+        assert!(error.to_string().ends_with("(it offers: none)"));
+    }
+}
@@ -0,0 +1,549 @@
+use anyhow::{Result, anyhow};
+use reqwest::{StatusCode, Url};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpListener;
+
+use super::streamable::HttpStatusError;
+use crate::ai_utils;
+use crate::policy;
+use crate::security;
+
+/// 원격 MCP 서버 OAuth 2.1 인증
+///
+/// 서버가 401로 응답하면 MCP 인증 사양에 따라 보호 리소스 메타데이터와 인증 서버 메타데이터를 찾고,
+/// 동적 클라이언트 등록과 PKCE 인증 코드 흐름(브라우저 + 로컬 콜백)으로 토큰을 받습니다.
+/// 토큰은 OS 키체인(macOS 키체인, Windows 자격 증명 관리자, Linux Secret Service)에 저장하고, 사용할 수 없으면
+/// `~/.ai-cli/mcp_tokens.json`(소유자만 읽기 가능)에 저장합니다. 만료된 토큰은 refresh token으로 갱신합니다.
+///
+/// ```sh
+/// export AI_CLI_MCP_TOKEN_STORE=file   # 키체인 대신 파일에 저장
+/// ```
+///
+/// 키체인 항목의 서비스 이름
+const KEYRING_SERVICE: &str = "ai-cli-mcp";
+
+/// 키체인을 사용할 수 없을 때의 토큰 파일 (`~/.ai-cli/` 아래)
+const TOKEN_FILE: &str = "mcp_tokens.json";
+
+/// 브라우저 인증을 기다리는 시간
+const CALLBACK_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// 만료 전에 미리 갱신하는 여유 시간 (초)
+const EXPIRY_MARGIN_SECS: u64 = 60;
+
+/// 응답이 인증 필요(401)인지
+pub fn is_unauthorized(error: &anyhow::Error) -> bool {
+    error
+        .downcast_ref::<HttpStatusError>()
+        .is_some_and(|error| error.status == StatusCode::UNAUTHORIZED)
+}
+
+/// 401 응답의 `WWW-Authenticate` 헤더
+pub fn authenticate_header(error: &anyhow::Error) -> Option<&str> {
+    error.downcast_ref::<HttpStatusError>().and_then(|error| error.authenticate.as_deref())
+}
+
+/// 저장한 토큰 (서버 주소별)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StoredToken {
+    pub access_token: String,
+    #[serde(default)]
+    pub refresh_token: Option<String>,
+    /// 만료 시각 (Unix 초, 알 수 없으면 None)
+    #[serde(default)]
+    pub expires_at: Option<u64>,
+    pub client_id: String,
+    #[serde(default)]
+    pub client_secret: Option<String>,
+    pub token_endpoint: String,
+}
+
+impl StoredToken {
+    /// 만료되었거나 곧 만료되는지
+    fn is_expired(&self, now: u64) -> bool {
+        self.expires_at.is_some_and(|expires_at| now + EXPIRY_MARGIN_SECS >= expires_at)
+    }
+}
+
+/// PKCE 검증값과 S256 챌린지
+#[derive(Debug, Clone)]
+struct Pkce {
+    verifier: String,
+    challenge: String,
+}
+
+impl Pkce {
+    fn generate() -> Result<Self> {
+        Ok(Self::from_verifier(random_token()?))
+    }
+
+    fn from_verifier(verifier: String) -> Self {
+        let challenge = base64url(&Sha256::digest(verifier.as_bytes()));
+        Self { verifier, challenge }
+    }
+}
+
+/// 패딩 없는 base64url 인코딩
+fn base64url(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let value = chunk.iter().enumerate().fold(0u32, |value, (i, byte)| value | ((*byte as u32) << (16 - 8 * i)));
+        for i in 0..=chunk.len() {
+            encoded.push(ALPHABET[((value >> (18 - 6 * i)) & 0x3f) as usize] as char);
+        }
+    }
+    encoded
+}
+
+/// 무작위 32바이트 문자열 (PKCE 검증값, state)
+fn random_token() -> Result<String> {
+    let mut bytes = [0u8; 32];
+    getrandom::getrandom(&mut bytes).map_err(|e| anyhow!("Failed to generate random value: {}", e))?;
+    Ok(base64url(&bytes))
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|now| now.as_secs()).unwrap_or_default()
+}
+
+/// `WWW-Authenticate: Bearer resource_metadata="..."`의 메타데이터 주소
+fn resource_metadata_url(header: &str) -> Option<String> {
+    let start = header.find("resource_metadata=")? + "resource_metadata=".len();
+    let value = &header[start..];
+    let value = match value.strip_prefix('"') {
+        Some(quoted) => &quoted[..quoted.find('"')?],
+        None => value.split([',', ' ']).next()?,
+    };
+    Some(value.to_string())
+}
+
+/// RFC 8414 인증 서버 메타데이터 주소 (경로가 있는 발급자는 well-known 뒤에 경로를 붙임)
+fn authorization_metadata_url(issuer: &Url) -> Result<Url> {
+    let path = issuer.path().trim_end_matches('/');
+    Ok(issuer.join(&format!("/.well-known/oauth-authorization-server{}", path))?)
+}
+
+/// 인증 서버 엔드포인트 주소 확인 (HTTPS만 허용, 로컬 테스트 서버를 위해 루프백은 HTTP도 허용)
+fn require_https(endpoint: &str) -> Result<Url> {
+    let url = Url::parse(endpoint).map_err(|e| anyhow!("Invalid OAuth endpoint '{}': {}", endpoint, e))?;
+    let host = url.host_str().unwrap_or_default().trim_start_matches('[').trim_end_matches(']');
+    match url.scheme() {
+        "https" => Ok(url),
+        "http" if policy::is_loopback_host(host) => Ok(url),
+        _ => Err(anyhow!("OAuth endpoint '{}' must use HTTPS", endpoint)),
+    }
+}
+
+/// 엔드포인트 호스트별 HTTP 클라이언트 (메타데이터, 등록, 토큰 서버가 서로 다른 호스트일 수 있으므로 각각 egress 정책 확인)
+fn endpoint_client(endpoint: &str) -> Result<reqwest::Client> {
+    require_https(endpoint)?;
+    ai_utils::http_client(endpoint)
+}
+
+/// 보호 리소스 메타데이터
+#[derive(Debug, Deserialize)]
+struct ProtectedResourceMetadata {
+    #[serde(default)]
+    authorization_servers: Vec<String>,
+}
+
+/// 인증 서버 메타데이터
+#[derive(Debug, Deserialize)]
+struct AuthorizationServerMetadata {
+    authorization_endpoint: String,
+    token_endpoint: String,
+    #[serde(default)]
+    registration_endpoint: Option<String>,
+}
+
+impl AuthorizationServerMetadata {
+    /// 메타데이터를 제공하지 않는 서버의 기본 주소 (MCP 2025-03-26)
+    fn defaults(origin: &Url) -> Result<Self> {
+        Ok(Self {
+            authorization_endpoint: origin.join("/authorize")?.to_string(),
+            token_endpoint: origin.join("/token")?.to_string(),
+            registration_endpoint: Some(origin.join("/register")?.to_string()),
+        })
+    }
+}
+
+/// 동적 클라이언트 등록 결과
+#[derive(Debug, Deserialize)]
+struct ClientRegistration {
+    client_id: String,
+    #[serde(default)]
+    client_secret: Option<String>,
+}
+
+/// 토큰 응답
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    #[serde(default)]
+    refresh_token: Option<String>,
+    #[serde(default)]
+    expires_in: Option<u64>,
+}
+
+/// 서버 주소로 인증 서버 찾기 (보호 리소스 메타데이터 → 인증 서버 메타데이터 → 기본 주소)
+async fn discover(server_url: &Url, authenticate: Option<&str>) -> Result<AuthorizationServerMetadata> {
+    let resource_url = match authenticate.and_then(resource_metadata_url) {
+        Some(url) => server_url.join(&url)?,
+        None => server_url.join("/.well-known/oauth-protected-resource")?,
+    };
+    let client = endpoint_client(resource_url.as_str())?;
+    let issuer = match client.get(resource_url).send().await {
+        Ok(response) if response.status().is_success() => {
+            let metadata: ProtectedResourceMetadata = response.json().await?;
+            match metadata.authorization_servers.first() {
+                Some(issuer) => Url::parse(issuer).map_err(|e| anyhow!("Invalid authorization server '{}': {}", issuer, e))?,
+                None => server_url.clone(),
+            }
+        }
+        _ => server_url.join("/")?,
+    };
+
+    let metadata_url = authorization_metadata_url(&issuer)?;
+    let client = endpoint_client(metadata_url.as_str())?;
+    let metadata: AuthorizationServerMetadata = match client.get(metadata_url).send().await {
+        Ok(response) if response.status().is_success() => response
+            .json()
+            .await
+            .map_err(|e| anyhow!("Invalid authorization server metadata: {}", e))?,
+        _ => AuthorizationServerMetadata::defaults(&issuer)?,
+    };
+
+    require_https(&metadata.authorization_endpoint)?;
+    require_https(&metadata.token_endpoint)?;
+    if let Some(endpoint) = &metadata.registration_endpoint {
+        require_https(endpoint)?;
+    }
+    Ok(metadata)
+}
+
+/// 동적 클라이언트 등록 (공개 클라이언트, 로컬 콜백 주소)
+async fn register_client(metadata: &AuthorizationServerMetadata, redirect_uri: &str) -> Result<ClientRegistration> {
+    let endpoint = metadata
+        .registration_endpoint
+        .as_deref()
+        .ok_or_else(|| anyhow!("The authorization server does not support dynamic client registration"))?;
+    let response = endpoint_client(endpoint)?
+        .post(endpoint)
+        .json(&serde_json::json!({
+            "client_name": "ai-cli",
+            "redirect_uris": [redirect_uri],
+            "grant_types": ["authorization_code", "refresh_token"],
+            "response_types": ["code"],
+            "token_endpoint_auth_method": "none",
+        }))
+        .send()
+        .await?;
+    if !response.status().is_success() {
+        return Err(anyhow!("Client registration failed: HTTP {}", response.status()));
+    }
+    response.json().await.map_err(|e| anyhow!("Invalid client registration response: {}", e))
+}
+
+/// 토큰 엔드포인트 요청 (인증 코드 교환, 갱신)
+async fn request_token(endpoint: &str, form: &[(&str, &str)]) -> Result<TokenResponse> {
+    let response = endpoint_client(endpoint)?.post(endpoint).form(form).send().await?;
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        return Err(anyhow!("Token request failed: HTTP {} {}", status, body.trim()));
+    }
+    response.json().await.map_err(|e| anyhow!("Invalid token response: {}", e))
+}
+
+/// 브라우저로 인증 페이지 열기 (`$BROWSER`가 있으면 사용, 실패해도 주소는 출력됨)
+/// Windows에서는 `cmd /C start`를 쓰면 주소의 `&`가 명령어 구분자로 해석되므로 셸 없이 URL 핸들러를 직접 호출
+fn open_browser(url: &str) {
+    let mut command = match std::env::var("BROWSER") {
+        Ok(browser) if !browser.trim().is_empty() => Command::new(browser.trim()),
+        _ if cfg!(target_os = "macos") => Command::new("open"),
+        _ if cfg!(windows) => {
+            let mut command = Command::new("rundll32");
+            command.arg("url.dll,FileProtocolHandler");
+            command
+        }
+        _ => Command::new("xdg-open"),
+    };
+    if let Err(e) = command.arg(url).stdout(Stdio::null()).stderr(Stdio::null()).spawn() {
+        tracing::debug!("Failed to open a browser: {}", e);
+    }
+}
+
+/// 로컬 콜백 요청을 받아 인증 코드 반환 (state가 다르면 무시하고 계속 기다림)
+async fn wait_for_callback(listener: TcpListener, state: &str) -> Result<String> {
+    loop {
+        let (mut stream, _) = listener.accept().await?;
+        let mut request_line = String::new();
+        BufReader::new(&mut stream).read_line(&mut request_line).await?;
+        let path = request_line.split_whitespace().nth(1).unwrap_or("/");
+        let url = Url::parse(&format!("http://localhost{}", path))?;
+        let params: BTreeMap<String, String> = url.query_pairs().into_owned().collect();
+
+        if params.get("state").map(String::as_str) != Some(state) {
+            let _ = stream.write_all(b"HTTP/1.1 400 Bad Request\r\nContent-Length: 0\r\nConnection: close\r\n\r\n").await;
+            continue;
+        }
+
+        let (body, result) = match (params.get("code"), params.get("error")) {
+            (Some(code), _) => ("Signed in. You can close this window and return to ai-cli.", Ok(code.clone())),
+            (None, error) => (
+                "Sign-in failed. Return to ai-cli for details.",
+                Err(anyhow!("Authorization was denied: {}", error.map(String::as_str).unwrap_or("no code returned"))),
+            ),
+        };
+        let response = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: text/plain; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            body.len(),
+            body
+        );
+        let _ = stream.write_all(response.as_bytes()).await;
+        return result;
+    }
+}
+
+/// 인증 흐름을 실행해 액세스 토큰을 받아 저장 (`authenticate`는 401 응답의 `WWW-Authenticate` 헤더)
+pub async fn authorize(server: &str, url: &str, authenticate: Option<&str>) -> Result<String> {
+    if ai_utils::is_non_interactive() {
+        return Err(anyhow!(
+            "MCP server '{}' requires sign-in; run `ai-cli mcp status` in a terminal to sign in first",
+            server
+        ));
+    }
+
+    let server_url = Url::parse(url).map_err(|e| anyhow!("Invalid MCP server URL '{}': {}", url, e))?;
+    let metadata = discover(&server_url, authenticate).await?;
+
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let redirect_uri = format!("http://127.0.0.1:{}/callback", listener.local_addr()?.port());
+    let registration = register_client(&metadata, &redirect_uri).await?;
+
+    let pkce = Pkce::generate()?;
+    let state = random_token()?;
+    let mut authorization_url = Url::parse(&metadata.authorization_endpoint)
+        .map_err(|e| anyhow!("Invalid authorization endpoint '{}': {}", metadata.authorization_endpoint, e))?;
+    authorization_url
+        .query_pairs_mut()
+        .append_pair("response_type", "code")
+        .append_pair("client_id", &registration.client_id)
+        .append_pair("redirect_uri", &redirect_uri)
+        .append_pair("code_challenge", &pkce.challenge)
+        .append_pair("code_challenge_method", "S256")
+        .append_pair("state", &state)
+        .append_pair("resource", url);
+
+    println!("🔑 MCP server '{}' requires sign-in. Opening your browser...", server);
+    println!("   If it does not open, visit: {}", authorization_url);
+    std::io::stdout().flush()?;
+    open_browser(authorization_url.as_str());
+
+    let code = tokio::time::timeout(CALLBACK_TIMEOUT, wait_for_callback(listener, &state))
+        .await
+        .map_err(|_| anyhow!("Sign-in was not completed within {} minutes", CALLBACK_TIMEOUT.as_secs() / 60))??;
+
+    let mut form = vec![
+        ("grant_type", "authorization_code"),
+        ("code", code.as_str()),
+        ("redirect_uri", redirect_uri.as_str()),
+        ("client_id", registration.client_id.as_str()),
+        ("code_verifier", pkce.verifier.as_str()),
+        ("resource", url),
+    ];
+    if let Some(secret) = &registration.client_secret {
+        form.push(("client_secret", secret));
+    }
+    let token = request_token(&metadata.token_endpoint, &form).await?;
+
+    let stored = StoredToken {
+        access_token: token.access_token,
+        refresh_token: token.refresh_token,
+        expires_at: token.expires_in.map(|expires_in| unix_now() + expires_in),
+        client_id: registration.client_id,
+        client_secret: registration.client_secret,
+        token_endpoint: metadata.token_endpoint,
+    };
+    save_token(url, &stored)?;
+    println!("✅ Signed in to MCP server '{}'", server);
+    Ok(stored.access_token)
+}
+
+/// 저장한 액세스 토큰 (만료되었으면 갱신, 갱신할 수 없으면 삭제하고 None)
+pub async fn access_token(url: &str) -> Result<Option<String>> {
+    let Some(stored) = load_token(url) else {
+        return Ok(None);
+    };
+    if !stored.is_expired(unix_now()) {
+        return Ok(Some(stored.access_token));
+    }
+
+    let Some(refresh_token) = stored.refresh_token.clone() else {
+        delete_token(url)?;
+        return Ok(None);
+    };
+    let mut form = vec![
+        ("grant_type", "refresh_token"),
+        ("refresh_token", refresh_token.as_str()),
+        ("client_id", stored.client_id.as_str()),
+        ("resource", url),
+    ];
+    if let Some(secret) = &stored.client_secret {
+        form.push(("client_secret", secret));
+    }
+
+    match request_token(&stored.token_endpoint, &form).await {
+        Ok(token) => {
+            let refreshed = StoredToken {
+                access_token: token.access_token,
+                // 새 refresh token을 주지 않으면 기존 것을 계속 사용
+                refresh_token: token.refresh_token.or(Some(refresh_token)),
+                expires_at: token.expires_in.map(|expires_in| unix_now() + expires_in),
+                ..stored
+            };
+            save_token(url, &refreshed)?;
+            tracing::info!("Refreshed OAuth token for {}", url);
+            Ok(Some(refreshed.access_token))
+        }
+        Err(e) => {
+            tracing::warn!("Refreshing OAuth token for {} failed: {}", url, e);
+            delete_token(url)?;
+            Ok(None)
+        }
+    }
+}
+
+/// 저장한 토큰 삭제 (로그아웃, 삭제했으면 true)
+pub fn logout(url: &str) -> Result<bool> {
+    let existed = load_token(url).is_some();
+    delete_token(url)?;
+    Ok(existed)
+}
+
+/// 키체인을 사용할지 (`AI_CLI_MCP_TOKEN_STORE=file`이면 파일만 사용)
+fn use_keyring() -> bool {
+    std::env::var("AI_CLI_MCP_TOKEN_STORE").map(|store| store != "file").unwrap_or(true)
+}
+
+fn load_token(url: &str) -> Option<StoredToken> {
+    if use_keyring() {
+        if let Some(token) = crate::keyring::get(KEYRING_SERVICE, url).and_then(|json| serde_json::from_str(&json).ok()) {
+            return Some(token);
+        }
+    }
+    load_token_file().remove(url)
+}
+
+fn save_token(url: &str, token: &StoredToken) -> Result<()> {
+    let json = serde_json::to_string(token)?;
+    if use_keyring() {
+        match crate::keyring::set(KEYRING_SERVICE, url, &json) {
+            Ok(()) => return Ok(()),
+            Err(e) => tracing::debug!("OS keychain unavailable, storing the token in {}: {}", TOKEN_FILE, e),
+        }
+    }
+    let mut tokens = load_token_file();
+    tokens.insert(url.to_string(), token.clone());
+    save_token_file(&tokens)
+}
+
+fn delete_token(url: &str) -> Result<()> {
+    if use_keyring() {
+        crate::keyring::delete(KEYRING_SERVICE, url);
+    }
+    let mut tokens = load_token_file();
+    if tokens.remove(url).is_some() {
+        save_token_file(&tokens)?;
+    }
+    Ok(())
+}
+
+fn token_file_path() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(".ai-cli").join(TOKEN_FILE))
+}
+
+fn load_token_file() -> BTreeMap<String, StoredToken> {
+    token_file_path()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// 토큰 파일 저장 (Unix에서는 내용을 쓰기 전에 소유자만 읽을 수 있는 파일로 생성)
+fn save_token_file(tokens: &BTreeMap<String, StoredToken>) -> Result<()> {
+    let Some(path) = token_file_path() else {
+        return Err(anyhow!("Cannot determine the home directory to store MCP tokens"));
+    };
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    security::write_private_file(&path, serde_json::to_string_pretty(tokens)?.as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pkce_challenge() {
+        // RFC 7636 부록 B
+        let pkce = Pkce::from_verifier("dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk".to_string());
+        assert_eq!(pkce.challenge, "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM");
+        assert_eq!(base64url(&[3, 236, 255, 224, 193]), "A-z_4ME");
+        assert_eq!(random_token().unwrap().len(), 43);
+    }
+
+    #[test]
+    fn test_discovery_urls() {
+        assert_eq!(
+            resource_metadata_url(r#"Bearer error="invalid_token", resource_metadata="https://mcp.example.com/.well-known/oauth-protected-resource""#),
+            Some("https://mcp.example.com/.well-known/oauth-protected-resource".to_string())
+        );
+        assert_eq!(resource_metadata_url("Bearer realm=\"mcp\""), None);
+
+        let issuer = Url::parse("https://auth.example.com/tenant1").unwrap();
+        assert_eq!(
+            authorization_metadata_url(&issuer).unwrap().as_str(),
+            "https://auth.example.com/.well-known/oauth-authorization-server/tenant1"
+        );
+        let root = Url::parse("https://auth.example.com/").unwrap();
+        assert_eq!(
+            AuthorizationServerMetadata::defaults(&root).unwrap().token_endpoint,
+            "https://auth.example.com/token"
+        );
+    }
+
+    #[test]
+    fn test_endpoints_require_https() {
+        assert!(require_https("https://auth.example.com/token").is_ok());
+        assert!(require_https("http://127.0.0.1:8080/token").is_ok());
+        assert!(require_https("http://[::1]:8080/token").is_ok());
+        assert!(require_https("http://auth.example.com/token").is_err());
+        assert!(require_https("ftp://auth.example.com/token").is_err());
+    }
+
+    #[test]
+    fn test_token_expiry() {
+        let token = StoredToken {
+            access_token: "a".to_string(),
+            refresh_token: None,
+            expires_at: Some(1_000),
+            client_id: "c".to_string(),
+            client_secret: None,
+            token_endpoint: "https://auth.example.com/token".to_string(),
+        };
+        assert!(!token.is_expired(900));
+        assert!(token.is_expired(950));
+        assert!(!StoredToken { expires_at: None, ..token }.is_expired(2_000));
+    }
+}
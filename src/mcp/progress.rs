@@ -0,0 +1,109 @@
+//! 진행 상황 알림 (`notifications/progress`)
+//!
+//! 도구 호출마다 요청 ID를 progress token으로 보내고, 서버가 보낸 진행 알림을 터미널(stderr)에
+//! 한 줄 진행 막대로 그립니다. 오래 걸리는 호출(큰 리포지토리 검색, CI 실행)이 멈춘 것처럼 보이지 않게 하며,
+//! 알림을 받을 때마다 응답 대기 시간도 다시 시작됩니다.
+
+use serde::Deserialize;
+use std::io::{IsTerminal, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// 진행 알림 메서드
+pub const PROGRESS_NOTIFICATION: &str = "notifications/progress";
+
+/// 진행 막대 너비 (문자)
+const BAR_WIDTH: usize = 24;
+
+/// 진행 메시지의 최대 길이 (문자)
+const MAX_MESSAGE_CHARS: usize = 60;
+
+/// 진행 줄을 그렸는지 (호출이 끝나면 지움)
+static DRAWN: AtomicBool = AtomicBool::new(false);
+
+/// 진행 알림 인자
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProgressParams {
+    pub progress_token: serde_json::Value,
+    pub progress: f64,
+    #[serde(default)]
+    pub total: Option<f64>,
+    #[serde(default)]
+    pub message: Option<String>,
+}
+
+/// 진행 줄 (전체 양을 알면 막대와 백분율, 모르면 진행 값만)
+pub fn render(params: &ProgressParams) -> String {
+    let mut line = match params.total.filter(|total| *total > 0.0) {
+        Some(total) => {
+            let ratio = (params.progress / total).clamp(0.0, 1.0);
+            let filled = (ratio * BAR_WIDTH as f64).round() as usize;
+            format!("⏳ [{}{}] {:>3}%", "█".repeat(filled), "░".repeat(BAR_WIDTH - filled), (ratio * 100.0).round() as u32)
+        }
+        None => format!("⏳ {}", params.progress),
+    };
+    if let Some(message) = params.message.as_deref().map(str::trim).filter(|message| !message.is_empty()) {
+        line.push(' ');
+        line.extend(message.chars().filter(|c| !c.is_control()).take(MAX_MESSAGE_CHARS));
+    }
+    line
+}
+
+/// 서버 알림 처리 (진행 알림이면 진행 줄을 다시 그림, 터미널이 아니거나 `--quiet`이면 무시)
+pub fn handle_notification(message: &serde_json::Value) {
+    if message.get("method").and_then(|method| method.as_str()) != Some(PROGRESS_NOTIFICATION) {
+        return;
+    }
+    let Some(params) = message.get("params").and_then(|params| ProgressParams::deserialize(params).ok()) else {
+        return;
+    };
+    tracing::debug!("MCP progress {}: {}/{:?}", params.progress_token, params.progress, params.total);
+
+    let mut stderr = std::io::stderr();
+    if !stderr.is_terminal() || crate::spinner::is_quiet() {
+        return;
+    }
+    let _ = write!(stderr, "\r\x1b[2K{}", render(&params));
+    let _ = stderr.flush();
+    DRAWN.store(true, Ordering::Relaxed);
+}
+
+/// 호출이 끝나면 진행 줄 지우기
+pub fn finish() {
+    if DRAWN.swap(false, Ordering::Relaxed) {
+        let mut stderr = std::io::stderr();
+        let _ = write!(stderr, "\r\x1b[2K");
+        let _ = stderr.flush();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params(progress: f64, total: Option<f64>, message: Option<&str>) -> ProgressParams {
+        ProgressParams { progress_token: serde_json::json!("t"), progress, total, message: message.map(str::to_string) }
+    }
+
+    #[test]
+    fn test_render() {
+        assert_eq!(render(&params(0.0, Some(4.0), None)), format!("⏳ [{}]   0%", "░".repeat(BAR_WIDTH)));
+        assert_eq!(
+            render(&params(1.0, Some(2.0), Some("Scanning files"))),
+            format!("⏳ [{}{}]  50% Scanning files", "█".repeat(BAR_WIDTH / 2), "░".repeat(BAR_WIDTH / 2))
+        );
+        // 전체보다 큰 값은 100%로 제한
+        assert!(render(&params(7.0, Some(5.0), None)).ends_with("100%"));
+        assert_eq!(render(&params(12.0, None, Some("\x1b[31mjobs\n"))), "⏳ 12 [31mjobs");
+    }
+
+    #[test]
+    fn test_progress_params() {
+        let params: ProgressParams = serde_json::from_value(serde_json::json!({
+            "progressToken": "abc", "progress": 3, "total": 10, "message": "Running CI"
+        }))
+        .unwrap();
+        assert_eq!(params.progress, 3.0);
+        assert_eq!(params.total, Some(10.0));
+    }
+}
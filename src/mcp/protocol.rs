@@ -48,6 +48,19 @@ pub enum MCPMessage {
         id: RequestId,
         params: CallToolParams,
     },
+    /// 연결 확인
+    #[serde(rename = "ping")]
+    Ping {
+        jsonrpc: String,
+        id: RequestId,
+    },
+    /// 서버 로그 수준 설정
+    #[serde(rename = "logging/setLevel")]
+    LoggingSetLevel {
+        jsonrpc: String,
+        id: RequestId,
+        params: SetLevelParams,
+    },
     /// 도구 호출 결과
     #[serde(rename = "tools/call/result")]
     ToolsCallResult {
@@ -57,8 +70,92 @@ pub enum MCPMessage {
     },
 }
 
+/// 파싱할 수 없는 메시지
+pub const PARSE_ERROR: i64 = -32700;
+/// 올바르지 않은 요청
+pub const INVALID_REQUEST: i64 = -32600;
+/// 지원하지 않는 메서드
+pub const METHOD_NOT_FOUND: i64 = -32601;
+/// 올바르지 않은 인자
+pub const INVALID_PARAMS: i64 = -32602;
+/// 내부 오류
+pub const INTERNAL_ERROR: i64 = -32603;
+
+/// JSON-RPC 응답 (`result`와 `error` 중 하나)
+/// 서버가 요청을 해석하지 못했으면 `id`가 null일 수 있음
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JsonRpcResponse {
+    #[serde(default)]
+    pub jsonrpc: String,
+    #[serde(default)]
+    pub id: serde_json::Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<JsonRpcError>,
+}
+
+impl JsonRpcResponse {
+    /// 메시지가 `id` 요청에 대한 응답인지 (요청·알림이 아니고 ID가 같거나, ID가 null인 오류 응답)
+    pub fn is_response_to(message: &serde_json::Value, id: &serde_json::Value) -> bool {
+        if message.get("method").is_some() {
+            return false;
+        }
+        match message.get("id") {
+            Some(serde_json::Value::Null) => message.get("error").is_some(),
+            Some(message_id) => message_id == id,
+            None => false,
+        }
+    }
+}
+
+/// JSON-RPC 오류 응답
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, thiserror::Error)]
+#[error("MCP server error {code} ({}): {message}{}", self.kind(), self.detail())]
+pub struct JsonRpcError {
+    pub code: i64,
+    #[serde(default)]
+    pub message: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<serde_json::Value>,
+}
+
+impl JsonRpcError {
+    /// 오류 코드 종류
+    pub fn kind(&self) -> &'static str {
+        match self.code {
+            PARSE_ERROR => "parse error",
+            INVALID_REQUEST => "invalid request",
+            METHOD_NOT_FOUND => "method not found",
+            INVALID_PARAMS => "invalid params",
+            INTERNAL_ERROR => "internal error",
+            -32099..=-32000 => "server error",
+            _ => "application error",
+        }
+    }
+
+    /// 오류의 `data` (문자열이면 그대로, 그 밖의 값은 JSON으로, 200자까지)
+    fn detail(&self) -> String {
+        let detail = match &self.data {
+            None | Some(serde_json::Value::Null) => return String::new(),
+            Some(serde_json::Value::String(text)) => text.trim().to_string(),
+            Some(value) => value.to_string(),
+        };
+        if detail.is_empty() {
+            return String::new();
+        }
+        format!(" ({})", detail.chars().take(200).collect::<String>())
+    }
+
+    /// 오류가 이 코드의 JSON-RPC 오류인지
+    pub fn has_code(error: &anyhow::Error, code: i64) -> bool {
+        error.chain().any(|cause| cause.downcast_ref::<Self>().is_some_and(|error| error.code == code))
+    }
+}
+
 /// 클라이언트 초기화 파라미터
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct InitializeParams {
     pub protocol_version: String,
     pub capabilities: ClientCapabilities,
@@ -69,10 +166,39 @@ pub struct InitializeParams {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ClientCapabilities {
     pub tools: Option<ToolsCapability>,
+    /// 서버의 LLM 호출 요청(`sampling/createMessage`)을 처리할 수 있음
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sampling: Option<SamplingCapability>,
+    /// 프로젝트 루트 목록(`roots/list`)을 제공하고 바뀌면 알림
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub roots: Option<RootsCapability>,
+}
+
+/// 샘플링 기능 (설정 항목 없음)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SamplingCapability {}
+
+/// 루트 기능
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RootsCapability {
+    pub list_changed: bool,
+}
+
+impl ClientCapabilities {
+    /// ai-cli가 서버에 알리는 기능 (`AI_CLI_MCP_SAMPLING=off`면 샘플링 제외)
+    pub fn current() -> Self {
+        Self {
+            tools: None,
+            sampling: super::sampling::is_enabled().then(SamplingCapability::default),
+            roots: Some(RootsCapability { list_changed: true }),
+        }
+    }
 }
 
 /// 도구 기능
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct ToolsCapability {
     pub list_changed: Option<bool>,
 }
@@ -86,6 +212,7 @@ pub struct ClientInfo {
 
 /// 초기화 결과
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct InitializeResult {
     pub protocol_version: String,
     pub capabilities: ServerCapabilities,
@@ -97,10 +224,14 @@ pub struct InitializeResult {
 pub struct ServerCapabilities {
     pub tools: Option<ToolsCapability>,
     pub resources: Option<ResourcesCapability>,
+    /// 서버가 `notifications/message`로 로그를 보내고 `logging/setLevel`을 받는지
+    #[serde(default)]
+    pub logging: Option<serde_json::Value>,
 }
 
 /// 리소스 기능
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct ResourcesCapability {
     pub subscribe: Option<bool>,
     pub list_changed: Option<bool>,
@@ -127,10 +258,33 @@ pub struct ToolsListResult {
 
 /// 도구 정의
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct Tool {
     pub name: String,
+    #[serde(default)]
     pub description: String,
     pub input_schema: ToolInputSchema,
+    /// 서버가 알려 주는 도구 성격 (승인 분류에 사용)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub annotations: Option<ToolAnnotations>,
+}
+
+/// 도구 주석 (서버가 주는 힌트이므로 신뢰할 수 있는 서버에서만 의미가 있음)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolAnnotations {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// 외부 상태를 바꾸지 않음
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub read_only_hint: Option<bool>,
+    /// 되돌릴 수 없는 변경을 할 수 있음
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub destructive_hint: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub idempotent_hint: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub open_world_hint: Option<bool>,
 }
 
 /// 도구 입력 스키마
@@ -138,33 +292,73 @@ pub struct Tool {
 pub struct ToolInputSchema {
     #[serde(rename = "type")]
     pub schema_type: String,
+    #[serde(default)]
     pub properties: HashMap<String, ToolProperty>,
+    #[serde(default)]
     pub required: Vec<String>,
 }
 
 /// 도구 속성
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ToolProperty {
-    #[serde(rename = "type")]
+    #[serde(rename = "type", default)]
     pub property_type: String,
+    #[serde(default)]
     pub description: String,
+    #[serde(rename = "enum")]
     pub enum_values: Option<Vec<String>>,
 }
 
+/// 로그 수준 설정 파라미터
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SetLevelParams {
+    pub level: String,
+}
+
 /// 도구 호출 파라미터
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CallToolParams {
     pub name: String,
     pub arguments: Option<serde_json::Value>,
+    #[serde(rename = "_meta", default, skip_serializing_if = "Option::is_none")]
+    pub meta: Option<RequestMeta>,
+}
+
+/// 요청 메타데이터 (서버가 진행 알림에 사용할 progress token)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestMeta {
+    pub progress_token: String,
 }
 
 /// 도구 호출 결과
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct CallToolResult {
     pub content: Vec<Content>,
     pub is_error: Option<bool>,
 }
 
+impl CallToolResult {
+    /// 텍스트 내용을 줄바꿈으로 합친 결과 (이미지는 제외)
+    pub fn text(&self) -> String {
+        self.content
+            .iter()
+            .filter_map(|item| match item {
+                Content::Text { text } => Some(text.as_str()),
+                Content::Resource { text, .. } => text.as_deref(),
+                Content::Image { .. } => None,
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// 서버가 도구 실행 실패로 표시했는지
+    pub fn is_error(&self) -> bool {
+        self.is_error.unwrap_or(false)
+    }
+}
+
 /// 콘텐츠
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type")]
@@ -176,11 +370,13 @@ pub enum Content {
     #[serde(rename = "image")]
     Image {
         data: String,
+        #[serde(rename = "mimeType")]
         mime_type: String,
     },
     #[serde(rename = "resource")]
     Resource {
         uri: String,
+        #[serde(rename = "mimeType")]
         mime_type: Option<String>,
         text: Option<String>,
         blob: Option<String>,
@@ -205,6 +401,8 @@ impl Default for InitializeParams {
                 tools: Some(ToolsCapability {
                     list_changed: Some(true),
                 }),
+                sampling: None,
+                roots: None,
             },
             client_info: ClientInfo {
                 name: "ai-cli".to_string(),
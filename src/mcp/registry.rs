@@ -0,0 +1,400 @@
+//! MCP 서버 레지스트리
+//!
+//! `~/.ai-cli/mcp.json`에 이름을 붙인 MCP 서버 목록을 정의합니다.
+//! stdio 서버는 `command`/`args`/`env`로, 원격 서버는 `url`/`headers`로 지정하며
+//! 값의 `${VAR}`는 환경 변수로 치환되어 토큰을 파일에 직접 적지 않아도 됩니다.
+//! stdio 서버는 `cwd`로 작업 디렉토리를 정할 수 있고, `command`/`args`/`env`/`cwd`의 `${PROJECT_ROOT}`(현재 Git
+//! 리포지토리 루트)와 `${CWD}`(현재 디렉토리), 맨 앞의 `~/`는 서버를 실행할 때 실제 경로로 바뀝니다.
+//! `toolAccess`로 도구별 읽기 전용/변경 분류를, `cacheTtl`로 읽기 전용 도구 결과의 캐시 시간을
+//! 지정할 수 있습니다 (`approval`, `cache` 모듈 참고).
+//!
+//! ```json
+//! {
+//!   "mcpServers": {
+//!     "github": {
+//!       "command": "npx",
+//!       "args": ["-y", "@modelcontextprotocol/server-github"],
+//!       "env": { "GITHUB_PERSONAL_ACCESS_TOKEN": "${GITHUB_TOKEN}" }
+//!     },
+//!     "filesystem": {
+//!       "command": "npx",
+//!       "args": ["-y", "@modelcontextprotocol/server-filesystem", "${PROJECT_ROOT}"],
+//!       "cwd": "~/tools"
+//!     },
+//!     "docs": {
+//!       "url": "https://mcp.example.com/mcp",
+//!       "headers": { "Authorization": "Bearer ${DOCS_TOKEN}" }
+//!     }
+//!   }
+//! }
+//! ```
+
+use anyhow::{Result, anyhow};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::approval::ToolAccess;
+use crate::config::expand_env;
+
+/// 레지스트리 파일 이름 (`~/.ai-cli/` 아래)
+pub const REGISTRY_FILE: &str = "mcp.json";
+
+/// 서버 하나의 설정
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct ServerConfig {
+    /// stdio 서버 실행 명령어
+    pub command: Option<String>,
+    pub args: Vec<String>,
+    /// stdio 서버에 추가로 전달할 환경 변수
+    pub env: BTreeMap<String, String>,
+    /// stdio 서버의 작업 디렉토리 (없으면 현재 디렉토리)
+    pub cwd: Option<String>,
+    /// 원격 서버 주소 (Streamable HTTP 또는 HTTP+SSE)
+    pub url: Option<String>,
+    /// 원격 서버 요청 헤더 (인증 토큰 등)
+    pub headers: BTreeMap<String, String>,
+    /// true면 연결하지 않음
+    pub disabled: bool,
+    /// 도구 이름(글롭 가능) → 읽기 전용/변경 분류 (서버의 `readOnlyHint`보다 우선)
+    #[serde(rename = "toolAccess")]
+    pub tool_access: BTreeMap<String, ToolAccess>,
+    /// 읽기 전용 도구 결과를 캐시할 시간 (초, 없으면 60, 0이면 캐시하지 않음)
+    #[serde(rename = "cacheTtl")]
+    pub cache_ttl: Option<u64>,
+    /// 서버에 요청할 로그 수준 (`debug` ~ `emergency`, 없으면 `warning`)
+    #[serde(rename = "logLevel")]
+    pub log_level: Option<String>,
+}
+
+/// 서버 연결 방식
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ServerTransport {
+    Stdio { command: String, args: Vec<String>, env: Vec<(String, String)>, cwd: Option<PathBuf> },
+    Http { url: String, headers: Vec<(String, String)> },
+}
+
+impl ServerConfig {
+    /// 설정 형식 검사 (`command`와 `url` 중 정확히 하나, `cwd`는 `command`와 함께만; 환경 변수는 보지 않음)
+    pub fn validate(&self) -> Result<()> {
+        match (&self.command, &self.url) {
+            (Some(_), None) => Ok(()),
+            (None, Some(_)) if self.cwd.is_some() => Err(anyhow!("'cwd' only applies to servers started with 'command'")),
+            (None, Some(_)) => Ok(()),
+            (Some(_), Some(_)) => Err(anyhow!("set either 'command' or 'url', not both")),
+            (None, None) => Err(anyhow!("missing 'command' or 'url'")),
+        }
+    }
+
+    /// `${VAR}`를 치환한 연결 방식 (설정되지 않은 변수를 쓰면 오류)
+    pub fn transport(&self) -> Result<ServerTransport> {
+        self.validate()?;
+        let expand_pairs = |field: &str, map: &BTreeMap<String, String>, expand: &dyn Fn(&str) -> Result<String>| -> Result<Vec<(String, String)>> {
+            map.iter()
+                .map(|(name, value)| Ok((name.clone(), expand(value).map_err(|e| anyhow!("'{}.{}': {}", field, name, e))?)))
+                .collect()
+        };
+
+        if let Some(url) = &self.url {
+            let expand = |value: &str| Ok(expand_env(value)?);
+            return Ok(ServerTransport::Http {
+                url: expand(url).map_err(|e: anyhow::Error| anyhow!("'url': {}", e))?,
+                headers: expand_pairs("headers", &self.headers, &expand)?,
+            });
+        }
+
+        let variables = template_variables();
+        let expand = |value: &str| expand_template(value, &variables);
+        Ok(ServerTransport::Stdio {
+            command: expand(self.command.as_deref().unwrap_or_default()).map_err(|e| anyhow!("'command': {}", e))?,
+            args: self.args.iter().map(|arg| expand(arg).map_err(|e| anyhow!("'args': {}", e))).collect::<Result<_>>()?,
+            env: expand_pairs("env", &self.env, &expand)?,
+            cwd: self.cwd.as_deref().map(|cwd| expand(cwd).map_err(|e| anyhow!("'cwd': {}", e))).transpose()?.map(PathBuf::from),
+        })
+    }
+}
+
+/// stdio 서버 설정에 쓸 수 있는 경로 변수 (`${PROJECT_ROOT}`는 Git 리포지토리 밖이면 현재 디렉토리)
+fn template_variables() -> Vec<(&'static str, String)> {
+    let current_dir = std::env::current_dir().unwrap_or_default();
+    let project_root = crate::context::ContextEngine::new()
+        .find_project_root(&current_dir)
+        .unwrap_or_else(|_| current_dir.clone());
+    vec![
+        ("PROJECT_ROOT", project_root.display().to_string()),
+        ("CWD", current_dir.display().to_string()),
+    ]
+}
+
+/// 경로 변수와 환경 변수를 치환하고 맨 앞의 `~/`를 홈 디렉토리로 바꿈 (경로 변수가 같은 이름의 환경 변수보다 우선)
+fn expand_template(value: &str, variables: &[(&str, String)]) -> Result<String> {
+    let mut value = value.to_string();
+    for (name, replacement) in variables {
+        value = value.replace(&format!("${{{}}}", name), replacement);
+    }
+    let value = expand_env(&value)?;
+    Ok(match (value.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest).display().to_string(),
+        _ => value,
+    })
+}
+
+/// 레지스트리 파일 원본 구조
+#[derive(Debug, Default, Deserialize)]
+struct RegistryFile {
+    #[serde(rename = "mcpServers", default)]
+    mcp_servers: BTreeMap<String, ServerConfig>,
+}
+
+/// 이름 붙은 MCP 서버 목록
+#[derive(Debug, Clone, Default)]
+pub struct McpRegistry {
+    servers: BTreeMap<String, ServerConfig>,
+}
+
+impl McpRegistry {
+    /// 레지스트리 파일 경로
+    pub fn path() -> Option<PathBuf> {
+        dirs::home_dir().map(|home| home.join(".ai-cli").join(REGISTRY_FILE))
+    }
+
+    /// `~/.ai-cli/mcp.json` 로드 (없으면 빈 목록)
+    pub fn load() -> Result<Self> {
+        match Self::path() {
+            Some(path) => Self::load_from(&path),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// 지정한 파일에서 로드 (없으면 빈 목록)
+    pub fn load_from(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let file: RegistryFile = serde_json::from_str(&fs::read_to_string(path)?)
+            .map_err(|e| anyhow!("Failed to parse {}: {}", path.display(), e))?;
+        Ok(Self { servers: file.mcp_servers })
+    }
+
+    /// 사용 중인 서버 (이름 순, `disabled` 제외)
+    pub fn servers(&self) -> impl Iterator<Item = (&String, &ServerConfig)> {
+        self.servers.iter().filter(|(_, config)| !config.disabled)
+    }
+
+    /// 이름으로 서버 찾기
+    pub fn get(&self, name: &str) -> Option<&ServerConfig> {
+        self.servers.get(name).filter(|config| !config.disabled)
+    }
+
+    /// 서버 추가 (같은 이름이 있으면 교체)
+    pub fn insert(&mut self, name: impl Into<String>, config: ServerConfig) {
+        self.servers.insert(name.into(), config);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.servers().next().is_none()
+    }
+
+    /// `disabled`를 포함한 모든 서버 (이름 순)
+    pub fn all_servers(&self) -> impl Iterator<Item = (&String, &ServerConfig)> {
+        self.servers.iter()
+    }
+
+    /// 레지스트리 파일의 서버 항목을 적힌 그대로 (없으면 빈 목록)
+    pub fn entries_from(path: &Path) -> Result<serde_json::Map<String, serde_json::Value>> {
+        let mut file = read_raw(path)?;
+        Ok(std::mem::take(servers_mut(&mut file, path)?))
+    }
+
+    /// 레지스트리 파일에 서버 추가 (`replace`가 아니면 같은 이름이 이미 있을 때 오류)
+    /// 파일의 다른 서버와 항목은 그대로 두고 `mcpServers`만 고침
+    pub fn add_to(path: &Path, name: &str, entry: serde_json::Value, replace: bool) -> Result<()> {
+        let config: ServerConfig = serde_json::from_value(entry.clone())?;
+        config.validate().map_err(|e| anyhow!("Invalid server '{}': {}", name, e))?;
+
+        let mut file = read_raw(path)?;
+        let servers = servers_mut(&mut file, path)?;
+        if servers.contains_key(name) && !replace {
+            return Err(anyhow!("MCP server '{}' already exists (use --force to replace it)", name));
+        }
+        servers.insert(name.to_string(), entry);
+        write_raw(path, &file)
+    }
+
+    /// 레지스트리 파일에서 서버 삭제 (없으면 false)
+    pub fn remove_from(path: &Path, name: &str) -> Result<bool> {
+        if !path.exists() {
+            return Ok(false);
+        }
+        let mut file = read_raw(path)?;
+        if servers_mut(&mut file, path)?.remove(name).is_none() {
+            return Ok(false);
+        }
+        write_raw(path, &file)?;
+        Ok(true)
+    }
+}
+
+/// `mcp add` 인자로 서버 항목 생성 (`env`는 `KEY=VALUE`, `headers`는 `Name: value`)
+pub fn server_entry(
+    command: Option<&str>,
+    args: &[String],
+    env: &[String],
+    cwd: Option<&str>,
+    url: Option<&str>,
+    headers: &[String],
+) -> Result<serde_json::Value> {
+    let mut entry = serde_json::Map::new();
+    if let Some(command) = command {
+        entry.insert("command".to_string(), command.into());
+        if !args.is_empty() {
+            entry.insert("args".to_string(), args.into());
+        }
+        if !env.is_empty() {
+            entry.insert("env".to_string(), parse_pairs(env, '=', "KEY=VALUE")?);
+        }
+        if let Some(cwd) = cwd {
+            entry.insert("cwd".to_string(), cwd.into());
+        }
+    }
+    if let Some(url) = url {
+        entry.insert("url".to_string(), url.into());
+        if !headers.is_empty() {
+            entry.insert("headers".to_string(), parse_pairs(headers, ':', "Name: value")?);
+        }
+    }
+    Ok(serde_json::Value::Object(entry))
+}
+
+/// `이름<구분자>값` 목록을 JSON 객체로 변환
+fn parse_pairs(pairs: &[String], separator: char, expected: &str) -> Result<serde_json::Value> {
+    let mut map = serde_json::Map::new();
+    for pair in pairs {
+        let (name, value) = pair
+            .split_once(separator)
+            .filter(|(name, _)| !name.trim().is_empty())
+            .ok_or_else(|| anyhow!("Expected '{}', got '{}'", expected, pair))?;
+        map.insert(name.trim().to_string(), value.trim().into());
+    }
+    Ok(serde_json::Value::Object(map))
+}
+
+/// 레지스트리 파일 원본 JSON (없으면 빈 목록)
+fn read_raw(path: &Path) -> Result<serde_json::Value> {
+    if !path.exists() {
+        return Ok(serde_json::json!({ "mcpServers": {} }));
+    }
+    serde_json::from_str(&fs::read_to_string(path)?).map_err(|e| anyhow!("Failed to parse {}: {}", path.display(), e))
+}
+
+fn servers_mut<'a>(file: &'a mut serde_json::Value, path: &Path) -> Result<&'a mut serde_json::Map<String, serde_json::Value>> {
+    let root = file.as_object_mut().ok_or_else(|| anyhow!("{} is not a JSON object", path.display()))?;
+    root.entry("mcpServers")
+        .or_insert_with(|| serde_json::json!({}))
+        .as_object_mut()
+        .ok_or_else(|| anyhow!("'mcpServers' in {} is not an object", path.display()))
+}
+
+fn write_raw(path: &Path, file: &serde_json::Value) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, format!("{}\n", serde_json::to_string_pretty(file)?))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_load_registry() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(REGISTRY_FILE);
+        fs::write(&path, r#"{
+            "mcpServers": {
+                "github": {"command": "npx", "args": ["-y", "@modelcontextprotocol/server-github"], "env": {"TOKEN": "${AI_CLI_TEST_REGISTRY_TOKEN}"}},
+                "docs": {"url": "https://mcp.example.com/mcp", "headers": {"Authorization": "Bearer ${AI_CLI_TEST_REGISTRY_TOKEN}"}},
+                "old": {"command": "old-server", "disabled": true}
+            }
+        }"#).unwrap();
+        std::env::set_var("AI_CLI_TEST_REGISTRY_TOKEN", "t0k");
+
+        let registry = McpRegistry::load_from(&path).unwrap();
+        let names: Vec<&str> = registry.servers().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, vec!["docs", "github"]);
+        assert!(registry.get("old").is_none());
+
+        assert_eq!(registry.get("github").unwrap().transport().unwrap(), ServerTransport::Stdio {
+            command: "npx".to_string(),
+            args: vec!["-y".to_string(), "@modelcontextprotocol/server-github".to_string()],
+            env: vec![("TOKEN".to_string(), "t0k".to_string())],
+            cwd: None,
+        });
+        assert_eq!(registry.get("docs").unwrap().transport().unwrap(), ServerTransport::Http {
+            url: "https://mcp.example.com/mcp".to_string(),
+            headers: vec![("Authorization".to_string(), "Bearer t0k".to_string())],
+        });
+
+        assert!(McpRegistry::load_from(&temp_dir.path().join("missing.json")).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_add_and_remove_server() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(".ai-cli").join(REGISTRY_FILE);
+
+        let github = server_entry(
+            Some("npx"),
+            &["-y".to_string(), "@modelcontextprotocol/server-github".to_string()],
+            &["GITHUB_PERSONAL_ACCESS_TOKEN=${GITHUB_TOKEN}".to_string()],
+            None,
+            None,
+            &[],
+        )
+        .unwrap();
+        McpRegistry::add_to(&path, "github", github.clone(), false).unwrap();
+        let docs = server_entry(None, &[], &[], None, Some("https://mcp.example.com/mcp"), &["Authorization: Bearer ${T}".to_string()]).unwrap();
+        assert_eq!(docs["headers"]["Authorization"], "Bearer ${T}");
+        McpRegistry::add_to(&path, "docs", docs, false).unwrap();
+
+        assert!(McpRegistry::add_to(&path, "github", github.clone(), false).is_err());
+        McpRegistry::add_to(&path, "github", github, true).unwrap();
+        assert!(McpRegistry::add_to(&path, "empty", serde_json::json!({}), false).is_err());
+        assert!(server_entry(Some("npx"), &[], &["NO_VALUE".to_string()], None, None, &[]).is_err());
+
+        let registry = McpRegistry::load_from(&path).unwrap();
+        assert_eq!(registry.servers().count(), 2);
+        assert_eq!(registry.get("github").unwrap().env.get("GITHUB_PERSONAL_ACCESS_TOKEN").unwrap(), "${GITHUB_TOKEN}");
+
+        assert!(McpRegistry::remove_from(&path, "docs").unwrap());
+        assert!(!McpRegistry::remove_from(&path, "docs").unwrap());
+        let names: Vec<String> = McpRegistry::load_from(&path).unwrap().servers().map(|(name, _)| name.clone()).collect();
+        assert_eq!(names, vec!["github"]);
+    }
+
+    #[test]
+    fn test_server_config_requires_one_transport() {
+        assert!(ServerConfig::default().transport().is_err());
+        let both = ServerConfig { command: Some("a".to_string()), url: Some("http://b".to_string()), ..ServerConfig::default() };
+        assert!(both.transport().is_err());
+        let remote_cwd = ServerConfig { url: Some("http://b".to_string()), cwd: Some("/tmp".to_string()), ..ServerConfig::default() };
+        assert!(remote_cwd.transport().is_err());
+    }
+
+    #[test]
+    fn test_expand_template() {
+        let variables = vec![("PROJECT_ROOT", "/work/app".to_string()), ("CWD", "/work/app/src".to_string())];
+        std::env::set_var("AI_CLI_TEST_TEMPLATE_DIR", "data");
+        assert_eq!(expand_template("${PROJECT_ROOT}/${AI_CLI_TEST_TEMPLATE_DIR}", &variables).unwrap(), "/work/app/data");
+        assert_eq!(expand_template("--root=${CWD}", &variables).unwrap(), "--root=/work/app/src");
+        assert_eq!(expand_template("a~/b", &variables).unwrap(), "a~/b");
+        assert!(expand_template("${AI_CLI_TEST_TEMPLATE_MISSING}", &variables).unwrap_err().to_string().contains("AI_CLI_TEST_TEMPLATE_MISSING"));
+
+        let home = dirs::home_dir().unwrap();
+        assert_eq!(expand_template("~/tools", &variables).unwrap(), home.join("tools").display().to_string());
+    }
+}
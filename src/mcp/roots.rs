@@ -0,0 +1,82 @@
+//! 루트 (`roots/list`)
+//!
+//! 서버(파일 시스템 서버 등)가 ai-cli가 작업 중인 프로젝트를 알 수 있도록 `ContextEngine`이 찾은
+//! 프로젝트 루트(Git 리포지토리 루트)를 `file://` URI로 알려 줍니다. 작업 디렉토리가 바뀌어
+//! 루트가 달라지면 다음 요청 전에 `notifications/roots/list_changed`를 보냅니다.
+
+use serde::Serialize;
+use std::path::Path;
+
+use crate::context::ContextEngine;
+
+/// 루트 목록 요청 메서드
+pub const ROOTS_LIST_METHOD: &str = "roots/list";
+
+/// 루트 목록 변경 알림 메서드
+pub const ROOTS_CHANGED_NOTIFICATION: &str = "notifications/roots/list_changed";
+
+/// 서버에 알리는 루트
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Root {
+    pub uri: String,
+    pub name: String,
+}
+
+/// 현재 디렉토리의 프로젝트 루트 (Git 리포지토리 밖이면 빈 목록)
+pub fn current_roots() -> Vec<Root> {
+    let Ok(current_dir) = std::env::current_dir() else {
+        return Vec::new();
+    };
+    let Ok(project_root) = ContextEngine::new().find_project_root(&current_dir) else {
+        return Vec::new();
+    };
+    let name = project_root
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| project_root.display().to_string());
+    vec![Root { uri: file_uri(&project_root), name }]
+}
+
+/// `roots/list` 응답의 `result`
+pub fn list_result(roots: &[Root]) -> serde_json::Value {
+    serde_json::json!({ "roots": roots })
+}
+
+/// 경로를 `file://` URI로 변환 (URI에 쓸 수 없는 바이트는 퍼센트 인코딩)
+pub fn file_uri(path: &Path) -> String {
+    let path = path.to_string_lossy().replace('\\', "/");
+    let mut uri = String::from("file://");
+    if !path.starts_with('/') {
+        // Windows 드라이브 경로 (C:/...)
+        uri.push('/');
+    }
+    for byte in path.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' | b':' => uri.push(byte as char),
+            _ => uri.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    uri
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_uri() {
+        assert_eq!(file_uri(Path::new("/home/dev/ai-cli")), "file:///home/dev/ai-cli");
+        assert_eq!(file_uri(Path::new("/home/dev/my project/한")), "file:///home/dev/my%20project/%ED%95%9C");
+        assert_eq!(file_uri(Path::new("C:\\work\\repo")), "file:///C:/work/repo");
+    }
+
+    #[test]
+    fn test_list_result() {
+        let roots = vec![Root { uri: "file:///home/dev/ai-cli".to_string(), name: "ai-cli".to_string() }];
+        assert_eq!(
+            list_result(&roots),
+            serde_json::json!({"roots": [{"uri": "file:///home/dev/ai-cli", "name": "ai-cli"}]})
+        );
+        assert_eq!(list_result(&[]), serde_json::json!({"roots": []}));
+    }
+}
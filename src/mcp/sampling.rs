@@ -0,0 +1,179 @@
+//! 샘플링 (`sampling/createMessage`)
+//!
+//! MCP 서버가 LLM 호출을 요청하면 사용자 승인을 받은 뒤 ai-cli에 설정된 백엔드
+//! (기본: 로컬 Ollama)로 응답을 생성해 돌려줍니다. 서버는 자체 API 키 없이 사용자의 모델을 사용합니다.
+//!
+//! ```sh
+//! export AI_CLI_MCP_SAMPLING=local   # local(기본), openai, anthropic, off
+//! ```
+
+use anyhow::{Result, anyhow};
+use serde::Deserialize;
+use std::io::{self, Write};
+
+use crate::ai_utils::{self, AIBackend, ChatMessage};
+
+/// 샘플링 요청 메서드
+pub const SAMPLING_METHOD: &str = "sampling/createMessage";
+
+/// 사용자가 요청을 거절했을 때의 JSON-RPC 오류 코드
+pub const USER_REJECTED: i64 = -1;
+
+/// 승인 프롬프트에 보여 주는 메시지 하나의 최대 길이 (문자)
+const PREVIEW_CHARS: usize = 300;
+
+/// 사용자가 샘플링 요청을 거절함 (서버에는 USER_REJECTED 코드로 알림)
+#[derive(Debug, thiserror::Error)]
+#[error("{0}")]
+pub struct SamplingRejected(pub String);
+
+/// 샘플링 요청 인자
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateMessageParams {
+    pub messages: Vec<SamplingMessage>,
+    #[serde(default)]
+    pub system_prompt: Option<String>,
+    pub max_tokens: u32,
+    #[serde(default)]
+    pub temperature: Option<f32>,
+}
+
+/// 샘플링 대화 메시지
+#[derive(Debug, Clone, Deserialize)]
+pub struct SamplingMessage {
+    pub role: String,
+    pub content: SamplingContent,
+}
+
+/// 샘플링 메시지 내용 (텍스트만 모델에 전달)
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type")]
+pub enum SamplingContent {
+    #[serde(rename = "text")]
+    Text { text: String },
+    #[serde(other)]
+    Unsupported,
+}
+
+/// 샘플링에 사용할 백엔드 (`AI_CLI_MCP_SAMPLING=off`면 None)
+pub fn sampling_backend() -> Option<Result<AIBackend>> {
+    let preference = std::env::var("AI_CLI_MCP_SAMPLING").unwrap_or_else(|_| "local".to_string());
+    match preference.trim() {
+        "off" | "none" | "" => None,
+        preference => Some(ai_utils::get_ai_backend(preference)),
+    }
+}
+
+/// 샘플링을 지원하는지 (초기화 시 `sampling` 기능을 알릴지 결정)
+pub fn is_enabled() -> bool {
+    sampling_backend().is_some()
+}
+
+/// 모델에 보낼 대화로 변환 (텍스트가 아닌 내용은 거부)
+fn chat_messages(params: &CreateMessageParams) -> Result<Vec<ChatMessage>> {
+    params
+        .messages
+        .iter()
+        .map(|message| match &message.content {
+            SamplingContent::Text { text } => Ok(ChatMessage { role: message.role.clone(), content: text.clone() }),
+            SamplingContent::Unsupported => Err(anyhow!("Only text content is supported for sampling")),
+        })
+        .collect()
+}
+
+/// 승인 프롬프트에 보여 줄 요청 요약
+fn request_preview(server: &str, backend: &AIBackend, params: &CreateMessageParams, messages: &[ChatMessage]) -> String {
+    let shorten = |text: &str| {
+        let mut short: String = text.chars().take(PREVIEW_CHARS).collect();
+        if short.len() < text.len() {
+            short.push('…');
+        }
+        short.replace('\n', " ")
+    };
+
+    let mut preview = format!(
+        "{}\n",
+        tr!("sampling.request", server = server, backend = backend.display_name(), model = backend.model(), tokens = params.max_tokens)
+    );
+    if let Some(system) = &params.system_prompt {
+        preview.push_str(&format!("   [system] {}\n", shorten(system)));
+    }
+    for message in messages {
+        preview.push_str(&format!("   [{}] {}\n", message.role, shorten(&message.content)));
+    }
+    preview
+}
+
+/// 샘플링 요청 처리: 사용자 승인 후 설정한 백엔드로 생성해 `CreateMessageResult` 반환
+/// 비대화형 실행에서는 물어볼 수 없으므로 거절
+pub async fn create_message(server: &str, params: serde_json::Value) -> Result<serde_json::Value> {
+    let backend = sampling_backend().ok_or_else(|| anyhow!("Sampling is disabled (AI_CLI_MCP_SAMPLING=off)"))??;
+    let params: CreateMessageParams =
+        serde_json::from_value(params).map_err(|e| anyhow!("Invalid sampling request: {}", e))?;
+    let messages = chat_messages(&params)?;
+
+    print!("{}", request_preview(server, &backend, &params, &messages));
+    if ai_utils::is_non_interactive() {
+        println!("{}", tr!("sampling.declined"));
+        return Err(SamplingRejected("User approval is required for sampling".to_string()).into());
+    }
+    print!("{}", tr!("sampling.prompt"));
+    io::stdout().flush()?;
+    let mut response = String::new();
+    io::stdin().read_line(&mut response)?;
+    if !matches!(response.trim().to_lowercase().as_str(), "y" | "yes") {
+        return Err(SamplingRejected("User rejected the sampling request".to_string()).into());
+    }
+
+    let reply = ai_utils::generate_chat(
+        &backend,
+        params.system_prompt.as_deref(),
+        &messages,
+        params.max_tokens,
+        params.temperature,
+    )
+    .await?;
+
+    Ok(serde_json::json!({
+        "role": "assistant",
+        "content": {"type": "text", "text": reply.content},
+        "model": reply.model,
+        "stopReason": "endTurn",
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sampling_request() {
+        let params: CreateMessageParams = serde_json::from_value(serde_json::json!({
+            "messages": [
+                {"role": "user", "content": {"type": "text", "text": "Summarize the issue"}},
+                {"role": "assistant", "content": {"type": "text", "text": "Which issue?"}}
+            ],
+            "systemPrompt": "Be brief",
+            "maxTokens": 200,
+            "modelPreferences": {"hints": [{"name": "claude"}]}
+        }))
+        .unwrap();
+        assert_eq!(params.max_tokens, 200);
+
+        let messages = chat_messages(&params).unwrap();
+        assert_eq!(messages[1], ChatMessage { role: "assistant".to_string(), content: "Which issue?".to_string() });
+
+        let backend = AIBackend::Local { model: "gemma2:9b".to_string(), url: "http://localhost:11434".to_string() };
+        let preview = request_preview("github", &backend, &params, &messages);
+        assert!(preview.contains("'github'"));
+        assert!(preview.contains("[system] Be brief"));
+
+        let image: CreateMessageParams = serde_json::from_value(serde_json::json!({
+            "messages": [{"role": "user", "content": {"type": "image", "data": "", "mimeType": "image/png"}}],
+            "maxTokens": 10
+        }))
+        .unwrap();
+        assert!(chat_messages(&image).is_err());
+    }
+}
@@ -0,0 +1,144 @@
+//! 도구 인자 검증
+//!
+//! `tools/call`을 보내기 전에 모델이 만든 인자를 도구의 `inputSchema`(필수 필드, 속성 타입, enum)와 비교해,
+//! 잘못된 인자는 서버의 알아보기 어려운 오류 대신 무엇이 틀렸는지 알려 주는 로컬 오류로 거부합니다.
+//! 스키마에 없는 속성은 서버가 판단하도록 그대로 보냅니다.
+
+use super::protocol::{Tool, ToolProperty};
+
+/// 도구 인자가 입력 스키마에 맞지 않음
+#[derive(Debug, thiserror::Error)]
+#[error("Invalid arguments for MCP tool '{tool}': {}", .problems.join("; "))]
+pub struct InvalidArguments {
+    pub tool: String,
+    pub problems: Vec<String>,
+}
+
+/// JSON 값의 스키마 타입 이름
+fn type_name(value: &serde_json::Value) -> &'static str {
+    match value {
+        serde_json::Value::Null => "null",
+        serde_json::Value::Bool(_) => "boolean",
+        serde_json::Value::Number(number) if number.is_i64() || number.is_u64() => "integer",
+        serde_json::Value::Number(_) => "number",
+        serde_json::Value::String(_) => "string",
+        serde_json::Value::Array(_) => "array",
+        serde_json::Value::Object(_) => "object",
+    }
+}
+
+/// 값이 스키마 타입에 맞는지 (타입이 없거나 모르는 타입이면 통과, 정수는 number로도 인정)
+fn matches_type(expected: &str, value: &serde_json::Value) -> bool {
+    match (expected, type_name(value)) {
+        ("", _) => true,
+        ("number", "integer") => true,
+        (expected, actual) if expected == actual => true,
+        ("string" | "number" | "integer" | "boolean" | "array" | "object" | "null", _) => false,
+        _ => true,
+    }
+}
+
+/// 속성 하나의 문제 (없으면 None)
+fn property_problem(name: &str, property: &ToolProperty, value: &serde_json::Value) -> Option<String> {
+    if !matches_type(&property.property_type, value) {
+        return Some(format!("'{}' must be {} (got {})", name, property.property_type, type_name(value)));
+    }
+    let allowed = property.enum_values.as_ref().filter(|values| !values.is_empty())?;
+    match value.as_str() {
+        Some(value) if allowed.iter().any(|allowed| allowed == value) => None,
+        _ => Some(format!("'{}' must be one of {} (got {})", name, allowed.join(", "), value)),
+    }
+}
+
+/// 도구 인자를 입력 스키마로 검증 (인자가 없으면 빈 객체로 봄)
+pub fn validate_arguments(tool: &Tool, arguments: Option<&serde_json::Value>) -> Result<(), InvalidArguments> {
+    let empty = serde_json::Map::new();
+    let fields = match arguments {
+        None | Some(serde_json::Value::Null) => &empty,
+        Some(serde_json::Value::Object(fields)) => fields,
+        Some(other) => {
+            return Err(InvalidArguments {
+                tool: tool.name.clone(),
+                problems: vec![format!("arguments must be an object (got {})", type_name(other))],
+            });
+        }
+    };
+
+    let schema = &tool.input_schema;
+    let mut problems: Vec<String> = schema
+        .required
+        .iter()
+        .filter(|name| fields.get(name.as_str()).is_none_or(serde_json::Value::is_null))
+        .map(|name| format!("missing required field '{}'", name))
+        .collect();
+    let mut names: Vec<&String> = fields.keys().collect();
+    names.sort();
+    for name in names {
+        let value = &fields[name.as_str()];
+        // 선택 속성에 null을 보내면 생략한 것으로 봄
+        if value.is_null() && !schema.required.contains(name) {
+            continue;
+        }
+        if let Some(problem) = schema.properties.get(name.as_str()).and_then(|property| property_problem(name, property, value)) {
+            problems.push(problem);
+        }
+    }
+
+    if problems.is_empty() {
+        Ok(())
+    } else {
+        Err(InvalidArguments { tool: tool.name.clone(), problems })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tool() -> Tool {
+        serde_json::from_value(serde_json::json!({
+            "name": "list_issues",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "repo": {"type": "string"},
+                    "state": {"type": "string", "enum": ["open", "closed"]},
+                    "per_page": {"type": "integer"},
+                    "labels": {"type": "array"},
+                    "score": {"type": "number"}
+                },
+                "required": ["repo"]
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_validate_arguments() {
+        let tool = tool();
+        let valid = serde_json::json!({"repo": "ai-cli", "state": "open", "per_page": 10, "score": 3, "labels": [], "extra": true});
+        assert!(validate_arguments(&tool, Some(&valid)).is_ok());
+        assert!(validate_arguments(&tool, Some(&serde_json::json!({"repo": "ai-cli", "state": null}))).is_ok());
+
+        let error = validate_arguments(&tool, Some(&serde_json::json!({"state": "opened", "per_page": "10"}))).unwrap_err();
+        assert_eq!(error.problems, vec![
+            "missing required field 'repo'".to_string(),
+            "'per_page' must be integer (got string)".to_string(),
+            "'state' must be one of open, closed (got \"opened\")".to_string(),
+        ]);
+        assert!(error.to_string().starts_with("Invalid arguments for MCP tool 'list_issues': missing required field 'repo'; "));
+
+        assert!(validate_arguments(&tool, None).is_err());
+        let error = validate_arguments(&tool, Some(&serde_json::json!(["ai-cli"]))).unwrap_err();
+        assert_eq!(error.problems, vec!["arguments must be an object (got array)".to_string()]);
+    }
+
+    #[test]
+    fn test_matches_type() {
+        assert!(matches_type("number", &serde_json::json!(2)));
+        assert!(!matches_type("integer", &serde_json::json!(2.5)));
+        assert!(matches_type("", &serde_json::json!("anything")));
+        assert!(matches_type("custom", &serde_json::json!(1)));
+        assert!(!matches_type("boolean", &serde_json::json!("true")));
+    }
+}
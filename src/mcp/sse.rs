@@ -0,0 +1,268 @@
+//! HTTP + SSE 전송
+//!
+//! MCP 2024-11-05 사양의 HTTP+SSE 전송을 구현합니다.
+//! 서버의 SSE 스트림을 열어 `endpoint` 이벤트로 메시지를 보낼 주소를 받고,
+//! 요청은 그 주소로 POST하며 응답은 SSE 스트림의 `message` 이벤트로 받습니다.
+
+use anyhow::{Result, anyhow};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, ACCEPT};
+use reqwest::Url;
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::client::{is_server_request, reply_to_server_request};
+use super::connection::ConnectionLost;
+use super::dispatch::Dispatcher;
+
+/// 요청 하나의 응답 대기 시간 (진행 알림을 받으면 다시 시작)
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// SSE 이벤트
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SseEvent {
+    /// 이벤트 이름 (지정하지 않으면 `message`)
+    pub event: String,
+    pub data: String,
+    pub id: Option<String>,
+}
+
+/// 바이트 조각을 받아 완성된 SSE 이벤트로 나누는 파서
+#[derive(Debug, Default)]
+pub struct SseParser {
+    buffer: Vec<u8>,
+    event: Option<String>,
+    data: Vec<String>,
+    id: Option<String>,
+}
+
+impl SseParser {
+    /// 받은 바이트를 추가하고 빈 줄로 끝난 이벤트들을 반환 (UTF-8 문자나 줄이 조각 경계에서 잘려도 됨)
+    pub fn push(&mut self, chunk: &[u8]) -> Vec<SseEvent> {
+        self.buffer.extend_from_slice(chunk);
+
+        let mut events = Vec::new();
+        while let Some(newline) = self.buffer.iter().position(|byte| *byte == b'\n') {
+            let line: Vec<u8> = self.buffer.drain(..=newline).collect();
+            let line = String::from_utf8_lossy(&line);
+            let line = line.trim_end_matches(['\n', '\r']);
+
+            if line.is_empty() {
+                if !self.data.is_empty() {
+                    events.push(SseEvent {
+                        event: self.event.take().unwrap_or_else(|| "message".to_string()),
+                        data: self.data.join("\n"),
+                        id: self.id.clone(),
+                    });
+                }
+                self.event = None;
+                self.data.clear();
+                continue;
+            }
+            if line.starts_with(':') {
+                continue; // 주석 (keep-alive)
+            }
+
+            let (field, value) = line.split_once(':').unwrap_or((line, ""));
+            let value = value.strip_prefix(' ').unwrap_or(value);
+            match field {
+                "event" => self.event = Some(value.to_string()),
+                "data" => self.data.push(value.to_string()),
+                "id" => self.id = Some(value.to_string()),
+                _ => {}
+            }
+        }
+
+        events
+    }
+}
+
+/// 서버의 SSE 스트림 (받은 이벤트를 하나씩 꺼냄)
+struct EventStream {
+    stream: reqwest::Response,
+    parser: SseParser,
+    pending: VecDeque<SseEvent>,
+}
+
+impl EventStream {
+    /// SSE 스트림의 다음 이벤트
+    async fn next_event(&mut self) -> Result<SseEvent> {
+        loop {
+            if let Some(event) = self.pending.pop_front() {
+                return Ok(event);
+            }
+            let chunk = self
+                .stream
+                .chunk()
+                .await
+                .map_err(|e| ConnectionLost(format!("MCP event stream failed: {}", e)))?;
+            match chunk {
+                Some(chunk) => self.pending.extend(self.parser.push(&chunk)),
+                None => return Err(ConnectionLost("MCP server closed the event stream".to_string()).into()),
+            }
+        }
+    }
+}
+
+/// 메시지 주소로 POST하는 쪽 (세션과 스트림 읽기 작업이 함께 사용)
+struct MessagePoster {
+    client: reqwest::Client,
+    headers: HeaderMap,
+    endpoint: Url,
+}
+
+impl MessagePoster {
+    /// 메시지 주소로 JSON-RPC 메시지 POST (응답은 SSE 스트림으로 옴)
+    async fn post(&self, message: &serde_json::Value) -> Result<()> {
+        let response = self
+            .client
+            .post(self.endpoint.clone())
+            .headers(self.headers.clone())
+            .json(message)
+            .send()
+            .await
+            .map_err(|e| ConnectionLost(format!("Failed to reach MCP server {}: {}", self.endpoint, e)))?;
+        if !response.status().is_success() {
+            return Err(anyhow!("MCP server rejected the message: {}", response.status()));
+        }
+        Ok(())
+    }
+}
+
+/// HTTP+SSE MCP 서버 세션 (세션을 버리면 SSE 연결도 닫힘)
+/// SSE 스트림은 읽기 작업이 받아 요청 ID별로 나눠 주므로 여러 요청을 동시에 보낼 수 있음
+pub struct SseSession {
+    poster: Arc<MessagePoster>,
+    dispatcher: Arc<Dispatcher>,
+    reader: tokio::task::JoinHandle<()>,
+}
+
+impl SseSession {
+    /// SSE 스트림에 연결하고 메시지를 보낼 주소를 받음 (초기화 핸드셰이크는 `HttpSession`이 수행)
+    /// `headers`는 모든 요청에 붙는 인증 헤더 등 (`Authorization: Bearer ...`)
+    pub async fn open(server: &str, url: &str, headers: &[(String, String)]) -> Result<Self> {
+        let base = Url::parse(url).map_err(|e| anyhow!("Invalid MCP server URL '{}': {}", url, e))?;
+        let client = crate::ai_utils::http_client(url)?;
+        let headers = header_map(headers)?;
+
+        let stream = client
+            .get(base.clone())
+            .headers(headers.clone())
+            .header(ACCEPT, "text/event-stream")
+            .send()
+            .await
+            .map_err(|e| anyhow!("Failed to connect to MCP server {}: {}", url, e))?;
+        if !stream.status().is_success() {
+            return Err(anyhow!("MCP server {} returned {}", url, stream.status()));
+        }
+        let mut events = EventStream { stream, parser: SseParser::default(), pending: VecDeque::new() };
+
+        // 첫 이벤트로 메시지를 보낼 주소를 받음 (상대 경로 가능, 다른 출처는 거부)
+        let endpoint = tokio::time::timeout(REQUEST_TIMEOUT, async {
+            loop {
+                let event = events.next_event().await?;
+                if event.event == "endpoint" {
+                    return Ok::<_, anyhow::Error>(event.data);
+                }
+            }
+        })
+        .await
+        .map_err(|_| anyhow!("MCP server did not send an endpoint event within {} seconds", REQUEST_TIMEOUT.as_secs()))??;
+        let endpoint = base.join(endpoint.trim()).map_err(|e| anyhow!("Invalid MCP endpoint '{}': {}", endpoint, e))?;
+        if endpoint.origin() != base.origin() {
+            return Err(anyhow!("MCP server sent an endpoint on a different origin: {}", endpoint));
+        }
+
+        let poster = Arc::new(MessagePoster { client, headers, endpoint });
+        let dispatcher = Dispatcher::new(server);
+        let reader = tokio::spawn(read_events(server.to_string(), events, Arc::clone(&poster), Arc::clone(&dispatcher)));
+        Ok(Self { poster, dispatcher, reader })
+    }
+
+    /// 메시지 주소로 JSON-RPC 메시지 POST (응답은 SSE 스트림으로 옴)
+    pub async fn notify(&self, message: &serde_json::Value) -> Result<()> {
+        self.poster.post(message).await
+    }
+
+    /// 요청을 보내고 같은 ID의 응답 반환 (진행 알림을 받으면 대기 시간을 다시 시작)
+    pub async fn request(&self, message: &serde_json::Value) -> Result<serde_json::Value> {
+        let pending = self.dispatcher.register(&message["id"])?;
+        self.notify(message).await?;
+        pending.response(REQUEST_TIMEOUT).await
+    }
+
+    /// SSE 스트림이 아직 열려 있는지
+    pub fn is_alive(&self) -> bool {
+        !self.dispatcher.is_closed()
+    }
+}
+
+impl Drop for SseSession {
+    fn drop(&mut self) {
+        self.reader.abort();
+    }
+}
+
+/// SSE 스트림 읽기 작업
+/// 서버가 보낸 요청(ping, 샘플링)에는 따로 응답하고, 응답과 알림은 기다리는 요청에 나눠 줌
+async fn read_events(server: String, mut events: EventStream, poster: Arc<MessagePoster>, dispatcher: Arc<Dispatcher>) {
+    let reason = loop {
+        let event = match events.next_event().await {
+            Ok(event) => event,
+            Err(e) => break e.to_string(),
+        };
+        if event.event != "message" {
+            continue;
+        }
+        let Ok(message) = serde_json::from_str::<serde_json::Value>(&event.data) else {
+            continue;
+        };
+
+        if is_server_request(&message) {
+            let (server, poster) = (server.clone(), Arc::clone(&poster));
+            tokio::spawn(async move {
+                let reply = reply_to_server_request(&server, &message).await;
+                if let Err(e) = poster.post(&reply).await {
+                    tracing::warn!("Failed to reply to MCP server '{}': {}", server, e);
+                }
+            });
+            continue;
+        }
+        dispatcher.route(message);
+    };
+    dispatcher.close(reason);
+}
+
+/// `(이름, 값)` 목록을 HTTP 헤더로 변환
+pub(super) fn header_map(headers: &[(String, String)]) -> Result<HeaderMap> {
+    let mut map = HeaderMap::new();
+    for (name, value) in headers {
+        let name = HeaderName::from_bytes(name.as_bytes()).map_err(|e| anyhow!("Invalid header name '{}': {}", name, e))?;
+        let mut value = HeaderValue::from_str(value).map_err(|e| anyhow!("Invalid value for header '{}': {}", name, e))?;
+        value.set_sensitive(true);
+        map.insert(name, value);
+    }
+    Ok(map)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sse_parser() {
+        let mut parser = SseParser::default();
+        assert!(parser.push(b"event: endpoint\r\ndata: /messages?session").is_empty());
+
+        let events = parser.push(b"Id=1\r\n\r\n: keep-alive\n\ndata: {\"id\":1,\ndata: \"result\":{}}\nid: 7\n\n");
+        assert_eq!(events, vec![
+            SseEvent { event: "endpoint".to_string(), data: "/messages?sessionId=1".to_string(), id: None },
+            SseEvent { event: "message".to_string(), data: "{\"id\":1,\n\"result\":{}}".to_string(), id: Some("7".to_string()) },
+        ]);
+
+        // 조각 경계에서 잘린 UTF-8 문자
+        let text = "data: 한글\n\n".as_bytes();
+        assert!(parser.push(&text[..8]).is_empty());
+        assert_eq!(parser.push(&text[8..])[0].data, "한글");
+    }
+}
@@ -0,0 +1,305 @@
+//! Streamable HTTP 전송
+//!
+//! MCP 2025-03-26 사양의 Streamable HTTP 전송을 구현합니다.
+//! 하나의 엔드포인트로 모든 메시지를 POST하고, 서버는 JSON 하나 또는 SSE 스트림으로 응답합니다.
+//! 초기화 응답의 `Mcp-Session-Id`를 이후 요청에 붙이고, 응답 스트림이 끊기면
+//! 마지막 이벤트 ID(`Last-Event-ID`)로 다시 연결해 이어서 받습니다.
+
+use anyhow::{Result, anyhow};
+use reqwest::header::{HeaderMap, HeaderValue, ACCEPT, CONTENT_TYPE, WWW_AUTHENTICATE};
+use reqwest::{StatusCode, Url};
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use super::client::{handle_server_notification, is_server_request, reply_to_server_request};
+use super::connection::ConnectionLost;
+use super::protocol::JsonRpcResponse;
+use super::sse::{header_map, SseEvent, SseParser};
+
+/// Streamable HTTP를 지원하는 프로토콜 버전
+pub const STREAMABLE_PROTOCOL_VERSION: &str = "2025-03-26";
+
+/// 세션 ID 헤더
+const SESSION_HEADER: &str = "mcp-session-id";
+
+/// 협상한 프로토콜 버전 헤더
+const PROTOCOL_VERSION_HEADER: &str = "mcp-protocol-version";
+
+/// 요청 하나의 응답 대기 시간
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// 끊긴 응답 스트림을 다시 연결하는 최대 횟수
+const MAX_RESUME_ATTEMPTS: usize = 3;
+
+/// 성공하지 못한 HTTP 응답 (이전 HTTP+SSE 서버 감지와 세션 만료 처리에 사용)
+#[derive(Debug, thiserror::Error)]
+#[error("MCP server returned HTTP {status}")]
+pub struct HttpStatusError {
+    pub status: StatusCode,
+    /// 세션 ID를 보낸 요청이었는지 (404면 세션 만료)
+    pub with_session: bool,
+    /// 401 응답의 `WWW-Authenticate` 헤더 (OAuth 메타데이터 위치)
+    pub authenticate: Option<String>,
+}
+
+impl HttpStatusError {
+    fn from_response(response: &reqwest::Response, with_session: bool) -> Self {
+        let authenticate = response
+            .headers()
+            .get(WWW_AUTHENTICATE)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+        Self { status: response.status(), with_session, authenticate }
+    }
+
+    /// Streamable HTTP를 지원하지 않는 이전 서버의 응답인지 (HTTP+SSE로 다시 시도)
+    pub fn is_legacy_server(&self) -> bool {
+        !self.with_session
+            && matches!(self.status, StatusCode::BAD_REQUEST | StatusCode::NOT_FOUND | StatusCode::METHOD_NOT_ALLOWED)
+    }
+
+    /// 서버가 세션을 종료했는지 (새로 초기화해야 함)
+    pub fn is_session_expired(&self) -> bool {
+        self.with_session && self.status == StatusCode::NOT_FOUND
+    }
+}
+
+/// Streamable HTTP MCP 서버 세션 (요청마다 응답을 따로 받으므로 여러 요청을 동시에 보낼 수 있음)
+pub struct StreamableSession {
+    server: String,
+    client: reqwest::Client,
+    headers: HeaderMap,
+    url: Url,
+    session_id: Mutex<Option<String>>,
+    protocol_version: Mutex<Option<String>>,
+}
+
+impl StreamableSession {
+    /// 엔드포인트 설정 (연결은 첫 요청에서 이루어짐)
+    pub fn open(server: &str, url: &str, headers: &[(String, String)]) -> Result<Self> {
+        let parsed = Url::parse(url).map_err(|e| anyhow!("Invalid MCP server URL '{}': {}", url, e))?;
+        Ok(Self {
+            server: server.to_string(),
+            client: crate::ai_utils::http_client(url)?,
+            headers: header_map(headers)?,
+            url: parsed,
+            session_id: Mutex::new(None),
+            protocol_version: Mutex::new(None),
+        })
+    }
+
+    /// 현재 세션 ID
+    pub fn session_id(&self) -> Option<String> {
+        self.session_id.lock().unwrap().clone()
+    }
+
+    /// 초기화 후 세션을 새로 시작할 때 이전 세션 정보 삭제
+    pub fn reset(&self) {
+        *self.session_id.lock().unwrap() = None;
+        *self.protocol_version.lock().unwrap() = None;
+    }
+
+    /// 초기화 응답에서 협상한 프로토콜 버전 기록 (이후 요청 헤더에 사용)
+    pub fn set_protocol_version(&self, version: &str) {
+        *self.protocol_version.lock().unwrap() = Some(version.to_string());
+    }
+
+    /// 세션 ID와 프로토콜 버전을 붙인 요청 헤더
+    fn request_headers(&self, session_id: Option<&str>) -> Result<HeaderMap> {
+        let mut headers = self.headers.clone();
+        if let Some(session_id) = session_id {
+            headers.insert(SESSION_HEADER, HeaderValue::from_str(session_id)?);
+        }
+        if let Some(version) = self.protocol_version.lock().unwrap().as_deref() {
+            headers.insert(PROTOCOL_VERSION_HEADER, HeaderValue::from_str(version)?);
+        }
+        Ok(headers)
+    }
+
+    /// 메시지 POST
+    async fn post(&self, message: &serde_json::Value) -> Result<reqwest::Response> {
+        let session_id = self.session_id();
+        let response = self
+            .client
+            .post(self.url.clone())
+            .headers(self.request_headers(session_id.as_deref())?)
+            .header(ACCEPT, "application/json, text/event-stream")
+            .json(message)
+            .send()
+            .await
+            .map_err(|e| ConnectionLost(format!("Failed to reach MCP server {}: {}", self.url, e)))?;
+
+        if !response.status().is_success() {
+            return Err(HttpStatusError::from_response(&response, session_id.is_some()).into());
+        }
+
+        // 서버가 세션을 만들면 초기화 응답 헤더로 ID를 알려 줌
+        if let Some(session_id) = response.headers().get(SESSION_HEADER).and_then(|value| value.to_str().ok()) {
+            *self.session_id.lock().unwrap() = Some(session_id.to_string());
+        }
+        Ok(response)
+    }
+
+    /// 알림 전송 (서버는 202 Accepted로 응답)
+    pub async fn notify(&self, message: &serde_json::Value) -> Result<()> {
+        self.post(message).await?;
+        Ok(())
+    }
+
+    /// 요청을 보내고 같은 ID의 응답 반환
+    /// 응답이 SSE 스트림이면 끊겼을 때 `Last-Event-ID`로 최대 MAX_RESUME_ATTEMPTS번 다시 연결
+    pub async fn request(&self, message: &serde_json::Value) -> Result<serde_json::Value> {
+        let id = message["id"].clone();
+        let response = self.post(message).await?;
+
+        let is_stream = response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(|value| value.starts_with("text/event-stream"));
+        if !is_stream {
+            let body: serde_json::Value = response.json().await.map_err(|e| anyhow!("Failed to parse MCP response: {}", e))?;
+            return find_response(body, &id).ok_or_else(|| anyhow!("MCP response has no reply for request {}", id));
+        }
+
+        // 응답 전에 서버가 보낸 요청(ping, 샘플링)에는 POST로 응답하고 진행 알림은 표시하며 계속 읽음
+        let mut stream = ResponseStream::new(response);
+        let mut attempts = 0;
+        loop {
+            let next = tokio::time::timeout(REQUEST_TIMEOUT, stream.next_message())
+                .await
+                .map_err(|_| anyhow!("MCP server did not respond within {} seconds", REQUEST_TIMEOUT.as_secs()))?;
+            match next {
+                Ok(Some(body)) => {
+                    let messages = match &body {
+                        serde_json::Value::Array(messages) => messages.iter().collect(),
+                        message => vec![message],
+                    };
+                    for message in messages {
+                        if is_server_request(message) {
+                            let reply = reply_to_server_request(&self.server, message).await;
+                            self.notify(&reply).await?;
+                        } else if message.get("id").is_none() {
+                            handle_server_notification(&self.server, message);
+                        }
+                    }
+                    if let Some(reply) = find_response(body, &id) {
+                        return Ok(reply);
+                    }
+                }
+                Ok(None) | Err(_) if stream.last_event_id.is_some() && attempts < MAX_RESUME_ATTEMPTS => {
+                    attempts += 1;
+                    tracing::debug!("Resuming MCP stream after event {:?}", stream.last_event_id);
+                    let response = self.resume(stream.last_event_id.as_deref().unwrap_or_default()).await?;
+                    stream.replace(response);
+                }
+                Ok(None) => return Err(anyhow!("MCP server closed the stream before replying")),
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// 끊긴 스트림을 마지막으로 받은 이벤트 다음부터 다시 받음
+    async fn resume(&self, last_event_id: &str) -> Result<reqwest::Response> {
+        let session_id = self.session_id();
+        let response = self
+            .client
+            .get(self.url.clone())
+            .headers(self.request_headers(session_id.as_deref())?)
+            .header(ACCEPT, "text/event-stream")
+            .header("last-event-id", last_event_id)
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            return Err(HttpStatusError::from_response(&response, session_id.is_some()).into());
+        }
+        Ok(response)
+    }
+
+    /// 세션 종료 요청 (세션이 없거나 서버가 지원하지 않으면 무시)
+    pub async fn close(&self) {
+        let Some(session_id) = self.session_id.lock().unwrap().take() else {
+            return;
+        };
+        if let Ok(headers) = self.request_headers(Some(&session_id)) {
+            let _ = self.client.delete(self.url.clone()).headers(headers).send().await;
+        }
+    }
+}
+
+/// 요청에 대한 SSE 응답 스트림 (끊기면 다시 연결한 응답으로 바꿔 이어서 읽음)
+struct ResponseStream {
+    response: reqwest::Response,
+    parser: SseParser,
+    pending: VecDeque<SseEvent>,
+    last_event_id: Option<String>,
+}
+
+impl ResponseStream {
+    fn new(response: reqwest::Response) -> Self {
+        Self { response, parser: SseParser::default(), pending: VecDeque::new(), last_event_id: None }
+    }
+
+    /// 다시 연결한 스트림으로 교체 (마지막 이벤트 ID는 유지)
+    fn replace(&mut self, response: reqwest::Response) {
+        self.response = response;
+        self.parser = SseParser::default();
+        self.pending.clear();
+    }
+
+    /// 다음 JSON-RPC 메시지(또는 배치) (스트림이 끝나면 None)
+    async fn next_message(&mut self) -> Result<Option<serde_json::Value>> {
+        loop {
+            while let Some(event) = self.pending.pop_front() {
+                if event.id.is_some() {
+                    self.last_event_id = event.id.clone();
+                }
+                if event.event != "message" {
+                    continue;
+                }
+                if let Ok(body) = serde_json::from_str(&event.data) {
+                    return Ok(Some(body));
+                }
+            }
+            match self.response.chunk().await? {
+                Some(chunk) => self.pending.extend(self.parser.push(&chunk)),
+                None => return Ok(None),
+            }
+        }
+    }
+}
+
+/// 메시지(또는 배치)에서 `id`에 대한 응답 찾기 (요청/알림은 무시)
+fn find_response(body: serde_json::Value, id: &serde_json::Value) -> Option<serde_json::Value> {
+    match body {
+        serde_json::Value::Array(messages) => messages.into_iter().find_map(|message| find_response(message, id)),
+        message if JsonRpcResponse::is_response_to(&message, id) => Some(message),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_response() {
+        let id = serde_json::json!("a1");
+        let reply = serde_json::json!({"jsonrpc": "2.0", "id": "a1", "result": {}});
+        let request = serde_json::json!({"jsonrpc": "2.0", "id": "a1", "method": "sampling/createMessage"});
+
+        assert_eq!(find_response(reply.clone(), &id), Some(reply.clone()));
+        assert_eq!(find_response(request.clone(), &id), None);
+        assert_eq!(find_response(serde_json::json!([request, reply.clone()]), &id), Some(reply));
+    }
+
+    #[test]
+    fn test_http_status_error() {
+        let legacy = HttpStatusError { status: StatusCode::METHOD_NOT_ALLOWED, with_session: false, authenticate: None };
+        assert!(legacy.is_legacy_server() && !legacy.is_session_expired());
+
+        let expired = HttpStatusError { status: StatusCode::NOT_FOUND, with_session: true, authenticate: None };
+        assert!(expired.is_session_expired() && !expired.is_legacy_server());
+    }
+}
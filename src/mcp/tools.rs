@@ -6,26 +6,69 @@ use anyhow::{Result, anyhow};
 use serde_json::Value;
 use super::protocol::*;
 use super::client::MCPClient;
+use super::registry::ServerConfig;
+use crate::ai_utils;
+use crate::aiignore::AiIgnore;
+use crate::audit;
+use crate::transcript;
+use crate::index::{search_project, DEFAULT_SNIPPET_LINES};
+use crate::policy::{CommandDecision, Policy};
+use crate::sandbox::Sandbox;
+use crate::security::{is_read_only_git_command, ApprovalOption, CommandType, SecurityManager};
+use std::path::Path;
+use std::sync::Arc;
 
 /// 도구 관리자
 pub struct ToolManager {
-    mcp_client: MCPClient,
+    mcp_client: Arc<MCPClient>,
 }
 
 impl ToolManager {
     /// 새 도구 관리자 생성
     pub fn new(mcp_client: MCPClient) -> Self {
-        Self { mcp_client }
+        Self { mcp_client: Arc::new(mcp_client) }
     }
 
-    /// GitHub Pull Request 생성
+    /// 도구를 호출하고 결과 텍스트 반환 (서버가 실패로 표시하면 그 내용을 오류로 반환)
+    pub async fn call(&self, tool_name: &str, arguments: Option<Value>) -> Result<String> {
+        let result = self.mcp_client.call_tool(tool_name, arguments).await?;
+        tool_text(tool_name, result)
+    }
+
+    /// 서로 관계없는 도구 호출 여러 개를 동시에 실행하고 결과 텍스트를 호출 순서대로 반환
+    /// (여러 파일 조회나 여러 티켓 갱신처럼 앞 결과가 필요 없는 호출용)
+    pub async fn call_many(&self, calls: Vec<(String, Option<Value>)>) -> Vec<Result<String>> {
+        let names: Vec<String> = calls.iter().map(|(name, _)| name.clone()).collect();
+        self.mcp_client
+            .call_tools(calls)
+            .await
+            .into_iter()
+            .zip(names)
+            .map(|(result, name)| result.and_then(|result| tool_text(&name, result)))
+            .collect()
+    }
+
+    /// 서비스의 MCP 서버가 제공하는 도구 이름
+    /// GitHub와 GitLab 서버가 함께 있으면 같은 이름의 도구(`create_issue`)가 `서버.도구`로 등록되므로 서버를 골라 찾고,
+    /// 해당 서비스로 보이는 서버가 없으면 같은 이름으로 등록된 도구를 사용
+    fn forge_tool(&self, forge: Forge, tool: &str) -> Result<String> {
+        if let Some(name) = self.mcp_client.find_server_tool(tool, |name, config| forge.matches_server(name, config)) {
+            return Ok(name);
+        }
+        if self.mcp_client.get_tool(tool).is_some() {
+            return Ok(tool.to_string());
+        }
+        Err(anyhow!("No {} MCP server provides '{}' (add one with `ai-cli mcp add`)", forge.name(), tool))
+    }
+
+    /// GitHub Pull Request 생성 (서버가 돌려준 결과 반환)
     pub async fn create_github_pull_request(
         &self,
         title: &str,
         body: Option<&str>,
         head: &str,
         base: &str,
-    ) -> Result<()> {
+    ) -> Result<String> {
         let mut args = serde_json::Map::new();
         args.insert("title".to_string(), Value::String(title.to_string()));
         args.insert("head".to_string(), Value::String(head.to_string()));
@@ -35,28 +78,18 @@ impl ToolManager {
             args.insert("body".to_string(), Value::String(desc.to_string()));
         }
 
-        let result = self.mcp_client.call_tool("create_pull_request", Some(Value::Object(args))).await?;
-
-        match result.is_error.unwrap_or(false) {
-            false => {
-                for content in result.content {
-                    if let Content::Text { text } = content {
-                        tracing::info!("Pull request created: {}", text);
-                        return Ok(());
-                    }
-                }
-                Ok(())
-            }
-            true => Err(anyhow!("Failed to create pull request")),
-        }
+        let tool = self.forge_tool(Forge::GitHub, "create_pull_request")?;
+        let text = self.call(&tool, Some(Value::Object(args))).await?;
+        tracing::info!("Pull request created: {}", text);
+        Ok(text)
     }
 
-    /// GitHub Issue 생성
+    /// GitHub Issue 생성 (서버가 돌려준 결과 반환)
     pub async fn create_github_issue(
         &self,
         title: &str,
         body: Option<&str>,
-    ) -> Result<()> {
+    ) -> Result<String> {
         let mut args = serde_json::Map::new();
         args.insert("title".to_string(), Value::String(title.to_string()));
 
@@ -64,20 +97,147 @@ impl ToolManager {
             args.insert("body".to_string(), Value::String(desc.to_string()));
         }
 
-        let result = self.mcp_client.call_tool("create_issue", Some(Value::Object(args))).await?;
+        let tool = self.forge_tool(Forge::GitHub, "create_issue")?;
+        let text = self.call(&tool, Some(Value::Object(args))).await?;
+        tracing::info!("Issue created: {}", text);
+        Ok(text)
+    }
 
-        match result.is_error.unwrap_or(false) {
-            false => {
-                for content in result.content {
-                    if let Content::Text { text } = content {
-                        tracing::info!("Issue created: {}", text);
-                        return Ok(());
-                    }
-                }
-                Ok(())
-            }
-            true => Err(anyhow!("Failed to create issue")),
+    /// GitLab Merge Request 생성 (`project`는 `group/project` 경로 또는 프로젝트 ID)
+    pub async fn create_gitlab_merge_request(
+        &self,
+        project: &str,
+        title: &str,
+        description: Option<&str>,
+        source_branch: &str,
+        target_branch: &str,
+    ) -> Result<String> {
+        let mut args = serde_json::Map::new();
+        args.insert("project_id".to_string(), Value::String(project.to_string()));
+        args.insert("title".to_string(), Value::String(title.to_string()));
+        args.insert("source_branch".to_string(), Value::String(source_branch.to_string()));
+        args.insert("target_branch".to_string(), Value::String(target_branch.to_string()));
+
+        if let Some(desc) = description {
+            args.insert("description".to_string(), Value::String(desc.to_string()));
+        }
+
+        let tool = self.forge_tool(Forge::GitLab, "create_merge_request")?;
+        let text = self.call(&tool, Some(Value::Object(args))).await?;
+        tracing::info!("Merge request created: {}", text);
+        Ok(text)
+    }
+
+    /// GitLab Issue 생성 (`project`는 `group/project` 경로 또는 프로젝트 ID)
+    pub async fn create_gitlab_issue(
+        &self,
+        project: &str,
+        title: &str,
+        description: Option<&str>,
+    ) -> Result<String> {
+        let mut args = serde_json::Map::new();
+        args.insert("project_id".to_string(), Value::String(project.to_string()));
+        args.insert("title".to_string(), Value::String(title.to_string()));
+
+        if let Some(desc) = description {
+            args.insert("description".to_string(), Value::String(desc.to_string()));
         }
+
+        let tool = self.forge_tool(Forge::GitLab, "create_issue")?;
+        let text = self.call(&tool, Some(Value::Object(args))).await?;
+        tracing::info!("Issue created: {}", text);
+        Ok(text)
+    }
+
+    /// origin 리모트의 서비스에 맞춰 Pull Request(GitHub) 또는 Merge Request(GitLab) 생성
+    pub async fn create_change_request(
+        &self,
+        remote: &RemoteRepo,
+        title: &str,
+        body: Option<&str>,
+        head: &str,
+        base: &str,
+    ) -> Result<String> {
+        match remote.forge {
+            Forge::GitHub => self.create_github_pull_request(title, body, head, base).await,
+            Forge::GitLab => self.create_gitlab_merge_request(&remote.path, title, body, head, base).await,
+        }
+    }
+
+    /// origin 리모트의 서비스에 맞춰 Issue 생성
+    pub async fn create_issue(&self, remote: &RemoteRepo, title: &str, body: Option<&str>) -> Result<String> {
+        match remote.forge {
+            Forge::GitHub => self.create_github_issue(title, body).await,
+            Forge::GitLab => self.create_gitlab_issue(&remote.path, title, body).await,
+        }
+    }
+
+    /// Jira MCP 서버가 제공하는 도구 이름 (Jira/Atlassian 서버의 도구를 먼저 찾고, 없으면 같은 이름으로 등록된 도구)
+    fn jira_tool(&self, tool: &str) -> Result<String> {
+        let is_jira = |name: &str, config: &ServerConfig| {
+            server_mentions(name, config, "jira") || server_mentions(name, config, "atlassian")
+        };
+        if let Some(name) = self.mcp_client.find_server_tool(tool, is_jira) {
+            return Ok(name);
+        }
+        if self.mcp_client.get_tool(tool).is_some() {
+            return Ok(tool.to_string());
+        }
+        Err(anyhow!("No Jira MCP server provides '{}' (add one with `ai-cli mcp add`)", tool))
+    }
+
+    /// Jira 티켓 조회 (`key`는 `PROJ-123`)
+    pub async fn get_jira_issue(&self, key: &str) -> Result<String> {
+        let tool = self.jira_tool("jira_get_issue")?;
+        self.call(&tool, Some(serde_json::json!({"issue_key": key}))).await
+    }
+
+    /// Jira 티켓 상태 변경 (`status`는 전환 이름 또는 도착 상태 이름, 대소문자 무시)
+    pub async fn transition_jira_issue(&self, key: &str, status: &str) -> Result<String> {
+        let tool = self.jira_tool("jira_get_transitions")?;
+        let transitions = self.call(&tool, Some(serde_json::json!({"issue_key": key}))).await?;
+        let transitions: Value = serde_json::from_str(transitions.trim())
+            .map_err(|e| anyhow!("Could not read the transitions of {}: {}", key, e))?;
+        let transition_id = find_transition(&transitions, status)
+            .ok_or_else(|| anyhow!("{} has no transition to '{}'", key, status))?;
+
+        let tool = self.jira_tool("jira_transition_issue")?;
+        let text = self.call(&tool, Some(serde_json::json!({"issue_key": key, "transition_id": transition_id}))).await?;
+        tracing::info!("Moved {} to {}", key, status);
+        Ok(text)
+    }
+
+    /// Jira 티켓에 댓글 추가
+    pub async fn comment_jira_issue(&self, key: &str, comment: &str) -> Result<String> {
+        let tool = self.jira_tool("jira_add_comment")?;
+        let text = self.call(&tool, Some(serde_json::json!({"issue_key": key, "comment": comment}))).await?;
+        tracing::info!("Commented on {}", key);
+        Ok(text)
+    }
+
+    /// Slack 채널에 메시지 게시
+    /// Slack MCP 서버의 `slack_post_message`를 쓰고, 없으면 `AI_CLI_SLACK_WEBHOOK`의 Incoming Webhook으로 전송 (둘 다 승인 필요)
+    pub async fn post_slack_message(&self, channel: &str, text: &str) -> Result<String> {
+        let tool = self
+            .mcp_client
+            .find_server_tool(SLACK_POST_TOOL, is_slack_server)
+            .or_else(|| self.mcp_client.get_tool(SLACK_POST_TOOL).map(|_| SLACK_POST_TOOL.to_string()));
+        if let Some(tool) = tool {
+            let text = self.call(&tool, Some(serde_json::json!({"channel_id": channel, "text": text}))).await?;
+            tracing::info!("Posted to Slack {}: {}", channel, text);
+            return Ok(text);
+        }
+
+        let webhook = std::env::var("AI_CLI_SLACK_WEBHOOK")
+            .ok()
+            .filter(|webhook| !webhook.trim().is_empty())
+            .ok_or_else(|| anyhow!("No Slack MCP server provides '{}' and AI_CLI_SLACK_WEBHOOK is not set", SLACK_POST_TOOL))?;
+        post_slack_webhook(webhook.trim(), channel, text).await
+    }
+
+    /// 서버 연결 종료
+    pub async fn shutdown(&self) {
+        self.mcp_client.shutdown().await;
     }
 
     /// 사용 가능한 도구 목록 반환
@@ -91,6 +251,154 @@ impl ToolManager {
     }
 }
 
+/// 코드 호스팅 서비스
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Forge {
+    GitHub,
+    GitLab,
+}
+
+impl Forge {
+    /// 리모트 호스트로 서비스 판별 (이름에 github/gitlab이 들어간 호스트, 그 밖의 자체 호스팅 GitLab은 `AI_CLI_GITLAB_HOSTS`)
+    pub fn from_host(host: &str) -> Option<Self> {
+        let host = host.to_lowercase();
+        let gitlab_hosts = std::env::var("AI_CLI_GITLAB_HOSTS").unwrap_or_default();
+        if host.contains("gitlab") || gitlab_hosts.split(',').any(|known| known.trim().eq_ignore_ascii_case(&host)) {
+            Some(Self::GitLab)
+        } else if host.contains("github") {
+            Some(Self::GitHub)
+        } else {
+            None
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::GitHub => "GitHub",
+            Self::GitLab => "GitLab",
+        }
+    }
+
+    /// 서버가 이 서비스용으로 보이는지
+    pub fn matches_server(self, name: &str, config: &ServerConfig) -> bool {
+        server_mentions(name, config, &self.name().to_lowercase())
+    }
+}
+
+/// 서버 이름, 실행 명령어와 인자, 주소에 서비스 이름(소문자)이 있는지
+fn server_mentions(name: &str, config: &ServerConfig, keyword: &str) -> bool {
+    std::iter::once(name)
+        .chain(config.command.as_deref())
+        .chain(config.args.iter().map(String::as_str))
+        .chain(config.url.as_deref())
+        .any(|value| value.to_lowercase().contains(keyword))
+}
+
+/// 도구 결과 텍스트 (서버가 실패로 표시하면 그 내용을 오류로 반환)
+fn tool_text(tool_name: &str, result: CallToolResult) -> Result<String> {
+    let text = result.text();
+    if result.is_error() {
+        return Err(anyhow!("MCP tool '{}' failed: {}", tool_name, text.trim()));
+    }
+    Ok(text)
+}
+
+/// Slack 메시지 게시 도구 (Slack MCP 서버)
+const SLACK_POST_TOOL: &str = "slack_post_message";
+
+/// Slack MCP 서버로 보이는지 (서버 이름, 실행 명령어와 인자, 주소에 slack이 있음)
+pub fn is_slack_server(name: &str, config: &ServerConfig) -> bool {
+    server_mentions(name, config, "slack")
+}
+
+/// Slack Incoming Webhook으로 메시지 전송 (호스트는 `egress.allow_hosts`에 등록 필요)
+async fn post_slack_webhook(webhook: &str, channel: &str, text: &str) -> Result<String> {
+    let payload = serde_json::json!({"channel": channel, "text": text});
+    super::approval::authorize_external_action("slack/webhook", Some(&payload), false)?;
+
+    let response = crate::ai_utils::http_client(webhook)?.post(webhook).json(&payload).send().await;
+    let exit_code = match &response {
+        Ok(response) if response.status().is_success() => 0,
+        _ => 1,
+    };
+    audit::record_command("mcp slack/webhook", Some(exit_code));
+
+    let response = response?;
+    let status = response.status();
+    let body = response.text().await.unwrap_or_default();
+    if !status.is_success() {
+        return Err(anyhow!("Slack webhook failed ({}): {}", status, body.trim()));
+    }
+    Ok(body)
+}
+
+/// Jira 전환 목록에서 전환 이름이나 도착 상태가 `status`인 전환의 ID
+/// 서버마다 형식이 달라 배열 또는 `{"transitions": [...]}`, 숫자 또는 문자열 ID, 문자열 또는 객체 도착 상태를 모두 받음
+fn find_transition(transitions: &Value, status: &str) -> Option<String> {
+    let transitions = transitions.get("transitions").unwrap_or(transitions).as_array()?;
+    let name_of = |value: &Value| -> Option<String> {
+        match value {
+            Value::String(name) => Some(name.clone()),
+            Value::Object(_) => value.get("name").and_then(Value::as_str).map(str::to_string),
+            _ => None,
+        }
+    };
+    transitions
+        .iter()
+        .find(|transition| {
+            ["name", "to", "to_status"]
+                .iter()
+                .filter_map(|field| transition.get(*field).and_then(name_of))
+                .any(|name| name.trim().eq_ignore_ascii_case(status.trim()))
+        })
+        .and_then(|transition| match transition.get("id")? {
+            Value::String(id) => Some(id.clone()),
+            Value::Number(id) => Some(id.to_string()),
+            _ => None,
+        })
+}
+
+/// 리모트 리포지토리
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteRepo {
+    pub forge: Forge,
+    pub host: String,
+    /// `owner/repo` 또는 `group/subgroup/project`
+    pub path: String,
+}
+
+impl RemoteRepo {
+    /// 리모트 URL 파싱 (`git@gitlab.com:group/app.git`, `https://gitlab.example.com/group/app`, `ssh://git@host:2222/group/app.git`)
+    /// 알 수 없는 서비스면 None
+    pub fn parse(url: &str) -> Option<Self> {
+        let url = url.trim();
+        let (host, path) = match url.split_once("://") {
+            Some((_, rest)) => {
+                let (authority, path) = rest.split_once('/')?;
+                let host = authority.rsplit('@').next()?;
+                (host.split(':').next()?, path)
+            }
+            // scp 형식 (user@host:path)
+            None => {
+                let (authority, path) = url.split_once(':')?;
+                (authority.rsplit('@').next()?, path)
+            }
+        };
+        let path = path.trim_matches('/').trim_end_matches(".git");
+        if host.is_empty() || !path.contains('/') {
+            return None;
+        }
+        Some(Self { forge: Forge::from_host(host)?, host: host.to_string(), path: path.to_string() })
+    }
+
+    /// 현재 리포지토리의 origin 리모트
+    pub fn origin() -> Option<Self> {
+        let repo = crate::git_utils::open_repository().ok()?;
+        let remote = repo.find_remote("origin").ok()?;
+        Self::parse(remote.url()?)
+    }
+}
+
 /// AI CLI용 내장 도구들
 #[derive(Debug, Clone)]
 pub enum BuiltInTool {
@@ -112,19 +420,49 @@ pub enum BuiltInTool {
     ListDirectory {
         path: String,
     },
+    /// 시맨틱 인덱스에서 질의와 관련된 코드 검색
+    SemanticSearch {
+        query: String,
+        top_k: usize,
+    },
 }
 
 impl BuiltInTool {
     /// 내장 도구 실행
     pub async fn execute(&self) -> Result<Value> {
+        let policy = Policy::load()?;
+        let sandbox = Sandbox::for_current_project()?;
+
         match self {
             BuiltInTool::GitCommand { command, args } => {
-                let output = tokio::process::Command::new("git")
-                    .arg(command)
-                    .args(args)
+                let command_line = format!("git {} {}", command, args.join(" "));
+                let decision = policy.check_command(&command_line);
+                if let CommandDecision::Deny(rule) = &decision {
+                    return Err(anyhow!("Command '{}' is denied by policy (rule: {})", command_line.trim_end(), rule));
+                }
+
+                // 신뢰하지 않는 폴더에서는 읽기 전용 git 명령어만 허용
+                if !is_read_only_git_command(command, args) {
+                    if let Some(refusal) = read_only_refusal(command_line.trim_end(), sandbox.root()) {
+                        return Ok(refusal);
+                    }
+                }
+
+                // 정책의 허용 목록에 없는 명령어는 사용자 승인을 받아야 실행
+                if decision == CommandDecision::Prompt {
+                    authorize_command(command_line.trim_end())?;
+                }
+
+                let mut git_args = vec![command.as_str()];
+                git_args.extend(args.iter().map(String::as_str));
+                let output = tokio::process::Command::from(sandbox.command("git", &git_args))
                     .output()
                     .await?;
 
+                audit::record_command(command_line.trim_end(), output.status.code());
+                let combined = format!("{}{}", String::from_utf8_lossy(&output.stdout), String::from_utf8_lossy(&output.stderr));
+                transcript::command(command_line.trim_end(), output.status.code(), &combined);
+
                 let result = serde_json::json!({
                     "success": output.status.success(),
                     "stdout": String::from_utf8_lossy(&output.stdout),
@@ -134,12 +472,21 @@ impl BuiltInTool {
                 Ok(result)
             }
             BuiltInTool::ReadFile { path } => {
+                let path = sandbox.resolve(Path::new(path))?;
+                if AiIgnore::load(sandbox.root()).is_ignored(&path, false) {
+                    return Err(anyhow!("'{}' is excluded by .aiignore", path.display()));
+                }
                 let content = tokio::fs::read_to_string(path).await?;
                 Ok(serde_json::json!({
                     "content": content
                 }))
             }
             BuiltInTool::WriteFile { path, content } => {
+                let path = sandbox.resolve(Path::new(path))?;
+                if let Some(refusal) = read_only_refusal("write_file", path.parent().unwrap_or(sandbox.root())) {
+                    return Ok(refusal);
+                }
+                policy.check_writable(&path)?;
                 tokio::fs::write(path, content).await?;
                 Ok(serde_json::json!({
                     "success": true
@@ -147,10 +494,19 @@ impl BuiltInTool {
             }
             BuiltInTool::ListDirectory { path } => {
                 let mut entries = Vec::new();
+                let path = sandbox.resolve(Path::new(path))?;
+                let aiignore = AiIgnore::load(sandbox.root());
+                if aiignore.is_ignored(&path, true) {
+                    return Err(anyhow!("'{}' is excluded by .aiignore", path.display()));
+                }
                 let mut dir = tokio::fs::read_dir(path).await?;
 
                 while let Some(entry) = dir.next_entry().await? {
                     let metadata = entry.metadata().await?;
+                    // `.aiignore` 대상은 목록에서 숨김
+                    if aiignore.is_ignored(&entry.path(), metadata.is_dir()) {
+                        continue;
+                    }
                     entries.push(serde_json::json!({
                         "name": entry.file_name().to_string_lossy(),
                         "is_file": metadata.is_file(),
@@ -162,6 +518,109 @@ impl BuiltInTool {
                     "entries": entries
                 }))
             }
+            BuiltInTool::SemanticSearch { query, top_k } => {
+                let matches = search_project(sandbox.root(), query, *top_k, DEFAULT_SNIPPET_LINES).await?;
+                Ok(serde_json::json!({
+                    "matches": matches
+                }))
+            }
         }
     }
-}
\ No newline at end of file
+}
+
+/// 정책이 승인을 요구하는 명령어 확인 (세션 승인이 없으면 물어보고, 비대화형 실행에서는 거부)
+fn authorize_command(command: &str) -> Result<()> {
+    let command_type = CommandType::classify(command);
+    let mut security_manager = SecurityManager::default();
+    if ai_utils::is_non_interactive() && !security_manager.is_command_approved(command, command_type) {
+        audit::record_command(command, None);
+        return Err(anyhow!("Command '{}' needs interactive approval (add it to the policy allow list or approve it for the session first)", command));
+    }
+
+    match security_manager.prompt_command_approval(command, command_type)? {
+        ApprovalOption::Yes | ApprovalOption::YesForSession => Ok(()),
+        ApprovalOption::No | ApprovalOption::EditAndRetry => {
+            audit::record_command(command, None);
+            Err(anyhow!("Command '{}' cancelled by user", command))
+        }
+    }
+}
+
+/// 대상 위치가 쓰기 불가능한 보안 레벨이면 에이전트에 전달할 구조화된 거부 응답 반환
+fn read_only_refusal(operation: &str, location: &Path) -> Option<Value> {
+    let level = SecurityManager::default().determine_security_level(location);
+    if level.allows_writes() {
+        return None;
+    }
+
+    Some(serde_json::json!({
+        "success": false,
+        "error": {
+            "code": "read_only_location",
+            "operation": operation,
+            "path": location.display().to_string(),
+            "security_level": level.as_str(),
+            "message": format!(
+                "'{}' is not allowed in {} folder '{}'. Ask the user to trust the folder first.",
+                operation,
+                level.as_str(),
+                location.display()
+            ),
+        }
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_remote_repo_parse() {
+        let gitlab = RemoteRepo::parse("git@gitlab.com:acme/platform/api.git").unwrap();
+        assert_eq!(gitlab, RemoteRepo { forge: Forge::GitLab, host: "gitlab.com".to_string(), path: "acme/platform/api".to_string() });
+
+        let self_hosted = RemoteRepo::parse("ssh://git@gitlab.acme.dev:2222/team/app.git").unwrap();
+        assert_eq!((self_hosted.forge, self_hosted.host.as_str(), self_hosted.path.as_str()), (Forge::GitLab, "gitlab.acme.dev", "team/app"));
+
+        let github = RemoteRepo::parse("https://github.com/acme/app/").unwrap();
+        assert_eq!((github.forge, github.path.as_str()), (Forge::GitHub, "acme/app"));
+
+        assert!(RemoteRepo::parse("https://bitbucket.org/acme/app.git").is_none());
+        assert!(RemoteRepo::parse("/srv/git/app.git").is_none());
+    }
+
+    #[test]
+    fn test_forge_matches_server() {
+        let gitlab: ServerConfig = serde_json::from_value(serde_json::json!({
+            "command": "npx", "args": ["-y", "@modelcontextprotocol/server-gitlab"]
+        }))
+        .unwrap();
+        assert!(Forge::GitLab.matches_server("work", &gitlab));
+        assert!(!Forge::GitHub.matches_server("work", &gitlab));
+        assert!(Forge::GitHub.matches_server("GitHub", &ServerConfig::default()));
+        assert!(is_slack_server("team-slack", &ServerConfig::default()));
+        assert!(!is_slack_server("work", &gitlab));
+    }
+
+    #[test]
+    fn test_find_transition() {
+        let transitions = serde_json::json!([
+            {"id": "11", "name": "Start Progress", "to": {"name": "In Progress"}},
+            {"id": 31, "name": "Done", "to_status": "Done"}
+        ]);
+        assert_eq!(find_transition(&transitions, "in progress").as_deref(), Some("11"));
+        assert_eq!(find_transition(&transitions, "Start Progress").as_deref(), Some("11"));
+        assert_eq!(find_transition(&serde_json::json!({"transitions": transitions}), "Done").as_deref(), Some("31"));
+        assert!(find_transition(&transitions, "In Review").is_none());
+    }
+
+    #[test]
+    fn test_read_only_refusal_in_untrusted_folder() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let refusal = read_only_refusal("write_file", temp_dir.path()).unwrap();
+
+        assert_eq!(refusal["success"], false);
+        assert_eq!(refusal["error"]["code"], "read_only_location");
+        assert_eq!(refusal["error"]["security_level"], "untrusted");
+    }
+}
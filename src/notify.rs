@@ -0,0 +1,125 @@
+use std::fs::OpenOptions;
+use std::io::{self, IsTerminal, Write};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+use crate::config;
+use crate::mcp::{self, MCPClientBuilder, McpRegistry, ToolManager};
+
+/// 알림 모듈
+/// 명령 결과(변경 설명 등)를 `--notify <채널>`로 팀 Slack 채널에 공유
+/// 등록한 Slack MCP 서버(`slack_post_message`)를 쓰고, 없으면 Incoming Webhook으로 전송
+/// 요청에 있던 `standup`, `pr` 명령은 아직 없어 지금은 `explain --notify`만 사용 (두 명령을 추가할 때 같은 `share_on_slack`으로 연결)
+/// 오래 걸린 작업(커밋 메시지 생성, 변경 설명, 에이전트 실행)이 끝나면 `notify` 설정에 따라 터미널 벨이나 데스크톱 알림도 보냄
+///
+/// ```sh
+/// export AI_CLI_SLACK_WEBHOOK="https://hooks.slack.com/services/..."   # Slack MCP 서버가 없을 때 (egress.allow_hosts에 hooks.slack.com 등록)
+/// ```
+///
+/// `notify` 설정 값 (`off`가 기본)
+pub const NOTIFY_MODES: [&str; 3] = ["bell", "desktop", "off"];
+
+/// 완료 알림을 보낼 만큼 오래 걸린 작업의 최소 시간
+const NOTIFY_AFTER: Duration = Duration::from_secs(10);
+
+/// Slack 메시지의 최대 길이 (문자)
+const MAX_MESSAGE_CHARS: usize = 3500;
+
+/// 메시지를 Slack 채널에 게시 (실패해도 명령은 성공으로 끝나도록 경고만 출력)
+pub async fn share_on_slack(channel: &str, text: &str) {
+    let policy = match crate::policy::Policy::load() {
+        Ok(policy) if !policy.is_local_only() => policy,
+        _ => {
+            println!("🔒 Not posting to {}: local-only privacy mode", channel);
+            return;
+        }
+    };
+
+    let mut builder = MCPClientBuilder::new("ai-cli").version(env!("CARGO_PKG_VERSION")).policy(policy);
+    for (name, config) in McpRegistry::load().unwrap_or_default().servers() {
+        if mcp::is_slack_server(name, config) {
+            builder = builder.server(name.clone(), config.clone());
+        }
+    }
+    let client = builder.build();
+    for (server, e) in client.connect_servers().await {
+        println!("⚠️  MCP server '{}' is unavailable: {}", server, e);
+    }
+
+    let tools = ToolManager::new(client);
+    let mut message: String = text.trim().chars().take(MAX_MESSAGE_CHARS).collect();
+    if message.len() < text.trim().len() {
+        message.push_str("\n…");
+    }
+    match tools.post_slack_message(channel, &message).await {
+        Ok(_) => println!("📣 Shared on Slack {}", channel),
+        Err(e) => println!("⚠️  Could not post to Slack {}: {}", channel, e),
+    }
+    tools.shutdown().await;
+}
+
+/// 오래 걸린 작업이 끝났음을 알림 (`notify` 설정이 `off`이거나 `NOTIFY_AFTER`보다 빨리 끝났으면 알리지 않음)
+pub fn finished(started: Instant, message: &str) {
+    if started.elapsed() < NOTIFY_AFTER {
+        return;
+    }
+    match config::setting("notify").trim() {
+        "bell" => ring_bell(),
+        // 알림 도구가 없으면 벨로 대신함
+        "desktop" => {
+            if let Err(e) = desktop_notification(message) {
+                tracing::debug!("Desktop notification failed: {}", e);
+                ring_bell();
+            }
+        }
+        _ => {}
+    }
+}
+
+/// 터미널 벨 (표준 출력이 파이프여도 제어 터미널에 보냄)
+fn ring_bell() {
+    if let Ok(mut tty) = OpenOptions::new().write(true).open(if cfg!(windows) { "CONOUT$" } else { "/dev/tty" }) {
+        let _ = tty.write_all(b"\x07");
+    } else if io::stderr().is_terminal() {
+        let _ = io::stderr().write_all(b"\x07");
+    }
+}
+
+/// 데스크톱 알림 (macOS `osascript`, Windows PowerShell 풍선 알림, 그 밖에는 `notify-send`)
+fn desktop_notification(message: &str) -> io::Result<()> {
+    let mut command = if cfg!(target_os = "macos") {
+        let mut command = Command::new("osascript");
+        command.arg("-e").arg(format!("display notification {} with title \"ai-cli\"", applescript_string(message)));
+        command
+    } else if cfg!(windows) {
+        let mut command = Command::new("powershell");
+        command.args(["-NoProfile", "-Command", "Add-Type -AssemblyName System.Windows.Forms; $n = New-Object System.Windows.Forms.NotifyIcon; $n.Icon = [System.Drawing.SystemIcons]::Information; $n.Visible = $true; $n.ShowBalloonTip(5000, 'ai-cli', $env:AI_CLI_NOTIFY_MESSAGE, 'Info'); Start-Sleep -Seconds 5; $n.Dispose()"]);
+        command.env("AI_CLI_NOTIFY_MESSAGE", message);
+        command
+    } else {
+        let mut command = Command::new("notify-send");
+        command.args(["--app-name=ai-cli", "ai-cli", message]);
+        command
+    };
+    let status = command.stdin(Stdio::null()).stdout(Stdio::null()).stderr(Stdio::null()).status()?;
+    if !status.success() {
+        return Err(io::Error::other(format!("notifier exited with {}", status)));
+    }
+    Ok(())
+}
+
+/// AppleScript 문자열 리터럴
+fn applescript_string(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_finished_notification() {
+        assert_eq!(applescript_string(r#"say "hi" \ bye"#), r#""say \"hi\" \\ bye""#);
+        assert_eq!(applescript_string("커밋 메시지 준비됨"), "\"커밋 메시지 준비됨\"");
+    }
+}
@@ -0,0 +1,156 @@
+use anyhow::{Result, anyhow};
+use clap::CommandFactory;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::process::{Command, Stdio};
+use std::sync::Mutex;
+use std::time::Instant;
+
+use crate::ai_utils::TokenUsage;
+use crate::cli::Cli;
+
+/// `--json` 구조화 출력 모듈
+/// 표준 출력에는 명령 결과 JSON 한 개(`command`, `ok`, `result`, `usage`, `duration_ms`, `error`)만 쓰고,
+/// 사람이 읽는 출력과 프롬프트는 모두 표준 에러로 보냄
+/// 출력 코드를 명령마다 나누지 않도록 같은 명령을 자식 프로세스로 다시 실행해 자식의 표준 출력을 표준 에러에 연결하고,
+/// 자식이 `record`/`add_usage`/`record_error`로 남긴 결과를 모아 출력
+///
+/// 결과를 남길 파일 경로 (`--json`으로 실행한 자식 프로세스에만 설정)
+const RESULT_ENV: &str = "AI_CLI_JSON_RESULT";
+
+/// 자식 프로세스가 남기는 결과
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Recorded {
+    result: serde_json::Value,
+    usage: Option<Usage>,
+    error: Option<String>,
+}
+
+/// 명령 하나에서 쓴 토큰 합계
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
+struct Usage {
+    prompt_tokens: u64,
+    completion_tokens: u64,
+    total_tokens: u64,
+}
+
+static RECORDED: Mutex<Option<Recorded>> = Mutex::new(None);
+
+/// `--json` 결과를 모으는 중인지 (명령 결과를 `record`로 남겨야 하는지)
+pub fn is_json() -> bool {
+    std::env::var_os(RESULT_ENV).is_some()
+}
+
+/// 남긴 결과를 파일에 저장 (`exit`로 끝나도 남도록 바뀔 때마다 씀)
+fn update(change: impl FnOnce(&mut Recorded)) {
+    let Some(path) = std::env::var_os(RESULT_ENV) else {
+        return;
+    };
+    let mut recorded = RECORDED.lock().unwrap_or_else(|e| e.into_inner());
+    change(recorded.get_or_insert_with(Recorded::default));
+    if let Err(e) = serde_json::to_vec(&*recorded).map_err(io::Error::from).and_then(|json| fs::write(&path, json)) {
+        tracing::warn!("Could not save the --json result: {}", e);
+    }
+}
+
+/// 명령 결과 남기기
+pub fn record(result: serde_json::Value) {
+    update(|recorded| recorded.result = result);
+}
+
+/// AI 응답의 토큰 사용량 더하기
+pub fn add_usage(usage: &TokenUsage) {
+    update(|recorded| {
+        let total = recorded.usage.get_or_insert_with(Usage::default);
+        total.prompt_tokens += u64::from(usage.prompt_tokens);
+        total.completion_tokens += u64::from(usage.completion_tokens);
+        total.total_tokens += u64::from(usage.total_tokens);
+    });
+}
+
+/// 명령이 실패한 이유 남기기
+pub fn record_error(error: &anyhow::Error) {
+    update(|recorded| recorded.error = Some(format!("{:#}", error)));
+}
+
+/// 실행한 하위 명령 이름 (예: `config where`)
+fn command_path() -> String {
+    let matches = Cli::command().get_matches();
+    let mut names = Vec::new();
+    let mut current = matches.subcommand();
+    while let Some((name, sub)) = current {
+        names.push(name.to_string());
+        current = sub.subcommand();
+    }
+    names.join(" ")
+}
+
+/// 같은 명령을 자식 프로세스로 실행하고 결과 JSON을 표준 출력에 씀 (자식의 종료 코드를 돌려줌)
+pub fn run_as_child() -> Result<i32> {
+    let command = command_path();
+    // 다른 사용자가 미리 만들거나 가로챌 수 없도록 이름을 예측할 수 없는 파일을 만들어 경로를 넘김
+    let file = tempfile::Builder::new()
+        .prefix("ai-cli-json-")
+        .suffix(".json")
+        .tempfile()
+        .map_err(|e| anyhow!("Failed to create a temporary file for --json: {}", e))?;
+
+    let started = Instant::now();
+    let status = Command::new(std::env::current_exe()?)
+        .args(std::env::args_os().skip(1))
+        .env(RESULT_ENV, file.path())
+        .stdout(Stdio::from(io::stderr()))
+        .status()
+        .map_err(|e| anyhow!("Failed to run ai-cli for --json: {}", e))?;
+    let duration_ms = started.elapsed().as_millis() as u64;
+
+    let recorded: Recorded = fs::read_to_string(file.path())
+        .ok()
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default();
+
+    let error = recorded.error.or_else(|| {
+        (!status.success()).then(|| match status.code() {
+            Some(code) => format!("ai-cli {} exited with code {}", command, code),
+            None => format!("ai-cli {} was terminated", command),
+        })
+    });
+    println!("{}", envelope(&command, status.success(), recorded.result, recorded.usage, duration_ms, error));
+    Ok(status.code().unwrap_or(1))
+}
+
+/// 표준 출력에 쓰는 결과
+fn envelope(command: &str, ok: bool, result: serde_json::Value, usage: Option<Usage>, duration_ms: u64, error: Option<String>) -> serde_json::Value {
+    serde_json::json!({
+        "command": command,
+        "ok": ok,
+        "result": result,
+        "usage": usage,
+        "duration_ms": duration_ms,
+        "error": error,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_envelope() {
+        let usage = Usage { prompt_tokens: 10, completion_tokens: 5, total_tokens: 15 };
+        let value = envelope("lint", false, serde_json::json!({ "violations": [] }), Some(usage), 42, Some("boom".to_string()));
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "command": "lint",
+                "ok": false,
+                "result": { "violations": [] },
+                "usage": { "prompt_tokens": 10, "completion_tokens": 5, "total_tokens": 15 },
+                "duration_ms": 42,
+                "error": "boom",
+            })
+        );
+        assert_eq!(envelope("config", true, serde_json::Value::Null, None, 1, None)["usage"], serde_json::Value::Null);
+    }
+}
@@ -0,0 +1,83 @@
+use std::io::{self, IsTerminal, Write};
+use std::process::{Command, Stdio};
+
+use crate::git_utils;
+use crate::output;
+
+/// 페이저 모듈
+/// 긴 출력(explain 결과, audit show)을 터미널에서 `$PAGER`, 없으면 git의 `core.pager`, 그것도 없으면 `less`로 보여 줌
+/// git처럼 `LESS`가 없으면 `FRX`로 실행해 한 화면에 들어가는 출력은 바로 끝나고 색도 유지됨
+/// `--no-pager`(`AI_CLI_NO_PAGER=1`), 표준 출력이 터미널이 아닐 때, `--json`으로 실행할 때는 그대로 출력
+///
+/// 페이저를 쓰지 않게 하는 환경 변수 (`--no-pager`)
+pub const NO_PAGER_ENV: &str = "AI_CLI_NO_PAGER";
+
+/// 페이저 명령 (`$PAGER`, `core.pager`, `less` 순, 비어 있거나 `cat`이면 `None`)
+fn pager_command() -> Option<String> {
+    let core_pager = || git_utils::open_repository().ok()?.config().ok()?.get_string("core.pager").ok();
+    choose_pager(std::env::var("PAGER").ok(), core_pager)
+}
+
+/// 설정된 값 중 쓸 페이저 (`core.pager`는 `PAGER`가 없을 때만 읽음)
+fn choose_pager(env: Option<String>, core_pager: impl FnOnce() -> Option<String>) -> Option<String> {
+    let pager = env.or_else(core_pager).unwrap_or_else(|| "less".to_string());
+    let pager = pager.trim();
+    (!pager.is_empty() && pager != "cat").then(|| pager.to_string())
+}
+
+/// 페이저를 쓸 수 있는지
+fn is_enabled() -> bool {
+    io::stdout().is_terminal() && std::env::var_os(NO_PAGER_ENV).is_none() && !output::is_json()
+}
+
+/// 텍스트를 페이저로 보여 줌 (페이저를 쓸 수 없거나 실행하지 못하면 그대로 출력)
+pub fn page(text: &str) {
+    let text = if text.ends_with('\n') { text.to_string() } else { format!("{}\n", text) };
+    if let Some(pager) = is_enabled().then(pager_command).flatten() {
+        match run(&pager, &text) {
+            Ok(()) => return,
+            Err(e) => tracing::debug!("Could not run pager '{}': {}", pager, e),
+        }
+    }
+    print!("{}", text);
+}
+
+/// 셸로 페이저를 실행해 표준 입력으로 텍스트 전달 (`less -S`처럼 인자가 붙은 값도 그대로 동작)
+fn run(pager: &str, text: &str) -> io::Result<()> {
+    let mut command = if cfg!(windows) {
+        let mut command = Command::new("cmd");
+        command.args(["/C", pager]);
+        command
+    } else {
+        let mut command = Command::new("sh");
+        command.args(["-c", pager]);
+        command
+    };
+    if std::env::var_os("LESS").is_none() {
+        command.env("LESS", "FRX");
+    }
+    let mut child = command.stdin(Stdio::piped()).spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        // 사용자가 끝까지 보지 않고 페이저를 닫으면 쓰기가 끊기므로 오류는 무시
+        let _ = stdin.write_all(text.as_bytes());
+    }
+    // 셸이 명령을 찾지 못했으면 그대로 출력하도록 오류 반환
+    if child.wait()?.code() == Some(127) {
+        return Err(io::Error::new(io::ErrorKind::NotFound, "command not found"));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_choose_pager() {
+        assert_eq!(choose_pager(Some("most".to_string()), || panic!("core.pager read")), Some("most".to_string()));
+        assert_eq!(choose_pager(None, || Some("less -S ".to_string())), Some("less -S".to_string()));
+        assert_eq!(choose_pager(None, || None), Some("less".to_string()));
+        assert_eq!(choose_pager(Some("cat".to_string()), || None), None);
+        assert_eq!(choose_pager(Some(String::new()), || None), None);
+    }
+}
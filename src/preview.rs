@@ -0,0 +1,189 @@
+use globset::GlobBuilder;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use walkdir::WalkDir;
+
+/// 명령어 효과 미리보기 모듈
+/// 승인 요청 시 명령어 문자열만 보여주는 대신, 가능한 경우 드라이런 결과를 보여줌
+/// - `git clean`: `git clean -n`으로 삭제될 파일 목록
+/// - `rm`: 인자와 글롭에 해당하는 파일 목록
+/// - `git reset --hard`, `git checkout -- <path>`, `git restore`: 버려질 변경 사항의 diff 통계
+///
+/// 미리보기에 표시할 최대 항목 수
+const MAX_PREVIEW_ENTRIES: usize = 20;
+
+/// 명령어 실행 효과 미리보기 (지원하지 않는 명령어면 None)
+pub fn preview_command_effect(command: &str, root: &Path) -> Option<String> {
+    let words: Vec<&str> = command
+        .split_whitespace()
+        .skip_while(|word| *word == "sudo")
+        .collect();
+
+    match words.as_slice() {
+        ["git", "clean", args @ ..] => preview_git_clean(args, root),
+        ["git", "reset", args @ ..] if args.contains(&"--hard") => {
+            let target = args.iter().find(|arg| !arg.starts_with('-')).copied().unwrap_or("HEAD");
+            preview_discarded_changes(&[target], root)
+        }
+        ["git", "checkout", args @ ..] => {
+            let paths = args.iter().position(|arg| *arg == "--").map(|index| &args[index + 1..])?;
+            preview_discarded_changes(&[&["--"][..], paths].concat(), root)
+        }
+        ["git", "restore", args @ ..] if !args.contains(&"--staged") => {
+            let paths: Vec<&str> = args.iter().filter(|arg| !arg.starts_with('-')).copied().collect();
+            preview_discarded_changes(&[&["--"][..], paths.as_slice()].concat(), root)
+        }
+        ["rm", args @ ..] | ["del", args @ ..] => preview_rm(args, root),
+        _ => None,
+    }
+}
+
+/// `git clean`을 `-n`(드라이런)으로 실행
+fn preview_git_clean(args: &[&str], root: &Path) -> Option<String> {
+    let mut dry_run_args = vec!["clean".to_string(), "-n".to_string()];
+    for arg in args {
+        match *arg {
+            "-f" | "--force" => {}
+            short if short.starts_with('-') && !short.starts_with("--") => {
+                let flags: String = short[1..].chars().filter(|c| *c != 'f').collect();
+                if !flags.is_empty() {
+                    dry_run_args.push(format!("-{}", flags));
+                }
+            }
+            other => dry_run_args.push(other.to_string()),
+        }
+    }
+
+    let output = run_git(&dry_run_args, root)?;
+    let lines: Vec<&str> = output.lines().collect();
+    if lines.is_empty() {
+        return Some("Nothing would be removed.".to_string());
+    }
+    Some(summarize(lines.iter().map(|line| line.to_string()).collect(), "git clean"))
+}
+
+/// 버려질 작업 트리 변경 사항을 `git diff --stat`으로 표시
+fn preview_discarded_changes(diff_args: &[&str], root: &Path) -> Option<String> {
+    let mut args = vec!["diff".to_string(), "--stat".to_string()];
+    args.extend(diff_args.iter().map(|arg| arg.to_string()));
+
+    let output = run_git(&args, root)?;
+    if output.trim().is_empty() {
+        return Some("No uncommitted changes would be discarded.".to_string());
+    }
+    Some(format!("Changes that would be discarded:\n{}", output.trim_end()))
+}
+
+/// `rm` 대상 파일 목록 (글롭 확장, `-r`이면 디렉토리 안 파일 포함)
+fn preview_rm(args: &[&str], root: &Path) -> Option<String> {
+    let recursive = args.iter().any(|arg| {
+        (arg.starts_with('-') && !arg.starts_with("--") && (arg.contains('r') || arg.contains('R')))
+            || *arg == "--recursive"
+    });
+
+    let mut targets: Vec<PathBuf> = Vec::new();
+    for pattern in args.iter().filter(|arg| !arg.starts_with('-')) {
+        if pattern.contains(['*', '?', '[']) {
+            // 셸과 같이 `*`는 디렉토리 구분자를 넘지 않음
+            let matcher = GlobBuilder::new(pattern).literal_separator(true).build().ok()?.compile_matcher();
+            targets.extend(
+                WalkDir::new(root)
+                    .min_depth(1)
+                    .into_iter()
+                    .filter_map(|entry| entry.ok())
+                    .filter(|entry| {
+                        entry.path().strip_prefix(root).map(|relative| matcher.is_match(relative)).unwrap_or(false)
+                    })
+                    .map(|entry| entry.into_path()),
+            );
+        } else {
+            let path = root.join(pattern);
+            if path.exists() {
+                targets.push(path);
+            }
+        }
+    }
+
+    let mut files: Vec<String> = Vec::new();
+    for target in &targets {
+        if target.is_dir() && recursive {
+            files.extend(
+                WalkDir::new(target)
+                    .into_iter()
+                    .filter_map(|entry| entry.ok())
+                    .filter(|entry| entry.file_type().is_file())
+                    .map(|entry| display_path(entry.path(), root)),
+            );
+        } else {
+            files.push(display_path(target, root));
+        }
+    }
+
+    if files.is_empty() {
+        return Some("No matching files would be removed.".to_string());
+    }
+    files.sort();
+    files.dedup();
+    Some(summarize(files.into_iter().map(|file| format!("Would remove {}", file)).collect(), "rm"))
+}
+
+/// 목록이 길면 일부만 표시
+fn summarize(lines: Vec<String>, label: &str) -> String {
+    let total = lines.len();
+    let mut shown: Vec<String> = lines.into_iter().take(MAX_PREVIEW_ENTRIES).collect();
+    if total > MAX_PREVIEW_ENTRIES {
+        shown.push(format!("... and {} more ({} entries affected by {})", total - MAX_PREVIEW_ENTRIES, total, label));
+    }
+    shown.join("\n")
+}
+
+fn display_path(path: &Path, root: &Path) -> String {
+    path.strip_prefix(root).unwrap_or(path).display().to_string()
+}
+
+fn run_git(args: &[String], root: &Path) -> Option<String> {
+    let output = Command::new("git").args(args).current_dir(root).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_preview_rm_glob() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("a.log"), "").unwrap();
+        fs::write(temp_dir.path().join("b.log"), "").unwrap();
+        fs::write(temp_dir.path().join("keep.txt"), "").unwrap();
+        fs::create_dir(temp_dir.path().join("build")).unwrap();
+        fs::write(temp_dir.path().join("build").join("out.o"), "").unwrap();
+
+        let preview = preview_command_effect("rm *.log", temp_dir.path()).unwrap();
+        assert_eq!(preview, "Would remove a.log\nWould remove b.log");
+
+        let preview = preview_command_effect("rm -rf build", temp_dir.path()).unwrap();
+        assert!(preview.contains("out.o"));
+
+        assert!(preview_command_effect("echo hello", temp_dir.path()).is_none());
+    }
+
+    #[test]
+    fn test_preview_git_clean() {
+        let temp_dir = TempDir::new().unwrap();
+        let git = |args: &[&str]| {
+            Command::new("git").args(args).current_dir(temp_dir.path()).output().unwrap();
+        };
+        git(&["init"]);
+        fs::write(temp_dir.path().join("untracked.txt"), "").unwrap();
+
+        let preview = preview_command_effect("git clean -fd", temp_dir.path()).unwrap();
+        assert_eq!(preview, "Would remove untracked.txt");
+        assert!(temp_dir.path().join("untracked.txt").exists());
+    }
+}
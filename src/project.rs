@@ -0,0 +1,341 @@
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+
+/// 프로젝트 프로필 모듈
+/// 매니페스트(Cargo.toml, package.json, pyproject.toml, go.mod)로 언어, 프레임워크, 빌드/테스트 도구를 감지해
+/// 프롬프트에 짧은 "Project profile"로 전달
+///
+/// 감지한 프로젝트 프로필
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ProjectProfile {
+    pub languages: Vec<String>,
+    pub frameworks: Vec<String>,
+    pub build_tools: Vec<String>,
+    pub test_tools: Vec<String>,
+}
+
+/// 의존성 이름과 표시할 프레임워크 이름
+const RUST_FRAMEWORKS: &[(&str, &str)] = &[
+    ("tokio", "Tokio"),
+    ("axum", "Axum"),
+    ("actix-web", "Actix Web"),
+    ("rocket", "Rocket"),
+    ("clap", "clap"),
+    ("serde", "Serde"),
+    ("diesel", "Diesel"),
+    ("sqlx", "SQLx"),
+    ("bevy", "Bevy"),
+    ("tauri", "Tauri"),
+];
+
+const NODE_FRAMEWORKS: &[(&str, &str)] = &[
+    ("next", "Next.js"),
+    ("react", "React"),
+    ("vue", "Vue"),
+    ("svelte", "Svelte"),
+    ("@angular/core", "Angular"),
+    ("express", "Express"),
+    ("fastify", "Fastify"),
+    ("@nestjs/core", "NestJS"),
+    ("electron", "Electron"),
+];
+
+const NODE_TEST_TOOLS: &[(&str, &str)] = &[
+    ("jest", "Jest"),
+    ("vitest", "Vitest"),
+    ("mocha", "Mocha"),
+    ("@playwright/test", "Playwright"),
+    ("cypress", "Cypress"),
+];
+
+const PYTHON_FRAMEWORKS: &[(&str, &str)] = &[
+    ("django", "Django"),
+    ("flask", "Flask"),
+    ("fastapi", "FastAPI"),
+    ("pydantic", "Pydantic"),
+    ("numpy", "NumPy"),
+    ("pandas", "pandas"),
+    ("torch", "PyTorch"),
+];
+
+const GO_FRAMEWORKS: &[(&str, &str)] = &[
+    ("github.com/gin-gonic/gin", "Gin"),
+    ("github.com/labstack/echo", "Echo"),
+    ("github.com/gofiber/fiber", "Fiber"),
+    ("github.com/spf13/cobra", "Cobra"),
+    ("google.golang.org/grpc", "gRPC"),
+];
+
+impl ProjectProfile {
+    /// 프로젝트 루트의 매니페스트로 프로필 감지
+    pub fn detect(root: &Path) -> Self {
+        let mut profile = ProjectProfile::default();
+
+        if let Ok(content) = fs::read_to_string(root.join("Cargo.toml")) {
+            profile.detect_cargo(&content);
+        }
+        if let Ok(content) = fs::read_to_string(root.join("package.json")) {
+            profile.detect_node(root, &content);
+        }
+        if let Ok(content) = fs::read_to_string(root.join("pyproject.toml")) {
+            profile.detect_python(&content);
+        } else if root.join("requirements.txt").exists() || root.join("setup.py").exists() {
+            profile.add_language("Python");
+            profile.add_build_tool("pip");
+        }
+        if let Ok(content) = fs::read_to_string(root.join("go.mod")) {
+            profile.detect_go(&content);
+        }
+
+        profile
+    }
+
+    /// 감지한 내용이 없는지 확인
+    pub fn is_empty(&self) -> bool {
+        self.languages.is_empty()
+    }
+
+    fn detect_cargo(&mut self, content: &str) {
+        self.add_language("Rust");
+        self.add_build_tool("cargo");
+        self.add_test_tool("cargo test");
+
+        let Ok(manifest) = content.parse::<toml::Table>() else {
+            return;
+        };
+        if manifest.contains_key("workspace") {
+            self.add_build_tool("cargo workspace");
+        }
+
+        let dependencies: Vec<&str> = ["dependencies", "dev-dependencies", "workspace.dependencies"]
+            .iter()
+            .filter_map(|section| {
+                section
+                    .split('.')
+                    .try_fold(&manifest, |table, key| table.get(key)?.as_table())
+            })
+            .flat_map(|table| table.keys().map(String::as_str))
+            .collect();
+        self.add_known(&dependencies, RUST_FRAMEWORKS, |profile, name| profile.add_framework(name));
+    }
+
+    fn detect_node(&mut self, root: &Path, content: &str) {
+        let Ok(manifest) = serde_json::from_str::<serde_json::Value>(content) else {
+            return;
+        };
+
+        let dependencies: Vec<&str> = ["dependencies", "devDependencies", "peerDependencies"]
+            .iter()
+            .filter_map(|section| manifest.get(section)?.as_object())
+            .flat_map(|deps| deps.keys().map(String::as_str))
+            .collect();
+
+        if dependencies.contains(&"typescript") || root.join("tsconfig.json").exists() {
+            self.add_language("TypeScript");
+        } else {
+            self.add_language("JavaScript");
+        }
+
+        // 잠금 파일로 패키지 매니저 판별
+        let package_manager = if root.join("pnpm-lock.yaml").exists() {
+            "pnpm"
+        } else if root.join("yarn.lock").exists() {
+            "yarn"
+        } else if root.join("bun.lockb").exists() || root.join("bun.lock").exists() {
+            "bun"
+        } else {
+            "npm"
+        };
+        self.add_build_tool(package_manager);
+        for (dependency, tool) in [("vite", "Vite"), ("webpack", "webpack"), ("esbuild", "esbuild")] {
+            if dependencies.contains(&dependency) {
+                self.add_build_tool(tool);
+            }
+        }
+
+        self.add_known(&dependencies, NODE_FRAMEWORKS, |profile, name| profile.add_framework(name));
+        self.add_known(&dependencies, NODE_TEST_TOOLS, |profile, name| profile.add_test_tool(name));
+    }
+
+    fn detect_python(&mut self, content: &str) {
+        self.add_language("Python");
+
+        let Ok(manifest) = content.parse::<toml::Table>() else {
+            return;
+        };
+
+        let tool = manifest.get("tool").and_then(|tool| tool.as_table());
+        let backend = manifest
+            .get("build-system")
+            .and_then(|build| build.get("build-backend"))
+            .and_then(|backend| backend.as_str())
+            .unwrap_or_default();
+        let build_tool = if tool.is_some_and(|tool| tool.contains_key("poetry")) || backend.starts_with("poetry") {
+            "poetry"
+        } else if tool.is_some_and(|tool| tool.contains_key("uv")) {
+            "uv"
+        } else if backend.starts_with("hatchling") {
+            "hatch"
+        } else if backend.starts_with("pdm") {
+            "pdm"
+        } else {
+            "pip"
+        };
+        self.add_build_tool(build_tool);
+
+        // `[project] dependencies`(PEP 621)와 `[tool.poetry.dependencies]`의 이름만 추출
+        let mut dependencies: Vec<String> = manifest
+            .get("project")
+            .and_then(|project| project.get("dependencies"))
+            .and_then(|deps| deps.as_array())
+            .map(|deps| deps.iter().filter_map(|dep| dep.as_str()).map(requirement_name).collect())
+            .unwrap_or_default();
+        if let Some(poetry) = tool.and_then(|tool| tool.get("poetry")).and_then(|poetry| poetry.as_table()) {
+            for section in ["dependencies", "dev-dependencies"] {
+                if let Some(deps) = poetry.get(section).and_then(|deps| deps.as_table()) {
+                    dependencies.extend(deps.keys().map(|name| name.to_lowercase()));
+                }
+            }
+        }
+        let dependencies: Vec<&str> = dependencies.iter().map(String::as_str).collect();
+        self.add_known(&dependencies, PYTHON_FRAMEWORKS, |profile, name| profile.add_framework(name));
+
+        if dependencies.contains(&"pytest") || tool.is_some_and(|tool| tool.contains_key("pytest")) {
+            self.add_test_tool("pytest");
+        }
+    }
+
+    fn detect_go(&mut self, content: &str) {
+        self.add_language("Go");
+        self.add_build_tool("go modules");
+        self.add_test_tool("go test");
+
+        let modules: Vec<&str> = content
+            .lines()
+            .map(|line| line.trim().trim_start_matches("require").trim().trim_start_matches('('))
+            .filter_map(|line| line.split_whitespace().next())
+            .collect();
+        self.add_known(&modules, GO_FRAMEWORKS, |profile, name| profile.add_framework(name));
+    }
+
+    fn add_known(&mut self, dependencies: &[&str], known: &[(&str, &str)], add: fn(&mut Self, &str)) {
+        for (dependency, name) in known {
+            if dependencies.iter().any(|dep| dep == dependency || dep.starts_with(&format!("{}/", dependency))) {
+                add(self, name);
+            }
+        }
+    }
+
+    fn add_language(&mut self, name: &str) {
+        push_unique(&mut self.languages, name);
+    }
+
+    fn add_framework(&mut self, name: &str) {
+        push_unique(&mut self.frameworks, name);
+    }
+
+    fn add_build_tool(&mut self, name: &str) {
+        push_unique(&mut self.build_tools, name);
+    }
+
+    fn add_test_tool(&mut self, name: &str) {
+        push_unique(&mut self.test_tools, name);
+    }
+
+    /// 프롬프트에 넣을 프로필 요약 (감지한 내용이 없으면 None)
+    pub fn summary(&self) -> Option<String> {
+        if self.is_empty() {
+            return None;
+        }
+
+        let mut lines = vec![format!("Project profile: {}", self.languages.join(", "))];
+        for (label, values) in [
+            ("Frameworks/libraries", &self.frameworks),
+            ("Build tools", &self.build_tools),
+            ("Test tools", &self.test_tools),
+        ] {
+            if !values.is_empty() {
+                lines.push(format!("- {}: {}", label, values.join(", ")));
+            }
+        }
+        lines.push("Follow this project's conventions and tooling.".to_string());
+
+        Some(lines.join("\n"))
+    }
+}
+
+/// 현재 프로젝트(Git 루트 또는 현재 디렉토리)의 프로필
+pub fn detect_current_project() -> ProjectProfile {
+    let root = crate::git_utils::open_repository()
+        .ok()
+        .and_then(|repo| repo.workdir().map(Path::to_path_buf))
+        .or_else(|| std::env::current_dir().ok());
+
+    root.map(|root| ProjectProfile::detect(&root)).unwrap_or_default()
+}
+
+/// PEP 508 요구 사항에서 패키지 이름만 추출 (`fastapi[all]>=0.100` → `fastapi`)
+fn requirement_name(requirement: &str) -> String {
+    requirement
+        .split(|c: char| !(c.is_alphanumeric() || c == '-' || c == '_' || c == '.'))
+        .next()
+        .unwrap_or_default()
+        .to_lowercase()
+}
+
+fn push_unique(values: &mut Vec<String>, value: &str) {
+    if !values.iter().any(|existing| existing == value) {
+        values.push(value.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_detect_rust_and_node() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("Cargo.toml"), "[package]\nname = \"x\"\n\n[dependencies]\ntokio = \"1\"\nclap = { version = \"4\" }\n").unwrap();
+        fs::write(
+            temp_dir.path().join("package.json"),
+            r#"{"dependencies": {"react": "^18"}, "devDependencies": {"typescript": "^5", "vitest": "^1", "vite": "^5"}}"#,
+        )
+        .unwrap();
+        fs::write(temp_dir.path().join("pnpm-lock.yaml"), "").unwrap();
+
+        let profile = ProjectProfile::detect(temp_dir.path());
+        assert_eq!(profile.languages, vec!["Rust", "TypeScript"]);
+        assert_eq!(profile.frameworks, vec!["Tokio", "clap", "React"]);
+        assert_eq!(profile.build_tools, vec!["cargo", "pnpm", "Vite"]);
+        assert_eq!(profile.test_tools, vec!["cargo test", "Vitest"]);
+    }
+
+    #[test]
+    fn test_detect_python_and_go() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("pyproject.toml"),
+            "[project]\nname = \"x\"\ndependencies = [\"fastapi[all]>=0.100\", \"pytest\"]\n\n[build-system]\nbuild-backend = \"hatchling.build\"\n",
+        )
+        .unwrap();
+        fs::write(temp_dir.path().join("go.mod"), "module example.com/x\n\ngo 1.22\n\nrequire (\n\tgithub.com/spf13/cobra v1.8.0\n)\n").unwrap();
+
+        let profile = ProjectProfile::detect(temp_dir.path());
+        assert_eq!(profile.languages, vec!["Python", "Go"]);
+        assert_eq!(profile.frameworks, vec!["FastAPI", "Cobra"]);
+        assert_eq!(profile.build_tools, vec!["hatch", "go modules"]);
+
+        let summary = profile.summary().unwrap();
+        assert!(summary.starts_with("Project profile: Python, Go"));
+        assert!(summary.contains("- Test tools: pytest, go test"));
+    }
+
+    #[test]
+    fn test_empty_project() {
+        let temp_dir = TempDir::new().unwrap();
+        assert!(ProjectProfile::detect(temp_dir.path()).summary().is_none());
+    }
+}
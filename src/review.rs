@@ -0,0 +1,372 @@
+use ansi_to_tui::IntoText;
+use anyhow::Result;
+use ratatui::backend::{Backend, CrosstermBackend};
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::crossterm::execute;
+use ratatui::crossterm::terminal::{self, EnterAlternateScreen, LeaveAlternateScreen};
+use ratatui::layout::{Constraint, Layout as Split, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Text};
+use ratatui::widgets::{Block, Borders, Paragraph};
+use ratatui::{Frame, Terminal};
+use std::io::{self, IsTerminal};
+
+use crate::highlight;
+
+/// 커밋 검토 화면 모듈
+/// 터미널 전체를 써서 스테이징된 diff와 생성된 커밋 메시지를 두 영역에 보여 주고 키 하나로 다음 동작을 고름
+/// ratatui와 crossterm으로 그리므로 Windows 콘솔에서도 쓰고, 대화형 터미널이 아니면 기존 프롬프트를 씀
+///
+/// | 키 | 동작 |
+/// |----|------|
+/// | `a`, Enter | 커밋 |
+/// | `e` | 메시지 직접 수정 |
+/// | `r` | 메시지 다시 생성 (온도를 바꿔 가며) |
+/// | `<`, `>` | 이전/다음 후보 메시지 (다시 생성한 메시지는 후보로 남음) |
+/// | `m` | 다른 모델로 다시 생성 |
+/// | Tab | 스크롤할 영역 전환 |
+/// | `j`/`k`, 방향키, Space/`b` | 스크롤 |
+/// | `q`, Esc, Ctrl-C | 취소 |
+///
+/// 커밋 메시지를 만들 수 있는 모델 (`m`을 누를 때마다 다음 모델로)
+pub const COMMIT_MODELS: [&str; 3] = ["local", "openai", "anthropic"];
+
+/// 화면을 그릴 수 있는 최소 크기
+const MIN_ROWS: usize = 12;
+const MIN_COLUMNS: usize = 40;
+
+/// 검토 화면에 보여 줄 내용
+pub struct Review<'a> {
+    pub diff: &'a str,
+    pub message: &'a str,
+    /// 메시지를 만든 모델 (`auto`면 로컬 모델, 실패 시 OpenAI)
+    pub model: &'a str,
+    /// 메시지 아래에 보여 줄 경고 (커밋 규칙 위반 등)
+    pub notice: Option<&'a str>,
+    /// 보여 주는 후보 번호(1부터)와 지금까지 생성한 후보 수
+    pub candidate: (usize, usize),
+}
+
+/// 사용자가 고른 동작
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReviewAction {
+    Accept,
+    Edit,
+    Regenerate,
+    /// 이 모델로 다시 생성
+    ChangeModel(String),
+    /// 이전/다음 후보 메시지
+    Previous,
+    Next,
+    Cancel,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Pane {
+    Diff,
+    Message,
+}
+
+/// 화면 상태 (스크롤 위치, 스크롤할 영역)
+#[derive(Debug, Clone, Copy)]
+struct State {
+    focus: Pane,
+    diff_scroll: usize,
+    message_scroll: usize,
+}
+
+/// 검토 화면을 쓸 수 있는지 (대화형 터미널이고 화면이 충분히 큰 경우)
+pub fn is_available() -> bool {
+    !crate::ai_utils::is_non_interactive()
+        && io::stdout().is_terminal()
+        && std::env::var("TERM").map(|term| term != "dumb").unwrap_or(true)
+        && terminal_size().is_some_and(|(rows, columns)| rows >= MIN_ROWS && columns >= MIN_COLUMNS)
+}
+
+/// 다음에 쓸 모델
+pub fn next_model(current: &str) -> &'static str {
+    let index = COMMIT_MODELS.iter().position(|model| *model == current).unwrap_or(0);
+    COMMIT_MODELS[(index + 1) % COMMIT_MODELS.len()]
+}
+
+/// 터미널 크기 (행, 열, 크기를 알 수 없어 0으로 나오면 `None`)
+pub(crate) fn terminal_size() -> Option<(usize, usize)> {
+    let (columns, rows) = terminal::size().ok()?;
+    (rows > 0 && columns > 0).then_some((rows as usize, columns as usize))
+}
+
+/// 화면을 쓰는 동안 터미널을 키 입력 모드와 대체 화면으로 두고, 끝나면(오류가 나도) 원래대로 되돌림
+struct RawTerminal;
+
+impl RawTerminal {
+    fn enter() -> Result<Self> {
+        terminal::enable_raw_mode()?;
+        let guard = Self;
+        execute!(io::stdout(), EnterAlternateScreen)?;
+        Ok(guard)
+    }
+}
+
+impl Drop for RawTerminal {
+    fn drop(&mut self) {
+        let _ = execute!(io::stdout(), LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+/// 검토 화면을 띄우고 사용자가 고른 동작을 돌려줌
+pub fn run(review: &Review) -> Result<ReviewAction> {
+    let _raw = RawTerminal::enter()?;
+    let mut terminal = Terminal::new(CrosstermBackend::new(io::stdout()))?;
+    let mut state = State { focus: Pane::Diff, diff_scroll: 0, message_scroll: 0 };
+
+    loop {
+        draw(&mut terminal, review, &state)?;
+        // Windows는 키를 뗄 때도 이벤트가 오므로 누를 때만 처리 (크기 변경은 다시 그림)
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        let rows = terminal.size()?.height as usize;
+        if let Some(action) = handle_key(review, &mut state, &Layout::new(review, rows), key) {
+            return Ok(action);
+        }
+    }
+}
+
+fn draw<B: Backend>(terminal: &mut Terminal<B>, review: &Review, state: &State) -> Result<()> {
+    terminal.draw(|frame| render(frame, review, state))?;
+    Ok(())
+}
+
+/// 키 하나 처리 (고른 동작이 있으면 반환, 스크롤과 영역 전환은 상태만 바꿈)
+fn handle_key(review: &Review, state: &mut State, layout: &Layout, key: KeyEvent) -> Option<ReviewAction> {
+    let (lines, height) = match state.focus {
+        Pane::Diff => (layout.diff_lines, layout.diff_height),
+        Pane::Message => (layout.message_lines, layout.message_height),
+    };
+    let scroll = match state.focus {
+        Pane::Diff => &mut state.diff_scroll,
+        Pane::Message => &mut state.message_scroll,
+    };
+    let max_scroll = lines.saturating_sub(height);
+
+    match key.code {
+        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => return Some(ReviewAction::Cancel),
+        KeyCode::Char('a') | KeyCode::Enter => return Some(ReviewAction::Accept),
+        KeyCode::Char('e') => return Some(ReviewAction::Edit),
+        KeyCode::Char('r') => return Some(ReviewAction::Regenerate),
+        KeyCode::Char('m') => return Some(ReviewAction::ChangeModel(next_model(review.model).to_string())),
+        KeyCode::Char('<' | ',') if review.candidate.1 > 1 => return Some(ReviewAction::Previous),
+        KeyCode::Char('>' | '.') if review.candidate.1 > 1 => return Some(ReviewAction::Next),
+        KeyCode::Char('q') | KeyCode::Esc => return Some(ReviewAction::Cancel),
+        KeyCode::Tab => {
+            state.focus = match state.focus {
+                Pane::Diff => Pane::Message,
+                Pane::Message => Pane::Diff,
+            }
+        }
+        KeyCode::Char('j') | KeyCode::Down => *scroll = (*scroll + 1).min(max_scroll),
+        KeyCode::Char('k') | KeyCode::Up => *scroll = scroll.saturating_sub(1),
+        KeyCode::Char(' ' | 'f') | KeyCode::PageDown => *scroll = (*scroll + height).min(max_scroll),
+        KeyCode::Char('b') | KeyCode::PageUp => *scroll = scroll.saturating_sub(height),
+        KeyCode::Char('g') | KeyCode::Home => *scroll = 0,
+        KeyCode::Char('G') | KeyCode::End => *scroll = max_scroll,
+        _ => {}
+    }
+    None
+}
+
+/// 영역 크기
+struct Layout {
+    diff_lines: usize,
+    diff_height: usize,
+    message_lines: usize,
+    message_height: usize,
+}
+
+impl Layout {
+    /// 제목 줄, 영역 제목 두 줄, 키 안내 줄을 뺀 나머지를 나눔 (메시지 영역은 최대 화면의 1/3)
+    fn new(review: &Review, rows: usize) -> Self {
+        let message_lines = message_pane(review).len();
+        let available = rows.saturating_sub(4);
+        let message_height = message_lines.clamp(3, (rows / 3).max(3)).min(available.saturating_sub(3));
+        Self {
+            diff_lines: review.diff.lines().count(),
+            diff_height: available - message_height,
+            message_lines,
+            message_height,
+        }
+    }
+}
+
+/// 메시지 영역 내용 (메시지 다음에 노란색 경고)
+fn message_pane<'a>(review: &Review<'a>) -> Vec<Line<'a>> {
+    let mut lines: Vec<Line> = review.message.lines().map(Line::raw).collect();
+    if let Some(notice) = review.notice {
+        lines.push(Line::default());
+        lines.extend(notice.lines().map(|line| Line::styled(line, Style::new().fg(Color::Yellow))));
+    }
+    lines
+}
+
+/// 탭은 공백 네 칸으로 바꾸고 다른 제어 문자는 지움 (색을 칠하기 전의 diff 줄)
+fn clean(line: &str) -> String {
+    line.replace('\t', "    ").chars().filter(|c| !c.is_control()).collect()
+}
+
+/// diff 영역에 보이는 줄 (보이지 않는 위쪽 줄도 넘겨야 파일별 언어를 알 수 있음)
+fn diff_pane(review: &Review, scroll: usize, height: usize) -> Text<'static> {
+    let color = highlight::color_enabled();
+    let mut highlighter = highlight::DiffHighlighter::default();
+    let mut lines = Vec::with_capacity(height);
+    for (index, line) in review.diff.lines().enumerate().take(scroll + height) {
+        highlighter.track(line);
+        if index < scroll {
+            continue;
+        }
+        let line = clean(line);
+        let painted = color.then(|| highlighter.paint(&line).into_text().ok()).flatten();
+        match painted {
+            Some(text) => lines.extend(text.lines),
+            None => lines.push(Line::raw(line)),
+        }
+    }
+    Text::from(lines)
+}
+
+/// 영역 제목 (스크롤 중인 영역은 반전, 내용이 넘치면 보이는 범위 표시)
+fn pane_block(title: &str, focused: bool, scroll: usize, lines: usize, height: usize) -> Block<'static> {
+    let position = if lines > height { format!(" {}-{}/{}", scroll + 1, (scroll + height).min(lines), lines) } else { String::new() };
+    let style = if focused { Style::new().add_modifier(Modifier::REVERSED) } else { Style::new() };
+    Block::new().borders(Borders::TOP).border_style(style).title(format!("── {}{} ", title, position)).title_style(style)
+}
+
+/// 화면 전체 그리기
+fn render(frame: &mut Frame, review: &Review, state: &State) {
+    let area = frame.area();
+    let layout = Layout::new(review, area.height as usize);
+    let [title_area, diff_area, message_area, keys_area] = Split::vertical([
+        Constraint::Length(1),
+        Constraint::Length(layout.diff_height as u16 + 1),
+        Constraint::Length(layout.message_height as u16 + 1),
+        Constraint::Length(1),
+    ])
+    .areas(area);
+
+    let mut title = tr!("review.title", model = review.model);
+    if review.candidate.1 > 1 {
+        title.push_str(&tr!("review.candidate", index = review.candidate.0, count = review.candidate.1));
+    }
+    frame.render_widget(Line::styled(title, Style::new().add_modifier(Modifier::BOLD)), title_area);
+
+    render_pane(
+        frame,
+        diff_area,
+        diff_pane(review, state.diff_scroll, layout.diff_height),
+        pane_block(&tr!("review.diff", lines = layout.diff_lines), state.focus == Pane::Diff, state.diff_scroll, layout.diff_lines, layout.diff_height),
+        0,
+    );
+    render_pane(
+        frame,
+        message_area,
+        Text::from(message_pane(review)),
+        pane_block(tr!("review.message"), state.focus == Pane::Message, state.message_scroll, layout.message_lines, layout.message_height),
+        state.message_scroll,
+    );
+
+    let keys = tr!("review.keys", model = next_model(review.model));
+    frame.render_widget(Line::styled(keys, Style::new().add_modifier(Modifier::REVERSED)), keys_area);
+}
+
+/// 제목 있는 영역에 내용 그리기 (넘치는 줄은 잘림)
+fn render_pane(frame: &mut Frame, area: Rect, text: Text, block: Block, scroll: usize) {
+    frame.render_widget(Paragraph::new(text).block(block).scroll((scroll as u16, 0)), area);
+}
+
+/// 터미널에서 차지하는 칸 수 (한글, 한자, 전각 문자, 그림 문자는 두 칸)
+fn char_width(c: char) -> usize {
+    match c {
+        '\u{1100}'..='\u{115F}' | '\u{2E80}'..='\u{A4CF}' | '\u{AC00}'..='\u{D7A3}' | '\u{F900}'..='\u{FAFF}' | '\u{FF00}'..='\u{FF60}' | '\u{1F300}'..='\u{1FAFF}' => 2,
+        _ => 1,
+    }
+}
+
+/// 문자열이 차지하는 칸 수
+pub(crate) fn text_width(text: &str) -> usize {
+    text.chars().map(char_width).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::backend::TestBackend;
+
+    fn screen(review: &Review, state: &State, width: u16, height: u16) -> Vec<String> {
+        let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+        draw(&mut terminal, review, state).unwrap();
+        let buffer = terminal.backend().buffer().clone();
+        (0..height)
+            .map(|y| (0..width).map(|x| buffer[(x, y)].symbol()).collect::<String>().trim_end().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_render_layout() {
+        let diff: String = (0..50).map(|i| format!("+line {}\n", i)).collect();
+        let review = Review {
+            diff: &diff,
+            message: "feat(parser): add tuple support\n\nParses (a, b).",
+            model: "auto",
+            notice: Some("[require_scope] add a scope"),
+            candidate: (2, 3),
+        };
+        let layout = Layout::new(&review, 24);
+        assert_eq!(layout.message_height, 5);
+        assert_eq!(layout.diff_height, 15);
+
+        let state = State { focus: Pane::Diff, diff_scroll: 10, message_scroll: 0 };
+        let screen = screen(&review, &state, 20, 24);
+        assert_eq!(screen.len(), 24);
+        assert_eq!(screen[2], "+line 10");
+        assert_eq!(screen[16], "+line 24");
+        assert!(screen[1].starts_with("── Staged changes"));
+        assert_eq!(screen[18], "feat(parser): add tu");
+        assert_eq!(screen[22], "[require_scope] add");
+        assert!(screen[23].starts_with(" [a]ccept"));
+
+        assert_eq!(next_model("auto"), "openai");
+        assert_eq!(next_model("openai"), "anthropic");
+        assert_eq!(next_model("anthropic"), "local");
+        assert_eq!(clean("\tx\x1b"), "    x");
+        assert_eq!(text_width("커밋 메시지"), 11);
+    }
+
+    #[test]
+    fn test_handle_key() {
+        let diff: String = (0..50).map(|i| format!("+line {}\n", i)).collect();
+        let review = Review { diff: &diff, message: "fix: typo", model: "openai", notice: None, candidate: (1, 1) };
+        let layout = Layout::new(&review, 24);
+        let mut state = State { focus: Pane::Diff, diff_scroll: 0, message_scroll: 0 };
+        let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+
+        assert_eq!(handle_key(&review, &mut state, &layout, key(KeyCode::Char('j'))), None);
+        assert_eq!(state.diff_scroll, 1);
+        handle_key(&review, &mut state, &layout, key(KeyCode::End));
+        assert_eq!(state.diff_scroll, 50 - layout.diff_height);
+        handle_key(&review, &mut state, &layout, key(KeyCode::Tab));
+        assert_eq!(state.focus, Pane::Message);
+
+        // 후보가 하나뿐이면 이전/다음 후보 키는 무시
+        assert_eq!(handle_key(&review, &mut state, &layout, key(KeyCode::Char('<'))), None);
+        assert_eq!(handle_key(&review, &mut state, &layout, key(KeyCode::Enter)), Some(ReviewAction::Accept));
+        assert_eq!(
+            handle_key(&review, &mut state, &layout, key(KeyCode::Char('m'))),
+            Some(ReviewAction::ChangeModel("anthropic".to_string()))
+        );
+        let ctrl_c = KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL);
+        assert_eq!(handle_key(&review, &mut state, &layout, ctrl_c), Some(ReviewAction::Cancel));
+    }
+}
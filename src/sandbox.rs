@@ -0,0 +1,417 @@
+use anyhow::{Result, anyhow};
+use std::env;
+use std::ffi::OsStr;
+use std::path::{Component, Path, PathBuf};
+use std::process::{Command, Output, Stdio};
+
+/// 샌드박스 실행 모듈
+/// 에이전트/도구가 실행하는 명령어를 프로젝트 디렉토리에 가두고 환경 변수를 제한
+/// - Linux: bubblewrap(bwrap) 또는 firejail이 있으면 파일 시스템을 읽기 전용으로 두고 프로젝트만 쓰기 가능
+/// - Windows: 작업 개체(Job Object)로 자식 프로세스를 묶어 명령어 종료 시 함께 정리
+/// - 공통: 허용된 환경 변수만 전달하고 작업 디렉토리를 프로젝트 루트로 고정
+///
+/// 자식 프로세스에 전달하는 환경 변수
+const ENV_ALLOWLIST: &[&str] = &[
+    "PATH", "HOME", "USER", "LANG", "LC_ALL", "TERM", "TMPDIR",
+    // Windows
+    "SystemRoot", "SYSTEMROOT", "ComSpec", "PATHEXT", "TEMP", "TMP", "USERPROFILE",
+];
+
+/// 네트워크를 쓰는 서버(npx, uvx 등)에 추가로 전달하는 프록시/인증서 환경 변수
+const NETWORK_ENV: &[&str] = &[
+    "HTTPS_PROXY", "https_proxy", "HTTP_PROXY", "http_proxy", "ALL_PROXY", "all_proxy", "NO_PROXY", "no_proxy",
+    "NODE_EXTRA_CA_CERTS", "SSL_CERT_FILE", "SSL_CERT_DIR", "REQUESTS_CA_BUNDLE", "CURL_CA_BUNDLE",
+];
+
+/// 샌드박스 종류
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SandboxBackend {
+    Bubblewrap, // bwrap 네임스페이스 격리
+    Firejail,   // firejail 격리
+    Process,    // 제한된 환경 변수 + 작업 디렉토리 고정 (+ Windows 작업 개체)
+}
+
+impl SandboxBackend {
+    /// `AI_CLI_SANDBOX` 환경 변수 또는 설치된 도구로 결정
+    /// (auto | bwrap | firejail | process)
+    pub fn detect() -> Result<Self> {
+        let requested = env::var("AI_CLI_SANDBOX").unwrap_or_else(|_| "auto".to_string());
+
+        match requested.to_lowercase().as_str() {
+            "auto" => Ok(Self::auto_detect()),
+            "bwrap" | "bubblewrap" => Self::require("bwrap", SandboxBackend::Bubblewrap),
+            "firejail" => Self::require("firejail", SandboxBackend::Firejail),
+            "process" => Ok(SandboxBackend::Process),
+            other => Err(anyhow!("Unsupported AI_CLI_SANDBOX value: {}. Use 'auto', 'bwrap', 'firejail' or 'process'", other)),
+        }
+    }
+
+    /// 격리 도구가 없으면 `Process`로 대체하되, 파일 시스템 격리가 없다는 것을 한 번 경고
+    /// (경고 없이 쓰려면 `AI_CLI_SANDBOX=process`로 명시)
+    fn auto_detect() -> Self {
+        if cfg!(target_os = "linux") {
+            if find_executable("bwrap").is_some() {
+                return SandboxBackend::Bubblewrap;
+            }
+            if find_executable("firejail").is_some() {
+                return SandboxBackend::Firejail;
+            }
+        }
+
+        static WARNED: std::sync::Once = std::sync::Once::new();
+        WARNED.call_once(|| {
+            println!("⚠️  No sandbox tool (bwrap or firejail) found: commands run without file system isolation");
+            println!("   Install bubblewrap, or set AI_CLI_SANDBOX=process to accept this and hide the warning");
+        });
+        SandboxBackend::Process
+    }
+
+    fn require(program: &str, backend: SandboxBackend) -> Result<Self> {
+        if find_executable(program).is_some() {
+            Ok(backend)
+        } else {
+            Err(anyhow!("Sandbox '{}' was requested but is not installed", program))
+        }
+    }
+}
+
+/// 샌드박스
+#[derive(Debug, Clone)]
+pub struct Sandbox {
+    root: PathBuf,
+    backend: SandboxBackend,
+    /// 명령어 작업 디렉토리 (없으면 루트)
+    working_dir: Option<PathBuf>,
+    /// 루트 밖에서 추가로 쓰기를 허용할 디렉토리 (서버별 캐시 등)
+    writable_dirs: Vec<PathBuf>,
+    /// 프록시/인증서 환경 변수도 전달할지
+    network_env: bool,
+}
+
+impl Sandbox {
+    /// 지정한 디렉토리에 갇힌 샌드박스
+    pub fn new(root: impl Into<PathBuf>, backend: SandboxBackend) -> Self {
+        Self { root: root.into(), backend, working_dir: None, writable_dirs: Vec::new(), network_env: false }
+    }
+
+    /// 명령어를 루트가 아닌 디렉토리에서 실행 (쓰기 가능 범위는 그대로 루트)
+    pub fn with_working_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.working_dir = Some(dir.into());
+        self
+    }
+
+    /// 루트 밖의 디렉토리 하나를 쓰기 가능하게 (bwrap/firejail에서만 의미가 있으며, 디렉토리는 미리 만들어 둬야 함)
+    pub fn with_writable_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.writable_dirs.push(dir.into());
+        self
+    }
+
+    /// 허용 목록에 더해 프록시(`HTTPS_PROXY` 등)와 인증서(`NODE_EXTRA_CA_CERTS` 등) 환경 변수도 전달
+    pub fn with_network_env(mut self) -> Self {
+        self.network_env = true;
+        self
+    }
+
+    /// 현재 프로젝트(Git 루트 또는 현재 디렉토리)용 샌드박스
+    pub fn for_current_project() -> Result<Self> {
+        let current_dir = env::current_dir()?;
+        let root = crate::git_utils::open_repository()
+            .ok()
+            .and_then(|repo| repo.workdir().map(Path::to_path_buf))
+            .unwrap_or(current_dir);
+
+        Ok(Self::new(root.canonicalize()?, SandboxBackend::detect()?))
+    }
+
+    /// 샌드박스 루트 디렉토리
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// 샌드박스 종류
+    pub fn backend(&self) -> &SandboxBackend {
+        &self.backend
+    }
+
+    /// 프로젝트 안의 경로로 변환 (밖을 가리키면 오류)
+    /// 상대 경로는 루트 기준이며, 아직 없는 파일이라도 존재하는 가장 가까운 상위 경로를 심볼릭 링크까지 따라가 확인
+    pub fn resolve(&self, path: &Path) -> Result<PathBuf> {
+        let outside = || anyhow!("Path '{}' is outside the project directory {}", path.display(), self.root.display());
+
+        // 뒤에서부터 아직 없는 구성 요소를 떼어 내고, 그 안의 `..`는 바로 앞 구성 요소와 상쇄
+        let mut existing = self.root.join(path);
+        let mut missing = Vec::new();
+        let mut parents = 0;
+        let mut resolved = loop {
+            if let Ok(real) = existing.canonicalize() {
+                break real;
+            }
+            match existing.components().next_back() {
+                Some(Component::Normal(_)) if parents > 0 => parents -= 1,
+                Some(Component::Normal(name)) => missing.push(name.to_os_string()),
+                Some(Component::ParentDir) => parents += 1,
+                Some(Component::CurDir) => {}
+                _ => return Err(outside()),
+            }
+            if !existing.pop() {
+                return Err(outside());
+            }
+        };
+        for _ in 0..parents {
+            resolved.pop();
+        }
+        resolved.extend(missing.iter().rev());
+
+        if resolved.starts_with(&self.root) {
+            Ok(resolved)
+        } else {
+            Err(outside())
+        }
+    }
+
+    /// 셸 명령어 실행 (`sh -c` / `cmd /C`)
+    pub fn run_shell(&self, command: &str) -> Result<Output> {
+        #[cfg(target_os = "windows")]
+        let mut process = self.command("cmd", &["/C", command]);
+
+        #[cfg(not(target_os = "windows"))]
+        let mut process = self.command("sh", &["-c", command]);
+
+        process.stdin(Stdio::null());
+        run_contained(process)
+    }
+
+    /// 샌드박스 안에서 실행할 명령어 생성
+    /// 프로젝트의 `.git`은 읽기 전용으로 두어, 샌드박스 밖에서 실행될 훅이나 설정을 심지 못하게 함
+    pub fn command<S: AsRef<OsStr>>(&self, program: &str, args: &[S]) -> Command {
+        let working_dir = self.working_dir.as_deref().unwrap_or(&self.root);
+        let git_dir = self.root.join(".git");
+        let mut command = match self.backend {
+            SandboxBackend::Bubblewrap => {
+                let root = self.root.to_string_lossy().to_string();
+                let mut command = Command::new("bwrap");
+                command
+                    .args(["--ro-bind", "/", "/"])
+                    .args(["--bind", &root, &root]);
+                for dir in &self.writable_dirs {
+                    command.arg("--bind").arg(dir).arg(dir);
+                }
+                if git_dir.exists() {
+                    command.arg("--ro-bind").arg(&git_dir).arg(&git_dir);
+                }
+                command
+                    .args(["--dev", "/dev"])
+                    .args(["--proc", "/proc"])
+                    .args(["--tmpfs", "/tmp"])
+                    .args(["--unshare-all", "--share-net", "--die-with-parent"])
+                    .arg("--chdir")
+                    .arg(working_dir)
+                    .arg("--")
+                    .arg(program)
+                    .args(args);
+                command
+            }
+            SandboxBackend::Firejail => {
+                let mut command = Command::new("firejail");
+                command
+                    .args(["--quiet", "--noprofile", "--private-tmp", "--read-only=/"])
+                    .arg(format!("--read-write={}", self.root.display()));
+                for dir in &self.writable_dirs {
+                    command.arg(format!("--read-write={}", dir.display()));
+                }
+                if git_dir.exists() {
+                    command.arg(format!("--read-only={}", git_dir.display()));
+                }
+                command
+                    .arg("--")
+                    .arg(program)
+                    .args(args);
+                command
+            }
+            SandboxBackend::Process => {
+                let mut command = Command::new(program);
+                command.args(args);
+                command
+            }
+        };
+
+        command.current_dir(working_dir).env_clear();
+        let network_env = if self.network_env { NETWORK_ENV } else { &[] };
+        for key in ENV_ALLOWLIST.iter().chain(network_env) {
+            if let Ok(value) = env::var(key) {
+                command.env(key, value);
+            }
+        }
+
+        command
+    }
+}
+
+/// 명령어 실행 후 출력 수집
+/// Windows에서는 작업 개체에 넣어, 명령어가 끝나면 남은 자식 프로세스도 종료
+#[cfg(not(windows))]
+fn run_contained(mut command: Command) -> Result<Output> {
+    Ok(command.output()?)
+}
+
+#[cfg(windows)]
+fn run_contained(mut command: Command) -> Result<Output> {
+    use std::os::windows::io::AsRawHandle;
+    use windows_sys::Win32::Foundation::CloseHandle;
+    use windows_sys::Win32::System::JobObjects::{
+        AssignProcessToJobObject, CreateJobObjectW, JobObjectExtendedLimitInformation,
+        SetInformationJobObject, JOBOBJECT_EXTENDED_LIMIT_INFORMATION, JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE,
+    };
+
+    let child = command
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    // SAFETY: 유효한 핸들만 사용하며, 작업 개체 핸들은 출력 수집 후 닫음
+    let job = unsafe {
+        let job = CreateJobObjectW(std::ptr::null(), std::ptr::null());
+        if !job.is_null() {
+            let mut info: JOBOBJECT_EXTENDED_LIMIT_INFORMATION = std::mem::zeroed();
+            info.BasicLimitInformation.LimitFlags = JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE;
+            SetInformationJobObject(
+                job,
+                JobObjectExtendedLimitInformation,
+                &info as *const _ as *const std::ffi::c_void,
+                std::mem::size_of::<JOBOBJECT_EXTENDED_LIMIT_INFORMATION>() as u32,
+            );
+            AssignProcessToJobObject(job, child.as_raw_handle() as _);
+        }
+        job
+    };
+
+    let output = child.wait_with_output();
+
+    if !job.is_null() {
+        // SAFETY: CreateJobObjectW가 반환한 핸들
+        unsafe { CloseHandle(job) };
+    }
+
+    Ok(output?)
+}
+
+/// PATH에서 실행 파일 찾기
+fn find_executable(program: &str) -> Option<PathBuf> {
+    let paths = env::var_os("PATH")?;
+    env::split_paths(&paths)
+        .map(|dir| dir.join(program))
+        .find(|candidate| candidate.is_file())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_process_sandbox_limits_environment() {
+        let temp_dir = TempDir::new().unwrap();
+        let sandbox = Sandbox::new(temp_dir.path().canonicalize().unwrap(), SandboxBackend::Process);
+
+        let command = sandbox.command("git", &["status"]);
+        assert_eq!(command.get_current_dir(), Some(sandbox.root()));
+
+        let envs: Vec<_> = command.get_envs().map(|(key, _)| key.to_string_lossy().to_string()).collect();
+        assert!(envs.iter().all(|key| ENV_ALLOWLIST.contains(&key.as_str())));
+    }
+
+    #[test]
+    fn test_isolated_backends_keep_git_dir_read_only() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().canonicalize().unwrap();
+        std::fs::create_dir(root.join(".git")).unwrap();
+        let git_dir = root.join(".git").to_string_lossy().to_string();
+
+        let bwrap = Sandbox::new(root.clone(), SandboxBackend::Bubblewrap).command("git", &["status"]);
+        let args: Vec<_> = bwrap.get_args().map(|arg| arg.to_string_lossy().to_string()).collect();
+        let bind = args.iter().position(|arg| arg == "--bind").unwrap();
+        let ro_git = args.windows(3).position(|window| window == ["--ro-bind", git_dir.as_str(), git_dir.as_str()]).unwrap();
+        assert!(ro_git > bind);
+
+        let firejail = Sandbox::new(root, SandboxBackend::Firejail).command("git", &["status"]);
+        let args: Vec<_> = firejail.get_args().map(|arg| arg.to_string_lossy().to_string()).collect();
+        assert!(args.contains(&format!("--read-only={}", git_dir)));
+    }
+
+    #[test]
+    fn test_writable_dirs_and_network_env() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache = TempDir::new().unwrap();
+        let root = temp_dir.path().canonicalize().unwrap();
+        let cache_dir = cache.path().to_string_lossy().to_string();
+
+        let bwrap = Sandbox::new(root.clone(), SandboxBackend::Bubblewrap)
+            .with_writable_dir(cache.path())
+            .command("npx", &["-y", "server"]);
+        let args: Vec<_> = bwrap.get_args().map(|arg| arg.to_string_lossy().to_string()).collect();
+        assert!(args.windows(3).any(|window| window == ["--bind", cache_dir.as_str(), cache_dir.as_str()]));
+
+        let firejail = Sandbox::new(root.clone(), SandboxBackend::Firejail)
+            .with_writable_dir(cache.path())
+            .command("npx", &["-y", "server"]);
+        let args: Vec<_> = firejail.get_args().map(|arg| arg.to_string_lossy().to_string()).collect();
+        assert!(args.contains(&format!("--read-write={}", cache_dir)));
+
+        // 프록시/인증서 변수는 요청한 경우에만 전달
+        let allowed = |sandbox: Sandbox| -> Vec<String> {
+            sandbox.command("npx", &["server"]).get_envs().map(|(key, _)| key.to_string_lossy().to_string()).collect()
+        };
+        std::env::set_var("SSL_CERT_DIR", "/etc/ssl/certs");
+        assert!(!allowed(Sandbox::new(root.clone(), SandboxBackend::Process)).contains(&"SSL_CERT_DIR".to_string()));
+        assert!(allowed(Sandbox::new(root, SandboxBackend::Process).with_network_env()).contains(&"SSL_CERT_DIR".to_string()));
+    }
+
+    #[test]
+    fn test_working_dir_overrides_root() {
+        let temp_dir = TempDir::new().unwrap();
+        let tools = TempDir::new().unwrap();
+        let sandbox = Sandbox::new(temp_dir.path().canonicalize().unwrap(), SandboxBackend::Process)
+            .with_working_dir(tools.path());
+
+        let command = sandbox.command("node", &["server.js"]);
+        assert_eq!(command.get_current_dir(), Some(tools.path()));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_shell_in_project_root() {
+        let temp_dir = TempDir::new().unwrap();
+        let sandbox = Sandbox::new(temp_dir.path().canonicalize().unwrap(), SandboxBackend::Process);
+
+        let output = sandbox.run_shell("pwd").unwrap();
+        assert!(output.status.success());
+        assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), sandbox.root().to_string_lossy());
+    }
+
+    #[test]
+    fn test_resolve_rejects_paths_outside_root() {
+        let temp_dir = TempDir::new().unwrap();
+        let sandbox = Sandbox::new(temp_dir.path().canonicalize().unwrap(), SandboxBackend::Process);
+
+        assert!(sandbox.resolve(Path::new("src/main.rs")).is_ok());
+        assert!(sandbox.resolve(Path::new("src/../Cargo.toml")).is_ok());
+        assert!(sandbox.resolve(Path::new("../outside.txt")).is_err());
+        assert!(sandbox.resolve(Path::new("/etc/passwd")).is_err());
+        assert!(sandbox.resolve(Path::new("new/dir/../../../outside.txt")).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_resolve_follows_symlinks_for_new_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let outside = TempDir::new().unwrap();
+        let root = temp_dir.path().canonicalize().unwrap();
+        std::os::unix::fs::symlink(outside.path(), root.join("link-to-outside")).unwrap();
+        std::fs::create_dir(root.join("src")).unwrap();
+        let sandbox = Sandbox::new(root.clone(), SandboxBackend::Process);
+
+        assert!(sandbox.resolve(Path::new("link-to-outside/newfile")).is_err());
+        assert!(sandbox.resolve(Path::new("link-to-outside/new/dir/file")).is_err());
+        assert_eq!(sandbox.resolve(Path::new("src/new/file.rs")).unwrap(), root.join("src/new/file.rs"));
+    }
+}
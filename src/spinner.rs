@@ -0,0 +1,157 @@
+use indicatif::{ProgressBar, ProgressDrawTarget, ProgressState, ProgressStyle};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+/// 진행 표시 모듈
+/// AI 호출처럼 오래 걸리는 작업 동안 표준 에러에 현재 단계와 경과 시간을 `indicatif` 스피너로 표시
+/// (`⠋ calling gpt-4o-mini… 3.4s`). 작업 안에서 다른 작업을 시작하면 안쪽 단계를 보여 주고, 끝나면 바깥 단계로 돌아감
+/// `--quiet`(`AI_CLI_QUIET`)이면 숨긴 출력 대상을 쓰고, 표준 에러가 터미널이 아니면 indicatif가 그리지 않음
+///
+/// 스피너 모양 (마지막은 끝난 뒤 모양)
+const TICKS: &str = "⠋⠙⠹⠸⠼⠴⠦⠧⠇⠏ ";
+const TICK: Duration = Duration::from_millis(80);
+
+/// 진행 중인 단계 (마지막이 화면에 보이는 단계)
+struct Step {
+    id: u64,
+    label: String,
+    started: Instant,
+}
+
+struct Shared {
+    steps: Vec<Step>,
+    next_id: u64,
+    /// 맨 위 단계를 그리는 스피너 (단계가 바뀌면 그 단계의 경과 시간으로 다시 만듦)
+    bar: Option<ProgressBar>,
+}
+
+static SHARED: Mutex<Shared> = Mutex::new(Shared { steps: Vec::new(), next_id: 0, bar: None });
+
+/// 스피너를 멈춘 화면 수 (체크리스트처럼 여러 줄을 그리는 동안에는 새 단계를 표시하지 않음)
+static SUSPENDED: AtomicUsize = AtomicUsize::new(0);
+
+/// 진행 표시를 끌지 (`--quiet` 또는 `AI_CLI_QUIET`)
+pub fn is_quiet() -> bool {
+    std::env::var_os("AI_CLI_QUIET").is_some_and(|value| !value.is_empty() && value != "0")
+}
+
+/// 표시 중인 단계 (버리면 사라짐)
+pub struct Spinner {
+    id: Option<u64>,
+}
+
+impl Spinner {
+    /// 단계 표시 시작
+    pub fn start(label: impl Into<String>) -> Self {
+        if SUSPENDED.load(Ordering::SeqCst) > 0 {
+            return Self { id: None };
+        }
+        let mut shared = SHARED.lock().unwrap_or_else(|e| e.into_inner());
+        let id = shared.next_id;
+        shared.next_id += 1;
+        shared.steps.push(Step { id, label: label.into(), started: Instant::now() });
+        shared.show_top();
+        Self { id: Some(id) }
+    }
+}
+
+impl Drop for Spinner {
+    fn drop(&mut self) {
+        let Some(id) = self.id else {
+            return;
+        };
+        let mut shared = SHARED.lock().unwrap_or_else(|e| e.into_inner());
+        let was_top = shared.steps.last().is_some_and(|step| step.id == id);
+        shared.steps.retain(|step| step.id != id);
+        if was_top {
+            shared.show_top();
+        }
+    }
+}
+
+impl Shared {
+    /// 맨 위 단계로 스피너를 바꿈 (남은 단계가 없으면 줄을 지움)
+    fn show_top(&mut self) {
+        if let Some(bar) = self.bar.take() {
+            bar.finish_and_clear();
+        }
+        let Some(step) = self.steps.last() else {
+            return;
+        };
+        let target = if is_quiet() { ProgressDrawTarget::hidden() } else { ProgressDrawTarget::stderr() };
+        let bar = ProgressBar::with_draw_target(None, target)
+            .with_style(style())
+            .with_message(step.label.clone())
+            .with_elapsed(step.started.elapsed());
+        bar.enable_steady_tick(TICK);
+        self.bar = Some(bar);
+    }
+}
+
+/// 진행 줄 모양 (`⠋ calling gpt-4o-mini… 3.4s`)
+fn style() -> ProgressStyle {
+    ProgressStyle::with_template("{spinner} {msg} {seconds}")
+        .expect("valid spinner template")
+        .tick_chars(TICKS)
+        .with_key("seconds", |state: &ProgressState, out: &mut dyn std::fmt::Write| {
+            let _ = write!(out, "{}", format_seconds(state.elapsed()));
+        })
+}
+
+/// 경과 시간 (`3.4s`)
+fn format_seconds(elapsed: Duration) -> String {
+    format!("{:.1}s", elapsed.as_secs_f64())
+}
+
+/// 스피너를 멈춘 동안 (버리면 다시 표시)
+pub struct Suspended(());
+
+impl Drop for Suspended {
+    fn drop(&mut self) {
+        SUSPENDED.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// 새 단계 표시 멈춤 (다른 진행 표시가 같은 줄을 쓰는 동안)
+pub fn suspend() -> Suspended {
+    SUSPENDED.fetch_add(1, Ordering::SeqCst);
+    Suspended(())
+}
+
+/// 작업이 끝날 때까지 단계 표시
+pub async fn wait<F: std::future::Future>(label: impl Into<String>, future: F) -> F::Output {
+    let _spinner = Spinner::start(label);
+    future.await
+}
+
+/// 큰 수를 짧게 (`1234` → `1.2k`)
+pub fn format_count(count: usize) -> String {
+    match count {
+        0..=999 => count.to_string(),
+        1_000..=999_999 => format!("{:.1}k", count as f64 / 1_000.0),
+        _ => format!("{:.1}M", count as f64 / 1_000_000.0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nested_steps() {
+        let shown = || SHARED.lock().unwrap().bar.as_ref().map(|bar| bar.message());
+        let outer = Spinner::start("reading diff…");
+        let inner = Spinner::start("calling gpt-4o-mini…");
+        assert_eq!(shown().as_deref(), Some("calling gpt-4o-mini…"));
+        drop(inner);
+        assert_eq!(shown().as_deref(), Some("reading diff…"));
+        drop(outer);
+        assert_eq!(shown(), None);
+
+        assert_eq!(format_seconds(Duration::from_millis(3420)), "3.4s");
+        assert_eq!(format_count(42), "42");
+        assert_eq!(format_count(1234), "1.2k");
+        assert_eq!(format_count(2_500_000), "2.5M");
+    }
+}
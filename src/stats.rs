@@ -0,0 +1,302 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::config;
+
+/// 로컬 사용 통계 모듈
+/// `stats = "on"` 설정(`AI_CLI_STATS=on`)을 켠 경우에만 실행한 명령 수, 생성한 커밋 메시지를 그대로 썼는지/고쳐 썼는지/버렸는지,
+/// 백엔드별 응답 시간을 `~/.ai-cli/stats.json`에 숫자로만 남김 (프롬프트, 메시지, 경로, 리포지토리 이름은 남기지 않고 어디로도 보내지 않음)
+/// `ai-cli stats`는 요약을, `--dashboard`는 막대 그래프로 보여 줘 AI 메시지가 실제로 쓰이는지 판단할 수 있게 함
+///
+/// `stats` 설정 값 (기본 off)
+pub const STATS_MODES: [&str; 2] = ["on", "off"];
+
+/// 대시보드 막대 길이
+const BAR_WIDTH: usize = 20;
+
+/// 생성한 커밋 메시지를 어떻게 했는지
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageOutcome {
+    /// 그대로 커밋
+    Accepted,
+    /// 고쳐서 커밋
+    Edited,
+    /// 커밋하지 않고 취소
+    Rejected,
+    /// 다시 생성하거나 다른 모델로 생성
+    Regenerated,
+}
+
+/// 커밋 메시지 결과 수
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MessageCounts {
+    pub accepted: u64,
+    pub edited: u64,
+    pub rejected: u64,
+    pub regenerated: u64,
+}
+
+impl MessageCounts {
+    /// 결정을 내린 메시지 수 (다시 생성은 제외)
+    pub fn decided(&self) -> u64 {
+        self.accepted + self.edited + self.rejected
+    }
+
+    /// 그대로 커밋한 비율 (0.0~1.0, 결정한 메시지가 없으면 `None`)
+    pub fn acceptance_rate(&self) -> Option<f64> {
+        (self.decided() > 0).then(|| self.accepted as f64 / self.decided() as f64)
+    }
+}
+
+/// 백엔드 응답 시간
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Latency {
+    pub requests: u64,
+    pub total_ms: u64,
+    pub max_ms: u64,
+}
+
+impl Latency {
+    pub fn average_ms(&self) -> u64 {
+        self.total_ms.checked_div(self.requests).unwrap_or(0)
+    }
+}
+
+/// 저장하는 통계
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Stats {
+    /// 처음 기록한 날짜
+    pub since: Option<String>,
+    pub commands: BTreeMap<String, u64>,
+    pub messages: MessageCounts,
+    pub latency: BTreeMap<String, Latency>,
+}
+
+/// 통계 파일 (~/.ai-cli/stats.json)
+pub fn stats_path() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(".ai-cli").join("stats.json"))
+}
+
+/// 통계를 남기는지 (`stats = "on"`일 때만)
+pub fn is_enabled() -> bool {
+    config::setting("stats").trim() == "on"
+}
+
+/// 저장된 통계 (없거나 읽을 수 없으면 빈 통계)
+pub fn load() -> Stats {
+    stats_path()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+/// 통계 저장 (실패해도 명령은 계속 진행)
+fn save(stats: &Stats) {
+    let Some(path) = stats_path() else {
+        return;
+    };
+    let result = path
+        .parent()
+        .map_or(Ok(()), fs::create_dir_all)
+        .and_then(|()| serde_json::to_vec_pretty(stats).map_err(io::Error::from))
+        .and_then(|json| fs::write(&path, json));
+    if let Err(e) = result {
+        tracing::debug!("Could not save usage stats to {}: {}", path.display(), e);
+    }
+}
+
+/// 통계를 켠 경우에만 읽고 고쳐 저장
+fn update(change: impl FnOnce(&mut Stats)) {
+    if !is_enabled() {
+        return;
+    }
+    let mut stats = load();
+    if stats.since.is_none() {
+        stats.since = Some(chrono::Local::now().format("%Y-%m-%d").to_string());
+    }
+    change(&mut stats);
+    save(&stats);
+}
+
+/// 실행한 명령 기록
+pub fn command(name: &str) {
+    update(|stats| *stats.commands.entry(name.to_string()).or_default() += 1);
+}
+
+/// 생성한 커밋 메시지의 결과 기록
+pub fn message(outcome: MessageOutcome) {
+    update(|stats| {
+        let counts = &mut stats.messages;
+        match outcome {
+            MessageOutcome::Accepted => counts.accepted += 1,
+            MessageOutcome::Edited => counts.edited += 1,
+            MessageOutcome::Rejected => counts.rejected += 1,
+            MessageOutcome::Regenerated => counts.regenerated += 1,
+        }
+    });
+}
+
+/// 백엔드 응답 시간 기록
+pub fn latency(backend: &str, elapsed: Duration) {
+    update(|stats| {
+        let latency = stats.latency.entry(backend.to_string()).or_default();
+        let ms = elapsed.as_millis() as u64;
+        latency.requests += 1;
+        latency.total_ms += ms;
+        latency.max_ms = latency.max_ms.max(ms);
+    });
+}
+
+/// 통계 파일 삭제 (삭제했으면 `true`)
+pub fn reset() -> Result<bool> {
+    match stats_path() {
+        Some(path) if path.exists() => {
+            fs::remove_file(path)?;
+            Ok(true)
+        }
+        _ => Ok(false),
+    }
+}
+
+/// 요청 URL의 백엔드 이름 (OpenAI, Anthropic 외에는 Ollama)
+pub fn backend_for_host(host: &str) -> &'static str {
+    match host {
+        "api.openai.com" => "OpenAI",
+        "api.anthropic.com" => "Anthropic",
+        _ => "Ollama",
+    }
+}
+
+/// 한 줄씩 보는 요약
+pub fn summary(stats: &Stats) -> Vec<String> {
+    let mut lines = vec![format!("📊 Usage since {}", stats.since.as_deref().unwrap_or("-"))];
+    let messages = &stats.messages;
+    lines.push(match messages.acceptance_rate() {
+        Some(rate) => format!(
+            "  Commit messages: {} accepted, {} edited, {} rejected ({} accepted as is), {} regeneration(s)",
+            messages.accepted,
+            messages.edited,
+            messages.rejected,
+            percent(rate),
+            messages.regenerated
+        ),
+        None => "  Commit messages: none reviewed yet".to_string(),
+    });
+    let commands: Vec<String> = by_count(&stats.commands).iter().map(|(name, count)| format!("{} {}", name, count)).collect();
+    lines.push(format!("  Commands: {}", if commands.is_empty() { "-".to_string() } else { commands.join(", ") }));
+    let latency: Vec<String> = stats
+        .latency
+        .iter()
+        .map(|(backend, latency)| format!("{} {} avg ({} request(s))", backend, seconds(latency.average_ms()), latency.requests))
+        .collect();
+    lines.push(format!("  Latency: {}", if latency.is_empty() { "-".to_string() } else { latency.join(", ") }));
+    lines
+}
+
+/// 막대 그래프 화면
+pub fn dashboard(stats: &Stats) -> Vec<String> {
+    let rule = "═".repeat(BAR_WIDTH + 40);
+    let mut lines = vec![format!("📊 ai-cli usage since {} (kept on this machine only)", stats.since.as_deref().unwrap_or("-")), rule.clone()];
+
+    let messages = &stats.messages;
+    lines.push(format!("Generated commit messages ({} reviewed)", messages.decided()));
+    if messages.decided() == 0 {
+        lines.push("  (none yet)".to_string());
+    } else {
+        for (label, count) in [("accepted as is", messages.accepted), ("edited", messages.edited), ("rejected", messages.rejected)] {
+            let rate = count as f64 / messages.decided() as f64;
+            lines.push(format!("  {:<15} {} {:>4} ({})", label, bar(rate), percent(rate), count));
+        }
+        lines.push(format!("  regenerated {} time(s) before deciding", messages.regenerated));
+    }
+
+    let commands = by_count(&stats.commands);
+    lines.push(String::new());
+    lines.push(format!("Commands ({} run)", commands.iter().map(|(_, count)| count).sum::<u64>()));
+    let most = commands.first().map_or(1, |(_, count)| *count).max(1);
+    if commands.is_empty() {
+        lines.push("  (none yet)".to_string());
+    }
+    for (name, count) in &commands {
+        lines.push(format!("  {:<15} {} {}", name, bar(*count as f64 / most as f64), count));
+    }
+
+    lines.push(String::new());
+    lines.push("Average latency per backend".to_string());
+    let slowest = stats.latency.values().map(Latency::average_ms).max().unwrap_or(0).max(1);
+    if stats.latency.is_empty() {
+        lines.push("  (none yet)".to_string());
+    }
+    for (backend, latency) in &stats.latency {
+        lines.push(format!(
+            "  {:<15} {} {} avg · max {} · {} request(s)",
+            backend,
+            bar(latency.average_ms() as f64 / slowest as f64),
+            seconds(latency.average_ms()),
+            seconds(latency.max_ms),
+            latency.requests
+        ));
+    }
+    lines.push(rule);
+    lines
+}
+
+/// 많이 쓴 순서로 정렬한 명령
+fn by_count(commands: &BTreeMap<String, u64>) -> Vec<(&str, u64)> {
+    let mut commands: Vec<(&str, u64)> = commands.iter().map(|(name, count)| (name.as_str(), *count)).collect();
+    commands.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+    commands
+}
+
+/// 비율 막대 (`████░░░░`)
+fn bar(ratio: f64) -> String {
+    let filled = (ratio.clamp(0.0, 1.0) * BAR_WIDTH as f64).round() as usize;
+    format!("{}{}", "█".repeat(filled), "░".repeat(BAR_WIDTH - filled))
+}
+
+fn percent(ratio: f64) -> String {
+    format!("{:.0}%", ratio * 100.0)
+}
+
+fn seconds(ms: u64) -> String {
+    format!("{:.1}s", ms as f64 / 1000.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summary_and_dashboard() {
+        let mut stats = Stats { since: Some("2026-10-01".to_string()), ..Stats::default() };
+        assert_eq!(stats.messages.acceptance_rate(), None);
+        assert_eq!(summary(&stats)[1], "  Commit messages: none reviewed yet");
+
+        stats.messages = MessageCounts { accepted: 3, edited: 1, rejected: 0, regenerated: 2 };
+        stats.commands.insert("explain".to_string(), 2);
+        stats.commands.insert("commit".to_string(), 4);
+        stats.latency.insert("Ollama".to_string(), Latency { requests: 2, total_ms: 2500, max_ms: 1800 });
+        assert_eq!(stats.messages.acceptance_rate(), Some(0.75));
+
+        let lines = summary(&stats);
+        assert_eq!(lines[1], "  Commit messages: 3 accepted, 1 edited, 0 rejected (75% accepted as is), 2 regeneration(s)");
+        assert_eq!(lines[2], "  Commands: commit 4, explain 2");
+        assert_eq!(lines[3], "  Latency: Ollama 1.2s avg (2 request(s))");
+
+        let lines = dashboard(&stats);
+        assert!(lines.contains(&"  accepted as is  ███████████████░░░░░  75% (3)".to_string()));
+        assert!(lines.contains(&"  explain         ██████████░░░░░░░░░░ 2".to_string()));
+        assert!(lines.contains(&"  Ollama          ████████████████████ 1.2s avg · max 1.8s · 2 request(s)".to_string()));
+
+        assert_eq!(backend_for_host("api.anthropic.com"), "Anthropic");
+        assert_eq!(backend_for_host("localhost"), "Ollama");
+    }
+}
@@ -0,0 +1,258 @@
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+use tree_sitter::{Language, Node, Parser};
+
+/// 심볼 추출 모듈
+/// tree-sitter로 변경된 파일을 파싱해 각 hunk를 감싸는 함수/구조체/클래스 이름을 찾음
+/// (지원 언어: Rust, Python, JavaScript, TypeScript, Go)
+///
+/// 변경된 코드를 감싸는 심볼
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct AffectedSymbol {
+    pub path: String,
+    pub kind: String,
+    pub name: String,
+}
+
+/// 변경된 코드를 감싸는 정의와 그 위치 (1부터 시작, 끝 포함)
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct EnclosingDefinition {
+    pub symbol: AffectedSymbol,
+    pub start_line: usize,
+    pub end_line: usize,
+}
+
+/// 언어별 정의 노드 종류와 표시 이름
+struct Grammar {
+    language: Language,
+    definitions: &'static [(&'static str, &'static str)],
+    separator: &'static str,
+}
+
+const RUST_DEFINITIONS: &[(&str, &str)] = &[
+    ("function_item", "function"),
+    ("struct_item", "struct"),
+    ("enum_item", "enum"),
+    ("union_item", "union"),
+    ("trait_item", "trait"),
+    ("impl_item", "impl"),
+    ("mod_item", "module"),
+    ("const_item", "const"),
+    ("static_item", "static"),
+    ("type_item", "type"),
+    ("macro_definition", "macro"),
+];
+
+const PYTHON_DEFINITIONS: &[(&str, &str)] = &[
+    ("function_definition", "function"),
+    ("class_definition", "class"),
+];
+
+const JAVASCRIPT_DEFINITIONS: &[(&str, &str)] = &[
+    ("function_declaration", "function"),
+    ("generator_function_declaration", "function"),
+    ("class_declaration", "class"),
+    ("abstract_class_declaration", "class"),
+    ("method_definition", "method"),
+    ("variable_declarator", "function"),
+    ("interface_declaration", "interface"),
+    ("type_alias_declaration", "type"),
+    ("enum_declaration", "enum"),
+    ("internal_module", "namespace"),
+];
+
+const GO_DEFINITIONS: &[(&str, &str)] = &[
+    ("function_declaration", "function"),
+    ("method_declaration", "method"),
+    ("type_spec", "type"),
+];
+
+/// 파일 확장자로 문법 선택
+fn grammar_for(path: &Path) -> Option<Grammar> {
+    let extension = path.extension()?.to_str()?.to_lowercase();
+    let (language, definitions, separator): (Language, _, _) = match extension.as_str() {
+        "rs" => (tree_sitter_rust::LANGUAGE.into(), RUST_DEFINITIONS, "::"),
+        "py" | "pyi" => (tree_sitter_python::LANGUAGE.into(), PYTHON_DEFINITIONS, "."),
+        "js" | "jsx" | "mjs" | "cjs" => (tree_sitter_javascript::LANGUAGE.into(), JAVASCRIPT_DEFINITIONS, "."),
+        "ts" | "mts" | "cts" => (tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into(), JAVASCRIPT_DEFINITIONS, "."),
+        "tsx" => (tree_sitter_typescript::LANGUAGE_TSX.into(), JAVASCRIPT_DEFINITIONS, "."),
+        "go" => (tree_sitter_go::LANGUAGE.into(), GO_DEFINITIONS, "."),
+        _ => return None,
+    };
+    Some(Grammar { language, definitions, separator })
+}
+
+/// 변경된 줄 범위(1부터 시작, 끝 포함)를 감싸는 가장 안쪽 정의들 찾기
+/// 지원하지 않는 언어이거나 파싱에 실패하면 빈 목록
+pub fn symbols_in_ranges(path: &str, source: &str, ranges: &[(usize, usize)]) -> Vec<AffectedSymbol> {
+    let mut symbols: Vec<AffectedSymbol> = enclosing_definitions(path, source, ranges)
+        .into_iter()
+        .map(|definition| definition.symbol)
+        .collect();
+
+    symbols.sort();
+    symbols.dedup();
+    symbols
+}
+
+/// 변경된 줄 범위를 감싸는 가장 안쪽 정의와 그 줄 범위 (시작 줄 순)
+pub fn enclosing_definitions(path: &str, source: &str, ranges: &[(usize, usize)]) -> Vec<EnclosingDefinition> {
+    let Some(grammar) = grammar_for(Path::new(path)) else {
+        return Vec::new();
+    };
+
+    let mut parser = Parser::new();
+    if parser.set_language(&grammar.language).is_err() {
+        return Vec::new();
+    }
+    let Some(tree) = parser.parse(source, None) else {
+        return Vec::new();
+    };
+
+    let mut definitions = Vec::new();
+    for &(start, end) in ranges {
+        // tree-sitter 행은 0부터 시작
+        let range = (start.saturating_sub(1), end.max(start).saturating_sub(1));
+        collect_definitions(tree.root_node(), range, source, &grammar, &mut Vec::new(), path, &mut definitions);
+    }
+
+    definitions.sort_by_key(|definition| (definition.start_line, definition.end_line));
+    definitions.dedup();
+    definitions
+}
+
+/// 범위와 겹치는 정의 노드를 재귀적으로 찾아 가장 안쪽 정의만 기록
+/// 반환값은 이 노드 아래에서 정의를 하나라도 기록했는지 여부
+fn collect_definitions(
+    node: Node,
+    range: (usize, usize),
+    source: &str,
+    grammar: &Grammar,
+    parents: &mut Vec<String>,
+    path: &str,
+    definitions: &mut Vec<EnclosingDefinition>,
+) -> bool {
+    if node.end_position().row < range.0 || node.start_position().row > range.1 {
+        return false;
+    }
+
+    let definition = definition_of(node, source, grammar);
+    if let Some((_, name)) = &definition {
+        parents.push(name.clone());
+    }
+
+    let mut cursor = node.walk();
+    let mut found = false;
+    for child in node.children(&mut cursor) {
+        found |= collect_definitions(child, range, source, grammar, parents, path, definitions);
+    }
+
+    if let Some((kind, _)) = definition {
+        if !found {
+            let kind = if kind == "function" && parents.len() > 1 { "method" } else { kind };
+            definitions.push(EnclosingDefinition {
+                symbol: AffectedSymbol {
+                    path: path.to_string(),
+                    kind: kind.to_string(),
+                    name: parents.join(grammar.separator),
+                },
+                start_line: node.start_position().row + 1,
+                end_line: node.end_position().row + 1,
+            });
+        }
+        parents.pop();
+        return true;
+    }
+
+    found
+}
+
+/// 정의 노드면 (종류, 이름) 반환
+fn definition_of(node: Node, source: &str, grammar: &Grammar) -> Option<(&'static str, String)> {
+    let (_, kind) = grammar.definitions.iter().find(|(node_kind, _)| *node_kind == node.kind())?;
+
+    // 변수 선언은 함수 표현식을 담을 때만 정의로 취급 (`const f = () => {}`)
+    if node.kind() == "variable_declarator" {
+        let value = node.child_by_field_name("value")?;
+        if !matches!(value.kind(), "arrow_function" | "function_expression" | "function") {
+            return None;
+        }
+    }
+
+    // Rust impl은 대상 타입 이름 사용
+    let name_node = if node.kind() == "impl_item" {
+        node.child_by_field_name("type")?
+    } else {
+        node.child_by_field_name("name")?
+    };
+    let name = name_node.utf8_text(source.as_bytes()).ok()?.to_string();
+
+    Some((kind, name))
+}
+
+/// 프롬프트에 넣을 변경 심볼 요약 생성
+pub fn format_symbol_summary(symbols: &[AffectedSymbol]) -> Option<String> {
+    if symbols.is_empty() {
+        return None;
+    }
+
+    let mut by_path: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+    for symbol in symbols {
+        by_path.entry(&symbol.path).or_default().push(format!("{} {}", symbol.kind, symbol.name));
+    }
+
+    let mut summary = String::from("Symbols affected:\n");
+    for (path, names) in by_path {
+        summary.push_str(&format!("- {}: {}\n", path, names.join(", ")));
+    }
+    summary.push_str("Use these names to choose the scope and describe the change precisely.\n");
+
+    Some(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rust_symbols() {
+        let source = "struct Parser {\n    pos: usize,\n}\n\nimpl Parser {\n    fn next(&mut self) {\n        self.pos += 1;\n    }\n\n    fn peek(&self) {}\n}\n\nfn main() {}\n";
+
+        let symbols = symbols_in_ranges("src/parser.rs", source, &[(7, 7), (2, 2)]);
+        let names: Vec<_> = symbols.iter().map(|s| (s.kind.as_str(), s.name.as_str())).collect();
+        assert_eq!(names, vec![("method", "Parser::next"), ("struct", "Parser")]);
+
+        // 여러 함수에 걸친 hunk는 각 함수를 모두 기록
+        let symbols = symbols_in_ranges("src/parser.rs", source, &[(7, 13)]);
+        let names: Vec<_> = symbols.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["main", "Parser::next", "Parser::peek"]);
+    }
+
+    #[test]
+    fn test_enclosing_definitions() {
+        let source = "struct Parser {\n    pos: usize,\n}\n\nimpl Parser {\n    fn next(&mut self) {\n        self.pos += 1;\n    }\n}\n";
+
+        let definitions = enclosing_definitions("src/parser.rs", source, &[(7, 7)]);
+        assert_eq!(definitions.len(), 1);
+        assert_eq!(definitions[0].symbol.name, "Parser::next");
+        assert_eq!((definitions[0].start_line, definitions[0].end_line), (6, 8));
+    }
+
+    #[test]
+    fn test_python_and_unsupported() {
+        let source = "class Cart:\n    def total(self):\n        return 0\n";
+        let symbols = symbols_in_ranges("cart.py", source, &[(3, 3)]);
+        assert_eq!(symbols, vec![AffectedSymbol { path: "cart.py".to_string(), kind: "method".to_string(), name: "Cart.total".to_string() }]);
+
+        assert!(symbols_in_ranges("notes.txt", "hello", &[(1, 1)]).is_empty());
+    }
+
+    #[test]
+    fn test_format_symbol_summary() {
+        let symbols = vec![AffectedSymbol { path: "a.rs".to_string(), kind: "function".to_string(), name: "run".to_string() }];
+        let summary = format_symbol_summary(&symbols).unwrap();
+        assert!(summary.contains("- a.rs: function run"));
+        assert!(format_symbol_summary(&[]).is_none());
+    }
+}
@@ -0,0 +1,172 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::fs;
+use std::io::{self, IsTerminal};
+use std::path::PathBuf;
+
+use crate::cli::{Cli, Commands, ConfigAction};
+use crate::config;
+use crate::i18n;
+use crate::output;
+use crate::spinner;
+
+/// 사용 팁 모듈
+/// 사용한 기능과 이미 보여 준 팁을 `~/.ai-cli/tips.json`에 남기고, 명령이 끝난 뒤 아직 쓰지 않은 관련 기능의 팁을 한 줄 보여 줌
+/// 팁은 한 번 실행에 하나, 같은 팁은 한 번만 보여 주며 기능을 이미 써 봤으면 보여 주지 않음
+/// `--no-tips`, `tips = "off"` 설정(`AI_CLI_TIPS=off`), 터미널이 아닌 출력, `--json`, `--quiet`에서는 보여 주지 않음 (사용 기록은 계속 남김)
+///
+/// `tips` 설정 값
+pub const TIP_MODES: [&str; 2] = ["on", "off"];
+
+/// 팁 (보여 줄 명령, 써 봤으면 팁이 필요 없는 기능, 메시지 ID), 앞에 있는 팁부터 보여 줌
+const TIPS: &[(&str, &str, &str)] = &[
+    ("commit", "commit.regenerate", "tip.commit.regenerate"),
+    ("commit", "index", "tip.index"),
+    ("commit", "lint", "tip.lint"),
+    ("commit", "show_payload", "tip.show_payload"),
+    ("commit", "copy", "tip.copy"),
+    ("explain", "explain.detailed", "tip.explain.detailed"),
+    ("explain", "index", "tip.index"),
+    ("explain", "explain.show_diff", "tip.explain.show_diff"),
+    ("explain", "copy", "tip.copy"),
+    ("explain", "show_payload", "tip.show_payload"),
+    ("config", "config.where", "tip.config.where"),
+];
+
+/// 저장하는 상태
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct State {
+    #[serde(default)]
+    used: BTreeSet<String>,
+    #[serde(default)]
+    shown: BTreeSet<String>,
+}
+
+/// 상태 파일 (~/.ai-cli/tips.json)
+fn state_path() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(".ai-cli").join("tips.json"))
+}
+
+fn load() -> State {
+    state_path()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+/// 상태 저장 (실패해도 명령은 계속 진행)
+fn save(state: &State) {
+    let Some(path) = state_path() else {
+        return;
+    };
+    let result = path
+        .parent()
+        .map_or(Ok(()), fs::create_dir_all)
+        .and_then(|()| serde_json::to_vec_pretty(state).map_err(io::Error::from))
+        .and_then(|json| fs::write(&path, json));
+    if let Err(e) = result {
+        tracing::debug!("Could not save tips state to {}: {}", path.display(), e);
+    }
+}
+
+/// 명령 실행 중에 쓴 기능 기록 (예: 승인 화면에서 다시 생성)
+pub fn used(feature: &str) {
+    let mut state = load();
+    if state.used.insert(feature.to_string()) {
+        save(&state);
+    }
+}
+
+/// 명령 이름과 명령줄에서 쓴 기능
+pub fn features(cli: &Cli) -> (&'static str, Vec<&'static str>) {
+    let mut features = Vec::new();
+    let mut flag = |on: bool, feature: &'static str| {
+        if on {
+            features.push(feature);
+        }
+    };
+    let command = match &cli.command {
+        Commands::Commit { show_payload, copy, .. } => {
+            flag(*show_payload, "show_payload");
+            flag(*copy, "copy");
+            "commit"
+        }
+        Commands::Explain { detailed, show_diff, show_payload, copy, .. } => {
+            flag(*detailed, "explain.detailed");
+            flag(*show_diff, "explain.show_diff");
+            flag(*show_payload, "show_payload");
+            flag(*copy, "copy");
+            "explain"
+        }
+        Commands::Index { .. } | Commands::Search { .. } => {
+            flag(true, "index");
+            "index"
+        }
+        Commands::Lint { .. } => {
+            flag(true, "lint");
+            "lint"
+        }
+        Commands::Config { action, .. } => {
+            flag(matches!(action, Some(ConfigAction::Where)), "config.where");
+            "config"
+        }
+        _ => "other",
+    };
+    (command, features)
+}
+
+/// 팁을 보여 줄 수 있는지
+fn is_enabled(no_tips: bool) -> bool {
+    !no_tips
+        && config::setting("tips").trim() != "off"
+        && io::stdout().is_terminal()
+        && !output::is_json()
+        && !spinner::is_quiet()
+}
+
+/// 명령이 끝난 뒤 쓴 기능을 기록하고 팁 하나를 보여 줌
+pub fn after_command(command: &str, features: &[&str], no_tips: bool) {
+    let mut state = load();
+    state.used.extend(features.iter().map(|feature| feature.to_string()));
+    if is_enabled(no_tips) {
+        if let Some(message) = next_tip(&state, command) {
+            state.shown.insert(message.to_string());
+            println!("\n{}", tr!("tip.line", tip = i18n::message(message)));
+        }
+    }
+    save(&state);
+}
+
+/// 보여 줄 팁 (명령에 맞고, 기능을 아직 쓰지 않았고, 보여 준 적 없는 첫 팁)
+fn next_tip(state: &State, command: &str) -> Option<&'static str> {
+    TIPS.iter()
+        .find(|(tip_command, feature, message)| {
+            *tip_command == command && !state.used.contains(*feature) && !state.shown.contains(*message)
+        })
+        .map(|(_, _, message)| *message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_tip() {
+        let mut state = State::default();
+        assert_eq!(next_tip(&state, "commit"), Some("tip.commit.regenerate"));
+        state.used.insert("commit.regenerate".to_string());
+        assert_eq!(next_tip(&state, "commit"), Some("tip.index"));
+
+        // 한 명령에서 본 팁은 다른 명령에서도 다시 보여 주지 않음
+        state.shown.insert("tip.index".to_string());
+        assert_eq!(next_tip(&state, "commit"), Some("tip.lint"));
+        state.used.insert("explain.detailed".to_string());
+        assert_eq!(next_tip(&state, "explain"), Some("tip.explain.show_diff"));
+        assert_eq!(next_tip(&state, "audit"), None);
+
+        let cli = <Cli as clap::Parser>::parse_from(["ai-cli", "explain", "--detailed", "--copy"]);
+        assert_eq!(features(&cli), ("explain", vec!["explain.detailed", "copy"]));
+        let cli = <Cli as clap::Parser>::parse_from(["ai-cli", "config", "where"]);
+        assert_eq!(features(&cli), ("config", vec!["config.where"]));
+    }
+}
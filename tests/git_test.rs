@@ -5,15 +5,46 @@
 use ai_cli::git_utils::*;
 use tempfile::TempDir;
 use std::fs;
+use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::sync::{Mutex, MutexGuard};
 
-/// 테스트용 Git 리포지토리 설정
-fn setup_test_repo() -> TempDir {
+/// 작업 디렉토리는 프로세스 전체에서 하나이므로 디렉토리를 바꾸는 테스트는 하나씩 실행
+static CURRENT_DIR_LOCK: Mutex<()> = Mutex::new(());
+
+/// 작업 디렉토리를 바꾼 임시 디렉토리 (끝나면 원래 디렉토리로 돌아감)
+struct TestDir {
+    dir: TempDir,
+    previous: PathBuf,
+    _lock: MutexGuard<'static, ()>,
+}
+
+impl TestDir {
+    fn enter(dir: TempDir) -> Self {
+        let lock = CURRENT_DIR_LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let previous = std::env::current_dir().unwrap();
+        std::env::set_current_dir(dir.path()).unwrap();
+        Self { dir, previous, _lock: lock }
+    }
+
+    fn path(&self) -> &Path {
+        self.dir.path()
+    }
+}
+
+impl Drop for TestDir {
+    fn drop(&mut self) {
+        let _ = std::env::set_current_dir(&self.previous);
+    }
+}
+
+/// 테스트용 Git 리포지토리 설정 (작업 디렉토리를 리포지토리로 바꿈)
+fn setup_test_repo() -> TestDir {
     let temp_dir = TempDir::new().unwrap();
 
-    // Git 리포지토리 초기화
+    // Git 리포지토리 초기화 (기본 브랜치 main)
     Command::new("git")
-        .arg("init")
+        .args(["init", "-b", "main"])
         .current_dir(temp_dir.path())
         .output()
         .expect("Failed to init git repo");
@@ -31,13 +62,13 @@ fn setup_test_repo() -> TempDir {
         .output()
         .expect("Failed to set git user email");
 
-    temp_dir
+    TestDir::enter(temp_dir)
 }
 
 /// Git 리포지토리 열기 테스트
 #[test]
 fn test_open_repository() {
-    let temp_dir = setup_test_repo();
+    let _temp_dir = setup_test_repo();
 
     let repo = open_repository();
     assert!(repo.is_ok());
@@ -46,7 +77,7 @@ fn test_open_repository() {
 /// Git 리포지토리 없을 때 테스트
 #[test]
 fn test_open_repository_no_git() {
-    let temp_dir = TempDir::new().unwrap();
+    let _temp_dir = TestDir::enter(TempDir::new().unwrap());
 
     let result = open_repository();
     assert!(result.is_err());
@@ -80,7 +111,7 @@ fn test_get_staged_diff() {
 /// 스테이징된 변경 없을 때 테스트
 #[test]
 fn test_get_staged_diff_empty() {
-    let temp_dir = setup_test_repo();
+    let _temp_dir = setup_test_repo();
 
     let result = get_staged_diff();
     assert!(result.is_err());
@@ -148,10 +179,10 @@ fn test_get_commit_diff() {
         .output()
         .expect("Failed to get commit hash");
 
-    let commit_hash = String::from_utf8_lossy(&output.stdout).trim();
+    let commit_hash = String::from_utf8_lossy(&output.stdout).trim().to_string();
 
     // 커밋 diff 읽기
-    let diff = get_commit_diff(commit_hash);
+    let diff = get_commit_diff(&commit_hash);
     assert!(diff.is_ok());
 
     let diff_content = diff.unwrap();
@@ -162,7 +193,7 @@ fn test_get_commit_diff() {
 /// 잘못된 커밋 해시 테스트
 #[test]
 fn test_get_commit_diff_invalid_hash() {
-    let temp_dir = setup_test_repo();
+    let _temp_dir = setup_test_repo();
 
     let result = get_commit_diff("invalid_hash");
     assert!(result.is_err());
@@ -195,7 +226,7 @@ fn test_get_staged_files() {
 /// 현재 브랜치 이름 테스트
 #[test]
 fn test_get_current_branch() {
-    let temp_dir = setup_test_repo();
+    let _temp_dir = setup_test_repo();
 
     let branch = get_current_branch().unwrap();
     assert_eq!(branch, "main"); // Git의 기본 브랜치는 'main'
@@ -262,12 +293,275 @@ fn test_diff_to_string() {
         .output()
         .expect("Failed to stage file");
 
+    // 아직 커밋이 없으므로 빈 트리와 비교
     let repo = open_repository().unwrap();
-    let head = repo.head().unwrap().peel_to_tree().unwrap();
-    let mut diff = repo.diff_tree_to_index(Some(&head), None, None).unwrap();
+    let diff = repo.diff_tree_to_index(None, None, None).unwrap();
 
     // diff를 문자열로 변환
     let diff_text = ai_cli::git_utils::diff_to_string(&diff).unwrap();
     assert!(diff_text.contains("Hello, World!"));
     assert!(diff_text.contains("+++"));
-}
\ No newline at end of file
+}
+
+/// libgit2 커밋 생성 테스트
+#[test]
+fn test_create_commit() {
+    let temp_dir = setup_test_repo();
+
+    fs::write(temp_dir.path().join("test.txt"), "Hello, World!").unwrap();
+
+    Command::new("git")
+        .args(["add", "test.txt"])
+        .current_dir(temp_dir.path())
+        .output()
+        .expect("Failed to stage file");
+
+    let oid = create_commit("feat: add greeting", false).unwrap();
+
+    let output = Command::new("git")
+        .args(["log", "-1", "--format=%H %s"])
+        .current_dir(temp_dir.path())
+        .output()
+        .expect("Failed to read git log");
+
+    let log = String::from_utf8_lossy(&output.stdout);
+    assert!(log.contains(&oid.to_string()));
+    assert!(log.contains("feat: add greeting"));
+
+    // 변경 사항 없이 다시 커밋 시도
+    let result = create_commit("chore: nothing", false);
+    assert!(matches!(result, Err(CommitError::NothingToCommit)));
+}
+
+/// 훅이 커밋을 거부하는 경우 테스트
+#[cfg(unix)]
+#[test]
+fn test_create_commit_hook_rejected() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let temp_dir = setup_test_repo();
+
+    let hook = temp_dir.path().join(".git").join("hooks").join("pre-commit");
+    fs::create_dir_all(hook.parent().unwrap()).unwrap();
+    fs::write(&hook, "#!/bin/sh\nexit 1\n").unwrap();
+    fs::set_permissions(&hook, fs::Permissions::from_mode(0o755)).unwrap();
+
+    fs::write(temp_dir.path().join("test.txt"), "Hello, World!").unwrap();
+
+    Command::new("git")
+        .args(["add", "test.txt"])
+        .current_dir(temp_dir.path())
+        .output()
+        .expect("Failed to stage file");
+
+    let result = create_commit("feat: add greeting", false);
+    assert!(matches!(result, Err(CommitError::HookRejected(ref hook)) if hook == "pre-commit"));
+
+    // --no-verify는 훅을 건너뜀
+    assert!(create_commit("feat: add greeting", true).is_ok());
+}
+
+/// GIT_AUTHOR_* / GIT_COMMITTER_* 환경 변수가 git 설정보다 우선하는지 테스트
+#[test]
+fn test_create_commit_env_identity() {
+    let temp_dir = setup_test_repo();
+
+    fs::write(temp_dir.path().join("test.txt"), "Hello, World!").unwrap();
+    Command::new("git")
+        .args(["add", "test.txt"])
+        .current_dir(temp_dir.path())
+        .output()
+        .expect("Failed to stage file");
+
+    // 환경 변수는 프로세스 전체에서 공유되므로 작업 디렉토리 잠금 안에서만 설정
+    let vars = [
+        ("GIT_AUTHOR_NAME", "Env Author"),
+        ("GIT_AUTHOR_EMAIL", "author@example.com"),
+        ("GIT_AUTHOR_DATE", "@1700000000 +0900"),
+        ("GIT_COMMITTER_NAME", "Env Committer"),
+        ("GIT_COMMITTER_EMAIL", "committer@example.com"),
+    ];
+    for (key, value) in vars {
+        std::env::set_var(key, value);
+    }
+    let result = create_commit("feat: add greeting", false);
+    for (key, _) in vars {
+        std::env::remove_var(key);
+    }
+    result.unwrap();
+
+    let output = Command::new("git")
+        .args(["log", "-1", "--format=%an <%ae> %at %ad|%cn <%ce>", "--date=format:%z"])
+        .current_dir(temp_dir.path())
+        .output()
+        .expect("Failed to read git log");
+
+    let log = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(log.trim(), "Env Author <author@example.com> 1700000000 +0900|Env Committer <committer@example.com>");
+}
+
+/// commit.gpgsign이 켜져 있으면 gpg.program으로 서명하는지 테스트
+#[cfg(unix)]
+#[test]
+fn test_create_commit_signed() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let temp_dir = setup_test_repo();
+
+    // 커밋 내용을 받아 고정된 서명을 출력하는 가짜 gpg (옆에 sign-fails 파일이 있으면 실패)
+    // 실행 중인 다른 테스트와 겹쳐 "Text file busy"가 나지 않도록 스크립트는 한 번만 씀
+    let signer = temp_dir.path().join(".git").join("fake-gpg");
+    fs::write(
+        &signer,
+        "#!/bin/sh\ndir=\"$(dirname \"$0\")\"\ncat > /dev/null\nif [ -e \"$dir/sign-fails\" ]; then echo 'gpg: signing failed: No secret key' >&2; exit 2; fi\necho \"$@\" > \"$dir/signer-args\"\nprintf -- '-----BEGIN PGP SIGNATURE-----\\n\\nZmFrZQ==\\n-----END PGP SIGNATURE-----\\n'\n",
+    )
+    .unwrap();
+    fs::set_permissions(&signer, fs::Permissions::from_mode(0o755)).unwrap();
+
+    for (key, value) in [
+        ("commit.gpgsign", "true"),
+        ("gpg.program", signer.to_str().unwrap()),
+        ("user.signingkey", "ABCDEF12"),
+    ] {
+        Command::new("git")
+            .args(["config", key, value])
+            .current_dir(temp_dir.path())
+            .output()
+            .expect("Failed to set git config");
+    }
+
+    fs::write(temp_dir.path().join("test.txt"), "Hello, World!").unwrap();
+    Command::new("git")
+        .args(["add", "test.txt"])
+        .current_dir(temp_dir.path())
+        .output()
+        .expect("Failed to stage file");
+
+    let oid = create_commit("feat: add greeting", false).unwrap();
+
+    let output = Command::new("git")
+        .args(["cat-file", "-p", "HEAD"])
+        .current_dir(temp_dir.path())
+        .output()
+        .expect("Failed to read commit");
+    let commit = String::from_utf8_lossy(&output.stdout);
+    assert!(commit.contains("gpgsig -----BEGIN PGP SIGNATURE-----"));
+    assert!(commit.contains("feat: add greeting"));
+
+    let head = Command::new("git")
+        .args(["rev-parse", "HEAD"])
+        .current_dir(temp_dir.path())
+        .output()
+        .expect("Failed to read HEAD");
+    assert_eq!(String::from_utf8_lossy(&head.stdout).trim(), oid.to_string());
+
+    let args = fs::read_to_string(temp_dir.path().join(".git").join("signer-args")).unwrap();
+    assert_eq!(args.trim(), "--status-fd=2 -bsau ABCDEF12");
+
+    // 서명 프로그램이 실패하면 서명 없는 커밋을 만들지 않고 오류
+    fs::write(temp_dir.path().join(".git").join("sign-fails"), "").unwrap();
+    fs::write(temp_dir.path().join("test.txt"), "Changed").unwrap();
+    Command::new("git")
+        .args(["add", "test.txt"])
+        .current_dir(temp_dir.path())
+        .output()
+        .expect("Failed to stage file");
+
+    let result = create_commit("fix: change greeting", false);
+    assert!(matches!(result, Err(CommitError::Signing(ref reason)) if reason.contains("No secret key")));
+    let head_after = Command::new("git")
+        .args(["rev-parse", "HEAD"])
+        .current_dir(temp_dir.path())
+        .output()
+        .expect("Failed to read HEAD");
+    assert_eq!(head_after.stdout, head.stdout);
+}
+
+/// prepare-commit-msg 훅은 --no-verify여도 실행되고, 메시지는 정리 없이 그대로 기록되는지 테스트
+#[cfg(unix)]
+#[test]
+fn test_create_commit_prepare_commit_msg() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let temp_dir = setup_test_repo();
+
+    let hooks = temp_dir.path().join(".git").join("hooks");
+    fs::create_dir_all(&hooks).unwrap();
+    let hook = hooks.join("prepare-commit-msg");
+    fs::write(&hook, "#!/bin/sh\n[ \"$2\" = message ] || exit 1\nprintf '\\nRefs: #42\\n' >> \"$1\"\n").unwrap();
+    fs::set_permissions(&hook, fs::Permissions::from_mode(0o755)).unwrap();
+
+    fs::write(temp_dir.path().join("test.txt"), "Hello, World!").unwrap();
+    Command::new("git")
+        .args(["add", "test.txt"])
+        .current_dir(temp_dir.path())
+        .output()
+        .expect("Failed to stage file");
+
+    create_commit("feat: add greeting  ", true).unwrap();
+
+    let output = Command::new("git")
+        .args(["cat-file", "-p", "HEAD"])
+        .current_dir(temp_dir.path())
+        .output()
+        .expect("Failed to read commit");
+    let commit = String::from_utf8_lossy(&output.stdout);
+    assert!(commit.ends_with("\n\nfeat: add greeting  \nRefs: #42\n"));
+}
+
+/// partial clone / sparse checkout 감지 테스트
+#[test]
+fn test_detect_checkout_shape() {
+    let temp_dir = setup_test_repo();
+
+    let repo = open_repository().unwrap();
+    assert_eq!(detect_checkout_shape(&repo), CheckoutShape::default());
+
+    Command::new("git")
+        .args(["config", "remote.origin.promisor", "true"])
+        .current_dir(temp_dir.path())
+        .output()
+        .expect("Failed to set promisor remote");
+
+    Command::new("git")
+        .args(["config", "core.sparseCheckout", "true"])
+        .current_dir(temp_dir.path())
+        .output()
+        .expect("Failed to enable sparse checkout");
+
+    let repo = open_repository().unwrap();
+    let shape = detect_checkout_shape(&repo);
+    assert!(shape.partial_clone);
+    assert!(shape.sparse_checkout);
+}
+
+/// 파일별 최근 커밋 제목 테스트
+#[test]
+fn test_recent_commit_subjects() {
+    let temp_dir = setup_test_repo();
+    let commit = |file: &str, message: &str| {
+        fs::write(temp_dir.path().join(file), message).unwrap();
+        for args in [vec!["add", file], vec!["commit", "-m", message]] {
+            Command::new("git")
+                .args(&args)
+                .current_dir(temp_dir.path())
+                .output()
+                .expect("Failed to run git");
+        }
+    };
+
+    commit("parser.rs", "feat(parser): add tokenizer");
+    commit("README.md", "docs: describe usage");
+    commit("parser.rs", "fix(parser): handle empty input");
+
+    let repo = git2::Repository::open(temp_dir.path()).unwrap();
+    let subjects = recent_commit_subjects(&repo, &["parser.rs".to_string()], 10).unwrap();
+    assert_eq!(subjects, vec!["fix(parser): handle empty input", "feat(parser): add tokenizer"]);
+
+    let all = recent_commit_subjects(&repo, &[], 2).unwrap();
+    assert_eq!(all.len(), 2);
+
+    let section = format_commit_history(&subjects).unwrap();
+    assert!(section.contains("- fix(parser): handle empty input"));
+    assert!(format_commit_history(&[]).is_none());
+}
@@ -3,9 +3,9 @@
 //! AI CLI의 전체 기능을 테스트합니다.
 
 use assert_cmd::Command;
+use predicates::prelude::*;
 use tempfile::TempDir;
 use std::fs;
-use std::process::Stdio;
 
 /// 기본 CLI 명령어 테스트
 #[tokio::test]
@@ -14,11 +14,13 @@ async fn test_cli_help() {
     cmd.arg("--help");
 
     cmd.assert().success()
-        .stdout(predicates::str::contains("AI-powered CLI Git Assistant"))
+        .stdout(predicates::str::contains("AI CLI is an intelligent command-line tool"))
         .stdout(predicates::str::contains("commit"))
         .stdout(predicates::str::contains("explain"))
         .stdout(predicates::str::contains("init"))
-        .stdout(predicates::str::contains("config"));
+        .stdout(predicates::str::contains("audit"))
+        .stdout(predicates::str::contains("config"))
+        .stdout(predicates::str::contains("--local-only"));
 }
 
 /// commit 명령어 도움말 테스트
@@ -31,7 +33,9 @@ async fn test_commit_help() {
         .stdout(predicates::str::contains("Generate a conventional commit message"))
         .stdout(predicates::str::contains("--message"))
         .stdout(predicates::str::contains("--all"))
-        .stdout(predicates::str::contains("--model"));
+        .stdout(predicates::str::contains("--model"))
+        .stdout(predicates::str::contains("--no-verify"))
+        .stdout(predicates::str::contains("--show-payload"));
 }
 
 /// explain 명령어 도움말 테스트
@@ -41,10 +45,13 @@ async fn test_explain_help() {
     cmd.args(["explain", "--help"]);
 
     cmd.assert().success()
-        .stdout(predicates::str::contains("Explain the staged changes"))
+        .stdout(predicates::str::contains("Explain the staged (or specific commit) changes"))
         .stdout(predicates::str::contains("--hash"))
         .stdout(predicates::str::contains("--detailed"))
-        .stdout(predicates::str::contains("--format"));
+        .stdout(predicates::str::contains("--format"))
+        .stdout(predicates::str::contains("--context-lines"))
+        .stdout(predicates::str::contains("--word-diff"))
+        .stdout(predicates::str::contains("--ignore-whitespace"));
 }
 
 /// init 명령어 테스트
@@ -102,6 +109,40 @@ async fn test_commit_without_git_repo() {
         .stderr(predicates::str::contains("Git repository"));
 }
 
+/// --json과 --ascii를 함께 쓰면 결과 JSON의 문자열 값은 바꾸지 않음
+#[tokio::test]
+async fn test_json_output_with_ascii() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let mut cmd = Command::cargo_bin("ai-cli").unwrap();
+    cmd.current_dir(temp_dir.path())
+        .args(["--json", "--ascii", "lint", "-F", "missing-✅→.txt"]);
+
+    let output = cmd.assert().failure().get_output().stdout.clone();
+    let result: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(result["ok"], false);
+    assert!(result["error"].as_str().unwrap().contains("missing-✅→.txt"));
+}
+
+/// 신뢰하지 않는 폴더에서 mcp run 에이전트 실행 거부 테스트
+#[tokio::test]
+async fn test_mcp_run_in_untrusted_folder() {
+    let temp_dir = TempDir::new().unwrap();
+    let home_dir = TempDir::new().unwrap();
+
+    // 신뢰 목록이 없는 홈에서 실행하고, 신뢰 질문에는 입력 없이(EOF) 답함
+    let mut cmd = Command::cargo_bin("ai-cli").unwrap();
+    cmd.current_dir(temp_dir.path())
+        .env("HOME", home_dir.path())
+        .env("USERPROFILE", home_dir.path())
+        .args(["mcp", "run", "open a PR for this branch"])
+        .write_stdin("");
+
+    cmd.assert().success()
+        .stdout(predicates::str::contains("Not running the agent: this folder is not trusted"))
+        .stdout(predicates::str::contains("Working on it").not());
+}
+
 /// 프롬프트 엔지니어링 테스트
 #[test]
 fn test_commit_prompt_generation() {
@@ -140,7 +181,7 @@ fn test_conventional_commit_refinement() {
     ];
 
     for (input, expected) in test_cases {
-        let result = ai_cli::ai_utils::refine_conventional_commit(input);
+        let result = ai_cli::ai_utils::refine_conventional_commit(input, &ai_cli::config::CommitRules::default());
         assert_eq!(result, expected, "Input: {}", input);
     }
 }
@@ -149,8 +190,8 @@ fn test_conventional_commit_refinement() {
 #[test]
 fn test_context_engine() {
     let engine = ai_cli::context::ContextEngine::new();
-    assert!(engine.project_root.is_none());
-    assert!(engine.contexts.is_empty());
+    assert!(engine.project_root().is_none());
+    assert!(engine.get_combined_context().is_empty());
 }
 
 /// 파일 참조 해석 테스트
@@ -168,8 +209,8 @@ fn test_file_reference_resolution() {
 #[test]
 fn test_security_manager() {
     let manager = ai_cli::security::SecurityManager::new();
-    assert_eq!(manager.current_level, ai_cli::security::SecurityLevel::Untrusted);
-    assert!(manager.trusted_folders.is_empty());
+    assert_eq!(manager.current_level(), &ai_cli::security::SecurityLevel::Untrusted);
+    assert!(manager.trusted_folders().is_empty());
 }
 
 /// 위험한 명령어 감지 테스트
@@ -195,7 +236,7 @@ fn test_warning_command_detection() {
 fn test_mcp_client_builder() {
     let client = ai_cli::mcp::MCPClientBuilder::new("test-client")
         .version("1.0.0")
-        .server_url("stdio://")
+        .registry(ai_cli::mcp::McpRegistry::default())
         .build();
 
     assert!(!client.is_initialized());